@@ -1,5 +1,8 @@
-use crate::{ReadCapability, WriteCapability};
-use core::marker::PhantomData;
+use crate::{FieldSet, ReadCapability, WriteCapability};
+use core::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+};
 
 /// Common error definition for (async) [BufferInterface]
 pub trait BufferInterfaceError {
@@ -58,24 +61,92 @@ pub trait AsyncBufferInterface: BufferInterfaceError {
 ///
 /// If the interface error implements [embedded_io::Error],
 /// then this operation type also implements the [embedded_io] traits
-pub struct BufferOperation<'i, Interface, AddressType: Copy, Access> {
+///
+/// When the buffer has a frame layout, the `Frame` is the field set of a frame
+/// and the buffer can be read frame by frame.
+///
+/// When the buffer has a length prefix, the `Prefix` is the field set of the prefix
+/// and the buffer can be read one length-prefixed payload at a time.
+pub struct BufferOperation<'i, Interface, AddressType: Copy, Access, Frame = (), Prefix = ()> {
     interface: &'i mut Interface,
     address: AddressType,
-    _phantom: PhantomData<Access>,
+    max_size: Option<usize>,
+    _phantom: PhantomData<(Access, Frame, Prefix)>,
 }
 
-impl<'i, Interface, AddressType: Copy, Access> BufferOperation<'i, Interface, AddressType, Access> {
+impl<'i, Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'i, Interface, AddressType, Access, Frame, Prefix>
+{
     #[doc(hidden)]
     pub fn new(interface: &'i mut Interface, address: AddressType) -> Self {
         Self {
             interface,
             address,
+            max_size: None,
             _phantom: PhantomData,
         }
     }
+
+    /// Set the maximum amount of bytes [Self::write_checked] accepts
+    #[doc(hidden)]
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// The maximum amount of bytes the buffer accepts in one write, if the device has a maximum
+    pub fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
+
+    fn check_size<E>(&self, buf: &[u8]) -> Result<(), BufferError<E>> {
+        match self.max_size {
+            Some(max_size) if buf.len() > max_size => Err(BufferError::TooLong {
+                length: buf.len(),
+                max_size,
+            }),
+            _ => Ok(()),
+        }
+    }
 }
 
-impl<Interface, AddressType: Copy, Access> BufferOperation<'_, Interface, AddressType, Access>
+/// The error of writing a payload to a buffer with a maximum size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum BufferError<E> {
+    /// The interface returned an error
+    Interface(E),
+    /// The payload is longer than the buffer accepts. Nothing was written
+    TooLong {
+        /// The length of the payload
+        length: usize,
+        /// The maximum length the buffer accepts
+        max_size: usize,
+    },
+}
+
+impl<E> From<E> for BufferError<E> {
+    fn from(value: E) -> Self {
+        Self::Interface(value)
+    }
+}
+
+impl<E: Display> Display for BufferError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BufferError::Interface(e) => write!(f, "Interface error: {e}"),
+            BufferError::TooLong { length, max_size } => write!(
+                f,
+                "The payload of {length} bytes is longer than the maximum of {max_size} bytes"
+            ),
+        }
+    }
+}
+
+impl<E: Display + Debug> core::error::Error for BufferError<E> {}
+
+impl<Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: BufferInterface<AddressType = AddressType>,
     Access: WriteCapability,
@@ -103,6 +174,15 @@ where
         Ok(())
     }
 
+    /// Write an entire buffer into this writer after checking it's not longer than the maximum size of the buffer.
+    ///
+    /// When the buffer has a maximum size and `buf` is longer, [BufferError::TooLong] is returned and nothing is written.
+    /// Without a maximum size, this is the same as [Self::write_all].
+    pub fn write_checked(&mut self, buf: &[u8]) -> Result<(), BufferError<Interface::Error>> {
+        self.check_size(buf)?;
+        Ok(self.write_all(buf)?)
+    }
+
     /// Flush this output stream, blocking until all intermediately buffered contents reach their destination.
     ///
     /// Mirror function of [embedded_io::Write::flush].
@@ -111,7 +191,8 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Access> BufferOperation<'_, Interface, AddressType, Access>
+impl<Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: BufferInterface<AddressType = AddressType>,
     Access: ReadCapability,
@@ -146,7 +227,8 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Access> BufferOperation<'_, Interface, AddressType, Access>
+impl<Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: AsyncBufferInterface<AddressType = AddressType>,
     Access: WriteCapability,
@@ -174,6 +256,18 @@ where
         Ok(())
     }
 
+    /// Write an entire buffer into this writer after checking it's not longer than the maximum size of the buffer.
+    ///
+    /// When the buffer has a maximum size and `buf` is longer, [BufferError::TooLong] is returned and nothing is written.
+    /// Without a maximum size, this is the same as [Self::write_all_async].
+    pub async fn write_checked_async(
+        &mut self,
+        buf: &[u8],
+    ) -> Result<(), BufferError<Interface::Error>> {
+        self.check_size(buf)?;
+        Ok(self.write_all_async(buf).await?)
+    }
+
     /// Flush this output stream, blocking until all intermediately buffered contents reach their destination.
     ///
     /// Mirror function of [embedded_io_async::Write::flush].
@@ -182,7 +276,8 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Access> BufferOperation<'_, Interface, AddressType, Access>
+impl<Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: AsyncBufferInterface<AddressType = AddressType>,
     Access: ReadCapability,
@@ -218,10 +313,170 @@ where
     }
 }
 
+impl<Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
+where
+    Interface: BufferInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+    Frame: FieldSet,
+{
+    /// Read the next frame from the buffer.
+    ///
+    /// Returns `None` when the buffer has no complete frame left. An incomplete frame at the end is discarded.
+    pub fn read_frame(&mut self) -> Result<Option<Frame>, Interface::Error> {
+        let mut frame = Frame::new_with_zero();
+
+        match self.read_exact(frame.get_inner_buffer_mut()) {
+            Ok(()) => Ok(Some(frame)),
+            Err(embedded_io::ReadExactError::UnexpectedEof) => Ok(None),
+            Err(embedded_io::ReadExactError::Other(e)) => Err(e),
+        }
+    }
+
+    /// Iterate over the frames in the buffer.
+    ///
+    /// Every item is read with [Self::read_frame]. The iterator stops when there are no complete frames left
+    /// or after the first error.
+    pub fn frames(&mut self) -> impl Iterator<Item = Result<Frame, Interface::Error>> + '_ {
+        let mut done = false;
+
+        core::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+
+            let next = self.read_frame().transpose();
+            done = !matches!(next, Some(Ok(_)));
+            next
+        })
+    }
+}
+
+impl<Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
+where
+    Interface: AsyncBufferInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+    Frame: FieldSet,
+{
+    /// Read the next frame from the buffer.
+    ///
+    /// Returns `None` when the buffer has no complete frame left. An incomplete frame at the end is discarded.
+    pub async fn read_frame_async(&mut self) -> Result<Option<Frame>, Interface::Error> {
+        let mut frame = Frame::new_with_zero();
+
+        match self.read_exact_async(frame.get_inner_buffer_mut()).await {
+            Ok(()) => Ok(Some(frame)),
+            Err(embedded_io::ReadExactError::UnexpectedEof) => Ok(None),
+            Err(embedded_io::ReadExactError::Other(e)) => Err(e),
+        }
+    }
+}
+
+/// A field set that is the length prefix of a buffer. It holds the length of the payload that follows it.
+pub trait LengthPrefix: FieldSet {
+    /// The amount of payload bytes that follow the prefix
+    fn payload_len(&self) -> usize;
+}
+
+/// The error of reading a length-prefixed payload from a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum LengthPrefixedReadError<E> {
+    /// The interface returned an error
+    Interface(E),
+    /// The buffer ended before the prefix or the whole payload was read
+    UnexpectedEof,
+    /// The payload announced by the prefix doesn't fit in the given buffer
+    PayloadTooLong {
+        /// The length of the payload given by the prefix
+        length: usize,
+    },
+}
+
+impl<E> From<embedded_io::ReadExactError<E>> for LengthPrefixedReadError<E> {
+    fn from(value: embedded_io::ReadExactError<E>) -> Self {
+        match value {
+            embedded_io::ReadExactError::UnexpectedEof => Self::UnexpectedEof,
+            embedded_io::ReadExactError::Other(e) => Self::Interface(e),
+        }
+    }
+}
+
+impl<E: Display> Display for LengthPrefixedReadError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LengthPrefixedReadError::Interface(e) => write!(f, "Interface error: {e}"),
+            LengthPrefixedReadError::UnexpectedEof => {
+                write!(f, "The buffer ended before the whole payload was read")
+            }
+            LengthPrefixedReadError::PayloadTooLong { length } => {
+                write!(f, "The payload of {length} bytes doesn't fit in the buffer")
+            }
+        }
+    }
+}
+
+impl<E: Display + Debug> core::error::Error for LengthPrefixedReadError<E> {}
+
+impl<Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
+where
+    Interface: BufferInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+    Prefix: LengthPrefix,
+{
+    /// Read the length prefix and then exactly the amount of payload bytes it announces into the buffer.
+    ///
+    /// Returns the prefix and the part of the buffer that holds the payload.
+    pub fn read_length_prefixed<'b>(
+        &mut self,
+        buf: &'b mut [u8],
+    ) -> Result<(Prefix, &'b mut [u8]), LengthPrefixedReadError<Interface::Error>> {
+        let mut prefix = Prefix::new_with_zero();
+        self.read_exact(prefix.get_inner_buffer_mut())?;
+
+        let length = prefix.payload_len();
+        let payload = buf
+            .get_mut(..length)
+            .ok_or(LengthPrefixedReadError::PayloadTooLong { length })?;
+        self.read_exact(payload)?;
+
+        Ok((prefix, payload))
+    }
+}
+
+impl<Interface, AddressType: Copy, Access, Frame, Prefix>
+    BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
+where
+    Interface: AsyncBufferInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+    Prefix: LengthPrefix,
+{
+    /// Read the length prefix and then exactly the amount of payload bytes it announces into the buffer.
+    ///
+    /// Returns the prefix and the part of the buffer that holds the payload.
+    pub async fn read_length_prefixed_async<'b>(
+        &mut self,
+        buf: &'b mut [u8],
+    ) -> Result<(Prefix, &'b mut [u8]), LengthPrefixedReadError<Interface::Error>> {
+        let mut prefix = Prefix::new_with_zero();
+        self.read_exact_async(prefix.get_inner_buffer_mut()).await?;
+
+        let length = prefix.payload_len();
+        let payload = buf
+            .get_mut(..length)
+            .ok_or(LengthPrefixedReadError::PayloadTooLong { length })?;
+        self.read_exact_async(payload).await?;
+
+        Ok((prefix, payload))
+    }
+}
+
 // ------- embedded-io impls -------
 
-impl<Interface, AddressType: Copy, Access> embedded_io::ErrorType
-    for BufferOperation<'_, Interface, AddressType, Access>
+impl<Interface, AddressType: Copy, Access, Frame, Prefix> embedded_io::ErrorType
+    for BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: BufferInterfaceError,
     Interface::Error: embedded_io::Error,
@@ -229,8 +484,8 @@ where
     type Error = Interface::Error;
 }
 
-impl<Interface, AddressType: Copy, Access> embedded_io::Write
-    for BufferOperation<'_, Interface, AddressType, Access>
+impl<Interface, AddressType: Copy, Access, Frame, Prefix> embedded_io::Write
+    for BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: BufferInterface<AddressType = AddressType>,
     Interface::Error: embedded_io::Error,
@@ -245,8 +500,8 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Access> embedded_io::Read
-    for BufferOperation<'_, Interface, AddressType, Access>
+impl<Interface, AddressType: Copy, Access, Frame, Prefix> embedded_io::Read
+    for BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: BufferInterface<AddressType = AddressType>,
     Interface::Error: embedded_io::Error,
@@ -257,8 +512,8 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Access> embedded_io_async::Write
-    for BufferOperation<'_, Interface, AddressType, Access>
+impl<Interface, AddressType: Copy, Access, Frame, Prefix> embedded_io_async::Write
+    for BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: AsyncBufferInterface<AddressType = AddressType>,
     Interface::Error: embedded_io::Error,
@@ -273,8 +528,8 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Access> embedded_io_async::Read
-    for BufferOperation<'_, Interface, AddressType, Access>
+impl<Interface, AddressType: Copy, Access, Frame, Prefix> embedded_io_async::Read
+    for BufferOperation<'_, Interface, AddressType, Access, Frame, Prefix>
 where
     Interface: AsyncBufferInterface<AddressType = AddressType>,
     Interface::Error: embedded_io::Error,
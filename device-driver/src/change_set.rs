@@ -0,0 +1,52 @@
+use crate::FieldSet;
+
+/// The changes made to a field set by a tracked modify.
+///
+/// It holds the value as it was read and as it was written back to the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeSet<Register> {
+    before: Register,
+    after: Register,
+}
+
+impl<Register: FieldSet> ChangeSet<Register> {
+    /// Create a new change set from the value before and after the modification
+    pub fn new(before: Register, after: Register) -> Self {
+        Self { before, after }
+    }
+
+    /// The value of the field set as it was read
+    pub fn before(&self) -> &Register {
+        &self.before
+    }
+
+    /// The value of the field set as it was written
+    pub fn after(&self) -> &Register {
+        &self.after
+    }
+
+    /// The names of the fields that got a different value, in the order they are declared
+    pub fn changed_fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        Register::FIELD_MASKS
+            .iter()
+            .filter(|(_, mask)| {
+                self.before
+                    .get_inner_buffer()
+                    .iter()
+                    .zip(self.after.get_inner_buffer())
+                    .zip(mask.iter())
+                    .any(|((before, after), mask)| (before ^ after) & mask != 0)
+            })
+            .map(|(name, _)| *name)
+    }
+
+    /// Returns true if the field with the given name got a different value
+    pub fn contains(&self, field_name: &str) -> bool {
+        self.changed_fields().any(|name| name == field_name)
+    }
+
+    /// Returns true if no field got a different value
+    pub fn is_empty(&self) -> bool {
+        self.changed_fields().next().is_none()
+    }
+}
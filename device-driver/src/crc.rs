@@ -0,0 +1,94 @@
+use core::fmt::Display;
+
+/// A CRC-8 algorithm (non-reflected) as used by the generated crc fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc8 {
+    /// The generator polynomial
+    pub poly: u8,
+    /// The initial value of the crc register
+    pub init: u8,
+    /// The value the result is xor'ed with
+    pub xor_out: u8,
+}
+
+impl Crc8 {
+    /// CRC-8/SMBUS. Polynomial `0x07`, init `0x00`
+    pub const SMBUS: Self = Self {
+        poly: 0x07,
+        init: 0x00,
+        xor_out: 0x00,
+    };
+
+    /// CRC-8/NRSC-5. Polynomial `0x31`, init `0xFF`. This is the crc used by e.g. many Sensirion sensors
+    pub const NRSC5: Self = Self {
+        poly: 0x31,
+        init: 0xFF,
+        xor_out: 0x00,
+    };
+
+    /// CRC-8/AUTOSAR. Polynomial `0x2F`, init `0xFF`, xor out `0xFF`
+    pub const AUTOSAR: Self = Self {
+        poly: 0x2F,
+        init: 0xFF,
+        xor_out: 0xFF,
+    };
+
+    /// Calculate the crc over the data
+    pub const fn checksum(&self, data: &[u8]) -> u8 {
+        let mut crc = self.init;
+
+        let mut i = 0;
+        while i < data.len() {
+            crc ^= data[i];
+
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 0x80 != 0 {
+                    (crc << 1) ^ self.poly
+                } else {
+                    crc << 1
+                };
+                bit += 1;
+            }
+
+            i += 1;
+        }
+
+        crc ^ self.xor_out
+    }
+}
+
+/// The error returned by the getter of a crc field when the stored crc doesn't match
+/// the crc calculated over the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct CrcMismatch {
+    /// The crc that is stored in the field
+    pub stored: u8,
+    /// The crc that is calculated over the data
+    pub calculated: u8,
+}
+
+impl Display for CrcMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Crc mismatch: stored {:#04X}, but calculated {:#04X}",
+            self.stored, self.calculated
+        )
+    }
+}
+
+impl core::error::Error for CrcMismatch {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_values() {
+        assert_eq!(Crc8::SMBUS.checksum(b"123456789"), 0xF4);
+        assert_eq!(Crc8::NRSC5.checksum(b"123456789"), 0xF7);
+        assert_eq!(Crc8::AUTOSAR.checksum(b"123456789"), 0xDF);
+    }
+}
@@ -0,0 +1,40 @@
+use core::fmt::{Debug, Display};
+
+/// The error returned by the generated `read_field` and `write_field` functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum DynAccessError<E> {
+    /// The interface returned an error
+    Interface(E),
+    /// There's no register with the given name
+    UnknownRegister,
+    /// The register has no field with the given name
+    UnknownField,
+    /// The field can't be read, because the field or its register is write-only
+    NotReadable,
+    /// The field can't be written, because the field is read-only or its register can't be modified
+    NotWritable,
+    /// The value doesn't fit in the field
+    ValueOutOfRange,
+}
+
+impl<E> From<E> for DynAccessError<E> {
+    fn from(value: E) -> Self {
+        Self::Interface(value)
+    }
+}
+
+impl<E: Display> Display for DynAccessError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DynAccessError::Interface(e) => write!(f, "Interface error: {e}"),
+            DynAccessError::UnknownRegister => write!(f, "There's no register with that name"),
+            DynAccessError::UnknownField => write!(f, "The register has no field with that name"),
+            DynAccessError::NotReadable => write!(f, "The field is not readable"),
+            DynAccessError::NotWritable => write!(f, "The field is not writable"),
+            DynAccessError::ValueOutOfRange => write!(f, "The value doesn't fit in the field"),
+        }
+    }
+}
+
+impl<E: Display + Debug> core::error::Error for DynAccessError<E> {}
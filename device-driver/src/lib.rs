@@ -14,6 +14,28 @@ mod command;
 pub use command::*;
 mod buffer;
 pub use buffer::*;
+mod memory;
+pub use memory::*;
+mod split;
+pub use split::*;
+mod offset;
+pub use offset::*;
+mod crc;
+pub use crc::*;
+mod word;
+pub use word::*;
+mod spi;
+pub use spi::*;
+mod staged;
+pub use staged::*;
+mod mmio;
+pub use mmio::*;
+mod reset;
+pub use reset::*;
+mod change_set;
+pub use change_set::*;
+mod dyn_access;
+pub use dyn_access::*;
 
 #[doc(hidden)]
 pub mod ops;
@@ -31,6 +53,9 @@ pub trait FieldSet {
 
     fn get_inner_buffer(&self) -> &[u8];
     fn get_inner_buffer_mut(&mut self) -> &mut [u8];
+
+    /// The name of every field with a mask over the inner buffer of the bits it occupies
+    const FIELD_MASKS: &'static [(&'static str, &'static [u8])] = &[];
 }
 
 /// The error returned by the generated [TryFrom]s.
@@ -56,6 +81,28 @@ impl<T: Display> Display for ConversionError<T> {
 
 impl<T: Display + Debug> core::error::Error for ConversionError<T> {}
 
+/// The error returned by the generated `pack_into` functions when the output buffer can't hold all field sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BufferTooSmall {
+    /// The number of bytes needed to hold all field sets
+    pub required: usize,
+    /// The length of the given buffer
+    pub actual: usize,
+}
+
+impl Display for BufferTooSmall {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The buffer is {} bytes, but {} bytes are required",
+            self.actual, self.required
+        )
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
 #[doc(hidden)]
 pub struct WO;
 #[doc(hidden)]
@@ -78,3 +125,5 @@ impl ReadCapability for RO {}
 
 impl WriteCapability for RW {}
 impl ReadCapability for RW {}
+
+impl ReadCapability for RC {}
@@ -0,0 +1,168 @@
+use core::marker::PhantomData;
+
+use crate::{AsyncRegisterInterface, RegisterInterface};
+
+/// A [RegisterInterface] that keeps the register values in memory instead of talking to a device.
+///
+/// The memory is byte-addressed: a register at address `a` with a size of `n` bytes
+/// occupies the bytes `a..a + n`. This is useful for tests, e.g. to replay a captured register dump.
+///
+/// The generated device has a `DEVICE_SPAN` constant that can be used for `N`
+/// and a `from_bytes` constructor that creates the device on top of this interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryInterface<AddressType, const N: usize> {
+    memory: [u8; N],
+    _phantom: PhantomData<AddressType>,
+}
+
+impl<AddressType, const N: usize> MemoryInterface<AddressType, N> {
+    /// Create a new interface with all memory set to 0
+    pub const fn new() -> Self {
+        Self::from_array([0; N])
+    }
+
+    /// Create a new interface with the memory set to the given bytes
+    pub const fn from_array(memory: [u8; N]) -> Self {
+        Self {
+            memory,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the current contents of the memory
+    pub const fn memory(&self) -> &[u8; N] {
+        &self.memory
+    }
+
+    /// Get the current contents of the memory mutably
+    pub const fn memory_mut(&mut self) -> &mut [u8; N] {
+        &mut self.memory
+    }
+
+    fn register_range(
+        address: AddressType,
+        len: usize,
+    ) -> Result<core::ops::Range<usize>, MemoryInterfaceError>
+    where
+        AddressType: TryInto<usize>,
+    {
+        let start = address
+            .try_into()
+            .map_err(|_| MemoryInterfaceError::OutOfBounds)?;
+        let end = start
+            .checked_add(len)
+            .ok_or(MemoryInterfaceError::OutOfBounds)?;
+
+        if end > N {
+            return Err(MemoryInterfaceError::OutOfBounds);
+        }
+
+        Ok(start..end)
+    }
+}
+
+impl<AddressType, const N: usize> Default for MemoryInterface<AddressType, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<AddressType, const N: usize> TryFrom<&[u8]> for MemoryInterface<AddressType, N> {
+    type Error = MemoryInterfaceError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let memory = value
+            .try_into()
+            .map_err(|_| MemoryInterfaceError::SizeMismatch {
+                expected: N,
+                actual: value.len(),
+            })?;
+
+        Ok(Self::from_array(memory))
+    }
+}
+
+impl<AddressType: Copy + TryInto<usize>, const N: usize> RegisterInterface
+    for MemoryInterface<AddressType, N>
+{
+    type Error = MemoryInterfaceError;
+    type AddressType = AddressType;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        let range = Self::register_range(address, data.len())?;
+        self.memory[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let range = Self::register_range(address, data.len())?;
+        data.copy_from_slice(&self.memory[range]);
+        Ok(())
+    }
+}
+
+impl<AddressType: Copy + TryInto<usize>, const N: usize> AsyncRegisterInterface
+    for MemoryInterface<AddressType, N>
+{
+    type Error = MemoryInterfaceError;
+    type AddressType = AddressType;
+
+    async fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        RegisterInterface::write_register(self, address, size_bits, data)
+    }
+
+    async fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        RegisterInterface::read_register(self, address, size_bits, data)
+    }
+}
+
+/// The error returned by the [MemoryInterface]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum MemoryInterfaceError {
+    /// A register was accessed that doesn't (fully) fit in the memory
+    OutOfBounds,
+    /// The memory was seeded with a number of bytes that doesn't match the size of the memory
+    SizeMismatch {
+        /// The size of the memory
+        expected: usize,
+        /// The number of bytes that was given
+        actual: usize,
+    },
+}
+
+impl core::fmt::Display for MemoryInterfaceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MemoryInterfaceError::OutOfBounds => {
+                write!(f, "Register access is out of bounds of the memory")
+            }
+            MemoryInterfaceError::SizeMismatch { expected, actual } => write!(
+                f,
+                "Expected {expected} bytes to seed the memory, but got {actual}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for MemoryInterfaceError {}
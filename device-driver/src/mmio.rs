@@ -0,0 +1,130 @@
+use core::{cell::UnsafeCell, marker::PhantomData};
+
+use crate::{FieldSet, ReadCapability, WriteCapability};
+
+/// Object that performs volatile accesses on a memory-mapped register.
+///
+/// It's returned by the register accessors of the generated MMIO struct.
+pub struct MmioRegisterOperation<'r, Register: FieldSet, Access> {
+    register: &'r UnsafeCell<[u8]>,
+    register_new_with_reset: fn() -> Register,
+    _phantom: PhantomData<(Register, Access)>,
+}
+
+impl<'r, Register: FieldSet, Access> MmioRegisterOperation<'r, Register, Access> {
+    #[doc(hidden)]
+    pub fn new(register: &'r UnsafeCell<[u8]>, register_new_with_reset: fn() -> Register) -> Self {
+        Self {
+            register,
+            register_new_with_reset,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn read_volatile(&self) -> Register {
+        let mut register = Register::new_with_zero();
+        let buffer = register.get_inner_buffer_mut();
+        let src = self.register.get().cast::<u8>();
+
+        // Safety: The generated MMIO struct makes sure the memory has the size of the register.
+        // Registers with the size of an integer are accessed with that width when aligned.
+        unsafe {
+            match buffer.len() {
+                2 if src.cast::<u16>().is_aligned() => {
+                    buffer.copy_from_slice(&src.cast::<u16>().read_volatile().to_ne_bytes())
+                }
+                4 if src.cast::<u32>().is_aligned() => {
+                    buffer.copy_from_slice(&src.cast::<u32>().read_volatile().to_ne_bytes())
+                }
+                8 if src.cast::<u64>().is_aligned() => {
+                    buffer.copy_from_slice(&src.cast::<u64>().read_volatile().to_ne_bytes())
+                }
+                _ => {
+                    for (i, byte) in buffer.iter_mut().enumerate() {
+                        *byte = src.add(i).read_volatile();
+                    }
+                }
+            }
+        }
+
+        register
+    }
+
+    fn write_volatile(&self, register: &Register) {
+        let buffer = register.get_inner_buffer();
+        let dst = self.register.get().cast::<u8>();
+
+        // Safety: The generated MMIO struct makes sure the memory has the size of the register.
+        // Registers with the size of an integer are accessed with that width when aligned.
+        unsafe {
+            match buffer.len() {
+                2 if dst.cast::<u16>().is_aligned() => dst
+                    .cast::<u16>()
+                    .write_volatile(u16::from_ne_bytes(buffer.try_into().unwrap())),
+                4 if dst.cast::<u32>().is_aligned() => dst
+                    .cast::<u32>()
+                    .write_volatile(u32::from_ne_bytes(buffer.try_into().unwrap())),
+                8 if dst.cast::<u64>().is_aligned() => dst
+                    .cast::<u64>()
+                    .write_volatile(u64::from_ne_bytes(buffer.try_into().unwrap())),
+                _ => {
+                    for (i, byte) in buffer.iter().enumerate() {
+                        dst.add(i).write_volatile(*byte);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<Register: FieldSet, Access> MmioRegisterOperation<'_, Register, Access>
+where
+    Access: WriteCapability,
+{
+    /// Write to the register.
+    ///
+    /// The closure is given the write object initialized to the reset value of the register.
+    /// If no reset value is specified for this register, this function is the same as [Self::write_with_zero].
+    pub fn write<R>(&mut self, f: impl FnOnce(&mut Register) -> R) -> R {
+        let mut register = (self.register_new_with_reset)();
+        let returned = f(&mut register);
+        self.write_volatile(&register);
+        returned
+    }
+
+    /// Write to the register.
+    ///
+    /// The closure is given the write object initialized to all zero.
+    pub fn write_with_zero<R>(&mut self, f: impl FnOnce(&mut Register) -> R) -> R {
+        let mut register = Register::new_with_zero();
+        let returned = f(&mut register);
+        self.write_volatile(&register);
+        returned
+    }
+}
+
+impl<Register: FieldSet, Access> MmioRegisterOperation<'_, Register, Access>
+where
+    Access: ReadCapability,
+{
+    /// Read the register from memory
+    pub fn read(&mut self) -> Register {
+        self.read_volatile()
+    }
+}
+
+impl<Register: FieldSet, Access> MmioRegisterOperation<'_, Register, Access>
+where
+    Access: ReadCapability + WriteCapability,
+{
+    /// Modify the existing register value.
+    ///
+    /// The register is read, the value is then passed to the closure for making changes.
+    /// The result is then written back to memory.
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut Register) -> R) -> R {
+        let mut register = self.read_volatile();
+        let returned = f(&mut register);
+        self.write_volatile(&register);
+        returned
+    }
+}
@@ -0,0 +1,93 @@
+use core::ops::Add;
+
+use crate::{AsyncRegisterInterface, RegisterInterface};
+
+/// An interface that adds a fixed offset to the address of every register operation.
+///
+/// This is for devices that are made up of identical sub-devices at different base addresses.
+/// One generated driver can then be used for all of them: `MyDevice::new(OffsetInterface::new(interface, 0x40))`.
+///
+/// The offset is added with the `+` operator of the address type, so an overflowing address panics in debug builds.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OffsetInterface<I, AddressType> {
+    /// The wrapped interface
+    pub interface: I,
+    offset: AddressType,
+}
+
+impl<I, AddressType> OffsetInterface<I, AddressType> {
+    /// Create a new interface that adds the offset to every address before passing the operation to the wrapped interface
+    pub const fn new(interface: I, offset: AddressType) -> Self {
+        Self { interface, offset }
+    }
+
+    /// Get the offset that is added to every address
+    pub const fn offset(&self) -> &AddressType {
+        &self.offset
+    }
+
+    /// Get the wrapped interface back
+    pub fn into_inner(self) -> I {
+        self.interface
+    }
+}
+
+impl<I, AddressType> RegisterInterface for OffsetInterface<I, AddressType>
+where
+    I: RegisterInterface<AddressType = AddressType>,
+    AddressType: Copy + Add<Output = AddressType>,
+{
+    type Error = I::Error;
+    type AddressType = AddressType;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.interface
+            .write_register(address + self.offset, size_bits, data)
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.interface
+            .read_register(address + self.offset, size_bits, data)
+    }
+}
+
+impl<I, AddressType> AsyncRegisterInterface for OffsetInterface<I, AddressType>
+where
+    I: AsyncRegisterInterface<AddressType = AddressType>,
+    AddressType: Copy + Add<Output = AddressType>,
+{
+    type Error = I::Error;
+    type AddressType = AddressType;
+
+    async fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.interface
+            .write_register(address + self.offset, size_bits, data)
+            .await
+    }
+
+    async fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.interface
+            .read_register(address + self.offset, size_bits, data)
+            .await
+    }
+}
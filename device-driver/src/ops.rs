@@ -1,7 +1,7 @@
 use core::ops::{BitOrAssign, Shl, Shr};
 
 /// Load an integer from a byte slice located at the `start`..`end` range.
-/// The integer is loaded with the [LE] or [BE] byte order generic param and using lsb0 bit order.
+/// The integer is loaded with the [LE], [BE] or [SwapPairs] byte order generic param and using lsb0 bit order.
 ///
 /// ## Safety:
 ///
@@ -24,7 +24,7 @@ where
         while i < end {
             let byte = unsafe { ByteO::get_byte_from_index(data, i) };
 
-            if (i % 8 == 0) & (i + 8 <= end) {
+            if i.is_multiple_of(8) & (i + 8 <= end) {
                 // We are byte aligned and have a full byte of space left
                 // Do a whole byte in one go for extra performance
                 output |= T::detruncate(byte) << (i - start);
@@ -46,7 +46,7 @@ where
 }
 
 /// Store an integer into a byte slice located at the `start`..`end` range.
-/// The integer is stored with the [LE] or [BE] byte order generic param and using lsb0 bit order.
+/// The integer is stored with the [LE], [BE] or [SwapPairs] byte order generic param and using lsb0 bit order.
 ///
 /// ## Safety:
 ///
@@ -66,7 +66,7 @@ where
         while i < end {
             let byte = unsafe { ByteO::get_byte_from_index_mut(data, i) };
 
-            if (i % 8 == 0) & (i + 8 <= end) {
+            if i.is_multiple_of(8) & (i + 8 <= end) {
                 // We are byte aligned and have a full byte of space left
                 // Do a whole byte in one go for extra performance
                 *byte = (value >> (i - start)).truncate();
@@ -91,7 +91,7 @@ where
 }
 
 /// Load an integer from a byte slice located at the `start`..`end` range.
-/// The integer is loaded with the [LE] or [BE] byte order generic param and using msb0 bit order.
+/// The integer is loaded with the [LE], [BE] or [SwapPairs] byte order generic param and using msb0 bit order.
 /// This is more expensive than the [load_lsb0] function.
 ///
 /// ## Safety:
@@ -116,7 +116,7 @@ where
             // Get the proper byte we should be looking at
             let byte = unsafe { ByteO::get_byte_from_index(data, i) };
 
-            if (i % 8 == 0) & (i + 8 <= end) {
+            if i.is_multiple_of(8) & (i + 8 <= end) {
                 // We are byte aligned and have a full byte of space left
                 // Do a whole byte in one go for extra performance
                 output |= T::detruncate(byte) << (i - start);
@@ -142,7 +142,7 @@ where
 }
 
 /// Store an integer into byte slice located at the `start`..`end` range.
-/// The integer is stored with the [LE] or [BE] byte order generic param and using msb0 bit order.
+/// The integer is stored with the [LE], [BE] or [SwapPairs] byte order generic param and using msb0 bit order.
 /// This is more expensive than the [store_lsb0] function.
 ///
 /// ## Safety:
@@ -164,7 +164,7 @@ where
             // Get the proper byte we should be looking at
             let byte = unsafe { ByteO::get_byte_from_index_mut(data, i) };
 
-            if (i % 8 == 0) & (i + 8 <= end) {
+            if i.is_multiple_of(8) & (i + 8 <= end) {
                 // We are byte aligned and have a full byte of space left
                 // Do a whole byte in one go for extra performance
                 *byte = (value >> (i - start)).truncate();
@@ -216,7 +216,7 @@ fn pivot_msb0(start: usize, end: usize, i: usize) -> usize {
         let num_bits = end - (end - 8).next_multiple_of(8);
         // Calculate the pivot and force it to round down so any error is in the same direction
         // as in the start byte case
-        let pivot = end - ((num_bits + 1) / 2);
+        let pivot = end - num_bits.div_ceil(2);
 
         (num_bits, pivot)
     };
@@ -250,6 +250,9 @@ fn pivot_msb0(start: usize, end: usize, i: usize) -> usize {
 pub struct LE;
 /// Big endian byte order
 pub struct BE;
+/// Little endian byte order with the two bytes of every pair swapped.
+/// The data length must be a multiple of two.
+pub struct SwapPairs;
 
 /// Interface to byte order functions
 pub trait ByteOrder {
@@ -290,6 +293,14 @@ impl ByteOrder for BE {
     }
 }
 
+impl ByteOrder for SwapPairs {
+    #[inline]
+    fn get_byte_index(data_len: usize, bit_index: usize) -> usize {
+        debug_assert!(data_len.is_multiple_of(2));
+        (bit_index / 8) ^ 1
+    }
+}
+
 pub trait TruncateToU8 {
     fn truncate(self) -> u8;
     fn detruncate(val: u8) -> Self;
@@ -484,4 +495,46 @@ mod tests {
             assert_eq!(test_data1, check_data1);
         }
     }
+
+    #[test]
+    fn swap_pairs_same_as_swapped_le() {
+        let swapped = |data: &[u8]| {
+            let mut data = data.to_vec();
+            data.chunks_exact_mut(2).for_each(|pair| pair.swap(0, 1));
+            data
+        };
+
+        for _ in 0..10_000 {
+            let mut data = vec![0u8; rand::random_range(1..=8) * 2];
+            rand::fill(&mut data[..]);
+            let swapped_data = swapped(&data);
+
+            let total_bits = data.len() * 8;
+            let start = rand::random_range(0..total_bits - 1);
+            let end = start + rand::random_range(1..=total_bits - start).min(32);
+
+            assert_eq!(
+                unsafe { load_lsb0::<u32, SwapPairs>(&data, start, end) },
+                unsafe { load_lsb0::<u32, LE>(&swapped_data, start, end) },
+            );
+            assert_eq!(
+                unsafe { load_msb0::<u32, SwapPairs>(&data, start, end) },
+                unsafe { load_msb0::<u32, LE>(&swapped_data, start, end) },
+            );
+
+            let input_data = rand::random::<u32>();
+
+            let mut test_data = data.clone();
+            unsafe { store_lsb0::<_, SwapPairs>(input_data, start, end, &mut test_data) };
+            let mut check_data = swapped_data.clone();
+            unsafe { store_lsb0::<_, LE>(input_data, start, end, &mut check_data) };
+            assert_eq!(test_data, swapped(&check_data));
+
+            let mut test_data = data.clone();
+            unsafe { store_msb0::<_, SwapPairs>(input_data, start, end, &mut test_data) };
+            let mut check_data = swapped_data.clone();
+            unsafe { store_msb0::<_, LE>(input_data, start, end, &mut check_data) };
+            assert_eq!(test_data, swapped(&check_data));
+        }
+    }
 }
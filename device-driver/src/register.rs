@@ -1,6 +1,9 @@
-use core::marker::PhantomData;
+use core::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+};
 
-use crate::{FieldSet, ReadCapability, WriteCapability};
+use crate::{ChangeSet, FieldSet, ReadCapability, WriteCapability};
 
 /// A trait to represent the interface to the device.
 ///
@@ -54,16 +57,62 @@ pub trait AsyncRegisterInterface {
     ) -> Result<(), Self::Error>;
 }
 
+/// A hook that is run after every successful read of a register from the device.
+///
+/// The register operations take the hook as a type parameter, so [NoReadHook] doesn't cost anything.
+/// The generated code implements this trait when the `post_read` or `detect_bus_stuck` config is set.
+pub trait ReadHook<AddressType> {
+    /// The error of the reads with the given interface error.
+    /// This is the interface error itself, unless the hook can fail a read.
+    type Error<E>: From<E>;
+
+    /// Check the bytes that were read. An error fails the read.
+    ///
+    /// The reset value of the register is only created when the check needs it.
+    fn check_read<Register: FieldSet, E>(
+        read: &[u8],
+        reset: fn() -> Register,
+    ) -> Result<(), Self::Error<E>>;
+
+    /// Called with the address that was read, after the check passed
+    fn after_read(address: AddressType);
+}
+
+/// The read hook that does nothing. This is the default of the register operations.
+pub struct NoReadHook;
+
+impl<AddressType> ReadHook<AddressType> for NoReadHook {
+    type Error<E> = E;
+
+    #[inline(always)]
+    fn check_read<Register: FieldSet, E>(
+        _read: &[u8],
+        _reset: fn() -> Register,
+    ) -> Result<(), Self::Error<E>> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn after_read(_address: AddressType) {}
+}
+
 /// Object that performs actions on the device in the context of a register
-pub struct RegisterOperation<'i, Interface, AddressType: Copy, Register: FieldSet, Access> {
+pub struct RegisterOperation<
+    'i,
+    Interface,
+    AddressType: Copy,
+    Register: FieldSet,
+    Access,
+    Hook = NoReadHook,
+> {
     interface: &'i mut Interface,
     address: AddressType,
     register_new_with_reset: fn() -> Register,
-    _phantom: PhantomData<(Register, Access)>,
+    _phantom: PhantomData<(Register, Access, Hook)>,
 }
 
-impl<'i, Interface, AddressType: Copy, Register: FieldSet, Access>
-    RegisterOperation<'i, Interface, AddressType, Register, Access>
+impl<'i, Interface, AddressType: Copy, Register: FieldSet, Access, Hook>
+    RegisterOperation<'i, Interface, AddressType, Register, Access, Hook>
 {
     #[doc(hidden)]
     pub fn new(
@@ -78,10 +127,19 @@ impl<'i, Interface, AddressType: Copy, Register: FieldSet, Access>
             _phantom: PhantomData,
         }
     }
+
+    fn finish_read<E>(&self, read: &[u8]) -> Result<(), Hook::Error<E>>
+    where
+        Hook: ReadHook<AddressType>,
+    {
+        Hook::check_read::<Register, E>(read, self.register_new_with_reset)?;
+        Hook::after_read(self.address);
+        Ok(())
+    }
 }
 
-impl<Interface, AddressType: Copy, Register: FieldSet, Access>
-    RegisterOperation<'_, Interface, AddressType, Register, Access>
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
 where
     Interface: RegisterInterface<AddressType = AddressType>,
     Access: WriteCapability,
@@ -120,14 +178,15 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Register: FieldSet, Access>
-    RegisterOperation<'_, Interface, AddressType, Register, Access>
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
 where
     Interface: RegisterInterface<AddressType = AddressType>,
     Access: ReadCapability,
+    Hook: ReadHook<AddressType>,
 {
     /// Read the register from the device
-    pub fn read(&mut self) -> Result<Register, Interface::Error> {
+    pub fn read(&mut self) -> Result<Register, Hook::Error<Interface::Error>> {
         let mut register = Register::new_with_zero();
 
         self.interface.read_register(
@@ -135,21 +194,114 @@ where
             Register::SIZE_BITS,
             register.get_inner_buffer_mut(),
         )?;
+        self.finish_read::<Interface::Error>(register.get_inner_buffer())?;
         Ok(register)
     }
+
+    /// Read the register repeatedly until the predicate returns true for the read value.
+    ///
+    /// This is useful for e.g. waiting on a ready bit. The register is read at most `max_attempts` times
+    /// without any delay between the reads. When the predicate doesn't hold for any of them, [PollError::Timeout] is returned.
+    pub fn poll_until(
+        &mut self,
+        mut f: impl FnMut(&Register) -> bool,
+        max_attempts: u32,
+    ) -> Result<Register, PollError<Hook::Error<Interface::Error>>> {
+        for _ in 0..max_attempts {
+            let register = self.read()?;
+            if f(&register) {
+                return Ok(register);
+            }
+        }
+
+        Err(PollError::Timeout)
+    }
+
+    /// Read the register from the device and also get the raw bytes as they were read.
+    ///
+    /// This is useful to e.g. validate a checksum over the raw bytes next to the decoded fields, without reading twice.
+    pub fn read_with_raw<const N: usize>(
+        &mut self,
+    ) -> Result<(Register, [u8; N]), Hook::Error<Interface::Error>>
+    where
+        Register: Copy + Into<[u8; N]>,
+    {
+        let register = self.read()?;
+        Ok((register, register.into()))
+    }
+
+    /// Read the register from the device straight into the given buffer, without creating the register.
+    ///
+    /// This is useful for e.g. logging the raw contents of registers without copying them around.
+    /// The buffer must be exactly as long as the register, otherwise [ReadRawIntoError::WrongLength] is returned.
+    pub fn read_raw_into(
+        &mut self,
+        out: &mut [u8],
+    ) -> Result<(), ReadRawIntoError<Hook::Error<Interface::Error>>> {
+        check_raw_length::<Register, _>(out)?;
+        self.interface
+            .read_register(self.address, Register::SIZE_BITS, out)
+            .map_err(<Hook::Error<Interface::Error>>::from)?;
+        self.finish_read::<Interface::Error>(out)?;
+        Ok(())
+    }
 }
 
-impl<Interface, AddressType: Copy, Register: FieldSet, Access>
-    RegisterOperation<'_, Interface, AddressType, Register, Access>
+impl<Interface, AddressType: Copy, Register: VariableSize, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Read the register of which the size is determined by its length field.
+    ///
+    /// The register is read in two phases. First only the bytes that hold the length field are read.
+    /// Then the register is read with the size the length field indicates. The bytes past that size are left zero.
+    pub fn read_variable(
+        &mut self,
+    ) -> Result<Register, VariableSizeReadError<Hook::Error<Interface::Error>>> {
+        let mut register = Register::new_with_zero();
+
+        let prefix_len = Register::LENGTH_PREFIX_SIZE_BITS.div_ceil(8) as usize;
+        self.interface
+            .read_register(
+                self.address,
+                Register::LENGTH_PREFIX_SIZE_BITS,
+                &mut register.get_inner_buffer_mut()[..prefix_len],
+            )
+            .map_err(<Hook::Error<Interface::Error>>::from)?;
+
+        let size = checked_variable_size(&register)?;
+        self.interface
+            .read_register(
+                self.address,
+                size as u32 * 8,
+                &mut register.get_inner_buffer_mut()[..size],
+            )
+            .map_err(<Hook::Error<Interface::Error>>::from)?;
+        self.finish_read::<Interface::Error>(&register.get_inner_buffer()[..size])?;
+        Ok(register)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
 where
     Interface: RegisterInterface<AddressType = AddressType>,
     Access: ReadCapability + WriteCapability,
+    Hook: ReadHook<AddressType>,
 {
     /// Modify the existing register value.
     ///
     /// The register is read, the value is then passed to the closure for making changes.
     /// The result is then written back to the device.
-    pub fn modify<R>(&mut self, f: impl FnOnce(&mut Register) -> R) -> Result<R, Interface::Error> {
+    ///
+    /// The value returned by the closure is passed through. Use [Self::modify_tracked] to get the changed fields instead.
+    pub fn modify<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Hook::Error<Interface::Error>> {
         let mut register = self.read()?;
         let returned = f(&mut register);
         self.interface.write_register(
@@ -161,8 +313,35 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Register: FieldSet, Access>
-    RegisterOperation<'_, Interface, AddressType, Register, Access>
+impl<Interface, AddressType: Copy, Register: FieldSet + Copy, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability + WriteCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Modify the existing register value and report which fields were changed.
+    ///
+    /// This works like [Self::modify], but instead of the return value of the closure
+    /// the [ChangeSet] between the read and the written value is returned.
+    pub fn modify_tracked(
+        &mut self,
+        f: impl FnOnce(&mut Register),
+    ) -> Result<ChangeSet<Register>, Hook::Error<Interface::Error>> {
+        let before = self.read()?;
+        let mut after = before;
+        f(&mut after);
+        self.interface.write_register(
+            self.address,
+            Register::SIZE_BITS,
+            after.get_inner_buffer(),
+        )?;
+        Ok(ChangeSet::new(before, after))
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
 where
     Interface: AsyncRegisterInterface<AddressType = AddressType>,
     Access: WriteCapability,
@@ -208,14 +387,15 @@ where
     }
 }
 
-impl<Interface, AddressType: Copy, Register: FieldSet, Access>
-    RegisterOperation<'_, Interface, AddressType, Register, Access>
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
 where
     Interface: AsyncRegisterInterface<AddressType = AddressType>,
     Access: ReadCapability,
+    Hook: ReadHook<AddressType>,
 {
     /// Read the register from the device
-    pub async fn read_async(&mut self) -> Result<Register, Interface::Error> {
+    pub async fn read_async(&mut self) -> Result<Register, Hook::Error<Interface::Error>> {
         let mut register = Register::new_with_zero();
 
         self.interface
@@ -225,24 +405,123 @@ where
                 register.get_inner_buffer_mut(),
             )
             .await?;
+        self.finish_read::<Interface::Error>(register.get_inner_buffer())?;
         Ok(register)
     }
+
+    /// Read the register repeatedly until the predicate returns true for the read value.
+    ///
+    /// This is useful for e.g. waiting on a ready bit. The register is read at most `max_attempts` times.
+    /// Between the reads, the future returned by `delay` is awaited, e.g. `|| embassy_time::Timer::after_millis(1)`.
+    /// When the predicate doesn't hold for any of the reads, [PollError::Timeout] is returned.
+    pub async fn poll_until_async<DelayFuture: Future<Output = ()>>(
+        &mut self,
+        mut f: impl FnMut(&Register) -> bool,
+        max_attempts: u32,
+        mut delay: impl FnMut() -> DelayFuture,
+    ) -> Result<Register, PollError<Hook::Error<Interface::Error>>> {
+        for attempt in 0..max_attempts {
+            if attempt != 0 {
+                delay().await;
+            }
+
+            let register = self.read_async().await?;
+            if f(&register) {
+                return Ok(register);
+            }
+        }
+
+        Err(PollError::Timeout)
+    }
+
+    /// Read the register from the device and also get the raw bytes as they were read.
+    ///
+    /// This is useful to e.g. validate a checksum over the raw bytes next to the decoded fields, without reading twice.
+    pub async fn read_with_raw_async<const N: usize>(
+        &mut self,
+    ) -> Result<(Register, [u8; N]), Hook::Error<Interface::Error>>
+    where
+        Register: Copy + Into<[u8; N]>,
+    {
+        let register = self.read_async().await?;
+        Ok((register, register.into()))
+    }
+
+    /// Read the register from the device straight into the given buffer, without creating the register.
+    ///
+    /// This is useful for e.g. logging the raw contents of registers without copying them around.
+    /// The buffer must be exactly as long as the register, otherwise [ReadRawIntoError::WrongLength] is returned.
+    pub async fn read_raw_into_async(
+        &mut self,
+        out: &mut [u8],
+    ) -> Result<(), ReadRawIntoError<Hook::Error<Interface::Error>>> {
+        check_raw_length::<Register, _>(out)?;
+        self.interface
+            .read_register(self.address, Register::SIZE_BITS, out)
+            .await
+            .map_err(<Hook::Error<Interface::Error>>::from)?;
+        self.finish_read::<Interface::Error>(out)?;
+        Ok(())
+    }
 }
 
-impl<Interface, AddressType: Copy, Register: FieldSet, Access>
-    RegisterOperation<'_, Interface, AddressType, Register, Access>
+impl<Interface, AddressType: Copy, Register: VariableSize, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
+where
+    Interface: AsyncRegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Read the register of which the size is determined by its length field.
+    ///
+    /// The register is read in two phases. First only the bytes that hold the length field are read.
+    /// Then the register is read with the size the length field indicates. The bytes past that size are left zero.
+    pub async fn read_variable_async(
+        &mut self,
+    ) -> Result<Register, VariableSizeReadError<Hook::Error<Interface::Error>>> {
+        let mut register = Register::new_with_zero();
+
+        let prefix_len = Register::LENGTH_PREFIX_SIZE_BITS.div_ceil(8) as usize;
+        self.interface
+            .read_register(
+                self.address,
+                Register::LENGTH_PREFIX_SIZE_BITS,
+                &mut register.get_inner_buffer_mut()[..prefix_len],
+            )
+            .await
+            .map_err(<Hook::Error<Interface::Error>>::from)?;
+
+        let size = checked_variable_size(&register)?;
+        self.interface
+            .read_register(
+                self.address,
+                size as u32 * 8,
+                &mut register.get_inner_buffer_mut()[..size],
+            )
+            .await
+            .map_err(<Hook::Error<Interface::Error>>::from)?;
+        self.finish_read::<Interface::Error>(&register.get_inner_buffer()[..size])?;
+        Ok(register)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
 where
     Interface: AsyncRegisterInterface<AddressType = AddressType>,
     Access: ReadCapability + WriteCapability,
+    Hook: ReadHook<AddressType>,
 {
     /// Modify the existing register value.
     ///
     /// The register is read, the value is then passed to the closure for making changes.
     /// The result is then written back to the device.
+    ///
+    /// The value returned by the closure is passed through. Use [Self::modify_tracked_async] to get the changed fields instead.
     pub async fn modify_async<R>(
         &mut self,
         f: impl FnOnce(&mut Register) -> R,
-    ) -> Result<R, Interface::Error> {
+    ) -> Result<R, Hook::Error<Interface::Error>> {
         let mut register = self.read_async().await?;
         let returned = f(&mut register);
         self.interface
@@ -255,3 +534,664 @@ where
         Ok(returned)
     }
 }
+
+impl<Interface, AddressType: Copy, Register: FieldSet + Copy, Access, Hook>
+    RegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
+where
+    Interface: AsyncRegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability + WriteCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Modify the existing register value and report which fields were changed.
+    ///
+    /// This works like [Self::modify_async], but instead of the return value of the closure
+    /// the [ChangeSet] between the read and the written value is returned.
+    pub async fn modify_tracked_async(
+        &mut self,
+        f: impl FnOnce(&mut Register),
+    ) -> Result<ChangeSet<Register>, Hook::Error<Interface::Error>> {
+        let before = self.read_async().await?;
+        let mut after = before;
+        f(&mut after);
+        self.interface
+            .write_register(self.address, Register::SIZE_BITS, after.get_inner_buffer())
+            .await?;
+        Ok(ChangeSet::new(before, after))
+    }
+}
+
+/// The error returned when polling a register
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum PollError<E> {
+    /// The interface returned an error
+    Interface(E),
+    /// The predicate didn't hold within the maximum amount of attempts
+    Timeout,
+}
+
+impl<E> From<E> for PollError<E> {
+    fn from(value: E) -> Self {
+        Self::Interface(value)
+    }
+}
+
+impl<E: Display> Display for PollError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PollError::Interface(e) => write!(f, "Interface error: {e}"),
+            PollError::Timeout => write!(f, "Polling timed out"),
+        }
+    }
+}
+
+impl<E: Display + Debug> core::error::Error for PollError<E> {}
+
+/// The error returned when reading a register with a check for a stuck bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum BusStuckError<E> {
+    /// The interface returned an error
+    Interface(E),
+    /// All read bytes had this value (`0x00` or `0xFF`), while the reset value of the register doesn't
+    Stuck(u8),
+}
+
+impl<E> From<E> for BusStuckError<E> {
+    fn from(value: E) -> Self {
+        Self::Interface(value)
+    }
+}
+
+impl<E: Display> Display for BusStuckError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BusStuckError::Interface(e) => write!(f, "Interface error: {e}"),
+            BusStuckError::Stuck(value) => {
+                write!(f, "The bus seems stuck, all read bytes are {value:#04X}")
+            }
+        }
+    }
+}
+
+impl<E: Display + Debug> core::error::Error for BusStuckError<E> {}
+
+/// Check if the read bytes are all `0x00` or all `0xFF` while the reset value of the register isn't.
+/// This is used by the generated read hook when `detect_bus_stuck` is set.
+#[doc(hidden)]
+pub fn check_bus_stuck<Register: FieldSet, E>(
+    read: &[u8],
+    reset: fn() -> Register,
+) -> Result<(), BusStuckError<E>> {
+    for stuck_value in [0x00, 0xFF] {
+        let all_stuck = |bytes: &[u8]| bytes.iter().all(|byte| *byte == stuck_value);
+        if !read.is_empty() && all_stuck(read) && !all_stuck(reset().get_inner_buffer()) {
+            return Err(BusStuckError::Stuck(stuck_value));
+        }
+    }
+
+    Ok(())
+}
+
+/// The error returned when reading a register into a caller supplied buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum ReadRawIntoError<E> {
+    /// The interface returned an error
+    Interface(E),
+    /// The buffer doesn't have the same length as the register
+    WrongLength {
+        /// The size of the register in bytes
+        expected: usize,
+        /// The length of the given buffer
+        actual: usize,
+    },
+}
+
+impl<E> From<E> for ReadRawIntoError<E> {
+    fn from(value: E) -> Self {
+        Self::Interface(value)
+    }
+}
+
+impl<E: Display> Display for ReadRawIntoError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ReadRawIntoError::Interface(e) => write!(f, "Interface error: {e}"),
+            ReadRawIntoError::WrongLength { expected, actual } => {
+                write!(
+                    f,
+                    "The buffer is {actual} bytes long, but the register is {expected} bytes"
+                )
+            }
+        }
+    }
+}
+
+impl<E: Display + Debug> core::error::Error for ReadRawIntoError<E> {}
+
+fn check_raw_length<Register: FieldSet, E>(out: &[u8]) -> Result<(), ReadRawIntoError<E>> {
+    let expected = Register::SIZE_BITS.div_ceil(8) as usize;
+    match out.len() == expected {
+        true => Ok(()),
+        false => Err(ReadRawIntoError::WrongLength {
+            expected,
+            actual: out.len(),
+        }),
+    }
+}
+
+/// A register of which the size is determined at runtime by a length field in its first bytes
+pub trait VariableSize: FieldSet {
+    /// The size of the first part of the register that holds the length field, in whole bytes
+    const LENGTH_PREFIX_SIZE_BITS: u32;
+
+    /// The size of the register in bytes as indicated by the length field. This includes the length prefix.
+    fn size_bytes(&self) -> usize;
+}
+
+/// The error returned when reading a register with a variable size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum VariableSizeReadError<E> {
+    /// The interface returned an error
+    Interface(E),
+    /// The length field indicates a size in bytes that is bigger than the maximum size of the register
+    TooLong {
+        /// The size in bytes that was indicated
+        size: usize,
+    },
+}
+
+impl<E> From<E> for VariableSizeReadError<E> {
+    fn from(value: E) -> Self {
+        Self::Interface(value)
+    }
+}
+
+impl<E: Display> Display for VariableSizeReadError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VariableSizeReadError::Interface(e) => write!(f, "Interface error: {e}"),
+            VariableSizeReadError::TooLong { size } => {
+                write!(
+                    f,
+                    "The length field indicates a size of {size} bytes, which doesn't fit in the register"
+                )
+            }
+        }
+    }
+}
+
+impl<E: Display + Debug> core::error::Error for VariableSizeReadError<E> {}
+
+fn checked_variable_size<Register: VariableSize, E>(
+    register: &Register,
+) -> Result<usize, VariableSizeReadError<E>> {
+    let size = register.size_bytes();
+    match size <= register.get_inner_buffer().len() {
+        true => Ok(size),
+        false => Err(VariableSizeReadError::TooLong { size }),
+    }
+}
+
+/// Object that performs actions on the device in the context of a shadowed register.
+///
+/// The last written value is kept in a shadow in RAM. Reads return the shadow instead of reading the device,
+/// so even write-only registers can be read and modified.
+///
+/// The read hook is never run, since the device isn't read. It only sets the error type of the reads and `modify`,
+/// so that it's the same as for other registers.
+pub struct ShadowedRegisterOperation<
+    'i,
+    Interface,
+    AddressType: Copy,
+    Register: FieldSet,
+    Access,
+    Hook = NoReadHook,
+> {
+    interface: &'i mut Interface,
+    address: AddressType,
+    register_new_with_reset: fn() -> Register,
+    shadow: &'i mut Register,
+    _phantom: PhantomData<(Access, Hook)>,
+}
+
+impl<'i, Interface, AddressType: Copy, Register: FieldSet, Access, Hook>
+    ShadowedRegisterOperation<'i, Interface, AddressType, Register, Access, Hook>
+{
+    #[doc(hidden)]
+    pub fn new(
+        interface: &'i mut Interface,
+        address: AddressType,
+        register_new_with_reset: fn() -> Register,
+        shadow: &'i mut Register,
+    ) -> Self {
+        Self {
+            interface,
+            address,
+            register_new_with_reset,
+            shadow,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet + Copy, Access, Hook>
+    ShadowedRegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: WriteCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Write to the register and update the shadow.
+    ///
+    /// The closure is given the write object initialized to the reset value of the register.
+    /// If no reset value is specified for this register, this function is the same as [Self::write_with_zero].
+    pub fn write<R>(&mut self, f: impl FnOnce(&mut Register) -> R) -> Result<R, Interface::Error> {
+        let mut register = (self.register_new_with_reset)();
+        let returned = f(&mut register);
+        self.write_and_update_shadow(register)?;
+        Ok(returned)
+    }
+
+    /// Write to the register and update the shadow.
+    ///
+    /// The closure is given the write object initialized to all zero.
+    pub fn write_with_zero<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Interface::Error> {
+        let mut register = Register::new_with_zero();
+        let returned = f(&mut register);
+        self.write_and_update_shadow(register)?;
+        Ok(returned)
+    }
+
+    /// Modify the shadowed register value.
+    ///
+    /// The shadow is passed to the closure for making changes.
+    /// The result is then written to the device and the shadow.
+    pub fn modify<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Hook::Error<Interface::Error>> {
+        let mut register = *self.shadow;
+        let returned = f(&mut register);
+        self.write_and_update_shadow(register)?;
+        Ok(returned)
+    }
+
+    fn write_and_update_shadow(&mut self, register: Register) -> Result<(), Interface::Error> {
+        self.interface.write_register(
+            self.address,
+            Register::SIZE_BITS,
+            register.get_inner_buffer(),
+        )?;
+        *self.shadow = register;
+        Ok(())
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet + Copy, Access, Hook>
+    ShadowedRegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Hook: ReadHook<AddressType>,
+{
+    /// Read the shadow of the register. The device is not accessed.
+    ///
+    /// This is the last written value, or the reset value if the register hasn't been written yet.
+    pub fn read(&mut self) -> Result<Register, Hook::Error<Interface::Error>> {
+        Ok(*self.shadow)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet + Copy, Access, Hook>
+    ShadowedRegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
+where
+    Interface: AsyncRegisterInterface<AddressType = AddressType>,
+    Access: WriteCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Write to the register and update the shadow.
+    ///
+    /// The closure is given the write object initialized to the reset value of the register.
+    /// If no reset value is specified for this register, this function is the same as [Self::write_with_zero_async].
+    pub async fn write_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Interface::Error> {
+        let mut register = (self.register_new_with_reset)();
+        let returned = f(&mut register);
+        self.write_and_update_shadow_async(register).await?;
+        Ok(returned)
+    }
+
+    /// Write to the register and update the shadow.
+    ///
+    /// The closure is given the write object initialized to all zero.
+    pub async fn write_with_zero_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Interface::Error> {
+        let mut register = Register::new_with_zero();
+        let returned = f(&mut register);
+        self.write_and_update_shadow_async(register).await?;
+        Ok(returned)
+    }
+
+    /// Modify the shadowed register value.
+    ///
+    /// The shadow is passed to the closure for making changes.
+    /// The result is then written to the device and the shadow.
+    pub async fn modify_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Hook::Error<Interface::Error>> {
+        let mut register = *self.shadow;
+        let returned = f(&mut register);
+        self.write_and_update_shadow_async(register).await?;
+        Ok(returned)
+    }
+
+    async fn write_and_update_shadow_async(
+        &mut self,
+        register: Register,
+    ) -> Result<(), Interface::Error> {
+        self.interface
+            .write_register(
+                self.address,
+                Register::SIZE_BITS,
+                register.get_inner_buffer(),
+            )
+            .await?;
+        *self.shadow = register;
+        Ok(())
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet + Copy, Access, Hook>
+    ShadowedRegisterOperation<'_, Interface, AddressType, Register, Access, Hook>
+where
+    Interface: AsyncRegisterInterface<AddressType = AddressType>,
+    Hook: ReadHook<AddressType>,
+{
+    /// Read the shadow of the register. The device is not accessed.
+    ///
+    /// This is the last written value, or the reset value if the register hasn't been written yet.
+    pub async fn read_async(&mut self) -> Result<Register, Hook::Error<Interface::Error>> {
+        Ok(*self.shadow)
+    }
+}
+
+/// Object that performs actions on the device in the context of a virtual register.
+///
+/// A virtual register is made up of `PARTS` equally sized registers that are located at their own addresses.
+/// The value of the register is read from and written to every part in order,
+/// where the first address holds the first bytes of the register.
+pub struct SpannedRegisterOperation<
+    'i,
+    Interface,
+    AddressType: Copy,
+    Register: FieldSet,
+    Access,
+    const PARTS: usize,
+    Hook = NoReadHook,
+> {
+    interface: &'i mut Interface,
+    addresses: [AddressType; PARTS],
+    register_new_with_reset: fn() -> Register,
+    _phantom: PhantomData<(Register, Access, Hook)>,
+}
+
+impl<'i, Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'i, Interface, AddressType, Register, Access, PARTS, Hook>
+{
+    const PART_SIZE_BITS: u32 = Register::SIZE_BITS / PARTS as u32;
+
+    #[doc(hidden)]
+    pub fn new(
+        interface: &'i mut Interface,
+        addresses: [AddressType; PARTS],
+        register_new_with_reset: fn() -> Register,
+    ) -> Self {
+        Self {
+            interface,
+            addresses,
+            register_new_with_reset,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn part_len(register: &Register) -> usize {
+        register.get_inner_buffer().len() / PARTS
+    }
+
+    /// The whole register is checked, after which the hook is run for every part
+    fn finish_read<E>(&self, register: &Register) -> Result<(), Hook::Error<E>>
+    where
+        Hook: ReadHook<AddressType>,
+    {
+        Hook::check_read::<Register, E>(register.get_inner_buffer(), self.register_new_with_reset)?;
+        for address in self.addresses {
+            Hook::after_read(address);
+        }
+        Ok(())
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'_, Interface, AddressType, Register, Access, PARTS, Hook>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+{
+    fn write_parts(&mut self, register: &Register) -> Result<(), Interface::Error> {
+        let part_len = Self::part_len(register);
+
+        for (address, part) in self
+            .addresses
+            .iter()
+            .zip(register.get_inner_buffer().chunks(part_len))
+        {
+            self.interface
+                .write_register(*address, Self::PART_SIZE_BITS, part)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_parts(&mut self, register: &mut Register) -> Result<(), Interface::Error> {
+        let part_len = Self::part_len(register);
+
+        for (address, part) in self
+            .addresses
+            .iter()
+            .zip(register.get_inner_buffer_mut().chunks_mut(part_len))
+        {
+            self.interface
+                .read_register(*address, Self::PART_SIZE_BITS, part)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'_, Interface, AddressType, Register, Access, PARTS, Hook>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: WriteCapability,
+{
+    /// Write to the register.
+    ///
+    /// The closure is given the write object initialized to the reset value of the register.
+    /// If no reset value is specified for this register, this function is the same as [Self::write_with_zero].
+    pub fn write<R>(&mut self, f: impl FnOnce(&mut Register) -> R) -> Result<R, Interface::Error> {
+        let mut register = (self.register_new_with_reset)();
+        let returned = f(&mut register);
+        self.write_parts(&register)?;
+        Ok(returned)
+    }
+
+    /// Write to the register.
+    ///
+    /// The closure is given the write object initialized to all zero.
+    pub fn write_with_zero<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Interface::Error> {
+        let mut register = Register::new_with_zero();
+        let returned = f(&mut register);
+        self.write_parts(&register)?;
+        Ok(returned)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'_, Interface, AddressType, Register, Access, PARTS, Hook>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Read the register from the device
+    pub fn read(&mut self) -> Result<Register, Hook::Error<Interface::Error>> {
+        let mut register = Register::new_with_zero();
+        self.read_parts(&mut register)?;
+        self.finish_read::<Interface::Error>(&register)?;
+        Ok(register)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'_, Interface, AddressType, Register, Access, PARTS, Hook>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability + WriteCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Modify the existing register value.
+    ///
+    /// The register is read, the value is then passed to the closure for making changes.
+    /// The result is then written back to the device.
+    pub fn modify<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Hook::Error<Interface::Error>> {
+        let mut register = self.read()?;
+        let returned = f(&mut register);
+        self.write_parts(&register)?;
+        Ok(returned)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'_, Interface, AddressType, Register, Access, PARTS, Hook>
+where
+    Interface: AsyncRegisterInterface<AddressType = AddressType>,
+{
+    async fn write_parts_async(&mut self, register: &Register) -> Result<(), Interface::Error> {
+        let part_len = Self::part_len(register);
+
+        for (address, part) in self
+            .addresses
+            .iter()
+            .zip(register.get_inner_buffer().chunks(part_len))
+        {
+            self.interface
+                .write_register(*address, Self::PART_SIZE_BITS, part)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn read_parts_async(&mut self, register: &mut Register) -> Result<(), Interface::Error> {
+        let part_len = Self::part_len(register);
+
+        for (address, part) in self
+            .addresses
+            .iter()
+            .zip(register.get_inner_buffer_mut().chunks_mut(part_len))
+        {
+            self.interface
+                .read_register(*address, Self::PART_SIZE_BITS, part)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'_, Interface, AddressType, Register, Access, PARTS, Hook>
+where
+    Interface: AsyncRegisterInterface<AddressType = AddressType>,
+    Access: WriteCapability,
+{
+    /// Write to the register.
+    ///
+    /// The closure is given the write object initialized to the reset value of the register.
+    /// If no reset value is specified for this register, this function is the same as [Self::write_with_zero].
+    pub async fn write_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Interface::Error> {
+        let mut register = (self.register_new_with_reset)();
+        let returned = f(&mut register);
+        self.write_parts_async(&register).await?;
+        Ok(returned)
+    }
+
+    /// Write to the register.
+    ///
+    /// The closure is given the write object initialized to all zero.
+    pub async fn write_with_zero_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Interface::Error> {
+        let mut register = Register::new_with_zero();
+        let returned = f(&mut register);
+        self.write_parts_async(&register).await?;
+        Ok(returned)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'_, Interface, AddressType, Register, Access, PARTS, Hook>
+where
+    Interface: AsyncRegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Read the register from the device
+    pub async fn read_async(&mut self) -> Result<Register, Hook::Error<Interface::Error>> {
+        let mut register = Register::new_with_zero();
+        self.read_parts_async(&mut register).await?;
+        self.finish_read::<Interface::Error>(&register)?;
+        Ok(register)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access, const PARTS: usize, Hook>
+    SpannedRegisterOperation<'_, Interface, AddressType, Register, Access, PARTS, Hook>
+where
+    Interface: AsyncRegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability + WriteCapability,
+    Hook: ReadHook<AddressType>,
+{
+    /// Modify the existing register value.
+    ///
+    /// The register is read, the value is then passed to the closure for making changes.
+    /// The result is then written back to the device.
+    pub async fn modify_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Hook::Error<Interface::Error>> {
+        let mut register = self.read_async().await?;
+        let returned = f(&mut register);
+        self.write_parts_async(&register).await?;
+        Ok(returned)
+    }
+}
@@ -0,0 +1,21 @@
+/// A trait to represent control over the reset of the device, e.g. a reset pin.
+///
+/// Implement it on the interface to use the generated `reset_with_delay` function.
+pub trait ResetInterface {
+    /// The error type
+    type Error;
+
+    /// Assert (`true`) or release (`false`) the reset of the device
+    fn set_reset(&mut self, asserted: bool) -> Result<(), Self::Error>;
+}
+
+/// A trait to represent control over the reset of the device, e.g. a reset pin.
+///
+/// Implement it on the interface to use the generated `reset_with_delay_async` function.
+pub trait AsyncResetInterface {
+    /// The error type
+    type Error;
+
+    /// Assert (`true`) or release (`false`) the reset of the device
+    async fn set_reset(&mut self, asserted: bool) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,114 @@
+use crate::{AsyncRegisterInterface, RegisterInterface};
+
+/// A trait to represent a SPI device with registers.
+///
+/// Both functions must do a single transaction, keeping the chip select asserted in between the header and the data.
+/// Wrap it in a [SpiInterface] to use it with a generated device.
+///
+/// With `embedded-hal` this is a `transaction` on the `SpiDevice` with a write operation of the header
+/// followed by a write or read operation of the data.
+pub trait SpiRegisterDevice {
+    /// The error type
+    type Error;
+
+    /// Write the header, followed by the data
+    fn write(&mut self, header: &[u8], data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Write the header, followed by reading into the data
+    fn read(&mut self, header: &[u8], data: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// A trait to represent a SPI device with registers.
+///
+/// Both functions must do a single asynchronous transaction, keeping the chip select asserted in between the header and the data.
+/// Wrap it in a [SpiInterface] to use it with a generated device.
+///
+/// With `embedded-hal-async` this is a `transaction` on the `SpiDevice` with a write operation of the header
+/// followed by a write or read operation of the data.
+pub trait AsyncSpiRegisterDevice {
+    /// The error type
+    type Error;
+
+    /// Write the header, followed by the data
+    async fn write(&mut self, header: &[u8], data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Write the header, followed by reading into the data
+    async fn read(&mut self, header: &[u8], data: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// A register interface for SPI devices that send the register address as the first byte of a transaction.
+///
+/// The read or write mask is combined with the address (using bitwise or) to tell the device what to do.
+/// Many devices set the highest bit for reads, which is a read mask of `0x80` and a write mask of `0x00`.
+/// Other devices use a different bit or set a bit for writes instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpiInterface<I> {
+    /// The SPI device
+    pub interface: I,
+    read_mask: u8,
+    write_mask: u8,
+}
+
+impl<I> SpiInterface<I> {
+    /// Create a new interface on top of the SPI device with the masks that are applied to the address
+    pub const fn new(interface: I, read_mask: u8, write_mask: u8) -> Self {
+        Self {
+            interface,
+            read_mask,
+            write_mask,
+        }
+    }
+
+    /// Get the SPI device back
+    pub fn into_inner(self) -> I {
+        self.interface
+    }
+}
+
+impl<I: SpiRegisterDevice> RegisterInterface for SpiInterface<I> {
+    type Error = I::Error;
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.interface.write(&[address | self.write_mask], data)
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.interface.read(&[address | self.read_mask], data)
+    }
+}
+
+impl<I: AsyncSpiRegisterDevice> AsyncRegisterInterface for SpiInterface<I> {
+    type Error = I::Error;
+    type AddressType = u8;
+
+    async fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.interface
+            .write(&[address | self.write_mask], data)
+            .await
+    }
+
+    async fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.interface.read(&[address | self.read_mask], data).await
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{AsyncCommandInterface, AsyncRegisterInterface, CommandInterface, RegisterInterface};
+
+/// An interface that combines two separate interfaces, one for registers and one for commands.
+///
+/// This is for devices where registers and commands are accessed through a different interface,
+/// e.g. when their address spaces overlap. Register operations are done on the register interface
+/// and commands are dispatched on the command interface.
+///
+/// The generated device is created with this interface: `MyDevice::new(SplitInterface::new(registers, commands))`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SplitInterface<RegisterInterface, CommandInterface> {
+    /// The interface used for register operations
+    pub registers: RegisterInterface,
+    /// The interface used to dispatch commands
+    pub commands: CommandInterface,
+}
+
+impl<R, C> SplitInterface<R, C> {
+    /// Create a new interface out of the register and command interfaces
+    pub const fn new(registers: R, commands: C) -> Self {
+        Self {
+            registers,
+            commands,
+        }
+    }
+
+    /// Split the interface back into the register and command interfaces
+    pub fn into_inner(self) -> (R, C) {
+        (self.registers, self.commands)
+    }
+}
+
+impl<R: RegisterInterface, C> RegisterInterface for SplitInterface<R, C> {
+    type Error = R::Error;
+    type AddressType = R::AddressType;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.registers.write_register(address, size_bits, data)
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.registers.read_register(address, size_bits, data)
+    }
+}
+
+impl<R: AsyncRegisterInterface, C> AsyncRegisterInterface for SplitInterface<R, C> {
+    type Error = R::Error;
+    type AddressType = R::AddressType;
+
+    async fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.registers
+            .write_register(address, size_bits, data)
+            .await
+    }
+
+    async fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.registers.read_register(address, size_bits, data).await
+    }
+}
+
+impl<R, C: CommandInterface> CommandInterface for SplitInterface<R, C> {
+    type Error = C::Error;
+    type AddressType = C::AddressType;
+
+    fn dispatch_command(
+        &mut self,
+        address: Self::AddressType,
+        size_bits_in: u32,
+        input: &[u8],
+        size_bits_out: u32,
+        output: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.commands
+            .dispatch_command(address, size_bits_in, input, size_bits_out, output)
+    }
+}
+
+impl<R, C: AsyncCommandInterface> AsyncCommandInterface for SplitInterface<R, C> {
+    type Error = C::Error;
+    type AddressType = C::AddressType;
+
+    async fn dispatch_command(
+        &mut self,
+        address: Self::AddressType,
+        size_bits_in: u32,
+        input: &[u8],
+        size_bits_out: u32,
+        output: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.commands
+            .dispatch_command(address, size_bits_in, input, size_bits_out, output)
+            .await
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{AsyncRegisterInterface, RegisterInterface};
+
+/// A register write that is staged by the generated `configure` function
+#[doc(hidden)]
+pub struct StagedWrite<'a, AddressType> {
+    pub address: AddressType,
+    pub size_bits: u32,
+    /// The bytes of the register or `None` when the register is not staged
+    pub value: Option<&'a [u8]>,
+    /// The register is located right after the previous register, so they can be written in one go
+    pub follows_previous: bool,
+}
+
+/// Write all staged registers in order.
+/// Staged registers that follow each other are combined into a single write.
+///
+/// `BUFFER_SIZE` must be at least the summed size of the registers in bytes.
+#[doc(hidden)]
+pub fn write_staged<I: RegisterInterface, const BUFFER_SIZE: usize>(
+    interface: &mut I,
+    writes: &[StagedWrite<'_, I::AddressType>],
+) -> Result<(), I::Error> {
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut run = None;
+
+    for write in writes {
+        if let Some(flushed_run) = stage(&mut buffer, &mut run, write) {
+            let StagedRun {
+                address,
+                size_bits,
+                len,
+            } = flushed_run;
+            interface.write_register(address, size_bits, &buffer[..len])?;
+            stage(&mut buffer, &mut run, write);
+        }
+    }
+
+    if let Some(StagedRun {
+        address,
+        size_bits,
+        len,
+    }) = run
+    {
+        interface.write_register(address, size_bits, &buffer[..len])?;
+    }
+
+    Ok(())
+}
+
+/// Write all staged registers in order.
+/// Staged registers that follow each other are combined into a single write.
+///
+/// `BUFFER_SIZE` must be at least the summed size of the registers in bytes.
+#[doc(hidden)]
+pub async fn write_staged_async<I: AsyncRegisterInterface, const BUFFER_SIZE: usize>(
+    interface: &mut I,
+    writes: &[StagedWrite<'_, I::AddressType>],
+) -> Result<(), I::Error> {
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut run = None;
+
+    for write in writes {
+        if let Some(flushed_run) = stage(&mut buffer, &mut run, write) {
+            let StagedRun {
+                address,
+                size_bits,
+                len,
+            } = flushed_run;
+            interface
+                .write_register(address, size_bits, &buffer[..len])
+                .await?;
+            stage(&mut buffer, &mut run, write);
+        }
+    }
+
+    if let Some(StagedRun {
+        address,
+        size_bits,
+        len,
+    }) = run
+    {
+        interface
+            .write_register(address, size_bits, &buffer[..len])
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// A run of registers that follow each other, staged in the first `len` bytes of the buffer
+#[derive(Clone, Copy)]
+struct StagedRun<AddressType> {
+    address: AddressType,
+    size_bits: u32,
+    len: usize,
+}
+
+/// Add the write to the current run or start a new run.
+///
+/// When the current run has to be written out first, it's taken out and returned without staging the write.
+/// The write must then be staged again after the returned run is written.
+fn stage<AddressType: Copy>(
+    buffer: &mut [u8],
+    run: &mut Option<StagedRun<AddressType>>,
+    write: &StagedWrite<'_, AddressType>,
+) -> Option<StagedRun<AddressType>> {
+    let Some(value) = write.value else {
+        return run.take();
+    };
+
+    match run {
+        Some(run) if write.follows_previous => {
+            buffer[run.len..run.len + value.len()].copy_from_slice(value);
+            run.len += value.len();
+            run.size_bits = run.len as u32 * 8;
+            None
+        }
+        Some(_) => run.take(),
+        None => {
+            buffer[..value.len()].copy_from_slice(value);
+            *run = Some(StagedRun {
+                address: write.address,
+                size_bits: write.size_bits,
+                len: value.len(),
+            });
+            None
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{AsyncRegisterInterface, RegisterInterface};
+
+/// A trait to represent the interface to a device that addresses its registers in 16-bit words.
+///
+/// This is called to write to and read from registers. The address is a word address and the data is passed as words.
+/// Wrap it in a [WordInterface] to use it with a generated device.
+pub trait WordRegisterInterface {
+    /// The error type
+    type Error;
+    /// The address type used by this interface. Should likely be an integer.
+    type AddressType: Copy;
+
+    /// Write the given words to the register located at the given address
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u16],
+    ) -> Result<(), Self::Error>;
+
+    /// Read the register located at the given address to the given word slice
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u16],
+    ) -> Result<(), Self::Error>;
+}
+
+/// A trait to represent the interface to a device that addresses its registers in 16-bit words.
+///
+/// This is called to asynchronously write to and read from registers. The address is a word address and the data is passed as words.
+/// Wrap it in a [WordInterface] to use it with a generated device.
+pub trait AsyncWordRegisterInterface {
+    /// The error type
+    type Error;
+    /// The address type used by this interface. Should likely be an integer.
+    type AddressType: Copy;
+
+    /// Write the given words to the register located at the given address
+    async fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u16],
+    ) -> Result<(), Self::Error>;
+
+    /// Read the register located at the given address to the given word slice
+    async fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u16],
+    ) -> Result<(), Self::Error>;
+}
+
+/// The order of the two bytes within a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum WordByteOrder {
+    /// The first byte is the least significant byte of the word
+    LE,
+    /// The first byte is the most significant byte of the word
+    BE,
+}
+
+/// An interface that adapts a word-addressed interface to the byte based [RegisterInterface].
+///
+/// The bytes of a register are combined into words in order, so the first word holds the first two bytes.
+/// Within a word the bytes are combined with the given byte order, which should match the byte order of the registers.
+///
+/// `MAX_WORDS` is the size of the biggest register in words. Bigger registers will panic.
+/// Devices that are generated with `type AddressUnit = Word16;` have a `new_word` constructor that sets this up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordInterface<I, const MAX_WORDS: usize> {
+    /// The word-addressed interface
+    pub interface: I,
+    byte_order: WordByteOrder,
+}
+
+impl<I, const MAX_WORDS: usize> WordInterface<I, MAX_WORDS> {
+    /// Create a new interface on top of the word-addressed interface
+    pub const fn new(interface: I, byte_order: WordByteOrder) -> Self {
+        Self {
+            interface,
+            byte_order,
+        }
+    }
+
+    /// Get the word-addressed interface back
+    pub fn into_inner(self) -> I {
+        self.interface
+    }
+
+    fn bytes_to_words<'w>(&self, bytes: &[u8], words: &'w mut [u16; MAX_WORDS]) -> &'w [u16] {
+        let words = &mut words[..bytes.len().div_ceil(2)];
+
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(2)) {
+            let mut word_bytes = [0; 2];
+            word_bytes[..chunk.len()].copy_from_slice(chunk);
+
+            *word = match self.byte_order {
+                WordByteOrder::LE => u16::from_le_bytes(word_bytes),
+                WordByteOrder::BE => u16::from_be_bytes(word_bytes),
+            };
+        }
+
+        words
+    }
+
+    fn words_to_bytes(&self, words: &[u16], bytes: &mut [u8]) {
+        for (word, chunk) in words.iter().zip(bytes.chunks_mut(2)) {
+            let word_bytes = match self.byte_order {
+                WordByteOrder::LE => word.to_le_bytes(),
+                WordByteOrder::BE => word.to_be_bytes(),
+            };
+
+            let len = chunk.len();
+            chunk.copy_from_slice(&word_bytes[..len]);
+        }
+    }
+}
+
+impl<I: WordRegisterInterface, const MAX_WORDS: usize> RegisterInterface
+    for WordInterface<I, MAX_WORDS>
+{
+    type Error = I::Error;
+    type AddressType = I::AddressType;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        let mut words = [0; MAX_WORDS];
+        let words = self.bytes_to_words(data, &mut words);
+        self.interface.write_register(address, size_bits, words)
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let mut words = [0; MAX_WORDS];
+        let words = &mut words[..data.len().div_ceil(2)];
+        self.interface.read_register(address, size_bits, words)?;
+        self.words_to_bytes(words, data);
+        Ok(())
+    }
+}
+
+impl<I: AsyncWordRegisterInterface, const MAX_WORDS: usize> AsyncRegisterInterface
+    for WordInterface<I, MAX_WORDS>
+{
+    type Error = I::Error;
+    type AddressType = I::AddressType;
+
+    async fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        let mut words = [0; MAX_WORDS];
+        let words = self.bytes_to_words(data, &mut words);
+        self.interface
+            .write_register(address, size_bits, words)
+            .await
+    }
+
+    async fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let mut words = [0; MAX_WORDS];
+        let words = &mut words[..data.len().div_ceil(2)];
+        self.interface
+            .read_register(address, size_bits, words)
+            .await?;
+        self.words_to_bytes(words, data);
+        Ok(())
+    }
+}
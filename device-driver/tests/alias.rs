@@ -0,0 +1,82 @@
+use device_driver::RegisterInterface;
+
+pub struct DeviceInterface {
+    device_memory: [u8; 128],
+}
+
+impl Default for DeviceInterface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceInterface {
+    pub const fn new() -> Self {
+        Self {
+            device_memory: [0; 128],
+        }
+    }
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.device_memory[address as usize..][..data.len()].copy_from_slice(data);
+
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        data.copy_from_slice(&self.device_memory[address as usize..][..data.len()]);
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0x10;
+            const SIZE_BITS = 16;
+
+            value: uint = 0..16,
+        },
+        /// The foo register at the place of revision B of the chip
+        alias FooRevB = Foo @ 0x20,
+    }
+);
+
+#[test]
+fn alias_uses_own_address_and_same_field_set() {
+    let mut device = MyTestDevice::new(DeviceInterface::new());
+
+    device.foo().write(|reg| reg.set_value(0x1234)).unwrap();
+    device
+        .foo_rev_b()
+        .write(|reg| reg.set_value(0x5678))
+        .unwrap();
+
+    assert_eq!(device.interface.device_memory[0x10..0x12], [0x34, 0x12]);
+    assert_eq!(device.interface.device_memory[0x20..0x22], [0x78, 0x56]);
+
+    let foo: field_sets::Foo = device.foo().read().unwrap();
+    let foo_rev_b: field_sets::Foo = device.foo_rev_b().read().unwrap();
+    assert_eq!(foo.value(), 0x1234);
+    assert_eq!(foo_rev_b.value(), 0x5678);
+}
@@ -135,3 +135,157 @@ fn test_repeated_read_modify_write() {
         &[(0x39 << 1) + 1, 0x30 << 1, 0xFF]
     );
 }
+
+#[test]
+fn test_is_defined_address() {
+    assert!(MyTestDevice::<DeviceInterface>::is_defined_address(0));
+    assert!(MyTestDevice::<DeviceInterface>::is_defined_address(2));
+    // Last byte of the last repeated register
+    assert!(MyTestDevice::<DeviceInterface>::is_defined_address(14));
+    assert!(!MyTestDevice::<DeviceInterface>::is_defined_address(15));
+    assert!(!MyTestDevice::<DeviceInterface>::is_defined_address(255));
+}
+
+#[test]
+fn test_snapshot() {
+    let mut device = MyTestDevice::new(DeviceInterface::new());
+
+    device.foo().write(|reg| reg.set_value_1(12345)).unwrap();
+    device
+        .foo_repeated(3)
+        .write(|reg| reg.set_value_2(-1))
+        .unwrap();
+
+    let snapshot: MyTestDeviceSnapshot = device.snapshot().unwrap();
+
+    assert_eq!(snapshot.foo, device.foo().read().unwrap());
+    assert_eq!(snapshot.foo.value_1(), 12345);
+    assert_eq!(snapshot.foo_repeated[0].value_2(), 0);
+    assert_eq!(snapshot.foo_repeated[3].value_2(), -1);
+}
+
+#[test]
+fn test_fill_bytes_in_place() {
+    let mut reg = field_sets::Foo::new_zero();
+
+    // Like a DMA would do
+    reg.as_mut_bytes().copy_from_slice(&[0x03, 0x00, 0xFF]);
+
+    assert!(reg.value_0());
+    assert_eq!(reg.value_1(), 1);
+    assert_eq!(reg.value_2(), -1);
+}
+
+#[test]
+fn test_log_bytes() {
+    let mut reg = field_sets::Foo::new_zero();
+    reg.set_value_1(1);
+
+    assert_eq!(reg.log_bytes(), <[u8; 3]>::from(reg));
+}
+
+#[test]
+fn test_layout() {
+    assert_eq!(
+        field_sets::Foo::LAYOUT,
+        "[23:16 value_2 int RW][15:1 value_1 uint RW][0 value_0 bool RW]"
+    );
+}
+
+#[test]
+fn test_pack_into() {
+    let mut first = field_sets::Foo::new_zero();
+    first.set_value_1(0x1234);
+    let mut second = field_sets::Foo::new_zero();
+    second.set_value_0(true);
+    second.set_value_2(-2);
+
+    let mut buffer = [0xAA; 7];
+    assert_eq!(
+        field_sets::Foo::pack_into(&[first, second], &mut buffer),
+        Ok(6)
+    );
+    // The byte after the packed field sets is untouched
+    assert_eq!(buffer[6], 0xAA);
+
+    let unpacked = buffer[..6]
+        .chunks_exact(3)
+        .map(|chunk| field_sets::Foo::from_bits(chunk.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(unpacked, [first, second]);
+
+    assert_eq!(
+        field_sets::Foo::pack_into(&[first, second], &mut [0; 5]),
+        Err(device_driver::BufferTooSmall {
+            required: 6,
+            actual: 5
+        })
+    );
+}
+
+#[test]
+fn test_toggle() {
+    let mut device = MyTestDevice::new(DeviceInterface::new());
+
+    device.foo().write(|reg| reg.set_value_1(12345)).unwrap();
+    device.foo().modify(|reg| reg.toggle_value_0()).unwrap();
+
+    let mut reg = device.foo().read().unwrap();
+    assert!(reg.value_0());
+    assert_eq!(reg.value_1(), 12345);
+
+    reg.toggle_value_0();
+    assert!(!reg.value_0());
+    assert_eq!(reg.value_1(), 12345);
+}
+
+#[test]
+fn test_with_builders() {
+    let reg = field_sets::Foo::new_zero()
+        .with_value_1(5)
+        .with_value_0(true)
+        .with_value_2(-3);
+
+    assert!(reg.value_0());
+    assert_eq!(reg.value_1(), 5);
+    assert_eq!(reg.value_2(), -3);
+
+    let mut device = MyTestDevice::new(DeviceInterface::new());
+    device
+        .foo()
+        .write_with_zero(|reg| *reg = reg.with_value_1(42))
+        .unwrap();
+    assert_eq!(device.foo().read().unwrap().value_1(), 42);
+}
+
+#[test]
+fn test_field_bits() {
+    assert_eq!(field_sets::Foo::value_0_bits(), 1);
+    assert_eq!(field_sets::Foo::value_1_bits(), 15);
+    assert_eq!(field_sets::Foo::value_2_bits(), 8);
+}
+
+#[test]
+fn test_read_raw_into() {
+    let mut device = MyTestDevice::new(DeviceInterface::new());
+
+    device
+        .foo()
+        .write(|reg| {
+            reg.set_value_1(0x1234);
+            reg.set_value_2(-1);
+        })
+        .unwrap();
+
+    let mut out = [0; 3];
+    device.foo().read_raw_into(&mut out).unwrap();
+    assert_eq!(out, <[u8; 3]>::from(device.foo().read().unwrap()));
+
+    assert_eq!(
+        device.foo().read_raw_into(&mut [0; 4]),
+        Err(device_driver::ReadRawIntoError::WrongLength {
+            expected: 3,
+            actual: 4
+        })
+    );
+}
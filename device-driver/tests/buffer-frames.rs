@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+
+use device_driver::{BufferInterface, BufferInterfaceError};
+
+pub struct DeviceInterface {
+    fifo: VecDeque<u8>,
+}
+
+#[derive(Debug)]
+pub enum Error {}
+
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        todo!()
+    }
+}
+
+impl BufferInterfaceError for DeviceInterface {
+    type Error = Error;
+}
+
+impl BufferInterface for DeviceInterface {
+    type AddressType = u8;
+
+    fn write(&mut self, _address: Self::AddressType, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.fifo.extend(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self, _address: Self::AddressType) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn read(&mut self, _address: Self::AddressType, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let len = self.fifo.len().min(buf.len());
+        for (target, value) in buf.iter_mut().zip(self.fifo.drain(..len)) {
+            *target = value;
+        }
+        Ok(len)
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type BufferAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        /// The layout of a single fifo sample
+        register Sample {
+            const ADDRESS = 0;
+            const SIZE_BITS = 24;
+
+            channel: uint = 0..8,
+            value: int = 8..24,
+        },
+        /// Fifo with samples
+        buffer Fifo: RO = 1 as Sample,
+    }
+);
+
+#[test]
+fn frames_are_decoded() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        fifo: VecDeque::from([1, 0x34, 0x12, 2, 0xFE, 0xFF, 3]),
+    });
+
+    let frames = device
+        .fifo()
+        .frames()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].channel(), 1);
+    assert_eq!(frames[0].value(), 0x1234);
+    assert_eq!(frames[1].channel(), 2);
+    assert_eq!(frames[1].value(), -2);
+}
+
+#[test]
+fn read_frame_returns_none_when_empty() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        fifo: VecDeque::new(),
+    });
+
+    assert!(device.fifo().read_frame().unwrap().is_none());
+}
@@ -0,0 +1,77 @@
+use device_driver::{BufferError, BufferInterface, BufferInterfaceError};
+
+pub struct DeviceInterface {
+    written: Vec<u8>,
+}
+
+impl BufferInterfaceError for DeviceInterface {
+    type Error = ();
+}
+
+impl BufferInterface for DeviceInterface {
+    type AddressType = u8;
+
+    fn write(&mut self, _address: Self::AddressType, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self, _address: Self::AddressType) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn read(&mut self, _address: Self::AddressType, _buf: &mut [u8]) -> Result<usize, Self::Error> {
+        unreachable!()
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type BufferAddressType = u8;
+        }
+        buffer Fifo: WO = 0 {
+            const MAX_SIZE = 4;
+        },
+        buffer Unbounded: WO = 1,
+    }
+);
+
+fn new_device() -> MyTestDevice<DeviceInterface> {
+    MyTestDevice::new(DeviceInterface {
+        written: Vec::new(),
+    })
+}
+
+#[test]
+fn payload_within_max_size_is_written() {
+    let mut device = new_device();
+
+    assert_eq!(device.fifo().max_size(), Some(4));
+    device.fifo().write_checked(&[1, 2, 3, 4]).unwrap();
+    assert_eq!(device.interface.written, [1, 2, 3, 4]);
+}
+
+#[test]
+fn over_length_payload_is_rejected() {
+    let mut device = new_device();
+
+    assert_eq!(
+        device.fifo().write_checked(&[1, 2, 3, 4, 5]),
+        Err(BufferError::TooLong {
+            length: 5,
+            max_size: 4
+        })
+    );
+    assert!(device.interface.written.is_empty());
+}
+
+#[test]
+fn buffer_without_max_size_accepts_anything() {
+    let mut device = new_device();
+
+    assert_eq!(device.unbounded().max_size(), None);
+    device.unbounded().write_checked(&[0; 100]).unwrap();
+    assert_eq!(device.interface.written.len(), 100);
+}
@@ -0,0 +1,96 @@
+use device_driver::{BusStuckError, RegisterInterface};
+
+/// An interface that reads every byte as the same value, like a disconnected bus
+pub struct StuckInterface {
+    value: u8,
+}
+
+impl RegisterInterface for StuckInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &[u8],
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        data.fill(self.value);
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type DetectBusStuck = true;
+        }
+        register Id {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+            const RESET_VALUE = 0x1234;
+
+            value: uint = 0..16,
+        },
+        register Flags {
+            const ADDRESS = 2;
+            const SIZE_BITS = 8;
+            const RESET_VALUE = 0xFF;
+
+            value: uint = 0..8,
+        },
+        register Control {
+            const ADDRESS = 3;
+            const SIZE_BITS = 8;
+            const RESET_VALUE = 0x01;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+#[test]
+fn all_ones_is_stuck() {
+    let mut device = MyTestDevice::new(StuckInterface { value: 0xFF });
+
+    assert_eq!(device.id().read(), Err(BusStuckError::Stuck(0xFF)));
+    // Everything that reads is checked
+    assert_eq!(
+        device.control().modify(|reg| reg.set_value(0x02)),
+        Err(BusStuckError::Stuck(0xFF))
+    );
+    assert_eq!(device.snapshot(), Err(BusStuckError::Stuck(0xFF)));
+}
+
+#[test]
+fn all_zeros_is_stuck() {
+    let mut device = MyTestDevice::new(StuckInterface { value: 0x00 });
+
+    assert_eq!(device.id().read(), Err(BusStuckError::Stuck(0x00)));
+}
+
+#[test]
+fn not_stuck_when_reset_value_is_the_same() {
+    let mut device = MyTestDevice::new(StuckInterface { value: 0xFF });
+
+    assert_eq!(device.flags().read().unwrap().value(), 0xFF);
+}
+
+#[test]
+fn other_values_are_not_stuck() {
+    let mut device = MyTestDevice::new(StuckInterface { value: 0x12 });
+
+    assert_eq!(device.id().read().unwrap().value(), 0x1212);
+}
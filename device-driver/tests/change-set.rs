@@ -0,0 +1,100 @@
+use device_driver::RegisterInterface;
+
+pub struct DeviceInterface {
+    device_memory: [u8; 128],
+}
+
+impl Default for DeviceInterface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceInterface {
+    pub const fn new() -> Self {
+        Self {
+            device_memory: [0; 128],
+        }
+    }
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.device_memory[address as usize..][..data.len()].copy_from_slice(data);
+
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        data.copy_from_slice(&self.device_memory[address as usize..][..data.len()]);
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+            const RESET_VALUE = 0x0000;
+
+            enabled: bool = 0,
+            mode: uint = 1..4,
+            #[bit_order(MSB0)]
+            level: uint = 8..12,
+        },
+    }
+);
+
+#[test]
+fn modify_tracked_reports_changed_field() {
+    let mut device = MyTestDevice::new(DeviceInterface::new());
+    device.foo().write(|reg| reg.set_mode(3)).unwrap();
+
+    let changes = device
+        .foo()
+        .modify_tracked(|reg| {
+            reg.set_level(5);
+            // Writing the same value is not a change
+            reg.set_mode(3);
+        })
+        .unwrap();
+
+    assert_eq!(changes.changed_fields().collect::<Vec<_>>(), ["level"]);
+    assert!(changes.contains("level"));
+    assert!(!changes.contains("mode"));
+    assert_eq!(changes.before().level(), 0);
+    assert_eq!(changes.after().level(), 5);
+    assert_eq!(device.foo().read().unwrap().level(), 5);
+}
+
+#[test]
+fn modify_tracked_without_changes_is_empty() {
+    let mut device = MyTestDevice::new(DeviceInterface::new());
+
+    let changes = device
+        .foo()
+        .modify_tracked(|reg| reg.set_enabled(false))
+        .unwrap();
+
+    assert!(changes.is_empty());
+    assert_eq!(changes.changed_fields().count(), 0);
+}
@@ -0,0 +1,42 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type ChipVariants = [A, B];
+        }
+        register Gain {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+            const RESET_VALUE = { A: 0x01, B: 0x02 };
+
+            value: uint = 0..8,
+        },
+        register Offset {
+            const ADDRESS = 1;
+            const SIZE_BITS = 8;
+            const RESET_VALUE = 0x10;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+#[test]
+fn reset_value_per_chip_variant() {
+    assert_eq!(field_sets::Gain::new_as_variant_a().value(), 0x01);
+    assert_eq!(field_sets::Gain::new_as_variant_b().value(), 0x02);
+
+    // The first chip variant is the default
+    assert_eq!(
+        field_sets::Gain::new(),
+        field_sets::Gain::new_as_variant_a()
+    );
+}
+
+#[test]
+fn shared_reset_value_for_all_chip_variants() {
+    assert_eq!(field_sets::Offset::new_as_variant_a().value(), 0x10);
+    assert_eq!(field_sets::Offset::new_as_variant_b().value(), 0x10);
+}
@@ -0,0 +1,127 @@
+use device_driver::{CommandInterface, PollError, RegisterInterface};
+
+/// Simulates a device that stays busy for a number of status reads after a command
+pub struct DeviceInterface {
+    busy_reads: u32,
+    last_command: Option<(u8, Vec<u8>)>,
+    reads: u32,
+}
+
+impl CommandInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn dispatch_command(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits_in: u32,
+        input: &[u8],
+        _size_bits_out: u32,
+        _output: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.last_command = Some((address, input.to_vec()));
+        Ok(())
+    }
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &[u8],
+    ) -> Result<(), Self::Error> {
+        unreachable!()
+    }
+
+    fn read_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        assert!(
+            self.last_command.is_some(),
+            "The status is only read after dispatching"
+        );
+        self.reads += 1;
+        data[0] = (self.reads > self.busy_reads) as u8 | 0x40;
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type CommandAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Status {
+            type Access = RO;
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            done: bool = 0,
+            code: uint = 4..8,
+        },
+        /// Erase the flash, which takes a while
+        command Erase {
+            const ADDRESS = 0x60;
+            const POLL_UNTIL = Status.done;
+        },
+        /// Program a byte, which takes a while
+        command Program {
+            const ADDRESS = 0x02;
+            const SIZE_BITS_IN = 8;
+            const POLL_UNTIL = Status.done;
+
+            in {
+                value: uint = 0..8,
+            }
+        },
+    }
+);
+
+fn new_device(busy_reads: u32) -> MyTestDevice<DeviceInterface> {
+    MyTestDevice::new(DeviceInterface {
+        busy_reads,
+        last_command: None,
+        reads: 0,
+    })
+}
+
+#[test]
+fn command_waits_until_done() {
+    let mut device = new_device(3);
+
+    let status = device.erase_and_wait(10).unwrap();
+    assert!(status.done());
+    assert_eq!(status.code(), 4);
+    assert_eq!(device.interface.last_command, Some((0x60, Vec::new())));
+    assert_eq!(device.interface.reads, 4);
+}
+
+#[test]
+fn command_with_input_waits_until_done() {
+    let mut device = new_device(1);
+
+    let status = device
+        .program_and_wait(|cmd| cmd.set_value(0xAB), 10)
+        .unwrap();
+    assert!(status.done());
+    assert_eq!(device.interface.last_command, Some((0x02, vec![0xAB])));
+    assert_eq!(device.interface.reads, 2);
+}
+
+#[test]
+fn command_wait_times_out() {
+    let mut device = new_device(5);
+
+    assert_eq!(device.erase_and_wait(3).unwrap_err(), PollError::Timeout);
+    assert_eq!(device.interface.reads, 3);
+}
@@ -0,0 +1,109 @@
+use device_driver::RegisterInterface;
+
+#[derive(Default)]
+pub struct DeviceInterface {
+    writes: Vec<(u8, u32, Vec<u8>)>,
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.writes.push((address, size_bits, data.to_vec()));
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        unreachable!()
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Bar {
+            const ADDRESS = 2;
+            const SIZE_BITS = 8;
+            const RESET_VALUE = 0x30;
+
+            y: uint = 0..4,
+            z: uint = 4..8,
+        },
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+
+            x: uint = 0..16,
+        },
+        register Baz {
+            const ADDRESS = 5;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+        register Status {
+            type Access = RO;
+            const ADDRESS = 6;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+#[test]
+fn adjacent_registers_are_written_at_once() {
+    let mut device = MyTestDevice::new(DeviceInterface::default());
+
+    device
+        .configure(|c| {
+            c.bar().set_y(2);
+            c.foo().set_x(0x1234);
+        })
+        .unwrap();
+
+    assert_eq!(device.interface.writes, [(0, 24, vec![0x34, 0x12, 0x32])]);
+}
+
+#[test]
+fn registers_with_gaps_are_written_separately() {
+    let mut device = MyTestDevice::new(DeviceInterface::default());
+
+    device
+        .configure(|c| {
+            c.baz().set_value(5);
+            c.foo().set_x(1);
+        })
+        .unwrap();
+
+    assert_eq!(
+        device.interface.writes,
+        [(0, 16, vec![1, 0]), (5, 8, vec![5])]
+    );
+}
+
+#[test]
+fn unstaged_registers_are_not_written() {
+    let mut device = MyTestDevice::new(DeviceInterface::default());
+
+    device.configure(|_| {}).unwrap();
+    assert!(device.interface.writes.is_empty());
+
+    device.configure(|c| c.bar().set_z(1)).unwrap();
+    assert_eq!(device.interface.writes, [(2, 8, vec![0x10])]);
+}
@@ -0,0 +1,39 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register MyReg {
+            const ADDRESS = 0;
+            const SIZE_BITS = 24;
+
+            value_1: uint = 0..16,
+            enabled: bool = 16,
+            mode: uint as enum Mode {
+                A,
+                B,
+            } = 17..18,
+        },
+    }
+);
+
+const CONFIG: [u8; 3] = [0x39, 0x30, 0x01];
+const EXPECTED: u16 = field_sets::const_fields::my_reg::value_1(CONFIG);
+const _: () = assert!(EXPECTED == 12345);
+const _: () = assert!(field_sets::const_fields::my_reg::enabled(CONFIG));
+
+const FROM_BITS: field_sets::MyReg = field_sets::MyReg::from_bits(CONFIG);
+
+#[test]
+fn const_fields_match_getters() {
+    let reg = field_sets::MyReg::from(CONFIG);
+
+    assert_eq!(EXPECTED, reg.value_1());
+    assert_eq!(
+        field_sets::const_fields::my_reg::enabled(CONFIG),
+        reg.enabled()
+    );
+    assert_eq!(FROM_BITS, reg);
+}
@@ -0,0 +1,63 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = BE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 48;
+            const RESET_VALUE = 0x0000_0000_0103;
+
+            enabled: bool = 0,
+            lsb: uint = 1..13,
+            signed: int = 13..19,
+            #[bit_order(MSB0)]
+            msb: uint = 27..38,
+            #[bit_order(MSB0)]
+            flag: bool = 40,
+        },
+    }
+);
+
+const RESET: field_sets::Foo = field_sets::Foo::new();
+const _: () = assert!(RESET.enabled());
+const _: () = assert!(RESET.lsb() == 0x81);
+
+static RESET_FIELDS: (bool, u16, i8, u16, bool) = (
+    RESET.enabled(),
+    RESET.lsb(),
+    RESET.signed(),
+    RESET.msb(),
+    RESET.flag(),
+);
+
+#[test]
+fn const_getters_usable_in_const_context() {
+    assert_eq!(RESET_FIELDS, (true, 0x81, 0, 0, false));
+}
+
+#[test]
+fn const_getters_read_what_setters_write() {
+    for _ in 0..1000 {
+        let enabled = rand::random::<bool>();
+        let lsb = rand::random_range(0..1 << 12);
+        let signed = rand::random_range(0..1 << 5);
+        let msb = rand::random_range(0..1 << 11);
+        let flag = rand::random::<bool>();
+
+        let mut foo = field_sets::Foo::new_zero();
+        foo.set_enabled(enabled);
+        foo.set_lsb(lsb);
+        foo.set_signed(signed);
+        foo.set_msb(msb);
+        foo.set_flag(flag);
+
+        assert_eq!(foo.enabled(), enabled);
+        assert_eq!(foo.lsb(), lsb);
+        assert_eq!(foo.signed(), signed);
+        assert_eq!(foo.msb(), msb);
+        assert_eq!(foo.flag(), flag);
+    }
+}
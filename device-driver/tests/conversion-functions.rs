@@ -0,0 +1,57 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Amplifier {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            /// The gain is read back as a factor, but written as a setting
+            gain: uint as (Gain from gain_from_raw, GainSetting into crate::gain_setting_to_raw) = 0..3,
+        },
+    }
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gain(pub u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GainSetting {
+    Low,
+    High,
+}
+
+fn gain_from_raw(raw: u8) -> Gain {
+    Gain(1 << raw)
+}
+
+fn gain_setting_to_raw(setting: GainSetting) -> u8 {
+    match setting {
+        GainSetting::Low => 1,
+        GainSetting::High => 6,
+    }
+}
+
+#[test]
+fn read_and_write_use_their_own_function() {
+    let mut amplifier = field_sets::Amplifier::new_zero();
+    assert_eq!(amplifier.gain(), Gain(1));
+
+    amplifier.set_gain(GainSetting::Low);
+    assert_eq!(amplifier.gain_raw(), 1);
+    assert_eq!(amplifier.gain(), Gain(2));
+
+    amplifier.set_gain(GainSetting::High);
+    assert_eq!(amplifier.gain_raw(), 6);
+    assert_eq!(amplifier.gain(), Gain(64));
+}
+
+#[test]
+fn raw_setter_bypasses_the_function() {
+    let mut amplifier = field_sets::Amplifier::new_zero();
+    amplifier.set_gain_raw(3);
+
+    assert_eq!(amplifier.gain(), Gain(8));
+}
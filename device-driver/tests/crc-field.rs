@@ -0,0 +1,50 @@
+use device_driver::CrcMismatch;
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type CrcAlgorithm = Crc8Smbus;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 32;
+
+            value: uint = 0..24,
+            checksum: uint as crc over 0..24 = 24..32,
+        },
+    }
+);
+
+#[test]
+fn crc_round_trip() {
+    let mut device = MyTestDevice::from_bytes(&[0; 4]).unwrap();
+
+    device
+        .foo()
+        .write(|reg| {
+            reg.set_value(0x563412);
+            reg.set_checksum();
+        })
+        .unwrap();
+    assert_eq!(device.interface.memory(), &[0x12, 0x34, 0x56, 0x7C]);
+
+    let foo = device.foo().read().unwrap();
+    assert_eq!(foo.value(), 0x563412);
+    assert_eq!(foo.checksum(), Ok(0x7C));
+}
+
+#[test]
+fn crc_mismatch_detected() {
+    let mut device = MyTestDevice::from_bytes(&[0x12, 0x34, 0x57, 0x7C]).unwrap();
+
+    assert_eq!(
+        device.foo().read().unwrap().checksum(),
+        Err(CrcMismatch {
+            stored: 0x7C,
+            calculated: device_driver::Crc8::SMBUS.checksum(&[0x12, 0x34, 0x57]),
+        })
+    );
+}
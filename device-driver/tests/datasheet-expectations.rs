@@ -0,0 +1,41 @@
+// The expectations generate tests in this test binary, which fail when the computed values don't match
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type AutoAddress = true;
+        }
+        register Status {
+            const ADDRESS = 0x00;
+            const SIZE_BITS = 16;
+            const EXPECT_ADDRESS = 0x00;
+            const EXPECT_SIZE = 16;
+
+            value: uint = 0..16,
+        },
+        register Control {
+            const SIZE_BYTES = 1;
+            const EXPECT_ADDRESS = 0x02;
+            const EXPECT_SIZE = 8;
+
+            value: uint = 0..8,
+        },
+        block Bank {
+            const ADDRESS_OFFSET = 0x10;
+            const REPEAT = {
+                count: 2,
+                stride: 0x10,
+            };
+
+            register Data {
+                const ADDRESS = 0x04;
+                const SIZE_BITS = 32;
+                const EXPECT_ADDRESS = 0x14;
+
+                value: uint = 0..32,
+            },
+        },
+    }
+);
@@ -0,0 +1,58 @@
+use device_driver::FieldSet;
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0x01;
+            const SIZE_BITS = 16;
+
+            value: uint = 0..16,
+        },
+        block Bar {
+            const ADDRESS_OFFSET = 0x10;
+            const REPEAT = {
+                count: 2,
+                stride: 0x10,
+            };
+
+            register Baz {
+                const ADDRESS = 0x02;
+                const SIZE_BITS = 8;
+
+                enabled: bool = 0,
+                mode: uint = 1..4,
+            },
+        },
+    }
+);
+
+#[test]
+fn decode_known_address() {
+    let Some(field_sets::FieldSetValue::Foo(foo)) = field_sets::decode_any(0x01, &[0x34, 0x12])
+    else {
+        panic!("Address 0x01 is the Foo register");
+    };
+    assert_eq!(foo.value(), 0x1234);
+
+    for address in [0x12, 0x22] {
+        let Some(field_sets::FieldSetValue::Baz(baz)) = field_sets::decode_any(address, &[0x0B])
+        else {
+            panic!("Address {address:#X} is a repeat of the Baz register");
+        };
+        assert!(baz.enabled());
+        assert_eq!(baz.mode(), 5);
+        assert_eq!(baz.get_inner_buffer(), &[0x0B]);
+    }
+}
+
+#[test]
+fn decode_unknown_address_or_wrong_size() {
+    assert!(field_sets::decode_any(0x00, &[0x00]).is_none());
+    assert!(field_sets::decode_any(0x32, &[0x00]).is_none());
+    assert!(field_sets::decode_any(0x01, &[0x00]).is_none());
+}
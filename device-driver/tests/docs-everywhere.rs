@@ -14,6 +14,7 @@ device_driver::create_device!(
             const ADDRESS = 0;
             const SIZE_BITS = 8;
 
+            #[renamed_from = "old_value0"]
             value0: bool = 0,
         },
         command Bar {
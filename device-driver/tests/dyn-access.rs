@@ -0,0 +1,116 @@
+use device_driver::DynAccessError;
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type DynAccess = true;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+
+            enabled: bool = 0,
+            mode: uint as try Mode = 1..3,
+            offset: int = 4..8,
+            level: uint = 8..16,
+        },
+        register Status {
+            type Access = RO;
+            const ADDRESS = 2;
+            const SIZE_BITS = 8;
+
+            ready: bool = 0,
+        },
+        register Trigger {
+            type Access = WO;
+            const ADDRESS = 3;
+            const SIZE_BITS = 8;
+
+            start: bool = 0,
+        },
+    }
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    On,
+}
+
+impl TryFrom<u8> for Mode {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Mode::Off),
+            1 => Ok(Mode::On),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<Mode> for u8 {
+    fn from(value: Mode) -> Self {
+        value as u8
+    }
+}
+
+#[test]
+fn read_field_by_name() {
+    let mut device = MyTestDevice::from_bytes(&[0xA5, 0x42, 0x01, 0x00]).unwrap();
+
+    assert_eq!(device.read_field("foo", "enabled"), Ok(1));
+    assert_eq!(device.read_field("foo", "mode"), Ok(2));
+    assert_eq!(device.read_field("foo", "offset"), Ok(-6i64 as u64));
+    assert_eq!(device.read_field("foo", "level"), Ok(0x42));
+    assert_eq!(device.read_field("status", "ready"), Ok(1));
+
+    assert_eq!(
+        device.read_field("foo", "unknown"),
+        Err(DynAccessError::UnknownField)
+    );
+    assert_eq!(
+        device.read_field("unknown", "enabled"),
+        Err(DynAccessError::UnknownRegister)
+    );
+    assert_eq!(
+        device.read_field("trigger", "start"),
+        Err(DynAccessError::NotReadable)
+    );
+}
+
+#[test]
+fn write_field_by_name() {
+    let mut device = MyTestDevice::from_bytes(&[0x00; 4]).unwrap();
+
+    device.write_field("foo", "enabled", 1).unwrap();
+    device.write_field("foo", "mode", 3).unwrap();
+    device.write_field("foo", "offset", -2i64 as u64).unwrap();
+    device.write_field("foo", "level", 0x42).unwrap();
+    assert_eq!(device.interface.memory(), &[0xE7, 0x42, 0x00, 0x00]);
+
+    assert_eq!(
+        device.write_field("foo", "enabled", 2),
+        Err(DynAccessError::ValueOutOfRange)
+    );
+    assert_eq!(
+        device.write_field("foo", "offset", 8),
+        Err(DynAccessError::ValueOutOfRange)
+    );
+    assert_eq!(
+        device.write_field("foo", "level", 0x100),
+        Err(DynAccessError::ValueOutOfRange)
+    );
+    assert_eq!(
+        device.write_field("status", "ready", 1),
+        Err(DynAccessError::NotWritable)
+    );
+    assert_eq!(
+        device.write_field("trigger", "start", 1),
+        Err(DynAccessError::NotWritable)
+    );
+    assert_eq!(device.interface.memory(), &[0xE7, 0x42, 0x00, 0x00]);
+}
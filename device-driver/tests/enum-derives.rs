@@ -0,0 +1,77 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type EnumDerives = [Debug, Clone];
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            mode: uint as enum Mode {
+                Off,
+                On,
+                Unknown = catch_all,
+            } = 0..4,
+            speed: uint as try enum Speed {
+                Slow,
+                Fast,
+            } = 4..6,
+        },
+    }
+);
+
+#[test]
+fn enums_without_copy_work() {
+    let mut device = MyTestDevice::from_bytes(&[0x13]).unwrap();
+
+    let foo = device.foo().read().unwrap();
+    assert!(matches!(foo.mode(), Mode::Unknown(3)));
+    assert!(matches!(foo.speed(), Ok(Speed::Fast)));
+
+    let mode = Mode::On;
+    device
+        .foo()
+        .write(|reg| {
+            reg.set_mode(mode.clone());
+            reg.set_speed(Speed::Slow);
+        })
+        .unwrap();
+    assert_eq!(device.interface.memory(), &[0x01]);
+    assert_eq!(format!("{mode:?}"), "On");
+}
+
+// Only the required derive
+mod debug_only {
+    device_driver::create_device!(
+        device_name: MyTestDevice,
+        dsl: {
+            config {
+                type RegisterAddressType = u8;
+                type DefaultByteOrder = LE;
+                type EnumDerives = [Debug];
+            }
+            register Foo {
+                const ADDRESS = 0;
+                const SIZE_BITS = 8;
+
+                mode: uint as enum Mode {
+                    Off,
+                    On,
+                    Unknown = catch_all,
+                } = 0..4,
+            },
+        }
+    );
+}
+
+#[test]
+fn enums_with_only_debug_work() {
+    let mut device = debug_only::MyTestDevice::from_bytes(&[0x01]).unwrap();
+
+    let foo = device.foo().read().unwrap();
+    assert!(matches!(foo.mode(), debug_only::Mode::On));
+    assert_eq!(format!("{foo:?}"), "Foo { mode: On }");
+}
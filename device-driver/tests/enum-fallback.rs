@@ -0,0 +1,43 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            mode: uint as enum Mode {
+                Off,
+                On,
+                Unknown = fallback,
+            } = 0..4,
+        },
+    }
+);
+
+#[test]
+fn unknown_value_round_trips() {
+    let mut device = MyTestDevice::from_bytes(&[0x0B]).unwrap();
+
+    let foo = device.foo().read().unwrap();
+    assert_eq!(foo.mode(), Mode::Unknown(0x0B));
+
+    device.foo().write(|reg| reg.set_mode(foo.mode())).unwrap();
+    assert_eq!(device.interface.memory(), &[0x0B]);
+}
+
+#[test]
+fn known_values_and_default() {
+    let mut foo = field_sets::Foo::new_zero();
+
+    foo.set_mode_raw(1);
+    assert_eq!(foo.mode(), Mode::On);
+
+    assert_eq!(Mode::default(), Mode::Unknown(2));
+    foo.set_mode(Mode::default());
+    assert_eq!(foo.mode_raw(), 2);
+    assert_eq!(foo.mode(), Mode::Unknown(2));
+}
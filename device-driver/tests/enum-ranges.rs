@@ -0,0 +1,80 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            mode: uint as enum Mode {
+                Off,
+                Reserved = 1..=6,
+                On,
+            } = 0..3,
+            speed: uint as try enum Speed {
+                Slow,
+                Fast = 2..4,
+            } = 3..5,
+        },
+        register Wide {
+            const ADDRESS = 1;
+            const SIZE_BITS = 32;
+
+            status: uint as enum Status {
+                Idle,
+                Busy = 1..=0xFFFFFFFF,
+            } = 0..32,
+        },
+    }
+);
+
+use field_sets::{Foo, Wide};
+
+#[test]
+fn all_values_in_range_read_as_variant() {
+    for raw in 1..=6 {
+        let mut foo = Foo::new_zero();
+        foo.set_mode_raw(raw);
+        assert_eq!(foo.mode(), Mode::Reserved);
+    }
+
+    let mut foo = Foo::new_zero();
+    foo.set_mode_raw(7);
+    assert_eq!(foo.mode(), Mode::On);
+}
+
+#[test]
+fn range_variant_writes_range_start() {
+    let mut foo = Foo::new_zero();
+
+    foo.set_mode(Mode::Reserved);
+    assert_eq!(foo.mode_raw(), 1);
+    assert_eq!(foo.mode(), Mode::Reserved);
+
+    foo.set_speed(Speed::Fast);
+    assert_eq!(foo.speed_raw(), 2);
+    assert_eq!(foo.speed(), Ok(Speed::Fast));
+}
+
+#[test]
+fn values_outside_ranges_are_rejected() {
+    let mut foo = Foo::new_zero();
+
+    foo.set_speed_raw(3);
+    assert_eq!(foo.speed(), Ok(Speed::Fast));
+
+    foo.set_speed_raw(1);
+    assert!(foo.speed().is_err());
+}
+
+#[test]
+fn full_width_range() {
+    let mut wide = Wide::new_zero();
+    assert_eq!(wide.status(), Status::Idle);
+
+    wide.set_status_raw(u32::MAX);
+    assert_eq!(wide.status(), Status::Busy);
+}
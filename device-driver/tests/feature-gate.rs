@@ -0,0 +1,73 @@
+use device_driver::RegisterInterface;
+
+pub struct DeviceInterface {
+    device_memory: [u8; 2],
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.device_memory[address as usize..][..data.len()].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        data.copy_from_slice(&self.device_memory[address as usize..][..data.len()]);
+        Ok(())
+    }
+}
+
+// The `dsl` feature is enabled by default, so the gated driver is available
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type FeatureGate = "dsl";
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            mode: uint as enum Mode {
+                A,
+                B,
+            } = 0..1,
+        },
+        block Bar {
+            const ADDRESS_OFFSET = 1;
+
+            register Baz {
+                const ADDRESS = 0;
+                const SIZE_BITS = 8;
+
+                value: uint = 0..8,
+            },
+        },
+    }
+);
+
+#[test]
+fn gated_driver_is_generated_when_feature_enabled() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        device_memory: [0; 2],
+    });
+
+    device.foo().write(|reg| reg.set_mode(Mode::B)).unwrap();
+    device.bar().baz().write(|reg| reg.set_value(5)).unwrap();
+
+    assert_eq!(device.foo().read().unwrap().mode(), Mode::B);
+    assert_eq!(device.interface.device_memory, [1, 5]);
+}
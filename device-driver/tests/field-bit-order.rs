@@ -0,0 +1,36 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            low: uint = 0..4,
+            #[bit_order(MSB0)]
+            msb: uint = 0..2,
+        },
+    }
+);
+
+#[test]
+fn field_uses_own_bit_order() {
+    let mut device = MyTestDevice::from_bytes(&[0x00]).unwrap();
+
+    device
+        .foo()
+        .write(|reg| {
+            reg.set_low(0x5);
+            reg.set_msb(0x3);
+        })
+        .unwrap();
+    assert_eq!(device.interface.memory(), &[0xC5]);
+
+    device.interface.memory_mut()[0] = 0x81;
+    let foo = device.foo().read().unwrap();
+    assert_eq!(foo.low(), 0x1);
+    assert_eq!(foo.msb(), 0x2);
+}
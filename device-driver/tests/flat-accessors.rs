@@ -0,0 +1,57 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type FlatAccessors = true;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+
+            value_1: uint = 0..8,
+            value_2: uint as enum Mode {
+                A,
+                B,
+                C,
+                D,
+            } = 8..10,
+        },
+        register Bar {
+            type Access = RO;
+            const ADDRESS = 2;
+            const SIZE_BITS = 8;
+            const REPEAT = {
+                count: 2,
+                stride: 1,
+            };
+
+            value: int = 0..8,
+        }
+    }
+);
+
+#[test]
+fn flat_setter_modifies_single_field() {
+    let mut device = MyTestDevice::from_bytes(&[0x12, 0x03, 0, 0]).unwrap();
+
+    device.set_foo_value_1(0xAB).unwrap();
+
+    let foo = device.foo().read().unwrap();
+    assert_eq!(foo.value_1(), 0xAB);
+    assert_eq!(foo.value_2(), Mode::D);
+
+    device.set_foo_value_2(Mode::B).unwrap();
+    assert_eq!(device.interface.memory(), &[0xAB, 0x01, 0, 0]);
+}
+
+#[test]
+fn flat_getter_reads_field() {
+    let mut device = MyTestDevice::from_bytes(&[0x12, 0x02, 0xFF, 0x05]).unwrap();
+
+    assert_eq!(device.foo_value_1().unwrap(), 0x12);
+    assert_eq!(device.foo_value_2().unwrap(), Mode::C);
+    assert_eq!(device.bar_value(0).unwrap(), -1);
+    assert_eq!(device.bar_value(1).unwrap(), 5);
+}
@@ -0,0 +1,41 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        /// The result of one of the sensor channels
+        register Channel {
+            type Access = RO;
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+            const REPEAT = {
+                count: 4,
+                stride: 2,
+            };
+
+            value: uint = 0..12,
+            flags: uint = 12..16,
+        },
+    }
+);
+
+#[test]
+fn fold_sums_field_over_all_instances() {
+    let mut device =
+        MyTestDevice::from_bytes(&[0x10, 0xF0, 0x20, 0xF0, 0x30, 0x00, 0xFF, 0x0F]).unwrap();
+
+    let sum = device
+        .fold_channel(0u32, |acc, reg| acc + reg.value() as u32)
+        .unwrap();
+    assert_eq!(sum, 0x10 + 0x20 + 0x30 + 0xFFF);
+
+    let flags = device
+        .fold_channel(Vec::new(), |mut acc, reg| {
+            acc.push(reg.flags());
+            acc
+        })
+        .unwrap();
+    assert_eq!(flags, [0xF, 0xF, 0, 0]);
+}
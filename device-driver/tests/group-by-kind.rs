@@ -0,0 +1,70 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type CommandAddressType = u8;
+            type DefaultByteOrder = LE;
+            type GroupByKind = true;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+            const RESET_VALUE = 0x02;
+
+            mode: uint as enum Mode {
+                Off,
+                On,
+                Auto,
+                Test,
+            } = 0..2,
+            level: uint as Level = 2..8,
+        },
+        command Bar {
+            const ADDRESS = 1;
+            const SIZE_BITS_IN = 8;
+            const SIZE_BITS_OUT = 8;
+
+            in {
+                mode: uint as Mode = 0..2,
+            }
+            out {
+                value: uint = 0..8,
+            }
+        },
+    }
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level(u8);
+
+impl From<u8> for Level {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Level> for u8 {
+    fn from(value: Level) -> Self {
+        value.0
+    }
+}
+
+#[test]
+fn field_sets_in_kind_modules() {
+    let foo = field_sets::registers::Foo::new();
+    assert_eq!(foo.mode(), Mode::Auto);
+    assert_eq!(foo.level(), Level(0));
+
+    let mut bar = field_sets::commands::BarFieldsIn::new();
+    bar.set_mode(Mode::On);
+    assert_eq!(bar.mode(), Mode::On);
+
+    let _: field_sets::commands::BarFieldsOut = field_sets::BarFieldsOut::new();
+}
+
+#[test]
+fn field_sets_reexported() {
+    let foo: field_sets::Foo = field_sets::registers::Foo::new_zero();
+    assert_eq!(foo.mode(), Mode::Off);
+}
@@ -0,0 +1,39 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type IdRegister = (WhoAmI, chip_id, 0x42);
+        }
+        register Ctrl {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+        register WhoAmI {
+            type Access = RO;
+            const ADDRESS = 1;
+            const SIZE_BITS = 8;
+
+            chip_id: uint = 0..7,
+            revision: bool = 7,
+        },
+    }
+);
+
+#[test]
+fn matching_id() {
+    let mut device = MyTestDevice::from_bytes(&[0, 0x42]).unwrap();
+    assert!(device.check_id().unwrap());
+
+    // Other fields of the register are not checked
+    let mut device = MyTestDevice::from_bytes(&[0, 0xC2]).unwrap();
+    assert!(device.check_id().unwrap());
+}
+
+#[test]
+fn mismatching_id() {
+    let mut device = MyTestDevice::from_bytes(&[0x42, 0x24]).unwrap();
+    assert!(!device.check_id().unwrap());
+}
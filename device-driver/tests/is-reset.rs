@@ -0,0 +1,42 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Config {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+            const RESET_VALUE = 0x1205;
+
+            mode: uint = 0..4,
+            gain: uint = 8..16,
+        }
+    }
+);
+
+use field_sets::Config;
+
+#[test]
+fn new_value_is_reset() {
+    assert!(Config::new().is_reset());
+    assert!(!Config::new_zero().is_reset());
+}
+
+#[test]
+fn modified_value_is_not_reset() {
+    let mut config = Config::new();
+    config.set_gain(0x13);
+    assert!(!config.is_reset());
+
+    config.set_gain(0x12);
+    assert!(config.is_reset());
+}
+
+#[test]
+fn bits_outside_of_fields_are_ignored() {
+    let mut config = Config::new();
+    config.as_mut_bytes()[0] |= 0xF0;
+    assert!(config.is_reset());
+}
@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+
+use device_driver::{BufferInterface, BufferInterfaceError, LengthPrefixedReadError};
+
+pub struct DeviceInterface {
+    fifo: VecDeque<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {}
+
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        todo!()
+    }
+}
+
+impl BufferInterfaceError for DeviceInterface {
+    type Error = Error;
+}
+
+impl BufferInterface for DeviceInterface {
+    type AddressType = u8;
+
+    fn write(&mut self, _address: Self::AddressType, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.fifo.extend(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self, _address: Self::AddressType) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn read(&mut self, _address: Self::AddressType, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let len = self.fifo.len().min(buf.len());
+        for (target, value) in buf.iter_mut().zip(self.fifo.drain(..len)) {
+            *target = value;
+        }
+        Ok(len)
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type BufferAddressType = u8;
+        }
+        /// Packets with a length prefix
+        buffer Packets: RO = 1 {
+            length: uint = 0..8,
+        },
+    }
+);
+
+#[test]
+fn length_prefixed_frame_is_decoded() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        fifo: VecDeque::from([3, 0xAA, 0xBB, 0xCC, 1, 0xDD]),
+    });
+    let mut buf = [0; 8];
+
+    let (prefix, payload) = device.packets().read_length_prefixed(&mut buf).unwrap();
+    assert_eq!(prefix.length(), 3);
+    assert_eq!(payload, [0xAA, 0xBB, 0xCC]);
+
+    let (prefix, payload) = device.packets().read_length_prefixed(&mut buf).unwrap();
+    assert_eq!(prefix.length(), 1);
+    assert_eq!(payload, [0xDD]);
+}
+
+#[test]
+fn length_prefixed_read_errors() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        fifo: VecDeque::from([4, 0xAA, 0xBB, 0xCC, 0xDD]),
+    });
+    assert_eq!(
+        device.packets().read_length_prefixed(&mut [0; 2]),
+        Err(LengthPrefixedReadError::PayloadTooLong { length: 4 })
+    );
+
+    let mut device = MyTestDevice::new(DeviceInterface {
+        fifo: VecDeque::from([2, 0xAA]),
+    });
+    assert_eq!(
+        device.packets().read_length_prefixed(&mut [0; 8]),
+        Err(LengthPrefixedReadError::UnexpectedEof)
+    );
+}
@@ -0,0 +1,65 @@
+use device_driver::{MemoryInterface, MemoryInterfaceError};
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+
+            value: uint = 0..16,
+        },
+        register Bar {
+            const ADDRESS = 3;
+            const SIZE_BITS = 8;
+            const REPEAT = {
+                count: 2,
+                stride: 1,
+            };
+
+            value: uint = 0..8,
+        }
+    }
+);
+
+#[test]
+fn device_span_covers_all_registers() {
+    assert_eq!(MyTestDevice::<()>::DEVICE_SPAN, 5);
+}
+
+#[test]
+fn seeded_from_bytes() {
+    let mut device = MyTestDevice::from_bytes(&[0x34, 0x12, 0xFF, 0xAB, 0xCD]).unwrap();
+
+    assert_eq!(device.foo().read().unwrap().value(), 0x1234);
+    assert_eq!(device.bar(0).read().unwrap().value(), 0xAB);
+    assert_eq!(device.bar(1).read().unwrap().value(), 0xCD);
+
+    device.bar(1).write(|reg| reg.set_value(0x42)).unwrap();
+    assert_eq!(device.interface.memory(), &[0x34, 0x12, 0xFF, 0xAB, 0x42]);
+}
+
+#[test]
+fn seeding_with_wrong_size_fails() {
+    assert_eq!(
+        MyTestDevice::from_bytes(&[0; 4]).err(),
+        Some(MemoryInterfaceError::SizeMismatch {
+            expected: 5,
+            actual: 4
+        })
+    );
+}
+
+#[test]
+fn out_of_bounds_access_fails() {
+    let mut interface = MemoryInterface::<u8, 2>::new();
+
+    assert_eq!(
+        device_driver::RegisterInterface::write_register(&mut interface, 1, 16, &[1, 2]),
+        Err(MemoryInterfaceError::OutOfBounds)
+    );
+}
@@ -0,0 +1,53 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+
+            a: uint = 0..4,
+            b: uint = 4..8,
+            c: uint = 8..16,
+        },
+    }
+);
+
+#[test]
+fn merge_takes_masked_bits() {
+    let mut value = field_sets::Foo::from([0x21, 0x43]);
+
+    let mut staged = field_sets::Foo::new_zero();
+    staged.set_b(0xF);
+    staged.set_c(0x99);
+
+    let mut mask = field_sets::Foo::new_zero();
+    mask.set_b(0xF);
+
+    value.merge(&staged, &mask);
+    assert_eq!(value.a(), 0x1);
+    assert_eq!(value.b(), 0xF);
+    assert_eq!(value.c(), 0x43);
+}
+
+#[test]
+fn merge_round_trip() {
+    let mut device = MyTestDevice::from_bytes(&[0x21, 0x43]).unwrap();
+
+    let mut staged = field_sets::Foo::new_zero();
+    staged.set_a(0x5);
+    staged.set_c(0xAB);
+
+    let mut mask = field_sets::Foo::new_zero();
+    mask.set_a(0xF);
+    mask.set_c(0xFF);
+
+    let mut value = device.foo().read().unwrap();
+    value.merge(&staged, &mask);
+    device.foo().write(|reg| *reg = value).unwrap();
+
+    assert_eq!(device.interface.memory(), &[0x25, 0xAB]);
+}
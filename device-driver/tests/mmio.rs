@@ -0,0 +1,65 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type Mmio = true;
+        }
+        register Control {
+            const ADDRESS = 0;
+            const SIZE_BITS = 32;
+            const RESET_VALUE = 0x0000_0100;
+
+            enable: bool = 0,
+            divider: uint = 8..16,
+        },
+        register Status {
+            type Access = RO;
+            const ADDRESS = 8;
+            const SIZE_BITS = 16;
+
+            value: uint = 0..16,
+        },
+        register Data {
+            const ADDRESS = 10;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+/// Memory standing in for the memory-mapped registers
+#[repr(C, align(4))]
+struct Memory([u8; 12]);
+
+#[test]
+fn struct_has_register_layout() {
+    assert_eq!(core::mem::size_of::<MyTestDeviceMmio>(), 11);
+    assert_eq!(core::mem::align_of::<MyTestDeviceMmio>(), 1);
+}
+
+#[test]
+fn registers_are_written_at_their_address() {
+    let mut memory = Memory([0; 12]);
+    let mmio = unsafe { MyTestDeviceMmio::from_ptr(memory.0.as_mut_ptr().cast()) };
+
+    mmio.control().write(|reg| reg.set_enable(true));
+    mmio.data().write(|reg| reg.set_value(0xAB));
+
+    assert_eq!(memory.0, [0x01, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0xAB, 0]);
+}
+
+#[test]
+fn registers_are_read_from_their_address() {
+    let mut memory = Memory([0x00, 0x05, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0x34, 0x12, 0, 0]);
+    let mmio = unsafe { MyTestDeviceMmio::from_ptr(memory.0.as_mut_ptr().cast()) };
+
+    assert_eq!(mmio.status().read().value(), 0x1234);
+
+    mmio.control().modify(|reg| reg.set_enable(true));
+    assert_eq!(mmio.control().read().divider(), 5);
+    assert!(mmio.control().read().enable());
+    assert_eq!(memory.0[..4], [0x01, 0x05, 0, 0]);
+}
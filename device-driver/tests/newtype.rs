@@ -0,0 +1,44 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Measurement {
+            const ADDRESS = 0;
+            const SIZE_BITS = 32;
+
+            /// The temperature in 0.1 °C
+            temperature: int as newtype Temperature(i16) = 0..12,
+            /// The voltage in mV
+            voltage: uint as newtype Voltage(u32) = 16..32,
+        },
+        register Limit {
+            const ADDRESS = 4;
+            const SIZE_BITS = 16;
+
+            /// The temperature in 0.1 °C
+            max_temperature: int as newtype Temperature(i16) = 0..12,
+        },
+    }
+);
+
+#[test]
+fn fields_use_the_newtype() {
+    let mut measurement = field_sets::Measurement::new();
+    measurement.set_temperature(Temperature(215));
+    measurement.set_voltage(Voltage(3300));
+
+    assert_eq!(measurement.temperature(), Temperature(215));
+    assert_eq!(measurement.voltage(), Voltage(3300));
+    assert_eq!(measurement.temperature_raw(), 215);
+}
+
+#[test]
+fn identical_newtypes_are_shared() {
+    let mut limit = field_sets::Limit::new();
+    limit.set_max_temperature(field_sets::Measurement::new().temperature());
+
+    assert_eq!(limit.max_temperature(), Temperature(0));
+}
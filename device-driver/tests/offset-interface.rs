@@ -0,0 +1,82 @@
+use device_driver::{OffsetInterface, RegisterInterface};
+
+/// Records the addresses of all operations
+#[derive(Default)]
+pub struct DeviceInterface {
+    writes: Vec<(u8, Vec<u8>)>,
+    reads: Vec<u8>,
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.writes.push((address, data.to_vec()));
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.reads.push(address);
+        data.fill(address);
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+        register Bar {
+            const ADDRESS = 3;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+#[test]
+fn addresses_are_shifted_by_the_offset() {
+    let mut device = MyTestDevice::new(OffsetInterface::new(DeviceInterface::default(), 0x40));
+
+    device.foo().write(|reg| reg.set_value(0x12)).unwrap();
+    assert_eq!(device.bar().read().unwrap().value(), 0x43);
+
+    let interface = device.interface.into_inner();
+    assert_eq!(interface.writes, [(0x40, vec![0x12])]);
+    assert_eq!(interface.reads, [0x43]);
+}
+
+#[test]
+fn interface_reused_for_sub_devices() {
+    let mut first = MyTestDevice::new(OffsetInterface::new(DeviceInterface::default(), 0x00));
+    first.foo().write(|reg| reg.set_value(1)).unwrap();
+
+    let mut second = MyTestDevice::new(OffsetInterface::new(first.interface.into_inner(), 0x40));
+    second.foo().write(|reg| reg.set_value(2)).unwrap();
+
+    assert_eq!(
+        second.interface.into_inner().writes,
+        [(0x00, vec![1]), (0x40, vec![2])]
+    );
+}
@@ -0,0 +1,109 @@
+use core::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use device_driver::{AsyncRegisterInterface, PollError};
+
+pub struct DeviceInterface {
+    reads_until_ready: u32,
+    reads: u32,
+}
+
+impl AsyncRegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    async fn write_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &[u8],
+    ) -> Result<(), Self::Error> {
+        unreachable!()
+    }
+
+    async fn read_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.reads += 1;
+        data[0] = (self.reads > self.reads_until_ready) as u8 | 0x10;
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Status {
+            type Access = RO;
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            ready: bool = 0,
+            value: uint = 4..8,
+        },
+    }
+);
+
+/// The futures in these tests never wait, so polling them once is enough
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future is not ready"),
+    }
+}
+
+#[test]
+fn poll_until_ready() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        reads_until_ready: 2,
+        reads: 0,
+    });
+    let mut delays = 0;
+
+    let status = block_on(device.status().poll_until_async(
+        |reg| reg.ready(),
+        5,
+        || {
+            delays += 1;
+            async {}
+        },
+    ))
+    .unwrap();
+
+    assert!(status.ready());
+    assert_eq!(status.value(), 1);
+    assert_eq!(device.interface.reads, 3);
+    assert_eq!(delays, 2);
+}
+
+#[test]
+fn poll_until_timeout() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        reads_until_ready: 2,
+        reads: 0,
+    });
+    let mut delays = 0;
+
+    assert_eq!(
+        block_on(device.status().poll_until_async(
+            |reg| reg.ready(),
+            2,
+            || {
+                delays += 1;
+                async {}
+            },
+        ))
+        .unwrap_err(),
+        PollError::Timeout
+    );
+    assert_eq!(device.interface.reads, 2);
+    assert_eq!(delays, 1);
+}
@@ -0,0 +1,78 @@
+use device_driver::{PollError, RegisterInterface};
+
+pub struct DeviceInterface {
+    reads_until_ready: u32,
+    reads: u32,
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &[u8],
+    ) -> Result<(), Self::Error> {
+        unreachable!()
+    }
+
+    fn read_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.reads += 1;
+        data[0] = (self.reads > self.reads_until_ready) as u8 | 0x10;
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Status {
+            type Access = RO;
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            ready: bool = 0,
+            value: uint = 4..8,
+        },
+    }
+);
+
+#[test]
+fn poll_until_ready() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        reads_until_ready: 2,
+        reads: 0,
+    });
+
+    let status = device.status().poll_until(|reg| reg.ready(), 5).unwrap();
+    assert!(status.ready());
+    assert_eq!(status.value(), 1);
+    assert_eq!(device.interface.reads, 3);
+}
+
+#[test]
+fn poll_until_timeout() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        reads_until_ready: 2,
+        reads: 0,
+    });
+
+    assert_eq!(
+        device
+            .status()
+            .poll_until(|reg| reg.ready(), 2)
+            .unwrap_err(),
+        PollError::Timeout
+    );
+    assert_eq!(device.interface.reads, 2);
+}
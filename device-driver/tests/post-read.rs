@@ -0,0 +1,52 @@
+use std::sync::Mutex;
+
+static READ_ADDRESSES: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+/// Records the address of every read
+fn on_read(address: u8) {
+    READ_ADDRESSES.lock().unwrap().push(address);
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+            type PostRead = crate::on_read;
+        }
+        register Foo {
+            const ADDRESS = 1;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+        register Bar {
+            type Access = WO;
+            const ADDRESS = 2;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+        register Wide {
+            const ADDRESS = [4, 3];
+            const SIZE_BITS = 16;
+
+            value: uint = 0..16,
+        },
+    }
+);
+
+#[test]
+fn hook_runs_after_read() {
+    let mut device = MyTestDevice::from_bytes(&[0x00, 0x42, 0x00, 0x34, 0x12]).unwrap();
+
+    assert_eq!(device.foo().read().unwrap().value(), 0x42);
+    device.foo().modify(|reg| reg.set_value(0x43)).unwrap();
+    device.bar().write(|reg| reg.set_value(0x44)).unwrap();
+    // Every part of a spanned register is a read of its own
+    assert_eq!(device.wide().read().unwrap().value(), 0x3412);
+
+    assert_eq!(*READ_ADDRESSES.lock().unwrap(), [1, 1, 4, 3]);
+    assert_eq!(device.interface.memory(), &[0x00, 0x43, 0x44, 0x34, 0x12]);
+}
@@ -0,0 +1,70 @@
+use device_driver::RegisterInterface;
+
+#[derive(Default)]
+pub struct DeviceInterface {
+    reads: Vec<u8>,
+    status: u8,
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &[u8],
+    ) -> Result<(), Self::Error> {
+        unreachable!()
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.reads.push(address);
+        // The status flags are cleared on read
+        data[0] = core::mem::take(&mut self.status);
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Status {
+            type Access = RO;
+            const ADDRESS = 3;
+            const SIZE_BITS = 8;
+
+            overflow: RC bool = 0,
+            level: uint = 1..8,
+        },
+        register Level {
+            type Access = RO;
+            const ADDRESS = 4;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+#[test]
+fn touch_reads_register() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        status: 0x01,
+        ..Default::default()
+    });
+
+    device.touch_status().unwrap();
+    assert_eq!(device.interface.reads, [3]);
+    assert!(!device.status().read().unwrap().overflow());
+    assert_eq!(device.interface.reads, [3, 3]);
+}
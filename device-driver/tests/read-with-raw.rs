@@ -0,0 +1,55 @@
+use core::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = BE;
+        }
+        register Measurement {
+            const ADDRESS = 0;
+            const SIZE_BITS = 24;
+            type Access = RO;
+
+            value: uint = 8..24,
+            crc: uint = 0..8,
+        },
+    }
+);
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future is not ready"),
+    }
+}
+
+#[test]
+fn raw_bytes_match_decoded_fields() {
+    let mut device = MyTestDevice::from_bytes(&[0xBE, 0xEF, 0x92]).unwrap();
+
+    let (measurement, raw) = device.measurement().read_with_raw().unwrap();
+
+    assert_eq!(raw, [0xBE, 0xEF, 0x92]);
+    assert_eq!(measurement.value(), u16::from_be_bytes([raw[0], raw[1]]));
+    assert_eq!(measurement.crc(), raw[2]);
+    assert_eq!(
+        device_driver::Crc8::NRSC5.checksum(&raw[..2]),
+        measurement.crc()
+    );
+}
+
+#[test]
+fn raw_bytes_match_decoded_fields_async() {
+    let mut device = MyTestDevice::from_bytes(&[0x12, 0x34, 0x56]).unwrap();
+
+    let (measurement, raw) = block_on(device.measurement().read_with_raw_async()).unwrap();
+
+    assert_eq!(raw, [0x12, 0x34, 0x56]);
+    assert_eq!(measurement.value(), 0x1234);
+    assert_eq!(measurement.crc(), 0x56);
+}
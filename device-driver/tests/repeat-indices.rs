@@ -0,0 +1,44 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        /// One of the channels of the device
+        block Channel {
+            const ADDRESS_OFFSET = 0;
+            const REPEAT = {
+                count: 3,
+                stride: 2,
+            };
+
+            register Gain {
+                type Access = RO;
+                const ADDRESS = 0;
+                const SIZE_BITS = 8;
+
+                value: uint = 0..8,
+            },
+            register Offset {
+                type Access = RO;
+                const ADDRESS = 1;
+                const SIZE_BITS = 8;
+
+                value: uint = 0..8,
+            },
+        },
+    }
+);
+
+#[test]
+fn iterate_over_all_channels() {
+    let mut device = MyTestDevice::from_bytes(&[10, 1, 20, 2, 30, 3]).unwrap();
+
+    assert_eq!(device.channel_indices(), 0..3);
+
+    let mut gains = Vec::new();
+    for index in device.channel_indices() {
+        gains.push(device.channel(index).gain().read().unwrap().value());
+    }
+    assert_eq!(gains, [10, 20, 30]);
+}
@@ -0,0 +1,69 @@
+use device_driver::RegisterInterface;
+
+#[derive(Default)]
+pub struct DeviceInterface {
+    writes: Vec<(u8, Vec<u8>)>,
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.writes.push((address, data.to_vec()));
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = BE;
+            type ResetSequence = [(Bar, 0x1234), (Foo, 0x01), (Bar, 0xFF)];
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+        register Bar {
+            const ADDRESS = 1;
+            const SIZE_BITS = 16;
+
+            value: uint = 0..16,
+        }
+    }
+);
+
+#[test]
+fn init_writes_sequence_in_order() {
+    let mut device = MyTestDevice::new(DeviceInterface::default());
+
+    device.init().unwrap();
+
+    assert_eq!(
+        device.interface.writes,
+        [
+            (1, vec![0x12, 0x34]),
+            (0, vec![0x01]),
+            (1, vec![0x00, 0xFF]),
+        ]
+    );
+}
@@ -0,0 +1,36 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = BE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+            const RESET_VALUE = { value: 5, mode: Output, enabled: true, offset: -2 };
+
+            value: uint = 0..4,
+            mode: uint as try enum Mode {
+                Input,
+                Output,
+                Analog = 3,
+            } = 4..6,
+            enabled: bool = 6,
+            rest: uint = 7..8,
+            offset: int = 8..16,
+        },
+    }
+);
+
+#[test]
+fn reset_value_from_field_table() {
+    let reset = field_sets::Foo::new();
+
+    assert_eq!(reset.value(), 5);
+    assert_eq!(reset.mode(), Ok(Mode::Output));
+    assert!(reset.enabled());
+    assert_eq!(reset.rest(), 0);
+    assert_eq!(reset.offset(), -2);
+    assert_eq!(<[u8; 2]>::from(reset), [0xFE, 0x55]);
+}
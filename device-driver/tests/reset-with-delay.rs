@@ -0,0 +1,102 @@
+use core::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use device_driver::{AsyncResetInterface, RegisterInterface, ResetInterface};
+
+#[derive(Default)]
+pub struct DeviceInterface {
+    /// The reset states in the order they were set
+    resets: Vec<bool>,
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &[u8],
+    ) -> Result<(), Self::Error> {
+        unreachable!()
+    }
+
+    fn read_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        unreachable!()
+    }
+}
+
+impl ResetInterface for DeviceInterface {
+    type Error = &'static str;
+
+    fn set_reset(&mut self, asserted: bool) -> Result<(), Self::Error> {
+        self.resets.push(asserted);
+        Ok(())
+    }
+}
+
+impl AsyncResetInterface for DeviceInterface {
+    type Error = &'static str;
+
+    async fn set_reset(&mut self, asserted: bool) -> Result<(), Self::Error> {
+        self.resets.push(asserted);
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type ResetHoldUs = 10;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future is not ready"),
+    }
+}
+
+#[test]
+fn reset_is_held_for_configured_time() {
+    let mut device = MyTestDevice::new(DeviceInterface::default());
+    let mut delays = Vec::new();
+
+    device.reset_with_delay(|us| delays.push(us)).unwrap();
+
+    assert_eq!(delays, [10]);
+    assert_eq!(device.interface.resets, [true, false]);
+}
+
+#[test]
+fn reset_is_held_for_configured_time_async() {
+    let mut device = MyTestDevice::new(DeviceInterface::default());
+    let mut delays = Vec::new();
+
+    block_on(device.reset_with_delay_async(|us| {
+        delays.push(us);
+        async {}
+    }))
+    .unwrap();
+
+    assert_eq!(delays, [10]);
+    assert_eq!(device.interface.resets, [true, false]);
+}
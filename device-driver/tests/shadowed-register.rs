@@ -0,0 +1,67 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        /// A write-only register of which the last written value is remembered
+        register Control {
+            type Access = WO;
+            type Shadowed = true;
+            const ADDRESS = 0;
+            const SIZE_BITS = 16;
+            const RESET_VALUE = 0x0102;
+
+            enable: bool = 0,
+            speed: uint = 8..16,
+        },
+        register Status {
+            type Access = RO;
+            const ADDRESS = 2;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        }
+    }
+);
+
+#[test]
+fn shadow_starts_at_reset_value() {
+    let mut device = MyTestDevice::from_bytes(&[0; 3]).unwrap();
+
+    let control = device.control().read().unwrap();
+    assert!(!control.enable());
+    assert_eq!(control.speed(), 1);
+}
+
+#[test]
+fn write_only_register_is_read_from_shadow() {
+    let mut device = MyTestDevice::from_bytes(&[0; 3]).unwrap();
+
+    device
+        .control()
+        .write(|reg| {
+            reg.set_enable(true);
+            reg.set_speed(42);
+        })
+        .unwrap();
+    assert_eq!(device.interface.memory(), &[0x03, 42, 0]);
+
+    let control = device.control().read().unwrap();
+    assert!(control.enable());
+    assert_eq!(control.speed(), 42);
+
+    // The device is not read, so changes on the device side are not seen
+    device.interface.memory_mut()[1] = 0;
+    assert_eq!(device.control().read().unwrap().speed(), 42);
+}
+
+#[test]
+fn write_only_register_is_modified_from_shadow() {
+    let mut device = MyTestDevice::from_bytes(&[0; 3]).unwrap();
+
+    device.control().modify(|reg| reg.set_enable(true)).unwrap();
+    assert_eq!(device.interface.memory(), &[0x03, 0x01, 0]);
+    assert!(device.control().read().unwrap().enable());
+}
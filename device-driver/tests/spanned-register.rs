@@ -0,0 +1,57 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        /// A 24 bit value split over three 8 bit registers, most significant byte first
+        register Pressure {
+            type ByteOrder = BE;
+            const ADDRESS = [0, 1, 2];
+            const SIZE_BITS = 24;
+
+            value: uint = 0..24,
+        },
+        /// A 16 bit value split over two 8 bit registers, least significant byte at the highest address
+        register Temperature {
+            type Access = RO;
+            type ByteOrder = LE;
+            const ADDRESS = [4, 3];
+            const SIZE_BITS = 16;
+
+            value: int = 0..16,
+        }
+    }
+);
+
+#[test]
+fn spanned_register_is_assembled() {
+    let mut device = MyTestDevice::from_bytes(&[0x12, 0x34, 0x56, 0xFF, 0xFE]).unwrap();
+
+    assert_eq!(device.pressure().read().unwrap().value(), 0x123456);
+    assert_eq!(device.temperature().read().unwrap().value(), -2);
+}
+
+#[test]
+fn spanned_register_is_split() {
+    let mut device = MyTestDevice::from_bytes(&[0; 5]).unwrap();
+
+    device
+        .pressure()
+        .write(|reg| reg.set_value(0xABCDEF))
+        .unwrap();
+    assert_eq!(device.interface.memory(), &[0xAB, 0xCD, 0xEF, 0, 0]);
+
+    device
+        .pressure()
+        .modify(|reg| reg.set_value(reg.value() + 1))
+        .unwrap();
+    assert_eq!(device.interface.memory(), &[0xAB, 0xCD, 0xF0, 0, 0]);
+}
+
+#[test]
+fn spanned_register_addresses_are_defined() {
+    assert!(MyTestDevice::<()>::is_defined_address(3));
+    assert!(!MyTestDevice::<()>::is_defined_address(5));
+    assert_eq!(MyTestDevice::<()>::DEVICE_SPAN, 5);
+}
@@ -0,0 +1,64 @@
+use device_driver::{SpiInterface, SpiRegisterDevice};
+
+/// A SPI device that records the transactions and answers reads with a fixed value
+#[derive(Default)]
+pub struct MockSpi {
+    transactions: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl SpiRegisterDevice for MockSpi {
+    type Error = ();
+
+    fn write(&mut self, header: &[u8], data: &[u8]) -> Result<(), Self::Error> {
+        self.transactions.push((header.to_vec(), data.to_vec()));
+        Ok(())
+    }
+
+    fn read(&mut self, header: &[u8], data: &mut [u8]) -> Result<(), Self::Error> {
+        data.fill(0x5A);
+        self.transactions.push((header.to_vec(), data.to_vec()));
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Config {
+            const ADDRESS = 0x05;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+#[test]
+fn read_bit_set_for_reads() {
+    let mut device = MyTestDevice::new(SpiInterface::new(MockSpi::default(), 0x80, 0x00));
+
+    device.config().write(|reg| reg.set_value(0x12)).unwrap();
+    assert_eq!(device.config().read().unwrap().value(), 0x5A);
+
+    assert_eq!(
+        device.interface.interface.transactions,
+        [(vec![0x05], vec![0x12]), (vec![0x85], vec![0x5A])]
+    );
+}
+
+#[test]
+fn write_bit_set_for_writes() {
+    let mut device = MyTestDevice::new(SpiInterface::new(MockSpi::default(), 0x00, 0x20));
+
+    device.config().write(|reg| reg.set_value(0x12)).unwrap();
+    assert_eq!(device.config().read().unwrap().value(), 0x5A);
+
+    assert_eq!(
+        device.interface.interface.transactions,
+        [(vec![0x25], vec![0x12]), (vec![0x05], vec![0x5A])]
+    );
+}
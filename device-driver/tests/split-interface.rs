@@ -0,0 +1,66 @@
+use device_driver::{CommandInterface, MemoryInterface, SplitInterface};
+
+#[derive(Default)]
+pub struct CommandBus {
+    dispatched: Vec<(u8, Vec<u8>)>,
+}
+
+impl CommandInterface for CommandBus {
+    type Error = ();
+    type AddressType = u8;
+
+    fn dispatch_command(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits_in: u32,
+        input: &[u8],
+        _size_bits_out: u32,
+        _output: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.dispatched.push((address, input.to_vec()));
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type CommandAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+        /// A command with the same address as the register
+        command Bar {
+            const ADDRESS = 0;
+            const SIZE_BITS_IN = 8;
+
+            in {
+                value: uint = 0..8,
+            }
+        },
+    }
+);
+
+#[test]
+fn objects_use_their_own_interface() {
+    let mut device = MyTestDevice::new(SplitInterface::new(
+        MemoryInterface::<u8, 1>::new(),
+        CommandBus::default(),
+    ));
+
+    device.foo().write(|reg| reg.set_value(0x12)).unwrap();
+    device.bar().dispatch(|cmd| cmd.set_value(0x34)).unwrap();
+
+    assert_eq!(device.foo().read().unwrap().value(), 0x12);
+
+    let (registers, commands) = device.interface.into_inner();
+    assert_eq!(registers.memory(), &[0x12]);
+    assert_eq!(commands.dispatched, [(0, vec![0x34])]);
+}
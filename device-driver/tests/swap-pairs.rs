@@ -0,0 +1,62 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = SwapPairs;
+        }
+        register Sample {
+            const ADDRESS = 0;
+            const SIZE_BITS = 32;
+            const RESET_VALUE = 0x11223344;
+
+            low: uint = 0..16,
+            high: uint = 16..32,
+        },
+        register Small {
+            const ADDRESS = 4;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    }
+);
+
+#[test]
+fn register_round_trips() {
+    let mut device = MyTestDevice::from_bytes(&[0xCC, 0xDD, 0xAA, 0xBB, 0x00]).unwrap();
+
+    let sample = device.sample().read().unwrap();
+    assert_eq!(sample.low(), 0xCCDD);
+    assert_eq!(sample.high(), 0xAABB);
+
+    device
+        .sample()
+        .write(|reg| {
+            reg.set_low(0x1234);
+            reg.set_high(0x5678);
+        })
+        .unwrap();
+    assert_eq!(device.interface.memory(), &[0x12, 0x34, 0x56, 0x78, 0x00]);
+
+    let sample = device.sample().read().unwrap();
+    assert_eq!(sample.low(), 0x1234);
+    assert_eq!(sample.high(), 0x5678);
+}
+
+#[test]
+fn reset_value_is_swapped() {
+    let sample = field_sets::Sample::new();
+    assert_eq!(sample.low(), 0x3344);
+    assert_eq!(sample.high(), 0x1122);
+    assert_eq!(<[u8; 4]>::from(sample), [0x33, 0x44, 0x11, 0x22]);
+}
+
+#[test]
+fn single_byte_register_inherits_le() {
+    let mut device = MyTestDevice::from_bytes(&[0, 0, 0, 0, 0x5A]).unwrap();
+
+    assert_eq!(device.small().read().unwrap().value(), 0x5A);
+    device.small().write(|reg| reg.set_value(0xA5)).unwrap();
+    assert_eq!(device.interface.memory(), &[0, 0, 0, 0, 0xA5]);
+}
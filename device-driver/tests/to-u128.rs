@@ -0,0 +1,50 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Little {
+            type ByteOrder = LE;
+            const ADDRESS = 0;
+            const SIZE_BITS = 100;
+
+            low: uint = 0..8,
+            high: uint = 92..100,
+        },
+        register Big {
+            type ByteOrder = BE;
+            const ADDRESS = 13;
+            const SIZE_BITS = 100;
+
+            low: uint = 0..8,
+            high: uint = 92..100,
+        },
+    }
+);
+
+#[test]
+fn little_endian_round_trip() {
+    let mut reg = field_sets::Little::new_zero();
+    reg.set_low(0x12);
+    reg.set_high(0xAB);
+    assert_eq!(reg.to_u128(), 0xAB << 92 | 0x12);
+
+    let bytes = <[u8; 13]>::from(reg);
+    let mut padded = [0; 16];
+    padded[..13].copy_from_slice(&bytes);
+    assert_eq!(reg.to_u128(), u128::from_le_bytes(padded));
+}
+
+#[test]
+fn big_endian_round_trip() {
+    let mut reg = field_sets::Big::new_zero();
+    reg.set_low(0x12);
+    reg.set_high(0xAB);
+    assert_eq!(reg.to_u128(), 0xAB << 92 | 0x12);
+
+    let bytes = <[u8; 13]>::from(reg);
+    let mut padded = [0; 16];
+    padded[3..].copy_from_slice(&bytes);
+    assert_eq!(reg.to_u128(), u128::from_be_bytes(padded));
+}
@@ -0,0 +1,57 @@
+use device_driver::ConversionError;
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Little {
+            type ByteOrder = LE;
+            const ADDRESS = 0;
+            const SIZE_BITS = 20;
+
+            low: uint = 0..8,
+            high: uint = 8..20,
+        },
+        register Big {
+            type ByteOrder = BE;
+            const ADDRESS = 3;
+            const SIZE_BITS = 20;
+
+            low: uint = 0..8,
+            high: uint = 8..20,
+        },
+    }
+);
+
+#[test]
+fn value_is_laid_out_in_byte_order() {
+    let little = field_sets::Little::try_from(0xABCDE).unwrap();
+    assert_eq!(little.low(), 0xDE);
+    assert_eq!(little.high(), 0xABC);
+    assert_eq!(<[u8; 3]>::from(little), [0xDE, 0xBC, 0x0A]);
+
+    let big = field_sets::Big::try_from(0xABCDE).unwrap();
+    assert_eq!(big.low(), 0xDE);
+    assert_eq!(big.high(), 0xABC);
+    assert_eq!(<[u8; 3]>::from(big), [0x0A, 0xBC, 0xDE]);
+}
+
+#[test]
+fn value_too_big_is_rejected() {
+    assert_eq!(
+        field_sets::Little::try_from(0x10_0000),
+        Err(ConversionError {
+            source: 0x10_0000,
+            target: "Little"
+        })
+    );
+    assert_eq!(
+        field_sets::Big::try_from(0x8000_0000),
+        Err(ConversionError {
+            source: 0x8000_0000,
+            target: "Big"
+        })
+    );
+}
@@ -88,6 +88,14 @@ device_driver::create_device!(
             convert_generated_copied: uint as GenEnum = 12..14,
             /// Try needed since it recognizes GenEnum, but the bits are too big (3 bit vs 2 bit)
             convert_generated_copied_too_large: uint as try GenEnum = 14..17,
+            /// Extra attributes are passed through to the generated enum
+            #[derive(PartialOrd, Ord)]
+            convert_generated_ordered: uint as enum GenOrderedEnum {
+                Low,
+                Mid,
+                High,
+                Max,
+            } = 17..19,
         },
     }
 );
@@ -183,3 +191,44 @@ fn test_basic_read_modify_write() {
         GenCatchAllEnum::B(3)
     );
 }
+
+#[test]
+fn test_raw_write_bypasses_conversion() {
+    let mut device = MyTestDevice::new(DeviceInterface::new());
+
+    device
+        .foo()
+        .write(|reg| {
+            // 3 is not modelled by GenTryEnum
+            reg.set_convert_generated_try_raw(3);
+            reg.set_convert_generated_catchall_raw(2);
+        })
+        .unwrap();
+
+    assert_eq!(
+        device.foo().read().unwrap().convert_generated_try(),
+        Result::<_, ConversionError<u8>>::Err(ConversionError {
+            source: 3,
+            target: "GenTryEnum"
+        })
+    );
+    assert_eq!(
+        device.foo().read().unwrap().convert_generated_catchall(),
+        GenCatchAllEnum::B(2)
+    );
+}
+
+#[test]
+fn test_passthrough_derive_on_generated_enum() {
+    assert!(GenOrderedEnum::Low < GenOrderedEnum::High);
+    assert_eq!(
+        [
+            GenOrderedEnum::Max,
+            GenOrderedEnum::Low,
+            GenOrderedEnum::Mid
+        ]
+        .into_iter()
+        .max(),
+        Some(GenOrderedEnum::Max)
+    );
+}
@@ -0,0 +1,47 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Pin {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            mode: uint as enum Mode {
+                Input,
+                Output,
+                Analog,
+                Reserved,
+            } = 0..2,
+            /// The output level
+            #[valid_when = "mode == Output"]
+            level: bool = 2,
+            /// The pull resistor
+            #[valid_when = "mode != Output"]
+            pull_up: bool = 3,
+        },
+    }
+);
+
+#[test]
+fn valid_field_can_be_used() {
+    let mut pin = field_sets::Pin::new_zero();
+    pin.set_mode(Mode::Output);
+    pin.set_level(true);
+    assert!(pin.level());
+
+    pin.set_mode(Mode::Analog);
+    pin.set_pull_up(true);
+    assert!(pin.pull_up());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "The `level` field is only valid when `mode == Output`")]
+fn invalid_field_panics_in_debug() {
+    let mut pin = field_sets::Pin::new_zero();
+    pin.set_mode(Mode::Input);
+    pin.set_level(true);
+}
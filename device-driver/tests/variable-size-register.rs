@@ -0,0 +1,84 @@
+use device_driver::{RegisterInterface, VariableSizeReadError};
+
+pub struct DeviceInterface {
+    device_memory: [u8; 8],
+    /// The sizes in bits of the reads in the order they were done
+    read_sizes: Vec<u32>,
+}
+
+impl RegisterInterface for DeviceInterface {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        _address: Self::AddressType,
+        _size_bits: u32,
+        _data: &[u8],
+    ) -> Result<(), Self::Error> {
+        unreachable!()
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.read_sizes.push(size_bits);
+        data.copy_from_slice(&self.device_memory[address as usize..][..data.len()]);
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        /// A message of up to 7 bytes after the length
+        register Message {
+            const ADDRESS = 0;
+            const SIZE_BITS = 64;
+            const LENGTH_FIELD = length;
+
+            length: uint = 0..8,
+            first: uint = 8..16,
+            second: uint = 16..24,
+            last: uint = 56..64,
+        },
+    }
+);
+
+#[test]
+fn variable_length_register_read_in_two_phases() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        device_memory: [2, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x11],
+        read_sizes: Vec::new(),
+    });
+
+    let message = device.message().read_variable().unwrap();
+
+    assert_eq!(device.interface.read_sizes, [8, 24]);
+    assert_eq!(message.length(), 2);
+    assert_eq!(message.first(), 0xAA);
+    assert_eq!(message.second(), 0xBB);
+    // Past the read size, so left zero
+    assert_eq!(message.last(), 0);
+}
+
+#[test]
+fn variable_length_register_too_long() {
+    let mut device = MyTestDevice::new(DeviceInterface {
+        device_memory: [8, 0, 0, 0, 0, 0, 0, 0],
+        read_sizes: Vec::new(),
+    });
+
+    assert_eq!(
+        device.message().read_variable(),
+        Err(VariableSizeReadError::TooLong { size: 9 })
+    );
+    assert_eq!(device.interface.read_sizes, [8]);
+}
@@ -0,0 +1,44 @@
+// The device is only visible to this module, but `pub(super)` must then also work
+// for the items in the generated submodules
+mod inner {
+    device_driver::create_device!(
+        device_name: MyTestDevice,
+        dsl: {
+            config {
+                type RegisterAddressType = u8;
+                type DefaultByteOrder = LE;
+                type Visibility = "pub(super)";
+                type GroupByKind = true;
+                type ResetSequence = [(Foo, 0x05)];
+            }
+            register Foo {
+                const ADDRESS = 0;
+                const SIZE_BITS = 8;
+
+                value: uint = 0..8,
+            },
+        }
+    );
+}
+
+#[test]
+fn pub_super_reaches_the_parent_module() {
+    let mut device = inner::MyTestDevice::from_bytes(&[0x00]).unwrap();
+
+    device.init().unwrap();
+    assert_eq!(device.foo().read().unwrap().value(), 5);
+    assert_eq!(device.snapshot().unwrap().foo.value(), 5);
+
+    device.configure(|c| c.foo().set_value(7)).unwrap();
+    assert_eq!(device.foo().read().unwrap().value(), 7);
+
+    let mut count = 0;
+    device.read_all_registers(|_, _, _| count += 1).unwrap();
+    assert_eq!(count, 1);
+
+    let foo = inner::field_sets::Foo::from_bits([3]);
+    assert_eq!(foo.value(), 3);
+    assert_eq!(inner::field_sets::registers::Foo::new().value(), 0);
+    assert_eq!(inner::field_sets::const_fields::foo::value([3]), 3);
+    assert!(inner::MyTestDevice::<()>::is_defined_address(0));
+}
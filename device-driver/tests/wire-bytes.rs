@@ -0,0 +1,51 @@
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Big {
+            type ByteOrder = BE;
+            const ADDRESS = 0;
+            const SIZE_BITS = 24;
+
+            value: uint = 0..24,
+        },
+        register Little {
+            type ByteOrder = LE;
+            const ADDRESS = 3;
+            const SIZE_BITS = 24;
+
+            value: uint = 0..24,
+        },
+    }
+);
+
+#[test]
+fn wire_bytes_match_transmitted_bytes() {
+    let mut device = MyTestDevice::from_bytes(&[0; 6]).unwrap();
+
+    let mut big = field_sets::Big::new_zero();
+    big.set_value(0x123456);
+    assert_eq!(big.to_wire_bytes(), [0x12, 0x34, 0x56]);
+
+    let mut little = field_sets::Little::new_zero();
+    little.set_value(0x123456);
+    assert_eq!(little.to_wire_bytes(), [0x56, 0x34, 0x12]);
+
+    device.big().write(|reg| *reg = big).unwrap();
+    device.little().write(|reg| *reg = little).unwrap();
+
+    assert_eq!(device.interface.memory()[..3], big.to_wire_bytes());
+    assert_eq!(device.interface.memory()[3..], little.to_wire_bytes());
+}
+
+#[test]
+fn wire_bytes_round_trip() {
+    let mut big = field_sets::Big::new_zero();
+    big.set_value(0xABCDEF);
+
+    let round_tripped = field_sets::Big::from(big.to_wire_bytes());
+    assert_eq!(round_tripped, big);
+    assert_eq!(round_tripped.value(), 0xABCDEF);
+}
@@ -0,0 +1,104 @@
+use device_driver::WordRegisterInterface;
+
+/// A device with 16-bit words behind every address
+#[derive(Default)]
+pub struct WordBus {
+    memory: [u16; 4],
+}
+
+impl WordRegisterInterface for WordBus {
+    type Error = ();
+    type AddressType = u8;
+
+    fn write_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &[u16],
+    ) -> Result<(), Self::Error> {
+        let address = address as usize;
+        self.memory[address..address + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        address: Self::AddressType,
+        _size_bits: u32,
+        data: &mut [u16],
+    ) -> Result<(), Self::Error> {
+        let address = address as usize;
+        data.copy_from_slice(&self.memory[address..address + data.len()]);
+        Ok(())
+    }
+}
+
+device_driver::create_device!(
+    device_name: MyTestDevice,
+    dsl: {
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = BE;
+            type AddressUnit = Word16;
+            type AutoAddress = true;
+        }
+        register Config {
+            const SIZE_BITS = 16;
+
+            mode: uint = 0..4,
+            gain: uint = 8..16,
+        },
+        register Counter {
+            const SIZE_BITS = 32;
+
+            value: uint = 0..32,
+        },
+        register Status {
+            type Access = RO;
+            const SIZE_BITS = 16;
+
+            value: uint = 0..16,
+        }
+    }
+);
+
+#[test]
+fn registers_are_written_as_words() {
+    let mut device = MyTestDevice::new_word(WordBus::default());
+
+    device
+        .config()
+        .write(|reg| {
+            reg.set_mode(0x3);
+            reg.set_gain(0xAB);
+        })
+        .unwrap();
+    device
+        .counter()
+        .write(|reg| reg.set_value(0x1234_5678))
+        .unwrap();
+
+    assert_eq!(
+        device.interface.interface.memory,
+        [0xAB03, 0x1234, 0x5678, 0]
+    );
+}
+
+#[test]
+fn registers_are_read_from_words() {
+    let mut device = MyTestDevice::new_word(WordBus {
+        memory: [0x0102, 0xCAFE, 0xBABE, 0xBEEF],
+    });
+
+    let config = device.config().read().unwrap();
+    assert_eq!(config.mode(), 0x2);
+    assert_eq!(config.gain(), 0x01);
+    assert_eq!(device.counter().read().unwrap().value(), 0xCAFE_BABE);
+    assert_eq!(device.status().read().unwrap().value(), 0xBEEF);
+}
+
+#[test]
+fn addresses_are_word_addresses() {
+    assert!(MyTestDevice::<()>::is_defined_address(3));
+    assert!(!MyTestDevice::<()>::is_defined_address(4));
+}
@@ -19,6 +19,7 @@ impl From<dsl_hir::Access> for mir::Access {
             dsl_hir::Access::RW => mir::Access::RW,
             dsl_hir::Access::RO => mir::Access::RO,
             dsl_hir::Access::WO => mir::Access::WO,
+            dsl_hir::Access::RC => mir::Access::RC,
         }
     }
 }
@@ -28,6 +29,7 @@ impl From<dsl_hir::ByteOrder> for mir::ByteOrder {
         match value {
             dsl_hir::ByteOrder::LE => mir::ByteOrder::LE,
             dsl_hir::ByteOrder::BE => mir::ByteOrder::BE,
+            dsl_hir::ByteOrder::SwapPairs => mir::ByteOrder::SwapPairs,
         }
     }
 }
@@ -41,6 +43,25 @@ impl From<dsl_hir::BitOrder> for mir::BitOrder {
     }
 }
 
+impl From<dsl_hir::CrcAlgorithm> for mir::CrcAlgorithm {
+    fn from(value: dsl_hir::CrcAlgorithm) -> Self {
+        match value {
+            dsl_hir::CrcAlgorithm::Crc8Smbus => mir::CrcAlgorithm::Crc8Smbus,
+            dsl_hir::CrcAlgorithm::Crc8Nrsc5 => mir::CrcAlgorithm::Crc8Nrsc5,
+            dsl_hir::CrcAlgorithm::Crc8Autosar => mir::CrcAlgorithm::Crc8Autosar,
+        }
+    }
+}
+
+impl From<dsl_hir::AddressUnit> for mir::AddressUnit {
+    fn from(value: dsl_hir::AddressUnit) -> Self {
+        match value {
+            dsl_hir::AddressUnit::Byte => mir::AddressUnit::Byte,
+            dsl_hir::AddressUnit::Word16 => mir::AddressUnit::Word16,
+        }
+    }
+}
+
 impl TryFrom<syn::Ident> for mir::Integer {
     type Error = syn::Error;
 
@@ -92,11 +113,25 @@ impl TryFrom<dsl_hir::GlobalConfigList> for mir::GlobalConfig {
             let same_config_count = value
                 .configs
                 .iter()
-                .filter(|check_config| {
-                    std::mem::discriminant(*check_config) == std::mem::discriminant(config)
+                .filter(|check_config| match (check_config, config) {
+                    // Multiple type aliases are allowed, as long as their names differ
+                    (
+                        dsl_hir::GlobalConfig::TypeAlias(check_name, _),
+                        dsl_hir::GlobalConfig::TypeAlias(name, _),
+                    ) => check_name == name,
+                    _ => std::mem::discriminant(*check_config) == std::mem::discriminant(config),
                 })
                 .count();
 
+            if let dsl_hir::GlobalConfig::TypeAlias(name, _) = config
+                && same_config_count > 1
+            {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Duplicate type alias found: `{name}`"),
+                ));
+            }
+
             if same_config_count > 1 {
                 return Err(syn::Error::new(
                     Span::call_site(),
@@ -117,6 +152,12 @@ impl TryFrom<dsl_hir::GlobalConfigList> for mir::GlobalConfig {
                 dsl_hir::GlobalConfig::DefaultByteOrder(value) => {
                     global_config.default_byte_order = Some(value.into())
                 }
+                dsl_hir::GlobalConfig::DefaultRegisterByteOrder(value) => {
+                    global_config.default_register_byte_order = Some(value.into())
+                }
+                dsl_hir::GlobalConfig::DefaultCommandByteOrder(value) => {
+                    global_config.default_command_byte_order = Some(value.into())
+                }
                 dsl_hir::GlobalConfig::DefaultBitOrder(value) => {
                     global_config.default_bit_order = value.into()
                 }
@@ -135,6 +176,81 @@ impl TryFrom<dsl_hir::GlobalConfigList> for mir::GlobalConfig {
                 dsl_hir::GlobalConfig::DefmtFeature(lit_str) => {
                     global_config.defmt_feature = Some(lit_str.value())
                 }
+                dsl_hir::GlobalConfig::UfmtFeature(lit_str) => {
+                    global_config.ufmt_feature = Some(lit_str.value())
+                }
+                dsl_hir::GlobalConfig::AutoAddress(lit_bool) => {
+                    global_config.auto_address = lit_bool.value
+                }
+                dsl_hir::GlobalConfig::Visibility(lit_str) => {
+                    global_config.visibility = Some(lit_str.value())
+                }
+                dsl_hir::GlobalConfig::MaxFields(lit_int) => {
+                    global_config.max_fields = Some(lit_int.base10_parse()?)
+                }
+                dsl_hir::GlobalConfig::FlatAccessors(lit_bool) => {
+                    global_config.flat_accessors = lit_bool.value
+                }
+                dsl_hir::GlobalConfig::CrcAlgorithm(crc_algorithm) => {
+                    global_config.crc_algorithm = crc_algorithm.into()
+                }
+                dsl_hir::GlobalConfig::IdRegister(register, field, expected) => {
+                    global_config.id_register = Some(mir::IdRegister {
+                        register: register.to_string(),
+                        field: field.to_string(),
+                        expected: expected.base10_parse()?,
+                    })
+                }
+                dsl_hir::GlobalConfig::AddressUnit(address_unit) => {
+                    global_config.address_unit = address_unit.into()
+                }
+                dsl_hir::GlobalConfig::Mmio(lit_bool) => global_config.mmio = lit_bool.value,
+                dsl_hir::GlobalConfig::ResetHoldUs(lit_int) => {
+                    global_config.reset_hold_us = Some(lit_int.base10_parse()?)
+                }
+                dsl_hir::GlobalConfig::GroupByKind(lit_bool) => {
+                    global_config.group_by_kind = lit_bool.value
+                }
+                dsl_hir::GlobalConfig::GenerateBitOps(lit_bool) => {
+                    global_config.generate_bit_ops = lit_bool.value
+                }
+                dsl_hir::GlobalConfig::FeatureGate(lit_str) => {
+                    global_config.feature_gate = Some(lit_str.value())
+                }
+                dsl_hir::GlobalConfig::ChipVariants(variants) => {
+                    global_config.chip_variants =
+                        variants.iter().map(|variant| variant.to_string()).collect()
+                }
+                dsl_hir::GlobalConfig::DynAccess(lit_bool) => {
+                    global_config.dyn_access = lit_bool.value
+                }
+                dsl_hir::GlobalConfig::SharedCommandInterface(lit_bool) => {
+                    global_config.shared_command_interface = lit_bool.value
+                }
+                dsl_hir::GlobalConfig::PostRead(path) => {
+                    global_config.post_read = Some(path_to_type_name(&path))
+                }
+                dsl_hir::GlobalConfig::EnumDerives(derives) => {
+                    global_config.enum_derives =
+                        Some(derives.iter().map(|derive| derive.to_string()).collect())
+                }
+                dsl_hir::GlobalConfig::DetectBusStuck(lit_bool) => {
+                    global_config.detect_bus_stuck = lit_bool.value
+                }
+                dsl_hir::GlobalConfig::TypeAlias(name, path) => global_config
+                    .type_aliases
+                    .push((name.to_string(), path_to_type_name(&path))),
+                dsl_hir::GlobalConfig::ResetSequence(sequence) => {
+                    global_config.reset_sequence = sequence
+                        .iter()
+                        .map(|(register, value)| {
+                            Ok((
+                                register.to_string(),
+                                mir::ResetValue::Integer(value.base10_parse()?),
+                            ))
+                        })
+                        .collect::<Result<_, syn::Error>>()?
+                }
             }
         }
 
@@ -148,7 +264,12 @@ fn get_description(attrs: &dsl_hir::AttributeList) -> Option<String> {
         .iter()
         .filter_map(|attr| match attr {
             dsl_hir::Attribute::Doc(val) => Some(val.as_str()),
-            dsl_hir::Attribute::Cfg(_, _) => None,
+            dsl_hir::Attribute::Cfg(_, _)
+            | dsl_hir::Attribute::Passthrough(_, _)
+            | dsl_hir::Attribute::RenamedFrom(_, _)
+            | dsl_hir::Attribute::BitOrder(_, _)
+            | dsl_hir::Attribute::Unit(_, _)
+            | dsl_hir::Attribute::ValidWhen(_, _) => None,
         })
         .collect::<Vec<_>>()
         .join("\n");
@@ -162,7 +283,12 @@ fn get_cfg_attr(attrs: &dsl_hir::AttributeList) -> Result<mir::Cfg, syn::Error>
         .iter()
         .filter_map(|attr| match attr {
             dsl_hir::Attribute::Cfg(val, span) => Some((val, span)),
-            dsl_hir::Attribute::Doc(_) => None,
+            dsl_hir::Attribute::Doc(_)
+            | dsl_hir::Attribute::Passthrough(_, _)
+            | dsl_hir::Attribute::RenamedFrom(_, _)
+            | dsl_hir::Attribute::BitOrder(_, _)
+            | dsl_hir::Attribute::Unit(_, _)
+            | dsl_hir::Attribute::ValidWhen(_, _) => None,
         })
         .collect::<Vec<_>>();
 
@@ -181,6 +307,8 @@ fn transform_object_list(
     global_config: &mir::GlobalConfig,
 ) -> Result<Vec<mir::Object>, syn::Error> {
     let mut objects = Vec::new();
+    // The address the next register gets when it has no address specified and auto addressing is on
+    let mut auto_address = 0;
 
     for object in list.objects.into_iter() {
         let object = match object {
@@ -188,7 +316,9 @@ fn transform_object_list(
                 mir::Object::Block(transform_block(block, global_config)?)
             }
             dsl_hir::Object::Register(register) => {
-                mir::Object::Register(transform_register(register, global_config)?)
+                let register = transform_register(register, global_config, auto_address)?;
+                auto_address = register.next_auto_address(global_config.address_unit);
+                mir::Object::Register(register)
             }
             dsl_hir::Object::Command(command) => {
                 mir::Object::Command(transform_command(command, global_config)?)
@@ -197,6 +327,7 @@ fn transform_object_list(
                 mir::Object::Buffer(transform_buffer(buffer, global_config)?)
             }
             dsl_hir::Object::Ref(ref_object) => mir::Object::Ref(transform_ref(ref_object)?),
+            dsl_hir::Object::Alias(alias) => mir::Object::Ref(transform_alias(alias)?),
         };
 
         objects.push(object);
@@ -241,7 +372,22 @@ fn transform_block(
 fn transform_register(
     register: dsl_hir::Register,
     global_config: &mir::GlobalConfig,
+    auto_address: i64,
 ) -> Result<mir::Register, syn::Error> {
+    // The register can override the default field access for its own fields
+    let register_global_config = register
+        .register_item_list
+        .register_items
+        .iter()
+        .find_map(|i| match i {
+            dsl_hir::RegisterItem::DefaultFieldAccess(access) => Some(mir::GlobalConfig {
+                default_field_access: (*access).into(),
+                ..global_config.clone()
+            }),
+            _ => None,
+        });
+    let global_config = register_global_config.as_ref().unwrap_or(global_config);
+
     Ok(mir::Register {
         cfg_attr: get_cfg_attr(&register.attribute_list)?,
         description: get_description(&register.attribute_list).unwrap_or_default(),
@@ -290,39 +436,66 @@ fn transform_register(
                 _ => None,
             })
             .unwrap_or_default(),
+        reserved_rest: register
+            .register_item_list
+            .register_items
+            .contains(&dsl_hir::RegisterItem::ReservedRest),
+        shadowed: register
+            .register_item_list
+            .register_items
+            .iter()
+            .find_map(|i| match i {
+                dsl_hir::RegisterItem::Shadowed(b) => Some(b.value),
+                _ => None,
+            })
+            .unwrap_or_default(),
         address: register
             .register_item_list
             .register_items
             .iter()
             .find_map(|i| match i {
                 dsl_hir::RegisterItem::Address(addr) => Some(addr.base10_parse()),
+                dsl_hir::RegisterItem::AddressSpan(addresses) => {
+                    addresses.first().map(|addr| addr.base10_parse())
+                }
                 _ => None,
             })
             .transpose()?
+            .or(global_config.auto_address.then_some(auto_address))
             .ok_or_else(|| {
                 syn::Error::new(
                     register.identifier.span(),
                     format!("Register `{}` must have an address", register.identifier),
                 )
             })?,
-        size_bits: register
+        span_offsets: register
             .register_item_list
             .register_items
             .iter()
             .find_map(|i| match i {
-                dsl_hir::RegisterItem::SizeBits(sb) => Some(sb.base10_parse()),
+                dsl_hir::RegisterItem::AddressSpan(addresses) => Some(addresses),
                 _ => None,
             })
+            .map(|addresses| {
+                let addresses = addresses
+                    .iter()
+                    .map(|addr| addr.base10_parse::<i64>())
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                match addresses.first() {
+                    Some(first) => Ok(addresses.iter().map(|addr| addr - first).collect()),
+                    None => Err(syn::Error::new(
+                        register.identifier.span(),
+                        format!(
+                            "Register `{}` must have at least one address in its address span",
+                            register.identifier
+                        ),
+                    )),
+                }
+            })
             .transpose()?
-            .ok_or_else(|| {
-                syn::Error::new(
-                    register.identifier.span(),
-                    format!(
-                        "Register `{}` must have size bits specified",
-                        register.identifier
-                    ),
-                )
-            })?,
+            .unwrap_or_default(),
+        size_bits: get_size_bits(&register)?,
         reset_value: register
             .register_item_list
             .register_items
@@ -343,9 +516,13 @@ fn transform_register(
                         })
                         .map(mir::ResetValue::Integer),
                 ),
+                dsl_hir::RegisterItem::ResetValueFields(fields) => {
+                    Some(transform_reset_value_fields(fields))
+                }
                 _ => None,
             })
             .transpose()?,
+        chip_variant_reset_values: Vec::new(),
         repeat: register
             .register_item_list
             .register_items
@@ -361,9 +538,128 @@ fn transform_register(
             .iter()
             .map(|field| transform_field(field, global_config))
             .collect::<Result<_, _>>()?,
+        expect_address: register
+            .register_item_list
+            .register_items
+            .iter()
+            .find_map(|i| match i {
+                dsl_hir::RegisterItem::ExpectAddress(addr) => Some(addr.base10_parse()),
+                _ => None,
+            })
+            .transpose()?,
+        expect_size_bits: register
+            .register_item_list
+            .register_items
+            .iter()
+            .find_map(|i| match i {
+                dsl_hir::RegisterItem::ExpectSize(size) => Some(size.base10_parse()),
+                _ => None,
+            })
+            .transpose()?,
+        length_field: register
+            .register_item_list
+            .register_items
+            .iter()
+            .find_map(|i| match i {
+                dsl_hir::RegisterItem::LengthField(field) => Some(field.to_string()),
+                _ => None,
+            }),
+    })
+}
+
+/// Get the size of the register in bits, which can be specified with either `SIZE_BITS` or `SIZE_BYTES`
+fn transform_reset_value_fields(
+    fields: &[dsl_hir::FieldResetValue],
+) -> Result<mir::ResetValue, syn::Error> {
+    fields
+        .iter()
+        .map(|dsl_hir::FieldResetValue { field, value }| {
+            Ok((field.to_string(), transform_field_reset_value_kind(value)?))
+        })
+        .collect::<Result<_, syn::Error>>()
+        .map(mir::ResetValue::Fields)
+}
+
+fn transform_field_reset_value_kind(
+    value: &dsl_hir::FieldResetValueKind,
+) -> Result<mir::FieldResetValue, syn::Error> {
+    Ok(match value {
+        dsl_hir::FieldResetValueKind::Int(int) => {
+            mir::FieldResetValue::Integer(int.base10_parse()?)
+        }
+        dsl_hir::FieldResetValueKind::Bool(bool) => mir::FieldResetValue::Bool(bool.value),
+        dsl_hir::FieldResetValueKind::Variant(variant) => {
+            mir::FieldResetValue::Variant(variant.to_string())
+        }
     })
 }
 
+fn get_size_bits(register: &dsl_hir::Register) -> Result<u32, syn::Error> {
+    let items = &register.register_item_list.register_items;
+
+    let size_bits = items
+        .iter()
+        .find_map(|i| match i {
+            dsl_hir::RegisterItem::SizeBits(sb) => Some(sb),
+            _ => None,
+        })
+        .map(|sb| sb.base10_parse::<u32>())
+        .transpose()?;
+    let size_bytes = items
+        .iter()
+        .find_map(|i| match i {
+            dsl_hir::RegisterItem::SizeBytes(sb) => Some(sb),
+            _ => None,
+        })
+        .map(|sb| {
+            sb.base10_parse::<u32>()?.checked_mul(8).ok_or_else(|| {
+                syn::Error::new(
+                    sb.span(),
+                    "The size in bytes is too large to fit in the size bits",
+                )
+            })
+        })
+        .transpose()?;
+
+    match (size_bits, size_bytes) {
+        (Some(size_bits), None) => Ok(size_bits),
+        (None, Some(size_bits)) => Ok(size_bits),
+        (Some(_), Some(_)) => Err(syn::Error::new(
+            register.identifier.span(),
+            format!(
+                "Register `{}` has both `SIZE_BITS` and `SIZE_BYTES` specified. Only one is allowed",
+                register.identifier
+            ),
+        )),
+        (None, None) => Err(syn::Error::new(
+            register.identifier.span(),
+            format!(
+                "Register `{}` must have size bits specified",
+                register.identifier
+            ),
+        )),
+    }
+}
+
+fn transform_command_address(
+    address: &dsl_hir::CommandAddress,
+    span: Span,
+) -> Result<(i64, Vec<u8>), syn::Error> {
+    match address {
+        dsl_hir::CommandAddress::Integer(lit) => Ok((lit.base10_parse()?, Vec::new())),
+        dsl_hir::CommandAddress::Opcode(bytes) => {
+            let opcode = bytes
+                .iter()
+                .map(|byte| byte.base10_parse())
+                .collect::<Result<Vec<u8>, _>>()?;
+            let address = mir::Command::opcode_address(&opcode)
+                .ok_or_else(|| syn::Error::new(span, "An opcode must be 1 to 4 bytes long"))?;
+
+            Ok((address, opcode))
+        }
+    }
+}
+
 fn transform_command(
     command: dsl_hir::Command,
     global_config: &mir::GlobalConfig,
@@ -374,19 +670,16 @@ fn transform_command(
             format!("Command `{}` must have a value", command.identifier),
         )
     })?;
-    Ok(mir::Command {
-        cfg_attr: get_cfg_attr(&command.attribute_list)?,
-        description: get_description(&command.attribute_list).unwrap_or_default(),
-        name: command.identifier.to_string(),
-        address: match &command_value {
-            dsl_hir::CommandValue::Basic(lit) => lit,
+    let (address, opcode) = transform_command_address(
+        match &command_value {
+            dsl_hir::CommandValue::Basic(address) => address,
             dsl_hir::CommandValue::Extended {
                 command_item_list, ..
             } => command_item_list
                 .items
                 .iter()
                 .find_map(|item| match item {
-                    dsl_hir::CommandItem::Address(lit) => Some(lit),
+                    dsl_hir::CommandItem::Address(address) => Some(address),
                     _ => None,
                 })
                 .ok_or_else(|| {
@@ -395,8 +688,16 @@ fn transform_command(
                         format!("Command `{}` must have an address", command.identifier),
                     )
                 })?,
-        }
-        .base10_parse()?,
+        },
+        command.identifier.span(),
+    )?;
+
+    Ok(mir::Command {
+        cfg_attr: get_cfg_attr(&command.attribute_list)?,
+        description: get_description(&command.attribute_list).unwrap_or_default(),
+        name: command.identifier.to_string(),
+        address,
+        opcode,
         byte_order: match &command_value {
             dsl_hir::CommandValue::Basic(_) => None,
             dsl_hir::CommandValue::Extended {
@@ -496,20 +797,179 @@ fn transform_command(
                 .map(|field| transform_field(field, global_config))
                 .collect::<Result<_, _>>()?,
         },
+        poll_until: match &command_value {
+            dsl_hir::CommandValue::Basic(_) => None,
+            dsl_hir::CommandValue::Extended {
+                command_item_list, ..
+            } => command_item_list.items.iter().find_map(|item| match item {
+                dsl_hir::CommandItem::PollUntil(register, field) => Some(mir::PollUntil {
+                    register: register.to_string(),
+                    field: field.to_string(),
+                }),
+                _ => None,
+            }),
+        },
     })
 }
 
+fn get_passthrough_attrs(attrs: &dsl_hir::AttributeList) -> Vec<(String, Span)> {
+    attrs
+        .attributes
+        .iter()
+        .filter_map(|attr| match attr {
+            dsl_hir::Attribute::Passthrough(val, span) => Some((val.clone(), *span)),
+            dsl_hir::Attribute::Doc(_)
+            | dsl_hir::Attribute::Cfg(_, _)
+            | dsl_hir::Attribute::RenamedFrom(_, _)
+            | dsl_hir::Attribute::BitOrder(_, _)
+            | dsl_hir::Attribute::Unit(_, _)
+            | dsl_hir::Attribute::ValidWhen(_, _) => None,
+        })
+        .collect()
+}
+
+fn get_renamed_from(field: &dsl_hir::Field) -> Result<Vec<String>, syn::Error> {
+    field
+        .attribute_list
+        .attributes
+        .iter()
+        .filter_map(|attr| match attr {
+            dsl_hir::Attribute::RenamedFrom(val, span) if field.identifier == val => {
+                Some(Err(syn::Error::new(
+                    *span,
+                    format!(
+                        "Field `{}` can't be renamed from its own name",
+                        field.identifier
+                    ),
+                )))
+            }
+            dsl_hir::Attribute::RenamedFrom(val, _) => Some(Ok(val.clone())),
+            dsl_hir::Attribute::Doc(_)
+            | dsl_hir::Attribute::Cfg(_, _)
+            | dsl_hir::Attribute::Passthrough(_, _)
+            | dsl_hir::Attribute::BitOrder(_, _)
+            | dsl_hir::Attribute::Unit(_, _)
+            | dsl_hir::Attribute::ValidWhen(_, _) => None,
+        })
+        .collect()
+}
+
+fn get_bit_order(attrs: &dsl_hir::AttributeList) -> Result<Option<mir::BitOrder>, syn::Error> {
+    let mut bit_orders = attrs
+        .attributes
+        .iter()
+        .filter_map(|attr| match attr {
+            dsl_hir::Attribute::BitOrder(val, span) => Some((*val, *span)),
+            dsl_hir::Attribute::Doc(_)
+            | dsl_hir::Attribute::Cfg(_, _)
+            | dsl_hir::Attribute::Passthrough(_, _)
+            | dsl_hir::Attribute::RenamedFrom(_, _)
+            | dsl_hir::Attribute::Unit(_, _)
+            | dsl_hir::Attribute::ValidWhen(_, _) => None,
+        })
+        .collect::<Vec<_>>();
+
+    match bit_orders.len() {
+        0 => Ok(None),
+        1 => Ok(Some(bit_orders.remove(0).0.into())),
+        n => Err(syn::Error::new(
+            bit_orders.remove(1).1,
+            format!("Only one bit_order attribute is allowed, but {n} are found"),
+        )),
+    }
+}
+
+fn get_valid_when(attrs: &dsl_hir::AttributeList) -> Result<Option<mir::ValidWhen>, syn::Error> {
+    let mut conditions = attrs
+        .attributes
+        .iter()
+        .filter_map(|attr| match attr {
+            dsl_hir::Attribute::ValidWhen(val, span) => Some((val, *span)),
+            dsl_hir::Attribute::Doc(_)
+            | dsl_hir::Attribute::Cfg(_, _)
+            | dsl_hir::Attribute::Passthrough(_, _)
+            | dsl_hir::Attribute::RenamedFrom(_, _)
+            | dsl_hir::Attribute::BitOrder(_, _)
+            | dsl_hir::Attribute::Unit(_, _) => None,
+        })
+        .collect::<Vec<_>>();
+
+    match conditions.len() {
+        0 => Ok(None),
+        1 => {
+            let (condition, _) = conditions.remove(0);
+            Ok(Some(mir::ValidWhen {
+                field: condition.field.to_string(),
+                negated: condition.negated,
+                value: transform_field_reset_value_kind(&condition.value)?,
+                raw_value: 0,
+            }))
+        }
+        n => Err(syn::Error::new(
+            conditions.remove(1).1,
+            format!("Only one valid_when attribute is allowed, but {n} are found"),
+        )),
+    }
+}
+
+fn get_unit(attrs: &dsl_hir::AttributeList) -> Result<Option<String>, syn::Error> {
+    let mut units = attrs
+        .attributes
+        .iter()
+        .filter_map(|attr| match attr {
+            dsl_hir::Attribute::Unit(val, span) => Some((val.clone(), *span)),
+            dsl_hir::Attribute::Doc(_)
+            | dsl_hir::Attribute::Cfg(_, _)
+            | dsl_hir::Attribute::Passthrough(_, _)
+            | dsl_hir::Attribute::RenamedFrom(_, _)
+            | dsl_hir::Attribute::BitOrder(_, _)
+            | dsl_hir::Attribute::ValidWhen(_, _) => None,
+        })
+        .collect::<Vec<_>>();
+
+    match units.len() {
+        0 => Ok(None),
+        1 => Ok(Some(units.remove(0).0)),
+        n => Err(syn::Error::new(
+            units.remove(1).1,
+            format!("Only one unit attribute is allowed, but {n} are found"),
+        )),
+    }
+}
+
 fn transform_field(
     field: &dsl_hir::Field,
     global_config: &mir::GlobalConfig,
 ) -> Result<mir::Field, syn::Error> {
     let field_cfg_attr = get_cfg_attr(&field.attribute_list)?;
     let field_description = get_description(&field.attribute_list).unwrap_or_default();
+    let passthrough_attrs = get_passthrough_attrs(&field.attribute_list);
+
+    if let Some((_, span)) = passthrough_attrs.first()
+        && !matches!(
+            field.field_conversion,
+            Some(dsl_hir::FieldConversion::Enum { .. })
+        )
+    {
+        return Err(syn::Error::new(
+            *span,
+            format!(
+                "Field `{}` has an `allow` or `derive` attribute, but these are only allowed on fields that generate an enum",
+                field.identifier
+            ),
+        ));
+    }
+
+    check_bool_field_address(field)?;
 
     Ok(mir::Field {
         cfg_attr: field_cfg_attr.clone(),
         description: field_description.clone(),
         name: field.identifier.to_string(),
+        renamed_from: get_renamed_from(field)?,
+        bit_order: get_bit_order(&field.attribute_list)?,
+        unit: get_unit(&field.attribute_list)?,
+        valid_when: get_valid_when(&field.attribute_list)?,
         access: field
             .access
             .map(Into::into)
@@ -518,7 +978,17 @@ fn transform_field(
         field_conversion: field
             .field_conversion
             .as_ref()
-            .map(|fc| transform_field_conversion(field_description, fc))
+            .map(|fc| {
+                transform_field_conversion(
+                    global_config,
+                    field_description,
+                    passthrough_attrs
+                        .into_iter()
+                        .map(|(attr, _)| attr)
+                        .collect(),
+                    fc,
+                )
+            })
             .transpose()?,
         field_address: match &field.field_address {
             dsl_hir::FieldAddress::Integer(start) if field.base_type.is_bool() => {
@@ -537,30 +1007,83 @@ fn transform_field(
                 start.base10_parse()?..end.base10_parse()?
             }
             dsl_hir::FieldAddress::RangeInclusive { start, end } => {
-                start.base10_parse()?..(end.base10_parse::<u32>()? + 1)
+                start.base10_parse()?..inclusive_range_end(end)?
             }
         },
     })
 }
 
+/// The exclusive end of an inclusive bit range
+fn inclusive_range_end(end: &syn::LitInt) -> Result<u32, syn::Error> {
+    end.base10_parse::<u32>()?
+        .checked_add(1)
+        .ok_or_else(|| syn::Error::new(end.span(), "The end of the range is too large"))
+}
+
+/// A bool field can only be a single bit. Given as a range, it may not span more than that.
+fn check_bool_field_address(field: &dsl_hir::Field) -> Result<(), syn::Error> {
+    if !field.base_type.is_bool() {
+        return Ok(());
+    }
+
+    let (start, end) = match &field.field_address {
+        dsl_hir::FieldAddress::Integer(_) => return Ok(()),
+        dsl_hir::FieldAddress::Range { start, end } => {
+            (start.base10_parse::<u32>()?, end.base10_parse::<u32>()?)
+        }
+        dsl_hir::FieldAddress::RangeInclusive { start, end } => {
+            (start.base10_parse::<u32>()?, inclusive_range_end(end)?)
+        }
+    };
+
+    if end.saturating_sub(start) > 1 {
+        return Err(syn::Error::new(
+            field.identifier.span(),
+            format!(
+                "Field `{}` has base type bool and can only be one bit, but its address spans {} bits",
+                field.identifier,
+                end - start
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn path_to_type_name(path: &syn::Path) -> String {
+    path.to_token_stream()
+        .to_string()
+        .replace(char::is_whitespace, "")
+}
+
 fn transform_field_conversion(
+    global_config: &mir::GlobalConfig,
     field_description: String,
+    passthrough_attributes: Vec<String>,
     field_conversion: &dsl_hir::FieldConversion,
 ) -> Result<mir::FieldConversion, syn::Error> {
     match field_conversion {
-        dsl_hir::FieldConversion::Direct { path, use_try } => Ok(mir::FieldConversion::Direct {
-            type_name: path
-                .to_token_stream()
-                .to_string()
-                .replace(char::is_whitespace, ""),
-            use_try: *use_try,
-        }),
+        dsl_hir::FieldConversion::Direct { path, use_try } => {
+            let type_name = path_to_type_name(path);
+            // A single identifier can be a type alias from the global config
+            let type_name = global_config
+                .type_aliases
+                .iter()
+                .find(|(alias, _)| *alias == type_name)
+                .map(|(_, aliased_type)| aliased_type.clone())
+                .unwrap_or(type_name);
+
+            Ok(mir::FieldConversion::Direct {
+                type_name,
+                use_try: *use_try,
+            })
+        }
         dsl_hir::FieldConversion::Enum {
             identifier,
             enum_variant_list,
             use_try,
-        } => Ok(mir::FieldConversion::Enum {
-            enum_value: mir::Enum::new(
+        } => {
+            let mut enum_value = mir::Enum::new(
                 field_description,
                 identifier.to_string(),
                 enum_variant_list
@@ -576,15 +1099,76 @@ fn transform_field_conversion(
                                 Some(dsl_hir::EnumValue::Specified(val)) => {
                                     mir::EnumValue::Specified(val.base10_parse()?)
                                 }
+                                Some(dsl_hir::EnumValue::Range { start, end }) => {
+                                    mir::EnumValue::Range(
+                                        start.base10_parse()?..=(end.base10_parse::<i128>()? - 1),
+                                    )
+                                }
+                                Some(dsl_hir::EnumValue::RangeInclusive { start, end }) => {
+                                    mir::EnumValue::Range(
+                                        start.base10_parse()?..=end.base10_parse()?,
+                                    )
+                                }
                                 Some(dsl_hir::EnumValue::Default) => mir::EnumValue::Default,
                                 Some(dsl_hir::EnumValue::CatchAll) => mir::EnumValue::CatchAll,
+                                Some(dsl_hir::EnumValue::Fallback) => mir::EnumValue::Fallback,
                             },
                         })
                     })
                     .collect::<Result<_, syn::Error>>()?,
-            ),
-            use_try: *use_try,
+            );
+            enum_value.passthrough_attributes = passthrough_attributes;
+
+            Ok(mir::FieldConversion::Enum {
+                enum_value,
+                use_try: *use_try,
+            })
+        }
+        dsl_hir::FieldConversion::Crc { over } => Ok(mir::FieldConversion::Crc {
+            over: match over {
+                dsl_hir::FieldAddress::Integer(start) => {
+                    return Err(syn::Error::new(
+                        start.span(),
+                        "The crc must be over a range of bits, e.g. `crc over 0..24`",
+                    ));
+                }
+                dsl_hir::FieldAddress::Range { start, end } => {
+                    start.base10_parse()?..end.base10_parse()?
+                }
+                dsl_hir::FieldAddress::RangeInclusive { start, end } => {
+                    start.base10_parse()?..inclusive_range_end(end)?
+                }
+            },
         }),
+        dsl_hir::FieldConversion::Newtype { identifier, inner } => {
+            Ok(mir::FieldConversion::Newtype {
+                newtype: mir::Newtype {
+                    cfg_attr: mir::Cfg::default(),
+                    description: field_description,
+                    name: identifier.to_string(),
+                    inner: inner.clone().try_into()?,
+                },
+            })
+        }
+        dsl_hir::FieldConversion::Functions {
+            read_type,
+            read_fn,
+            write_type,
+            write_fn,
+        } => {
+            let path_to_string = |path: &syn::Path| {
+                path.to_token_stream()
+                    .to_string()
+                    .replace(char::is_whitespace, "")
+            };
+
+            Ok(mir::FieldConversion::Functions {
+                read_type: path_to_string(read_type),
+                read_fn: path_to_string(read_fn),
+                write_type: path_to_string(write_type),
+                write_fn: path_to_string(write_fn),
+            })
+        }
     }
 }
 
@@ -609,6 +1193,17 @@ fn transform_buffer(
                 )
             })?
             .base10_parse()?,
+        frame: buffer.frame.map(|frame| frame.to_string()),
+        max_size: buffer
+            .max_size
+            .as_ref()
+            .map(|max_size| max_size.base10_parse())
+            .transpose()?,
+        length_field: buffer
+            .length_field
+            .as_ref()
+            .map(|field| transform_field(field, global_config).map(Box::new))
+            .transpose()?,
     })
 }
 
@@ -633,7 +1228,7 @@ fn transform_ref(ref_object: dsl_hir::RefObject) -> Result<mir::RefObject, syn::
                     format!("Ref `{}` cannot ref a buffer", ref_object.identifier),
                 ));
             }
-            dsl_hir::Object::Ref(_) => {
+            dsl_hir::Object::Ref(_) | dsl_hir::Object::Alias(_) => {
                 return Err(syn::Error::new(
                     ref_object.identifier.span(),
                     format!(
@@ -646,6 +1241,20 @@ fn transform_ref(ref_object: dsl_hir::RefObject) -> Result<mir::RefObject, syn::
     })
 }
 
+/// An alias is a ref to a register that only overrides the address
+fn transform_alias(alias: dsl_hir::AliasObject) -> Result<mir::RefObject, syn::Error> {
+    Ok(mir::RefObject {
+        cfg_attr: get_cfg_attr(&alias.attribute_list)?,
+        description: get_description(&alias.attribute_list).unwrap_or_default(),
+        name: alias.identifier.to_string(),
+        object_override: mir::ObjectOverride::Register(mir::RegisterOverride {
+            name: alias.target.to_string(),
+            address: Some(alias.address.base10_parse()?),
+            ..Default::default()
+        }),
+    })
+}
+
 fn transform_block_override(
     block_override: dsl_hir::Block,
 ) -> Result<mir::BlockOverride, syn::Error> {
@@ -725,23 +1334,66 @@ fn transform_register_override(
                     "No `SizeBits` is allowed on register overrides",
                 ));
             }
+            dsl_hir::RegisterItem::SizeBytes(_) => {
+                return Err(syn::Error::new(
+                    register_override.identifier.span(),
+                    "No `SizeBytes` is allowed on register overrides",
+                ));
+            }
+            dsl_hir::RegisterItem::DefaultFieldAccess(_) => {
+                return Err(syn::Error::new(
+                    register_override.identifier.span(),
+                    "No `DefaultFieldAccess` is allowed on register overrides",
+                ));
+            }
             dsl_hir::RegisterItem::AllowBitOverlap(_) => {
                 return Err(syn::Error::new(
                     register_override.identifier.span(),
                     "No `AllowBitOverlap` is allowed on register overrides",
                 ));
             }
-            dsl_hir::RegisterItem::Access(_) => {}
-            dsl_hir::RegisterItem::Address(_) => {}
-            dsl_hir::RegisterItem::ResetValueInt(_) => {}
-            dsl_hir::RegisterItem::ResetValueArray(_) => {}
-            dsl_hir::RegisterItem::Repeat(_) => {}
-            dsl_hir::RegisterItem::AllowAddressOverlap(_) => {}
-        }
-    }
-
-    Ok(mir::RegisterOverride {
-        name: register_override.identifier.to_string(),
+            dsl_hir::RegisterItem::AddressSpan(_) => {
+                return Err(syn::Error::new(
+                    register_override.identifier.span(),
+                    "No address span is allowed on register overrides. Use a single address to move the whole span",
+                ));
+            }
+            dsl_hir::RegisterItem::ReservedRest => {
+                return Err(syn::Error::new(
+                    register_override.identifier.span(),
+                    "No `reserved_rest` is allowed on register overrides",
+                ));
+            }
+            dsl_hir::RegisterItem::Shadowed(_) => {
+                return Err(syn::Error::new(
+                    register_override.identifier.span(),
+                    "No `Shadowed` is allowed on register overrides",
+                ));
+            }
+            dsl_hir::RegisterItem::ExpectAddress(_) | dsl_hir::RegisterItem::ExpectSize(_) => {
+                return Err(syn::Error::new(
+                    register_override.identifier.span(),
+                    "No datasheet expectations are allowed on register overrides",
+                ));
+            }
+            dsl_hir::RegisterItem::LengthField(_) => {
+                return Err(syn::Error::new(
+                    register_override.identifier.span(),
+                    "No `LENGTH_FIELD` is allowed on register overrides",
+                ));
+            }
+            dsl_hir::RegisterItem::Access(_) => {}
+            dsl_hir::RegisterItem::Address(_) => {}
+            dsl_hir::RegisterItem::ResetValueInt(_) => {}
+            dsl_hir::RegisterItem::ResetValueArray(_) => {}
+            dsl_hir::RegisterItem::ResetValueFields(_) => {}
+            dsl_hir::RegisterItem::Repeat(_) => {}
+            dsl_hir::RegisterItem::AllowAddressOverlap(_) => {}
+        }
+    }
+
+    Ok(mir::RegisterOverride {
+        name: register_override.identifier.to_string(),
         access: register_override
             .register_item_list
             .register_items
@@ -788,6 +1440,9 @@ fn transform_register_override(
                         })
                         .map(mir::ResetValue::Integer),
                 ),
+                dsl_hir::RegisterItem::ResetValueFields(fields) => {
+                    Some(transform_reset_value_fields(fields))
+                }
                 _ => None,
             })
             .transpose()?,
@@ -869,9 +1524,21 @@ fn transform_command_override(
                             "No `AllowBitOverlap` is allowed on command overrides",
                         ));
                     }
+                    dsl_hir::CommandItem::PollUntil(_, _) => {
+                        return Err(syn::Error::new(
+                            command_override.identifier.span(),
+                            "No `POLL_UNTIL` is allowed on command overrides",
+                        ));
+                    }
                     dsl_hir::CommandItem::AllowAddressOverlap(_) => {}
                     dsl_hir::CommandItem::Repeat(_) => {}
-                    dsl_hir::CommandItem::Address(_) => {}
+                    dsl_hir::CommandItem::Address(dsl_hir::CommandAddress::Opcode(_)) => {
+                        return Err(syn::Error::new(
+                            command_override.identifier.span(),
+                            "No opcode is allowed on command overrides. Use an integer address instead",
+                        ));
+                    }
+                    dsl_hir::CommandItem::Address(dsl_hir::CommandAddress::Integer(_)) => {}
                 }
             }
 
@@ -897,7 +1564,7 @@ fn transform_command_override(
             .items
             .iter()
             .find_map(|item| match item {
-                dsl_hir::CommandItem::Address(lit) => Some(lit),
+                dsl_hir::CommandItem::Address(dsl_hir::CommandAddress::Integer(lit)) => Some(lit),
                 _ => None,
             })
             .map(|lit| lit.base10_parse())
@@ -948,12 +1615,35 @@ mod tests {
                 type DefaultFieldAccess = RW;
                 type DefaultBufferAccess = WO;
                 type DefaultByteOrder = LE;
+                type DefaultRegisterByteOrder = BE;
+                type DefaultCommandByteOrder = LE;
                 type DefaultBitOrder = MSB0;
                 type RegisterAddressType = i8;
                 type CommandAddressType = i64;
                 type BufferAddressType = u32;
                 type NameWordBoundaries = \"-\";
                 type DefmtFeature = \"defmt-03\";
+                type UfmtFeature = \"ufmt\";
+                type AutoAddress = true;
+                type ResetSequence = [(Foo, 0x01), (Bar, 2)];
+                type Visibility = \"pub(crate)\";
+                type MaxFields = 16;
+                type FlatAccessors = true;
+                type CrcAlgorithm = Crc8Nrsc5;
+                type IdRegister = (WhoAmI, chip_id, 0x42);
+                type AddressUnit = Word16;
+                type Mmio = true;
+                type ResetHoldUs = 10;
+                type GroupByKind = true;
+                type GenerateBitOps = false;
+                type FeatureGate = \"my-driver\";
+                type ChipVariants = [A, B];
+                type DynAccess = true;
+                type SharedCommandInterface = true;
+                type PostRead = crate::on_read;
+                type EnumDerives = [Clone, Debug];
+                type DetectBusStuck = true;
+                type_alias Temp = crate::Temperature;
             }",
         )
         .unwrap();
@@ -967,12 +1657,42 @@ mod tests {
                 default_field_access: mir::Access::RW,
                 default_buffer_access: mir::Access::WO,
                 default_byte_order: Some(mir::ByteOrder::LE),
+                default_register_byte_order: Some(mir::ByteOrder::BE),
+                default_command_byte_order: Some(mir::ByteOrder::LE),
                 default_bit_order: mir::BitOrder::MSB0,
                 register_address_type: Some(mir::Integer::I8),
                 command_address_type: Some(mir::Integer::I64),
                 buffer_address_type: Some(mir::Integer::U32),
                 name_word_boundaries: vec![Boundary::Hyphen],
                 defmt_feature: Some("defmt-03".into()),
+                ufmt_feature: Some("ufmt".into()),
+                auto_address: true,
+                reset_sequence: vec![
+                    ("Foo".into(), mir::ResetValue::Integer(1)),
+                    ("Bar".into(), mir::ResetValue::Integer(2)),
+                ],
+                visibility: Some("pub(crate)".into()),
+                max_fields: Some(16),
+                flat_accessors: true,
+                crc_algorithm: mir::CrcAlgorithm::Crc8Nrsc5,
+                id_register: Some(mir::IdRegister {
+                    register: "WhoAmI".into(),
+                    field: "chip_id".into(),
+                    expected: 0x42,
+                }),
+                address_unit: mir::AddressUnit::Word16,
+                mmio: true,
+                reset_hold_us: Some(10),
+                group_by_kind: true,
+                generate_bit_ops: false,
+                feature_gate: Some("my-driver".into()),
+                chip_variants: vec!["A".into(), "B".into()],
+                dyn_access: true,
+                shared_command_interface: true,
+                post_read: Some("crate::on_read".into()),
+                enum_derives: Some(vec!["Clone".into(), "Debug".into()]),
+                detect_bus_stuck: true,
+                type_aliases: vec![("Temp".into(), "crate::Temperature".into())],
             }
         );
     }
@@ -999,6 +1719,9 @@ mod tests {
                 name: "Foo".into(),
                 access: mir::Access::RW,
                 address: 5,
+                frame: None,
+                max_size: None,
+                length_field: None,
             })]
         );
 
@@ -1124,6 +1847,10 @@ mod tests {
                         cfg_attr: mir::Cfg::new(Some("bla")),
                         description: " Hello!".into(),
                         name: "val".into(),
+                        renamed_from: Vec::new(),
+                        bit_order: None,
+                        unit: None,
+                        valid_when: None,
                         access: mir::Access::WO,
                         base_type: mir::BaseType::Bool,
                         field_conversion: None,
@@ -1133,6 +1860,10 @@ mod tests {
                         cfg_attr: mir::Cfg::new(None),
                         description: Default::default(),
                         name: "foo".into(),
+                        renamed_from: Vec::new(),
+                        bit_order: None,
+                        unit: None,
+                        valid_when: None,
                         access: mir::Access::RO,
                         base_type: mir::BaseType::Uint,
                         field_conversion: Some(mir::FieldConversion::Direct {
@@ -1146,6 +1877,10 @@ mod tests {
                     cfg_attr: mir::Cfg::new(None),
                     description: Default::default(),
                     name: "val".into(),
+                    renamed_from: Vec::new(),
+                    bit_order: None,
+                    unit: None,
+                    valid_when: None,
                     access: mir::Access::RO,
                     base_type: mir::BaseType::Int,
                     field_conversion: Some(mir::FieldConversion::Enum {
@@ -1207,6 +1942,46 @@ mod tests {
             "Field `val` has a non-bool base type and must specify the start and the end address"
         );
 
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    command Foo {
+                        const ADDRESS = 0;
+
+                        in {
+                            flag: bool = 0..4,
+                        }
+                    }
+                    ",
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "Field `flag` has base type bool and can only be one bit, but its address spans 4 bits"
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    command Foo {
+                        const ADDRESS = 0;
+
+                        in {
+                            val: uint = 0..=4294967295,
+                        }
+                    }
+                    ",
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "The end of the range is too large"
+        );
+
         assert_eq!(
             transform(
                 syn::parse_str::<dsl_hir::Device>(
@@ -1239,6 +2014,10 @@ mod tests {
                     cfg_attr: mir::Cfg::new(None),
                     description: Default::default(),
                     name: "val".into(),
+                    renamed_from: Vec::new(),
+                    bit_order: None,
+                    unit: None,
+                    valid_when: None,
                     access: mir::Access::default(),
                     base_type: mir::BaseType::Bool,
                     field_conversion: None,
@@ -1292,6 +2071,9 @@ mod tests {
                 name: "Foo".into(),
                 access: mir::Access::default(),
                 address: 5,
+                frame: None,
+                max_size: None,
+                length_field: None,
             })]
         );
         assert_eq!(
@@ -1312,6 +2094,9 @@ mod tests {
                 name: "Foo".into(),
                 access: mir::Access::default(),
                 address: 5,
+                frame: None,
+                max_size: None,
+                length_field: None,
             })]
         );
         assert_eq!(
@@ -1834,6 +2619,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn alias_register() {
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    /// The foo register of the v2 chip
+                    #[cfg(feature = \"v2\")]
+                    alias FooV2 = Foo @ 0x20
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap()
+            .objects,
+            &[mir::Object::Ref(mir::RefObject {
+                cfg_attr: mir::Cfg::new(Some("feature = \"v2\"")),
+                description: " The foo register of the v2 chip".into(),
+                name: "FooV2".into(),
+                object_override: mir::ObjectOverride::Register(mir::RegisterOverride {
+                    name: "Foo".into(),
+                    address: Some(0x20),
+                    ..Default::default()
+                })
+            })]
+        );
+    }
+
     #[test]
     fn ref_block() {
         assert_eq!(
@@ -2002,7 +2815,10 @@ mod tests {
                     description: Default::default(),
                     name: "Bla".into(),
                     access: Default::default(),
-                    address: 5
+                    address: 5,
+                    frame: None,
+                    max_size: None,
+                    length_field: None,
                 })],
             })]
         );
@@ -2037,6 +2853,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn register_default_field_access() {
+        let device = transform(
+            syn::parse_str::<dsl_hir::Device>(
+                "
+                config {
+                    type DefaultFieldAccess = WO;
+                }
+                register Foo {
+                    type DefaultFieldAccess = RO;
+                    const ADDRESS = 0;
+                    const SIZE_BITS = 8;
+
+                    status: uint = 0..4,
+                    control: RW uint = 4..8,
+                },
+                register Bar {
+                    const ADDRESS = 1;
+                    const SIZE_BITS = 8;
+
+                    value: uint = 0..8,
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let field_access = device
+            .objects
+            .iter()
+            .filter_map(|object| match object {
+                mir::Object::Register(register) => Some(&register.fields),
+                _ => None,
+            })
+            .flatten()
+            .map(|field| (field.name.as_str(), field.access))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            field_access,
+            [
+                ("status", mir::Access::RO),
+                ("control", mir::Access::RW),
+                ("value", mir::Access::WO),
+            ]
+        );
+    }
+
     #[test]
     fn register() {
         assert_eq!(
@@ -2071,6 +2936,46 @@ mod tests {
             "Register `Foo` must have size bits specified"
         );
 
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    register Foo {
+                        const ADDRESS = 5;
+                        const SIZE_BITS = 24;
+                        const SIZE_BYTES = 3;
+                    }
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "Register `Foo` has both `SIZE_BITS` and `SIZE_BYTES` specified. Only one is allowed"
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    register Foo {
+                        const ADDRESS = 5;
+                        const SIZE_BYTES = 3;
+                    }
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap()
+            .objects,
+            &[mir::Object::Register(mir::Register {
+                name: "Foo".into(),
+                address: 5,
+                size_bits: 24,
+                ..Default::default()
+            })]
+        );
+
         assert_eq!(
             transform(
                 syn::parse_str::<dsl_hir::Device>(
@@ -2135,6 +3040,10 @@ mod tests {
                     cfg_attr: Default::default(),
                     description: Default::default(),
                     name: "val".into(),
+                    renamed_from: Vec::new(),
+                    bit_order: None,
+                    unit: None,
+                    valid_when: None,
                     access: Default::default(),
                     base_type: mir::BaseType::Int,
                     field_conversion: Default::default(),
@@ -2213,6 +3122,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn auto_address_registers() {
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    config {
+                        type AutoAddress = true;
+                    }
+                    register Foo {
+                        const SIZE_BITS = 16;
+                    },
+                    register Bar {
+                        const SIZE_BITS = 12;
+                    },
+                    register Baz {
+                        const ADDRESS = 10;
+                        const SIZE_BITS = 8;
+                    },
+                    register Qux {
+                        const SIZE_BITS = 8;
+                    }
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap()
+            .objects,
+            &[
+                mir::Object::Register(mir::Register {
+                    name: "Foo".into(),
+                    address: 0,
+                    size_bits: 16,
+                    ..Default::default()
+                }),
+                mir::Object::Register(mir::Register {
+                    name: "Bar".into(),
+                    address: 2,
+                    size_bits: 12,
+                    ..Default::default()
+                }),
+                mir::Object::Register(mir::Register {
+                    name: "Baz".into(),
+                    address: 10,
+                    size_bits: 8,
+                    ..Default::default()
+                }),
+                mir::Object::Register(mir::Register {
+                    name: "Qux".into(),
+                    address: 11,
+                    size_bits: 8,
+                    ..Default::default()
+                })
+            ]
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    config {
+                        type AutoAddress = true;
+                    }
+                    register Foo {
+                        const SIZE_BITS = 8;
+                        const REPEAT = {
+                            count: 3,
+                            stride: 2
+                        };
+                    },
+                    register Bar {
+                        const SIZE_BITS = 8;
+                    }
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap()
+            .objects[1],
+            mir::Object::Register(mir::Register {
+                name: "Bar".into(),
+                address: 5,
+                size_bits: 8,
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    config {
+                        type AutoAddress = true;
+                        type AddressUnit = Word16;
+                    }
+                    register Foo {
+                        const SIZE_BITS = 32;
+                    },
+                    register Bar {
+                        const SIZE_BITS = 16;
+                    }
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap()
+            .objects[1],
+            mir::Object::Register(mir::Register {
+                name: "Bar".into(),
+                address: 2,
+                size_bits: 16,
+                ..Default::default()
+            })
+        );
+    }
+
     #[test]
     fn test_integer_try_from_ident() {
         // Test for valid integer types
@@ -2242,4 +3267,428 @@ mod tests {
             "Must be an integer type: u8, u16, u32, i8, i16, i32, i64"
         );
     }
+
+    #[test]
+    fn passthrough_attributes_only_on_enum_fields() {
+        let device = transform(
+            syn::parse_str::<dsl_hir::Device>(
+                "
+                register Foo {
+                    const ADDRESS = 0;
+                    const SIZE_BITS = 8;
+
+                    #[allow(non_camel_case_types)]
+                    value: uint as enum Value { a, b } = 0..1,
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mir::Object::Register(register) = &device.objects[0] else {
+            panic!("Expected a register");
+        };
+        let Some(mir::FieldConversion::Enum { enum_value, .. }) =
+            &register.fields[0].field_conversion
+        else {
+            panic!("Expected an enum conversion");
+        };
+        assert_eq!(
+            enum_value.passthrough_attributes,
+            ["allow (non_camel_case_types)"]
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    register Foo {
+                        const ADDRESS = 0;
+                        const SIZE_BITS = 8;
+
+                        #[derive(PartialOrd)]
+                        value: uint = 0..8,
+                    }
+                    ",
+                )
+                .unwrap(),
+            )
+            .unwrap_err()
+            .to_string(),
+            "Field `value` has an `allow` or `derive` attribute, but these are only allowed on fields that generate an enum"
+        );
+    }
+
+    #[test]
+    fn field_renamed_from() {
+        let device = transform(
+            syn::parse_str::<dsl_hir::Device>(
+                "
+                register Foo {
+                    const ADDRESS = 0;
+                    const SIZE_BITS = 8;
+
+                    #[renamed_from = \"val\"]
+                    #[renamed_from = \"v\"]
+                    value: uint = 0..8,
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mir::Object::Register(register) = &device.objects[0] else {
+            panic!("Expected a register");
+        };
+        assert_eq!(register.fields[0].renamed_from, ["val", "v"]);
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    register Foo {
+                        const ADDRESS = 0;
+                        const SIZE_BITS = 8;
+
+                        #[renamed_from = \"value\"]
+                        value: uint = 0..8,
+                    }
+                    ",
+                )
+                .unwrap(),
+            )
+            .unwrap_err()
+            .to_string(),
+            "Field `value` can't be renamed from its own name"
+        );
+    }
+
+    #[test]
+    fn register_address_span() {
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    config {
+                        type AutoAddress = true;
+                    }
+                    register Foo {
+                        const ADDRESS = [0x12, 0x11, 0x10];
+                        const SIZE_BITS = 24;
+                    },
+                    register Bar {
+                        const SIZE_BITS = 8;
+                    }
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap()
+            .objects,
+            &[
+                mir::Object::Register(mir::Register {
+                    name: "Foo".into(),
+                    address: 0x12,
+                    span_offsets: vec![0, -1, -2],
+                    size_bits: 24,
+                    ..Default::default()
+                }),
+                mir::Object::Register(mir::Register {
+                    name: "Bar".into(),
+                    address: 0x13,
+                    size_bits: 8,
+                    ..Default::default()
+                }),
+            ]
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    register Foo {
+                        const ADDRESS = [];
+                        const SIZE_BITS = 24;
+                    }
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "Register `Foo` must have an address"
+        );
+    }
+
+    #[test]
+    fn reserved_rest_is_complement() {
+        let device = transform(
+            syn::parse_str::<dsl_hir::Device>(
+                "
+                register Foo {
+                    const ADDRESS = 0;
+                    const SIZE_BITS = 16;
+                    reserved_rest;
+
+                    b: uint = 8..12,
+                    a: uint = 4..8,
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mir::Object::Register(register) = &device.objects[0] else {
+            panic!("Expected a register");
+        };
+
+        assert!(register.reserved_rest);
+        assert_eq!(register.reserved_bits(), vec![0..4, 12..16]);
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    register Foo {
+                        const ADDRESS = 0;
+                        const SIZE_BITS = 8;
+                    },
+                    ref Bar = register Foo {
+                        reserved_rest;
+                    }
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "No `reserved_rest` is allowed on register overrides"
+        );
+    }
+
+    #[test]
+    fn command_opcode() {
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    command Foo = [0xDE, 0xAD],
+                    command Bar {
+                        const ADDRESS = [0x01, 0x02, 0x03];
+                    }
+                    ",
+                )
+                .unwrap()
+            )
+            .unwrap()
+            .objects,
+            &[
+                mir::Object::Command(mir::Command {
+                    name: "Foo".into(),
+                    address: 0xDEAD,
+                    opcode: vec![0xDE, 0xAD],
+                    ..Default::default()
+                }),
+                mir::Object::Command(mir::Command {
+                    name: "Bar".into(),
+                    address: 0x010203,
+                    opcode: vec![0x01, 0x02, 0x03],
+                    ..Default::default()
+                })
+            ]
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    command Foo = [1, 2, 3, 4, 5]
+                    ",
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "An opcode must be 1 to 4 bytes long"
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    command Foo = [0x100]
+                    ",
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn newtype_conversion() {
+        let device = transform(
+            syn::parse_str::<dsl_hir::Device>(
+                "
+                register Foo {
+                    const ADDRESS = 0;
+                    const SIZE_BITS = 16;
+
+                    /// The temperature
+                    temperature: uint as newtype Temperature(u16) = 0..12,
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mir::Object::Register(register) = &device.objects[0] else {
+            panic!("Expected a register");
+        };
+        assert_eq!(
+            register.fields[0].field_conversion,
+            Some(mir::FieldConversion::Newtype {
+                newtype: mir::Newtype {
+                    cfg_attr: mir::Cfg::default(),
+                    description: " The temperature".into(),
+                    name: "Temperature".into(),
+                    inner: mir::Integer::U16,
+                }
+            })
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "
+                    register Foo {
+                        const ADDRESS = 0;
+                        const SIZE_BITS = 16;
+
+                        temperature: uint as newtype Temperature(f32) = 0..12,
+                    }
+                    ",
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "Must be an integer type: u8, u16, u32, i8, i16, i32, i64"
+        );
+    }
+
+    #[test]
+    fn functions_conversion() {
+        let device = transform(
+            syn::parse_str::<dsl_hir::Device>(
+                "
+                register Foo {
+                    const ADDRESS = 0;
+                    const SIZE_BITS = 8;
+
+                    gain: uint as (crate::Gain from gain :: decode, GainSetting into encode_gain) = 0..4,
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mir::Object::Register(register) = &device.objects[0] else {
+            panic!("Expected a register");
+        };
+        assert_eq!(
+            register.fields[0].field_conversion,
+            Some(mir::FieldConversion::Functions {
+                read_type: "crate::Gain".into(),
+                read_fn: "gain::decode".into(),
+                write_type: "GainSetting".into(),
+                write_fn: "encode_gain".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn type_alias_resolved() {
+        let device = transform(
+            syn::parse_str::<dsl_hir::Device>(
+                "
+                config {
+                    type_alias Temp = crate::units::Temperature;
+                }
+                register Foo {
+                    const ADDRESS = 0;
+                    const SIZE_BITS = 16;
+
+                    inside: uint as Temp = 0..8,
+                    outside: uint as try Temp = 8..16,
+                },
+                register Bar {
+                    const ADDRESS = 1;
+                    const SIZE_BITS = 8;
+
+                    other: uint as other::Temp = 0..8,
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            device.global_config.type_aliases,
+            vec![("Temp".into(), "crate::units::Temperature".into())]
+        );
+
+        let mir::Object::Register(foo) = &device.objects[0] else {
+            panic!("Expected a register");
+        };
+        assert_eq!(
+            foo.fields[0].field_conversion,
+            Some(mir::FieldConversion::Direct {
+                type_name: "crate::units::Temperature".into(),
+                use_try: false,
+            })
+        );
+        assert_eq!(
+            foo.fields[1].field_conversion,
+            Some(mir::FieldConversion::Direct {
+                type_name: "crate::units::Temperature".into(),
+                use_try: true,
+            })
+        );
+
+        let mir::Object::Register(bar) = &device.objects[1] else {
+            panic!("Expected a register");
+        };
+        assert_eq!(
+            bar.fields[0].field_conversion,
+            Some(mir::FieldConversion::Direct {
+                type_name: "other::Temp".into(),
+                use_try: false,
+            })
+        );
+
+        assert_eq!(
+            transform(
+                syn::parse_str::<dsl_hir::Device>(
+                    "config {
+                        type_alias Temp = crate::Temperature;
+                        type_alias Volt = crate::Voltage;
+                        type_alias Temp = crate::Voltage;
+                    }",
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "Duplicate type alias found: `Temp`"
+        );
+    }
 }
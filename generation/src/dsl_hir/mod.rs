@@ -1,9 +1,10 @@
-use std::mem::Discriminant;
+use std::{cell::RefCell, mem::Discriminant, path::PathBuf};
 
 use convert_case::Boundary;
 use proc_macro2::Span;
+use quote::ToTokens;
 use syn::{
-    Ident, LitBool, LitInt, LitStr, Token, braced, bracketed,
+    Ident, LitBool, LitInt, LitStr, Token, braced, bracketed, parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
@@ -19,9 +20,110 @@ pub struct Device {
 
 impl Parse for Device {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut global_config_list: GlobalConfigList = input.parse()?;
+
+        let mut includes = Vec::new();
+        while input.peek(kw::include) {
+            includes.push(input.parse::<Include>()?);
+        }
+
+        let mut object_list: ObjectList = input.parse()?;
+
+        // The objects of the included files go first, in the order they were included
+        let mut objects = Vec::new();
+        for include in includes {
+            let included = include.load()?;
+
+            for config in included.global_config_list.configs {
+                match global_config_list.configs.iter().find(|existing| {
+                    core::mem::discriminant(*existing) == core::mem::discriminant(&config)
+                }) {
+                    Some(existing) if *existing == config => {}
+                    Some(_) => {
+                        return Err(syn::Error::new(
+                            include.path.span(),
+                            format!(
+                                "The included file \"{}\" has a conflicting global config: `{config:?}`",
+                                include.path.value(),
+                            ),
+                        ));
+                    }
+                    None => global_config_list.configs.push(config),
+                }
+            }
+
+            objects.extend(included.object_list.objects);
+        }
+        objects.append(&mut object_list.objects);
+        object_list.objects = objects;
+
         Ok(Self {
-            global_config_list: input.parse()?,
-            object_list: input.parse()?,
+            global_config_list,
+            object_list,
+        })
+    }
+}
+
+/// An `include!("path/to/file.dsl");` directive.
+/// The path is relative to `CARGO_MANIFEST_DIR`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Include {
+    path: LitStr,
+}
+
+impl Parse for Include {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::include>()?;
+        input.parse::<Token![!]>()?;
+        let path_input;
+        parenthesized!(path_input in input);
+        let path = path_input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(Self { path })
+    }
+}
+
+thread_local! {
+    /// The files that are being included right now. Used to detect include cycles.
+    static INCLUDE_STACK: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+impl Include {
+    /// Read and parse the included file
+    fn load(&self) -> syn::Result<Device> {
+        let mut path = PathBuf::from(self.path.value());
+        if path.is_relative()
+            && let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR")
+        {
+            path = PathBuf::from(manifest_dir).join(path);
+        }
+
+        let error = |message: String| syn::Error::new(self.path.span(), message);
+
+        if INCLUDE_STACK.with_borrow(|stack| stack.contains(&path)) {
+            return Err(error(format!(
+                "The file \"{}\" includes itself",
+                path.display()
+            )));
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            error(format!(
+                "Could not read the included file \"{}\": {e}",
+                path.display()
+            ))
+        })?;
+
+        INCLUDE_STACK.with_borrow_mut(|stack| stack.push(path.clone()));
+        let device = syn::parse_str::<Device>(&contents);
+        INCLUDE_STACK.with_borrow_mut(|stack| stack.pop());
+
+        device.map_err(|e| {
+            error(format!(
+                "Could not parse the included file \"{}\": {e}",
+                path.display()
+            ))
         })
     }
 }
@@ -59,16 +161,48 @@ pub enum GlobalConfig {
     DefaultFieldAccess(Access),
     DefaultBufferAccess(Access),
     DefaultByteOrder(ByteOrder),
+    DefaultRegisterByteOrder(ByteOrder),
+    DefaultCommandByteOrder(ByteOrder),
     DefaultBitOrder(BitOrder),
     RegisterAddressType(syn::Ident),
     CommandAddressType(syn::Ident),
     BufferAddressType(syn::Ident),
     NameWordBoundaries(Vec<Boundary>),
     DefmtFeature(syn::LitStr),
+    UfmtFeature(syn::LitStr),
+    AutoAddress(syn::LitBool),
+    ResetSequence(Vec<(syn::Ident, syn::LitInt)>),
+    Visibility(syn::LitStr),
+    MaxFields(syn::LitInt),
+    FlatAccessors(syn::LitBool),
+    CrcAlgorithm(CrcAlgorithm),
+    IdRegister(syn::Ident, syn::Ident, syn::LitInt),
+    AddressUnit(AddressUnit),
+    Mmio(syn::LitBool),
+    ResetHoldUs(syn::LitInt),
+    GroupByKind(syn::LitBool),
+    GenerateBitOps(syn::LitBool),
+    FeatureGate(syn::LitStr),
+    ChipVariants(Vec<syn::Ident>),
+    DynAccess(syn::LitBool),
+    SharedCommandInterface(syn::LitBool),
+    PostRead(syn::Path),
+    EnumDerives(Vec<syn::Ident>),
+    DetectBusStuck(syn::LitBool),
+    TypeAlias(syn::Ident, syn::Path),
 }
 
 impl Parse for GlobalConfig {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::type_alias) {
+            input.parse::<kw::type_alias>()?;
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let path = input.parse()?;
+            input.parse::<Token![;]>()?;
+            return Ok(Self::TypeAlias(name, path));
+        }
+
         input.parse::<Token![type]>()?;
 
         let lookahead = input.lookahead1();
@@ -97,6 +231,18 @@ impl Parse for GlobalConfig {
             let value = input.parse()?;
             input.parse::<Token![;]>()?;
             Ok(Self::DefaultByteOrder(value))
+        } else if lookahead.peek(kw::DefaultRegisterByteOrder) {
+            input.parse::<kw::DefaultRegisterByteOrder>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::DefaultRegisterByteOrder(value))
+        } else if lookahead.peek(kw::DefaultCommandByteOrder) {
+            input.parse::<kw::DefaultCommandByteOrder>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::DefaultCommandByteOrder(value))
         } else if lookahead.peek(kw::DefaultBitOrder) {
             input.parse::<kw::DefaultBitOrder>()?;
             input.parse::<Token![=]>()?;
@@ -163,6 +309,163 @@ impl Parse for GlobalConfig {
             let value = input.parse()?;
             input.parse::<Token![;]>()?;
             Ok(Self::DefmtFeature(value))
+        } else if lookahead.peek(kw::UfmtFeature) {
+            input.parse::<kw::UfmtFeature>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::UfmtFeature(value))
+        } else if lookahead.peek(kw::AutoAddress) {
+            input.parse::<kw::AutoAddress>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::AutoAddress(value))
+        } else if lookahead.peek(kw::ResetSequence) {
+            input.parse::<kw::ResetSequence>()?;
+            input.parse::<Token![=]>()?;
+
+            let bracket_input;
+            bracketed!(bracket_input in input);
+
+            let mut sequence = Vec::new();
+            while !bracket_input.is_empty() {
+                let paren_input;
+                parenthesized!(paren_input in bracket_input);
+
+                let register = paren_input.parse()?;
+                paren_input.parse::<Token![,]>()?;
+                let value = paren_input.parse()?;
+                sequence.push((register, value));
+
+                if !bracket_input.is_empty() {
+                    bracket_input.parse::<Token![,]>()?;
+                }
+            }
+
+            input.parse::<Token![;]>()?;
+            Ok(Self::ResetSequence(sequence))
+        } else if lookahead.peek(kw::Visibility) {
+            input.parse::<kw::Visibility>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::Visibility(value))
+        } else if lookahead.peek(kw::MaxFields) {
+            input.parse::<kw::MaxFields>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::MaxFields(value))
+        } else if lookahead.peek(kw::FlatAccessors) {
+            input.parse::<kw::FlatAccessors>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::FlatAccessors(value))
+        } else if lookahead.peek(kw::CrcAlgorithm) {
+            input.parse::<kw::CrcAlgorithm>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::CrcAlgorithm(value))
+        } else if lookahead.peek(kw::IdRegister) {
+            input.parse::<kw::IdRegister>()?;
+            input.parse::<Token![=]>()?;
+
+            let paren_input;
+            parenthesized!(paren_input in input);
+            let register = paren_input.parse()?;
+            paren_input.parse::<Token![,]>()?;
+            let field = paren_input.parse()?;
+            paren_input.parse::<Token![,]>()?;
+            let expected = paren_input.parse()?;
+            if !paren_input.is_empty() {
+                paren_input.parse::<Token![,]>()?;
+            }
+
+            input.parse::<Token![;]>()?;
+            Ok(Self::IdRegister(register, field, expected))
+        } else if lookahead.peek(kw::AddressUnit) {
+            input.parse::<kw::AddressUnit>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::AddressUnit(value))
+        } else if lookahead.peek(kw::Mmio) {
+            input.parse::<kw::Mmio>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::Mmio(value))
+        } else if lookahead.peek(kw::ResetHoldUs) {
+            input.parse::<kw::ResetHoldUs>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::ResetHoldUs(value))
+        } else if lookahead.peek(kw::GroupByKind) {
+            input.parse::<kw::GroupByKind>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::GroupByKind(value))
+        } else if lookahead.peek(kw::GenerateBitOps) {
+            input.parse::<kw::GenerateBitOps>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::GenerateBitOps(value))
+        } else if lookahead.peek(kw::FeatureGate) {
+            input.parse::<kw::FeatureGate>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::FeatureGate(value))
+        } else if lookahead.peek(kw::ChipVariants) {
+            input.parse::<kw::ChipVariants>()?;
+            input.parse::<Token![=]>()?;
+
+            let bracket_input;
+            bracketed!(bracket_input in input);
+            let variants = Punctuated::<Ident, Token![,]>::parse_terminated(&bracket_input)?;
+
+            input.parse::<Token![;]>()?;
+            Ok(Self::ChipVariants(variants.into_iter().collect()))
+        } else if lookahead.peek(kw::DynAccess) {
+            input.parse::<kw::DynAccess>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::DynAccess(value))
+        } else if lookahead.peek(kw::SharedCommandInterface) {
+            input.parse::<kw::SharedCommandInterface>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::SharedCommandInterface(value))
+        } else if lookahead.peek(kw::PostRead) {
+            input.parse::<kw::PostRead>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::PostRead(value))
+        } else if lookahead.peek(kw::EnumDerives) {
+            input.parse::<kw::EnumDerives>()?;
+            input.parse::<Token![=]>()?;
+
+            let bracket_input;
+            bracketed!(bracket_input in input);
+            let derives = Punctuated::<Ident, Token![,]>::parse_terminated(&bracket_input)?;
+
+            input.parse::<Token![;]>()?;
+            Ok(Self::EnumDerives(derives.into_iter().collect()))
+        } else if lookahead.peek(kw::DetectBusStuck) {
+            input.parse::<kw::DetectBusStuck>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Ok(Self::DetectBusStuck(value))
         } else {
             Err(lookahead.error())
         }
@@ -190,6 +493,7 @@ pub enum Object {
     Command(Command),
     Buffer(Buffer),
     Ref(RefObject),
+    Alias(AliasObject),
 }
 
 impl Parse for Object {
@@ -209,6 +513,8 @@ impl Parse for Object {
             Ok(Self::Buffer(input.parse()?))
         } else if lookahead.peek(Token![ref]) {
             Ok(Self::Ref(input.parse()?))
+        } else if lookahead.peek(kw::alias) {
+            Ok(Self::Alias(input.parse()?))
         } else {
             Err(lookahead.error())
         }
@@ -242,6 +548,40 @@ impl Parse for RefObject {
     }
 }
 
+/// A register at another address that uses the field set of the target register
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasObject {
+    pub attribute_list: AttributeList,
+    pub identifier: syn::Ident,
+    pub target: syn::Ident,
+    pub address: syn::LitInt,
+}
+
+impl Parse for AliasObject {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attribute_list = input.parse()?;
+
+        input.parse::<kw::alias>()?;
+
+        let identifier = input.parse()?;
+
+        input.parse::<Token![=]>()?;
+
+        let target = input.parse()?;
+
+        input.parse::<Token![@]>()?;
+
+        let address = input.parse()?;
+
+        Ok(Self {
+            attribute_list,
+            identifier,
+            target,
+            address,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct AttributeList {
     pub attributes: Vec<Attribute>,
@@ -258,6 +598,19 @@ impl AttributeList {
 
 impl Parse for AttributeList {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        Self::parse_impl(input, false)
+    }
+}
+
+impl AttributeList {
+    /// Parse the attribute list of a field. Next to `doc` and `cfg`, this also allows
+    /// the `allow` and `derive` attributes which are passed through to a generated enum
+    /// and the `renamed_from`, `bit_order`, `unit` and `valid_when` attributes.
+    pub fn parse_field_attributes(input: ParseStream) -> syn::Result<Self> {
+        Self::parse_impl(input, true)
+    }
+
+    fn parse_impl(input: ParseStream, allow_passthrough: bool) -> syn::Result<Self> {
         let attributes = syn::Attribute::parse_outer(input)?;
 
         Ok(Self {
@@ -278,6 +631,49 @@ impl Parse for AttributeList {
                         "cfg" => {
                             Ok(Attribute::Cfg(attr.meta.require_list()?.tokens.to_string(), attr.span()))
                         }
+                        "renamed_from" if allow_passthrough => match &attr.meta.require_name_value()?.value {
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(value),
+                                ..
+                            }) => Ok(Attribute::RenamedFrom(value.value(), attr.span())),
+                            _ => Err(syn::Error::new_spanned(
+                                attr,
+                                "Invalid renamed_from attribute format. Use `#[renamed_from = \"old_name\"]`",
+                            )),
+                        },
+                        "unit" if allow_passthrough => match &attr.meta.require_name_value()?.value {
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(value),
+                                ..
+                            }) => Ok(Attribute::Unit(value.value(), attr.span())),
+                            _ => Err(syn::Error::new_spanned(
+                                attr,
+                                "Invalid unit attribute format. Use `#[unit = \"°C\"]`",
+                            )),
+                        },
+                        "valid_when" if allow_passthrough => match &attr.meta.require_name_value()?.value {
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(value),
+                                ..
+                            }) => Ok(Attribute::ValidWhen(value.parse()?, attr.span())),
+                            _ => Err(syn::Error::new_spanned(
+                                attr,
+                                "Invalid valid_when attribute format. Use `#[valid_when = \"mode == Output\"]`",
+                            )),
+                        },
+                        "bit_order" if allow_passthrough => {
+                            Ok(Attribute::BitOrder(syn::parse2(attr.meta.require_list()?.tokens.clone())?, attr.span()))
+                        }
+                        "allow" | "derive" if allow_passthrough => {
+                            attr.meta.require_list()?;
+                            Ok(Attribute::Passthrough(attr.meta.to_token_stream().to_string(), attr.span()))
+                        }
+                        val if allow_passthrough => {
+                            Err(syn::Error::new_spanned(
+                                attr,
+                                format!("Unsupported attribute '{val}'. Only `doc`, `cfg`, `allow`, `derive`, `renamed_from`, `bit_order`, `unit` and `valid_when` attributes are allowed"),
+                            ))
+                        }
                         val => {
                             Err(syn::Error::new_spanned(
                                 attr,
@@ -295,6 +691,16 @@ impl Parse for AttributeList {
 pub enum Attribute {
     Doc(String),
     Cfg(String, Span),
+    /// An attribute that is passed through as is to the generated enum of a field
+    Passthrough(String, Span),
+    /// The old name of a field
+    RenamedFrom(String, Span),
+    /// The bit order of a field, overriding the bit order of its field set
+    BitOrder(BitOrder, Span),
+    /// The unit of the value of a field
+    Unit(String, Span),
+    /// The condition on another field that must hold for a field to be valid
+    ValidWhen(ValidWhen, Span),
 }
 
 impl Eq for Attribute {}
@@ -304,6 +710,11 @@ impl PartialEq for Attribute {
         match (self, other) {
             (Self::Doc(l0), Self::Doc(r0)) => l0 == r0,
             (Self::Cfg(l0, _), Self::Cfg(r0, _)) => l0 == r0,
+            (Self::Passthrough(l0, _), Self::Passthrough(r0, _)) => l0 == r0,
+            (Self::RenamedFrom(l0, _), Self::RenamedFrom(r0, _)) => l0 == r0,
+            (Self::BitOrder(l0, _), Self::BitOrder(r0, _)) => l0 == r0,
+            (Self::Unit(l0, _), Self::Unit(r0, _)) => l0 == r0,
+            (Self::ValidWhen(l0, _), Self::ValidWhen(r0, _)) => l0 == r0,
             _ => false,
         }
     }
@@ -505,6 +916,33 @@ impl Parse for RegisterItemList {
                     let value = input.parse()?;
                     input.parse::<Token![;]>()?;
                     register_items.push(RegisterItem::BitOrder(value));
+                } else if lookahead.peek(kw::Shadowed) {
+                    err_if_contains(
+                        &register_items,
+                        core::mem::discriminant(&RegisterItem::Shadowed(LitBool::new(
+                            false,
+                            Span::call_site(),
+                        ))),
+                        input.span(),
+                    )?;
+
+                    input.parse::<kw::Shadowed>()?;
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse()?;
+                    input.parse::<Token![;]>()?;
+                    register_items.push(RegisterItem::Shadowed(value));
+                } else if lookahead.peek(kw::DefaultFieldAccess) {
+                    err_if_contains(
+                        &register_items,
+                        core::mem::discriminant(&RegisterItem::DefaultFieldAccess(Access::RW)),
+                        input.span(),
+                    )?;
+
+                    input.parse::<kw::DefaultFieldAccess>()?;
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse()?;
+                    input.parse::<Token![;]>()?;
+                    register_items.push(RegisterItem::DefaultFieldAccess(value));
                 } else {
                     return Err(lookahead.error());
                 }
@@ -522,12 +960,31 @@ impl Parse for RegisterItemList {
                         ))),
                         input.span(),
                     )?;
+                    err_if_contains(
+                        &register_items,
+                        core::mem::discriminant(&RegisterItem::AddressSpan(Vec::new())),
+                        input.span(),
+                    )?;
 
                     input.parse::<kw::ADDRESS>()?;
                     input.parse::<Token![=]>()?;
-                    let value = input.parse()?;
+
+                    let lookahead = input.lookahead1();
+                    let value = if lookahead.peek(syn::LitInt) {
+                        RegisterItem::Address(input.parse()?)
+                    } else if lookahead.peek(syn::token::Bracket) {
+                        let bracket_input;
+                        bracketed!(bracket_input in input);
+
+                        let elems =
+                            Punctuated::<syn::LitInt, Token![,]>::parse_terminated(&bracket_input)?;
+
+                        RegisterItem::AddressSpan(elems.into_iter().collect())
+                    } else {
+                        return Err(lookahead.error());
+                    };
                     input.parse::<Token![;]>()?;
-                    register_items.push(RegisterItem::Address(value));
+                    register_items.push(value);
                 } else if lookahead.peek(kw::SIZE_BITS) {
                     err_if_contains(
                         &register_items,
@@ -543,6 +1000,21 @@ impl Parse for RegisterItemList {
                     let value = input.parse()?;
                     input.parse::<Token![;]>()?;
                     register_items.push(RegisterItem::SizeBits(value));
+                } else if lookahead.peek(kw::SIZE_BYTES) {
+                    err_if_contains(
+                        &register_items,
+                        core::mem::discriminant(&RegisterItem::SizeBytes(LitInt::new(
+                            "0",
+                            Span::call_site(),
+                        ))),
+                        input.span(),
+                    )?;
+
+                    input.parse::<kw::SIZE_BYTES>()?;
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse()?;
+                    input.parse::<Token![;]>()?;
+                    register_items.push(RegisterItem::SizeBytes(value));
                 } else if lookahead.peek(kw::RESET_VALUE) {
                     err_if_contains(
                         &register_items,
@@ -557,6 +1029,11 @@ impl Parse for RegisterItemList {
                         core::mem::discriminant(&RegisterItem::ResetValueArray(Vec::new())),
                         input.span(),
                     )?;
+                    err_if_contains(
+                        &register_items,
+                        core::mem::discriminant(&RegisterItem::ResetValueFields(Vec::new())),
+                        input.span(),
+                    )?;
 
                     input.parse::<kw::RESET_VALUE>()?;
                     input.parse::<Token![=]>()?;
@@ -578,6 +1055,15 @@ impl Parse for RegisterItemList {
                         }
 
                         RegisterItem::ResetValueArray(reset_data)
+                    } else if lookahead.peek(syn::token::Brace) {
+                        let brace_input;
+                        braced!(brace_input in input);
+
+                        let elems = Punctuated::<FieldResetValue, Token![,]>::parse_terminated(
+                            &brace_input,
+                        )?;
+
+                        RegisterItem::ResetValueFields(elems.into_iter().collect())
                     } else {
                         return Err(lookahead.error());
                     };
@@ -624,30 +1110,94 @@ impl Parse for RegisterItemList {
                     let value = input.parse()?;
                     input.parse::<Token![;]>()?;
                     register_items.push(RegisterItem::AllowAddressOverlap(value));
-                } else {
-                    return Err(lookahead.error());
-                }
-            } else {
-                break;
-            }
-        }
-
-        Ok(Self { register_items })
-    }
-}
+                } else if lookahead.peek(kw::EXPECT_ADDRESS) {
+                    err_if_contains(
+                        &register_items,
+                        core::mem::discriminant(&RegisterItem::ExpectAddress(LitInt::new(
+                            "0",
+                            Span::call_site(),
+                        ))),
+                        input.span(),
+                    )?;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum RegisterItem {
-    Access(Access),
-    ByteOrder(ByteOrder),
-    BitOrder(BitOrder),
-    Address(LitInt),
-    SizeBits(LitInt),
-    ResetValueInt(LitInt),
+                    input.parse::<kw::EXPECT_ADDRESS>()?;
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse()?;
+                    input.parse::<Token![;]>()?;
+                    register_items.push(RegisterItem::ExpectAddress(value));
+                } else if lookahead.peek(kw::EXPECT_SIZE) {
+                    err_if_contains(
+                        &register_items,
+                        core::mem::discriminant(&RegisterItem::ExpectSize(LitInt::new(
+                            "0",
+                            Span::call_site(),
+                        ))),
+                        input.span(),
+                    )?;
+
+                    input.parse::<kw::EXPECT_SIZE>()?;
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse()?;
+                    input.parse::<Token![;]>()?;
+                    register_items.push(RegisterItem::ExpectSize(value));
+                } else if lookahead.peek(kw::LENGTH_FIELD) {
+                    err_if_contains(
+                        &register_items,
+                        core::mem::discriminant(&RegisterItem::LengthField(Ident::new(
+                            "a",
+                            Span::call_site(),
+                        ))),
+                        input.span(),
+                    )?;
+
+                    input.parse::<kw::LENGTH_FIELD>()?;
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse()?;
+                    input.parse::<Token![;]>()?;
+                    register_items.push(RegisterItem::LengthField(value));
+                } else {
+                    return Err(lookahead.error());
+                }
+            } else if input.peek(kw::reserved_rest) && input.peek2(Token![;]) {
+                err_if_contains(
+                    &register_items,
+                    core::mem::discriminant(&RegisterItem::ReservedRest),
+                    input.span(),
+                )?;
+
+                input.parse::<kw::reserved_rest>()?;
+                input.parse::<Token![;]>()?;
+                register_items.push(RegisterItem::ReservedRest);
+            } else {
+                break;
+            }
+        }
+
+        Ok(Self { register_items })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisterItem {
+    Access(Access),
+    ByteOrder(ByteOrder),
+    BitOrder(BitOrder),
+    Address(LitInt),
+    AddressSpan(Vec<LitInt>),
+    SizeBits(LitInt),
+    SizeBytes(LitInt),
+    ResetValueInt(LitInt),
     ResetValueArray(Vec<u8>),
+    ResetValueFields(Vec<FieldResetValue>),
     Repeat(Repeat),
     AllowBitOverlap(LitBool),
     AllowAddressOverlap(LitBool),
+    ReservedRest,
+    Shadowed(LitBool),
+    DefaultFieldAccess(Access),
+    ExpectAddress(LitInt),
+    ExpectSize(LitInt),
+    LengthField(Ident),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -655,6 +1205,7 @@ pub enum Access {
     RW,
     RO,
     WO,
+    RC,
 }
 
 impl Parse for Access {
@@ -679,6 +1230,12 @@ impl Parse for Access {
         } else if lookahead.peek(kw::WO) {
             input.parse::<kw::WO>()?;
             Ok(Self::WO)
+        } else if lookahead.peek(kw::ReadClear) {
+            input.parse::<kw::ReadClear>()?;
+            Ok(Self::RC)
+        } else if lookahead.peek(kw::RC) {
+            input.parse::<kw::RC>()?;
+            Ok(Self::RC)
         } else {
             Err(lookahead.error())
         }
@@ -689,6 +1246,7 @@ impl Parse for Access {
 pub enum ByteOrder {
     LE,
     BE,
+    SwapPairs,
 }
 
 impl Parse for ByteOrder {
@@ -701,6 +1259,9 @@ impl Parse for ByteOrder {
         } else if lookahead.peek(kw::BE) {
             input.parse::<kw::BE>()?;
             Ok(Self::BE)
+        } else if lookahead.peek(kw::SwapPairs) {
+            input.parse::<kw::SwapPairs>()?;
+            Ok(Self::SwapPairs)
         } else {
             Err(lookahead.error())
         }
@@ -729,6 +1290,54 @@ impl Parse for BitOrder {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrcAlgorithm {
+    Crc8Smbus,
+    Crc8Nrsc5,
+    Crc8Autosar,
+}
+
+impl Parse for CrcAlgorithm {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(kw::Crc8Smbus) {
+            input.parse::<kw::Crc8Smbus>()?;
+            Ok(Self::Crc8Smbus)
+        } else if lookahead.peek(kw::Crc8Nrsc5) {
+            input.parse::<kw::Crc8Nrsc5>()?;
+            Ok(Self::Crc8Nrsc5)
+        } else if lookahead.peek(kw::Crc8Autosar) {
+            input.parse::<kw::Crc8Autosar>()?;
+            Ok(Self::Crc8Autosar)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressUnit {
+    Byte,
+    Word16,
+}
+
+impl Parse for AddressUnit {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(kw::Byte) {
+            input.parse::<kw::Byte>()?;
+            Ok(Self::Byte)
+        } else if lookahead.peek(kw::Word16) {
+            input.parse::<kw::Word16>()?;
+            Ok(Self::Word16)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct FieldList {
     pub fields: Vec<Field>,
@@ -763,7 +1372,7 @@ pub struct Field {
 
 impl Parse for Field {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let attribute_list = input.parse()?;
+        let attribute_list = AttributeList::parse_field_attributes(input)?;
         let identifier = input.parse()?;
         input.parse::<Token![:]>()?;
         let access = input.parse::<Access>().ok();
@@ -801,6 +1410,19 @@ pub enum FieldConversion {
         enum_variant_list: EnumVariantList,
         use_try: bool,
     },
+    Crc {
+        over: FieldAddress,
+    },
+    Newtype {
+        identifier: syn::Ident,
+        inner: syn::Ident,
+    },
+    Functions {
+        read_type: syn::Path,
+        read_fn: syn::Path,
+        write_type: syn::Path,
+        write_fn: syn::Path,
+    },
 }
 
 impl Parse for FieldConversion {
@@ -809,6 +1431,67 @@ impl Parse for FieldConversion {
 
         let use_try = input.parse::<Token![try]>().is_ok();
 
+        if input.peek(kw::crc) && input.peek2(kw::over) {
+            if use_try {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "A crc field can't be fallible. Remove the `try`",
+                ));
+            }
+
+            input.parse::<kw::crc>()?;
+            input.parse::<kw::over>()?;
+
+            return Ok(Self::Crc {
+                over: input.parse()?,
+            });
+        }
+
+        if input.peek(kw::newtype) && input.peek2(syn::Ident) {
+            if use_try {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "A newtype field can't be fallible. Remove the `try`",
+                ));
+            }
+
+            input.parse::<kw::newtype>()?;
+            let identifier = input.parse()?;
+
+            let paren_input;
+            parenthesized!(paren_input in input);
+            let inner = paren_input.parse()?;
+
+            return Ok(Self::Newtype { identifier, inner });
+        }
+
+        if input.peek(syn::token::Paren) {
+            if use_try {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "A conversion with functions can't be fallible. Remove the `try`",
+                ));
+            }
+
+            let paren_input;
+            parenthesized!(paren_input in input);
+
+            let read_type = paren_input.parse()?;
+            paren_input.parse::<kw::from>()?;
+            let read_fn = paren_input.parse()?;
+            paren_input.parse::<Token![,]>()?;
+            let write_type = paren_input.parse()?;
+            paren_input.parse::<kw::into>()?;
+            let write_fn = paren_input.parse()?;
+
+            return Ok(Self::Functions {
+                read_type,
+                read_fn,
+                write_type,
+                write_fn,
+            });
+        }
+
         if input.parse::<Token![enum]>().is_err() {
             return Ok(Self::Direct {
                 path: input.parse::<syn::Path>()?,
@@ -874,22 +1557,43 @@ impl Parse for EnumVariant {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EnumValue {
     Specified(LitInt),
+    Range { start: LitInt, end: LitInt },
+    RangeInclusive { start: LitInt, end: LitInt },
     Default,
     CatchAll,
+    Fallback,
 }
 
 impl Parse for EnumValue {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         if let Ok(specification) = input.parse::<LitInt>() {
-            Ok(Self::Specified(specification))
+            if input.peek(Token![..=]) {
+                input.parse::<Token![..=]>()?;
+                let end = input.parse()?;
+                Ok(Self::RangeInclusive {
+                    start: specification,
+                    end,
+                })
+            } else if input.peek(Token![..]) {
+                input.parse::<Token![..]>()?;
+                let end = input.parse()?;
+                Ok(Self::Range {
+                    start: specification,
+                    end,
+                })
+            } else {
+                Ok(Self::Specified(specification))
+            }
         } else if input.parse::<kw::default>().is_ok() {
             Ok(Self::Default)
         } else if input.parse::<kw::catch_all>().is_ok() {
             Ok(Self::CatchAll)
+        } else if input.parse::<kw::fallback>().is_ok() {
+            Ok(Self::Fallback)
         } else {
             Err(syn::Error::new(
                 input.span(),
-                "Specifier not recognized. Must be an integer literal, `default` or `catch_all`",
+                "Specifier not recognized. Must be an integer literal, `default`, `catch_all` or `fallback`",
             ))
         }
     }
@@ -985,7 +1689,7 @@ impl Parse for Command {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CommandValue {
-    Basic(LitInt),
+    Basic(CommandAddress),
     Extended {
         command_item_list: CommandItemList,
         in_field_list: Option<FieldList>,
@@ -993,6 +1697,32 @@ pub enum CommandValue {
     },
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandAddress {
+    Integer(LitInt),
+    /// A multi-byte opcode
+    Opcode(Vec<LitInt>),
+}
+
+impl Parse for CommandAddress {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(syn::LitInt) {
+            Ok(Self::Integer(input.parse()?))
+        } else if lookahead.peek(syn::token::Bracket) {
+            let bracket_input;
+            bracketed!(bracket_input in input);
+
+            let elems = Punctuated::<syn::LitInt, Token![,]>::parse_terminated(&bracket_input)?;
+
+            Ok(Self::Opcode(elems.into_iter().collect()))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
 impl Parse for CommandValue {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         if input.parse::<Token![=]>().is_ok() {
@@ -1050,12 +1780,14 @@ pub struct CommandItemList {
 pub enum CommandItem {
     ByteOrder(ByteOrder),
     BitOrder(BitOrder),
-    Address(LitInt),
+    Address(CommandAddress),
     SizeBitsIn(LitInt),
     SizeBitsOut(LitInt),
     Repeat(Repeat),
     AllowBitOverlap(LitBool),
     AllowAddressOverlap(LitBool),
+    /// The register and its bool field that are polled after dispatching until the field is set
+    PollUntil(Ident, Ident),
 }
 
 impl Parse for CommandItemList {
@@ -1110,9 +1842,8 @@ impl Parse for CommandItemList {
                 if lookahead.peek(kw::ADDRESS) {
                     err_if_contains(
                         &items,
-                        core::mem::discriminant(&CommandItem::Address(LitInt::new(
-                            "0",
-                            Span::call_site(),
+                        core::mem::discriminant(&CommandItem::Address(CommandAddress::Opcode(
+                            Vec::new(),
                         ))),
                         input.span(),
                     )?;
@@ -1193,6 +1924,23 @@ impl Parse for CommandItemList {
                     let value = input.parse()?;
                     input.parse::<Token![;]>()?;
                     items.push(CommandItem::AllowAddressOverlap(value));
+                } else if lookahead.peek(kw::POLL_UNTIL) {
+                    err_if_contains(
+                        &items,
+                        core::mem::discriminant(&CommandItem::PollUntil(
+                            Ident::new("A", Span::call_site()),
+                            Ident::new("a", Span::call_site()),
+                        )),
+                        input.span(),
+                    )?;
+
+                    input.parse::<kw::POLL_UNTIL>()?;
+                    input.parse::<Token![=]>()?;
+                    let register = input.parse()?;
+                    input.parse::<Token![.]>()?;
+                    let field = input.parse()?;
+                    input.parse::<Token![;]>()?;
+                    items.push(CommandItem::PollUntil(register, field));
                 } else {
                     return Err(lookahead.error());
                 }
@@ -1205,6 +1953,79 @@ impl Parse for CommandItemList {
     }
 }
 
+/// The reset value of one field in a reset value table, like `mode: Output`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldResetValue {
+    pub field: Ident,
+    pub value: FieldResetValueKind,
+}
+
+impl Parse for FieldResetValue {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let field = input.parse()?;
+        input.parse::<Token![:]>()?;
+
+        let value = input.parse()?;
+
+        Ok(Self { field, value })
+    }
+}
+
+/// A comparison of a field with a value, like `mode == Output` or `enabled != false`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidWhen {
+    pub field: Ident,
+    pub negated: bool,
+    pub value: FieldResetValueKind,
+}
+
+impl Parse for ValidWhen {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let field = input.parse()?;
+
+        let lookahead = input.lookahead1();
+        let negated = if lookahead.peek(Token![==]) {
+            input.parse::<Token![==]>()?;
+            false
+        } else if lookahead.peek(Token![!=]) {
+            input.parse::<Token![!=]>()?;
+            true
+        } else {
+            return Err(lookahead.error());
+        };
+
+        let value = input.parse()?;
+
+        Ok(Self {
+            field,
+            negated,
+            value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldResetValueKind {
+    Int(LitInt),
+    Bool(LitBool),
+    Variant(Ident),
+}
+
+impl Parse for FieldResetValueKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(syn::LitInt) || lookahead.peek(Token![-]) {
+            Ok(FieldResetValueKind::Int(input.parse()?))
+        } else if lookahead.peek(syn::LitBool) {
+            Ok(FieldResetValueKind::Bool(input.parse()?))
+        } else if lookahead.peek(syn::Ident) {
+            Ok(FieldResetValueKind::Variant(input.parse()?))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Repeat {
     pub count: LitInt,
@@ -1243,6 +2064,9 @@ pub struct Buffer {
     pub identifier: syn::Ident,
     pub access: Option<Access>,
     pub address: Option<LitInt>,
+    pub frame: Option<syn::Ident>,
+    pub max_size: Option<LitInt>,
+    pub length_field: Option<Box<Field>>,
 }
 
 impl Parse for Buffer {
@@ -1264,32 +2088,72 @@ impl Parse for Buffer {
             None
         };
 
+        let frame = if input.parse::<Token![as]>().is_ok() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let mut max_size = None;
+        let mut length_field = None;
+
+        if input.peek(syn::token::Brace) {
+            let braced_input;
+            braced!(braced_input in input);
+
+            if braced_input.peek(Token![const]) {
+                braced_input.parse::<Token![const]>()?;
+                braced_input.parse::<kw::MAX_SIZE>()?;
+                braced_input.parse::<Token![=]>()?;
+                max_size = Some(braced_input.parse()?);
+                braced_input.parse::<Token![;]>()?;
+            }
+
+            if !braced_input.is_empty() {
+                length_field = Some(Box::new(braced_input.parse()?));
+                braced_input.parse::<Option<Token![,]>>()?;
+            }
+        }
+
         Ok(Self {
             attribute_list,
             identifier,
             access,
             address,
+            frame,
+            max_size,
+            length_field,
         })
     }
 }
 
 mod kw {
     syn::custom_keyword!(config);
+    syn::custom_keyword!(include);
 
     // Objects
     syn::custom_keyword!(block);
     syn::custom_keyword!(register);
     syn::custom_keyword!(command);
     syn::custom_keyword!(buffer);
+    syn::custom_keyword!(alias);
 
     syn::custom_keyword!(ADDRESS);
     syn::custom_keyword!(ADDRESS_OFFSET);
     syn::custom_keyword!(SIZE_BITS);
+    syn::custom_keyword!(SIZE_BYTES);
     syn::custom_keyword!(SIZE_BITS_IN);
     syn::custom_keyword!(SIZE_BITS_OUT);
     syn::custom_keyword!(RESET_VALUE);
     syn::custom_keyword!(ALLOW_BIT_OVERLAP);
     syn::custom_keyword!(ALLOW_ADDRESS_OVERLAP);
+    syn::custom_keyword!(reserved_rest);
+    syn::custom_keyword!(Shadowed);
+    syn::custom_keyword!(EXPECT_ADDRESS);
+    syn::custom_keyword!(EXPECT_SIZE);
+    syn::custom_keyword!(LENGTH_FIELD);
+    syn::custom_keyword!(POLL_UNTIL);
+    syn::custom_keyword!(MAX_SIZE);
 
     // Repeat
     syn::custom_keyword!(REPEAT);
@@ -1301,12 +2165,35 @@ mod kw {
     syn::custom_keyword!(DefaultFieldAccess);
     syn::custom_keyword!(DefaultBufferAccess);
     syn::custom_keyword!(DefaultByteOrder);
+    syn::custom_keyword!(DefaultRegisterByteOrder);
+    syn::custom_keyword!(DefaultCommandByteOrder);
     syn::custom_keyword!(DefaultBitOrder);
     syn::custom_keyword!(RegisterAddressType);
     syn::custom_keyword!(CommandAddressType);
     syn::custom_keyword!(BufferAddressType);
     syn::custom_keyword!(NameWordBoundaries);
     syn::custom_keyword!(DefmtFeature);
+    syn::custom_keyword!(UfmtFeature);
+    syn::custom_keyword!(Visibility);
+    syn::custom_keyword!(MaxFields);
+    syn::custom_keyword!(FlatAccessors);
+    syn::custom_keyword!(CrcAlgorithm);
+    syn::custom_keyword!(IdRegister);
+    syn::custom_keyword!(AddressUnit);
+    syn::custom_keyword!(Mmio);
+    syn::custom_keyword!(ResetHoldUs);
+    syn::custom_keyword!(GroupByKind);
+    syn::custom_keyword!(GenerateBitOps);
+    syn::custom_keyword!(FeatureGate);
+    syn::custom_keyword!(ChipVariants);
+    syn::custom_keyword!(DynAccess);
+    syn::custom_keyword!(SharedCommandInterface);
+    syn::custom_keyword!(PostRead);
+    syn::custom_keyword!(EnumDerives);
+    syn::custom_keyword!(DetectBusStuck);
+    syn::custom_keyword!(type_alias);
+    syn::custom_keyword!(AutoAddress);
+    syn::custom_keyword!(ResetSequence);
 
     // Access
     syn::custom_keyword!(Access);
@@ -1316,17 +2203,29 @@ mod kw {
     syn::custom_keyword!(ReadOnly);
     syn::custom_keyword!(WO);
     syn::custom_keyword!(WriteOnly);
+    syn::custom_keyword!(RC);
+    syn::custom_keyword!(ReadClear);
 
     // ByteOrder
     syn::custom_keyword!(ByteOrder);
     syn::custom_keyword!(LE);
     syn::custom_keyword!(BE);
+    syn::custom_keyword!(SwapPairs);
 
     // BitOrder
     syn::custom_keyword!(BitOrder);
     syn::custom_keyword!(LSB0);
     syn::custom_keyword!(MSB0);
 
+    // CrcAlgorithm
+    syn::custom_keyword!(Crc8Smbus);
+    syn::custom_keyword!(Crc8Nrsc5);
+    syn::custom_keyword!(Crc8Autosar);
+
+    // AddressUnit
+    syn::custom_keyword!(Byte);
+    syn::custom_keyword!(Word16);
+
     // BaseType
     syn::custom_keyword!(bool);
     syn::custom_keyword!(uint);
@@ -1335,9 +2234,17 @@ mod kw {
     // EnumValue
     syn::custom_keyword!(default);
     syn::custom_keyword!(catch_all);
+    syn::custom_keyword!(fallback);
 
     // CommandValue
     syn::custom_keyword!(out);
+
+    // FieldConversion
+    syn::custom_keyword!(crc);
+    syn::custom_keyword!(over);
+    syn::custom_keyword!(newtype);
+    syn::custom_keyword!(from);
+    syn::custom_keyword!(into);
 }
 
 #[cfg(test)]
@@ -1355,10 +2262,12 @@ mod tests {
         assert_eq!(syn::parse_str::<Access>("ReadOnly").unwrap(), Access::RO);
         assert_eq!(syn::parse_str::<Access>("WO").unwrap(), Access::WO);
         assert_eq!(syn::parse_str::<Access>("WriteOnly").unwrap(), Access::WO);
+        assert_eq!(syn::parse_str::<Access>("RC").unwrap(), Access::RC);
+        assert_eq!(syn::parse_str::<Access>("ReadClear").unwrap(), Access::RC);
 
         assert_eq!(
             syn::parse_str::<Access>("ABCD").unwrap_err().to_string(),
-            "expected one of: `ReadWrite`, `RW`, `ReadOnly`, `RO`, `WriteOnly`, `WO`"
+            "expected one of: `ReadWrite`, `RW`, `ReadOnly`, `RO`, `WriteOnly`, `WO`, `ReadClear`, `RC`"
         );
     }
 
@@ -1366,10 +2275,14 @@ mod tests {
     fn parse_byte_order() {
         assert_eq!(syn::parse_str::<ByteOrder>("LE").unwrap(), ByteOrder::LE);
         assert_eq!(syn::parse_str::<ByteOrder>("BE").unwrap(), ByteOrder::BE);
+        assert_eq!(
+            syn::parse_str::<ByteOrder>("SwapPairs").unwrap(),
+            ByteOrder::SwapPairs
+        );
 
         assert_eq!(
             syn::parse_str::<ByteOrder>("ABCD").unwrap_err().to_string(),
-            "expected `LE` or `BE`"
+            "expected one of: `LE`, `BE`, `SwapPairs`"
         );
     }
 
@@ -1402,6 +2315,20 @@ mod tests {
             syn::parse_str::<EnumValue>("55").unwrap(),
             EnumValue::Specified(LitInt::new("55", Span::call_site()))
         );
+        assert_eq!(
+            syn::parse_str::<EnumValue>("1..=7").unwrap(),
+            EnumValue::RangeInclusive {
+                start: LitInt::new("1", Span::call_site()),
+                end: LitInt::new("7", Span::call_site())
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<EnumValue>("0x10..0x20").unwrap(),
+            EnumValue::Range {
+                start: LitInt::new("0x10", Span::call_site()),
+                end: LitInt::new("0x20", Span::call_site())
+            }
+        );
         assert_eq!(
             syn::parse_str::<EnumValue>("default").unwrap(),
             EnumValue::Default
@@ -1410,10 +2337,14 @@ mod tests {
             syn::parse_str::<EnumValue>("catch_all").unwrap(),
             EnumValue::CatchAll
         );
+        assert_eq!(
+            syn::parse_str::<EnumValue>("fallback").unwrap(),
+            EnumValue::Fallback
+        );
 
         assert_eq!(
             syn::parse_str::<EnumValue>("ABCD").unwrap_err().to_string(),
-            "Specifier not recognized. Must be an integer literal, `default` or `catch_all`"
+            "Specifier not recognized. Must be an integer literal, `default`, `catch_all` or `fallback`"
         );
     }
 
@@ -1472,11 +2403,24 @@ mod tests {
             CommandItemList {
                 items: vec![
                     CommandItem::BitOrder(BitOrder::LSB0),
-                    CommandItem::Address(LitInt::new("123", Span::call_site()))
+                    CommandItem::Address(CommandAddress::Integer(LitInt::new(
+                        "123",
+                        Span::call_site()
+                    )))
                 ]
             }
         );
 
+        assert_eq!(
+            syn::parse_str::<CommandItemList>("const POLL_UNTIL = Status.done;").unwrap(),
+            CommandItemList {
+                items: vec![CommandItem::PollUntil(
+                    Ident::new("Status", Span::call_site()),
+                    Ident::new("done", Span::call_site())
+                )]
+            }
+        );
+
         assert_eq!(
             syn::parse_str::<CommandItemList>(
                 "const SIZE_BITS_IN = 16;\nconst SIZE_BITS_OUT = 32;\nconst REPEAT = { count: 2, stride: 2 };"
@@ -1498,7 +2442,7 @@ mod tests {
             syn::parse_str::<CommandItemList>("const ABC = 16;")
                 .unwrap_err()
                 .to_string(),
-            "expected one of: `ADDRESS`, `SIZE_BITS_IN`, `SIZE_BITS_OUT`, `REPEAT`, `ALLOW_BIT_OVERLAP`, `ALLOW_ADDRESS_OVERLAP`"
+            "expected one of: `ADDRESS`, `SIZE_BITS_IN`, `SIZE_BITS_OUT`, `REPEAT`, `ALLOW_BIT_OVERLAP`, `ALLOW_ADDRESS_OVERLAP`, `POLL_UNTIL`"
         );
 
         assert_eq!(
@@ -1554,6 +2498,9 @@ mod tests {
                 identifier: Ident::new("TestBuffer", Span::call_site()),
                 access: None,
                 address: Some(LitInt::new("0x123", Span::call_site())),
+                frame: None,
+                max_size: None,
+                length_field: None,
             }
         );
 
@@ -1564,6 +2511,9 @@ mod tests {
                 identifier: Ident::new("TestBuffer", Span::call_site()),
                 access: None,
                 address: None,
+                frame: None,
+                max_size: None,
+                length_field: None,
             }
         );
 
@@ -1574,6 +2524,60 @@ mod tests {
                 identifier: Ident::new("TestBuffer", Span::call_site()),
                 access: Some(Access::WO),
                 address: None,
+                frame: None,
+                max_size: None,
+                length_field: None,
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<Buffer>("buffer TestBuffer: RO = 0x123 as Sample").unwrap(),
+            Buffer {
+                attribute_list: AttributeList::new(),
+                identifier: Ident::new("TestBuffer", Span::call_site()),
+                access: Some(Access::RO),
+                address: Some(LitInt::new("0x123", Span::call_site())),
+                frame: Some(Ident::new("Sample", Span::call_site())),
+                max_size: None,
+                length_field: None,
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<Buffer>("buffer TestBuffer: RO = 0x123 { length: uint = 0..8, }")
+                .unwrap(),
+            Buffer {
+                attribute_list: AttributeList::new(),
+                identifier: Ident::new("TestBuffer", Span::call_site()),
+                access: Some(Access::RO),
+                address: Some(LitInt::new("0x123", Span::call_site())),
+                frame: None,
+                max_size: None,
+                length_field: Some(Box::new(Field {
+                    attribute_list: AttributeList::new(),
+                    identifier: Ident::new("length", Span::call_site()),
+                    access: None,
+                    base_type: BaseType::Uint,
+                    field_conversion: None,
+                    field_address: FieldAddress::Range {
+                        start: LitInt::new("0", Span::call_site()),
+                        end: LitInt::new("8", Span::call_site()),
+                    },
+                })),
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<Buffer>("buffer TestBuffer: WO = 0x123 { const MAX_SIZE = 64; }")
+                .unwrap(),
+            Buffer {
+                attribute_list: AttributeList::new(),
+                identifier: Ident::new("TestBuffer", Span::call_site()),
+                access: Some(Access::WO),
+                address: Some(LitInt::new("0x123", Span::call_site())),
+                frame: None,
+                max_size: Some(LitInt::new("64", Span::call_site())),
+                length_field: None,
             }
         );
 
@@ -1593,6 +2597,9 @@ mod tests {
                 identifier: Ident::new("TestBuffer", Span::call_site()),
                 access: Some(Access::RO),
                 address: Some(LitInt::new("0x123", Span::call_site())),
+                frame: None,
+                max_size: None,
+                length_field: None,
             }
         );
     }
@@ -1725,7 +2732,38 @@ mod tests {
                     ]
                 },
                 identifier: Ident::new("Foo", Span::call_site()),
-                value: Some(CommandValue::Basic(LitInt::new("5", Span::call_site()))),
+                value: Some(CommandValue::Basic(CommandAddress::Integer(LitInt::new(
+                    "5",
+                    Span::call_site()
+                )))),
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<Command>("command Foo = [0xDE, 0xAD]").unwrap(),
+            Command {
+                attribute_list: AttributeList::new(),
+                identifier: Ident::new("Foo", Span::call_site()),
+                value: Some(CommandValue::Basic(CommandAddress::Opcode(vec![
+                    LitInt::new("0xDE", Span::call_site()),
+                    LitInt::new("0xAD", Span::call_site())
+                ]))),
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<Command>("command Foo { const ADDRESS = [0xDE, 0xAD]; }").unwrap(),
+            Command {
+                attribute_list: AttributeList::new(),
+                identifier: Ident::new("Foo", Span::call_site()),
+                value: Some(CommandValue::Extended {
+                    command_item_list: CommandItemList {
+                        items: vec![CommandItem::Address(CommandAddress::Opcode(vec![
+                            LitInt::new("0xDE", Span::call_site()),
+                            LitInt::new("0xAD", Span::call_site())
+                        ]))]
+                    },
+                    in_field_list: None,
+                    out_field_list: None
+                }),
             }
         );
         assert_eq!(
@@ -1849,14 +2887,14 @@ mod tests {
             syn::parse_str::<RegisterItemList>("const RST_VALUE = 5;")
                 .unwrap_err()
                 .to_string(),
-            "expected one of: `ADDRESS`, `SIZE_BITS`, `RESET_VALUE`, `REPEAT`, `ALLOW_BIT_OVERLAP`, `ALLOW_ADDRESS_OVERLAP`"
+            "expected one of: `ADDRESS`, `SIZE_BITS`, `SIZE_BYTES`, `RESET_VALUE`, `REPEAT`, `ALLOW_BIT_OVERLAP`, `ALLOW_ADDRESS_OVERLAP`, `EXPECT_ADDRESS`, `EXPECT_SIZE`, `LENGTH_FIELD`"
         );
 
         assert_eq!(
             syn::parse_str::<RegisterItemList>("type BT_ORDR = LSB0;")
                 .unwrap_err()
                 .to_string(),
-            "expected one of: `Access`, `ByteOrder`, `BitOrder`"
+            "expected one of: `Access`, `ByteOrder`, `BitOrder`, `Shadowed`, `DefaultFieldAccess`"
         );
 
         assert_eq!(
@@ -1874,15 +2912,93 @@ mod tests {
         );
 
         assert_eq!(
-            syn::parse_str::<RegisterItemList>("const RESET_VALUE = [0, 1, 2, 0x30];").unwrap(),
+            syn::parse_str::<RegisterItemList>("type DefaultFieldAccess = RO;").unwrap(),
             RegisterItemList {
-                register_items: vec![RegisterItem::ResetValueArray(vec![0, 1, 2, 0x30])]
+                register_items: vec![RegisterItem::DefaultFieldAccess(Access::RO)]
             }
         );
 
         assert_eq!(
-            syn::parse_str::<RegisterItemList>("const RESET_VALUE = [0, 1, 2, 0x300];")
-                .unwrap_err()
+            syn::parse_str::<RegisterItemList>("const SIZE_BYTES = 3;").unwrap(),
+            RegisterItemList {
+                register_items: vec![RegisterItem::SizeBytes(LitInt::new("3", Span::call_site()))]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>("const SIZE_BYTES = 3;\nconst SIZE_BYTES = 4;")
+                .unwrap_err()
+                .to_string(),
+            "duplicate item found"
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>("const ADDRESS = [0x10, 0x11, 0x12];").unwrap(),
+            RegisterItemList {
+                register_items: vec![RegisterItem::AddressSpan(vec![
+                    LitInt::new("0x10", Span::call_site()),
+                    LitInt::new("0x11", Span::call_site()),
+                    LitInt::new("0x12", Span::call_site()),
+                ])]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>("const ADDRESS = [0x10];\nconst ADDRESS = 0x10;")
+                .unwrap_err()
+                .to_string(),
+            "duplicate item found"
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>("const RESET_VALUE = [0, 1, 2, 0x30];").unwrap(),
+            RegisterItemList {
+                register_items: vec![RegisterItem::ResetValueArray(vec![0, 1, 2, 0x30])]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>(
+                "const RESET_VALUE = { value: 5, offset: -2, enabled: true, mode: Output };"
+            )
+            .unwrap(),
+            RegisterItemList {
+                register_items: vec![RegisterItem::ResetValueFields(vec![
+                    FieldResetValue {
+                        field: Ident::new("value", Span::call_site()),
+                        value: FieldResetValueKind::Int(LitInt::new("5", Span::call_site())),
+                    },
+                    FieldResetValue {
+                        field: Ident::new("offset", Span::call_site()),
+                        value: FieldResetValueKind::Int(LitInt::new("-2", Span::call_site())),
+                    },
+                    FieldResetValue {
+                        field: Ident::new("enabled", Span::call_site()),
+                        value: FieldResetValueKind::Bool(LitBool::new(true, Span::call_site())),
+                    },
+                    FieldResetValue {
+                        field: Ident::new("mode", Span::call_site()),
+                        value: FieldResetValueKind::Variant(Ident::new(
+                            "Output",
+                            Span::call_site()
+                        )),
+                    },
+                ])]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>(
+                "const RESET_VALUE = [0];\nconst RESET_VALUE = { value: 5 };"
+            )
+            .unwrap_err()
+            .to_string(),
+            "duplicate item found"
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>("const RESET_VALUE = [0, 1, 2, 0x300];")
+                .unwrap_err()
                 .to_string(),
             "number too large to fit in target type"
         );
@@ -1897,18 +3013,48 @@ mod tests {
             }
         );
 
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>(
+                "const EXPECT_ADDRESS = 0x10;\nconst EXPECT_SIZE = 16;"
+            )
+            .unwrap(),
+            RegisterItemList {
+                register_items: vec![
+                    RegisterItem::ExpectAddress(LitInt::new("0x10", Span::call_site())),
+                    RegisterItem::ExpectSize(LitInt::new("16", Span::call_site())),
+                ]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>("const LENGTH_FIELD = len;").unwrap(),
+            RegisterItemList {
+                register_items: vec![RegisterItem::LengthField(Ident::new(
+                    "len",
+                    Span::call_site()
+                ))]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<RegisterItemList>("const EXPECT_SIZE = 16;\nconst EXPECT_SIZE = 8;")
+                .unwrap_err()
+                .to_string(),
+            "duplicate item found"
+        );
+
         assert_eq!(
             syn::parse_str::<RegisterItemList>("const RRRRRESET_VALUE = [0, 1, 2, 0x30];")
                 .unwrap_err()
                 .to_string(),
-            "expected one of: `ADDRESS`, `SIZE_BITS`, `RESET_VALUE`, `REPEAT`, `ALLOW_BIT_OVERLAP`, `ALLOW_ADDRESS_OVERLAP`"
+            "expected one of: `ADDRESS`, `SIZE_BITS`, `SIZE_BYTES`, `RESET_VALUE`, `REPEAT`, `ALLOW_BIT_OVERLAP`, `ALLOW_ADDRESS_OVERLAP`, `EXPECT_ADDRESS`, `EXPECT_SIZE`, `LENGTH_FIELD`"
         );
 
         assert_eq!(
             syn::parse_str::<RegisterItemList>("const RESET_VALUE = ;")
                 .unwrap_err()
                 .to_string(),
-            "expected integer literal or square brackets"
+            "expected one of: integer literal, square brackets, curly braces"
         );
 
         assert_eq!(
@@ -1941,6 +3087,279 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_field_passthrough_attributes() {
+        assert_eq!(
+            syn::parse_str::<Field>(
+                "#[allow(non_camel_case_types)] #[derive(PartialOrd)] foo: uint as enum Foo { A, B } = 0..1"
+            )
+            .unwrap()
+            .attribute_list,
+            AttributeList {
+                attributes: vec![
+                    Attribute::Passthrough(
+                        "allow (non_camel_case_types)".into(),
+                        Span::call_site()
+                    ),
+                    Attribute::Passthrough("derive (PartialOrd)".into(), Span::call_site()),
+                ]
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[custom] foo: uint = 0..1")
+                .unwrap_err()
+                .to_string(),
+            "Unsupported attribute 'custom'. Only `doc`, `cfg`, `allow`, `derive`, `renamed_from`, `bit_order`, `unit` and `valid_when` attributes are allowed"
+        );
+        // The generated enum already has a repr of its base type
+        assert_eq!(
+            syn::parse_str::<Field>("#[repr(u16)] foo: uint as enum Foo { A, B } = 0..1")
+                .unwrap_err()
+                .to_string(),
+            "Unsupported attribute 'repr'. Only `doc`, `cfg`, `allow`, `derive`, `renamed_from`, `bit_order`, `unit` and `valid_when` attributes are allowed"
+        );
+        assert_eq!(
+            syn::parse_str::<AttributeList>("#[allow(dead_code)]")
+                .unwrap_err()
+                .to_string(),
+            "Unsupported attribute 'allow'. Only `doc` and `cfg` attributes are allowed"
+        );
+    }
+
+    #[test]
+    fn parse_field_renamed_from() {
+        assert_eq!(
+            syn::parse_str::<Field>("#[renamed_from = \"old_foo\"] foo: uint = 0..1")
+                .unwrap()
+                .attribute_list,
+            AttributeList {
+                attributes: vec![Attribute::RenamedFrom("old_foo".into(), Span::call_site())]
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[renamed_from(old_foo)] foo: uint = 0..1")
+                .unwrap_err()
+                .to_string(),
+            "expected `=`"
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[renamed_from = 5] foo: uint = 0..1")
+                .unwrap_err()
+                .to_string(),
+            "Invalid renamed_from attribute format. Use `#[renamed_from = \"old_name\"]`"
+        );
+        assert_eq!(
+            syn::parse_str::<AttributeList>("#[renamed_from = \"old_foo\"]")
+                .unwrap_err()
+                .to_string(),
+            "Unsupported attribute 'renamed_from'. Only `doc` and `cfg` attributes are allowed"
+        );
+    }
+
+    #[test]
+    fn parse_field_unit() {
+        assert_eq!(
+            syn::parse_str::<Field>("#[unit = \"°C\"] temperature: int = 0..16")
+                .unwrap()
+                .attribute_list,
+            AttributeList {
+                attributes: vec![Attribute::Unit("°C".into(), Span::call_site())]
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[unit = 5] temperature: int = 0..16")
+                .unwrap_err()
+                .to_string(),
+            "Invalid unit attribute format. Use `#[unit = \"°C\"]`"
+        );
+        assert_eq!(
+            syn::parse_str::<AttributeList>("#[unit = \"°C\"]")
+                .unwrap_err()
+                .to_string(),
+            "Unsupported attribute 'unit'. Only `doc` and `cfg` attributes are allowed"
+        );
+    }
+
+    #[test]
+    fn parse_field_valid_when() {
+        assert_eq!(
+            syn::parse_str::<Field>("#[valid_when = \"mode == Output\"] level: uint = 0..4")
+                .unwrap()
+                .attribute_list,
+            AttributeList {
+                attributes: vec![Attribute::ValidWhen(
+                    ValidWhen {
+                        field: Ident::new("mode", Span::call_site()),
+                        negated: false,
+                        value: FieldResetValueKind::Variant(Ident::new(
+                            "Output",
+                            Span::call_site()
+                        )),
+                    },
+                    Span::call_site()
+                )]
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[valid_when = \"enabled != false\"] level: uint = 0..4")
+                .unwrap()
+                .attribute_list,
+            AttributeList {
+                attributes: vec![Attribute::ValidWhen(
+                    ValidWhen {
+                        field: Ident::new("enabled", Span::call_site()),
+                        negated: true,
+                        value: FieldResetValueKind::Bool(LitBool::new(false, Span::call_site())),
+                    },
+                    Span::call_site()
+                )]
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[valid_when = \"mode = Output\"] level: uint = 0..4")
+                .unwrap_err()
+                .to_string(),
+            "expected `==` or `!=`"
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[valid_when(mode == Output)] level: uint = 0..4")
+                .unwrap_err()
+                .to_string(),
+            "expected `=`"
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[valid_when = 5] level: uint = 0..4")
+                .unwrap_err()
+                .to_string(),
+            "Invalid valid_when attribute format. Use `#[valid_when = \"mode == Output\"]`"
+        );
+    }
+
+    #[test]
+    fn parse_field_crc() {
+        assert_eq!(
+            syn::parse_str::<Field>("checksum: uint as crc over 0..24 = 24..32")
+                .unwrap()
+                .field_conversion,
+            Some(FieldConversion::Crc {
+                over: FieldAddress::Range {
+                    start: LitInt::new("0", Span::call_site()),
+                    end: LitInt::new("24", Span::call_site())
+                }
+            })
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("checksum: uint as crc::Checksum = 24..32")
+                .unwrap()
+                .field_conversion,
+            Some(FieldConversion::Direct {
+                path: syn::parse_str("crc::Checksum").unwrap(),
+                use_try: false,
+            })
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("checksum: uint as try crc over 0..24 = 24..32")
+                .unwrap_err()
+                .to_string(),
+            "A crc field can't be fallible. Remove the `try`"
+        );
+    }
+
+    #[test]
+    fn parse_field_newtype() {
+        assert_eq!(
+            syn::parse_str::<Field>("temperature: uint as newtype Temperature(u16) = 0..12")
+                .unwrap()
+                .field_conversion,
+            Some(FieldConversion::Newtype {
+                identifier: Ident::new("Temperature", Span::call_site()),
+                inner: Ident::new("u16", Span::call_site()),
+            })
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("temperature: uint as newtype::Temperature = 0..12")
+                .unwrap()
+                .field_conversion,
+            Some(FieldConversion::Direct {
+                path: syn::parse_str("newtype::Temperature").unwrap(),
+                use_try: false,
+            })
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("temperature: uint as try newtype Temperature(u16) = 0..12")
+                .unwrap_err()
+                .to_string(),
+            "A newtype field can't be fallible. Remove the `try`"
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("temperature: uint as newtype Temperature = 0..12")
+                .unwrap_err()
+                .to_string(),
+            "expected parentheses"
+        );
+    }
+
+    #[test]
+    fn parse_field_functions() {
+        assert_eq!(
+            syn::parse_str::<Field>(
+                "gain: uint as (Gain from gain::decode, GainSetting into gain::encode) = 0..4"
+            )
+            .unwrap()
+            .field_conversion,
+            Some(FieldConversion::Functions {
+                read_type: syn::parse_str("Gain").unwrap(),
+                read_fn: syn::parse_str("gain::decode").unwrap(),
+                write_type: syn::parse_str("GainSetting").unwrap(),
+                write_fn: syn::parse_str("gain::encode").unwrap(),
+            })
+        );
+        assert_eq!(
+            syn::parse_str::<Field>(
+                "gain: uint as try (Gain from decode, GainSetting into encode) = 0..4"
+            )
+            .unwrap_err()
+            .to_string(),
+            "A conversion with functions can't be fallible. Remove the `try`"
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("gain: uint as (Gain from decode) = 0..4")
+                .unwrap_err()
+                .to_string(),
+            "expected `,`"
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("gain: uint as (Gain decode, GainSetting into encode) = 0..4")
+                .unwrap_err()
+                .to_string(),
+            "expected `from`"
+        );
+    }
+
+    #[test]
+    fn parse_field_bit_order() {
+        assert_eq!(
+            syn::parse_str::<Field>("#[bit_order(MSB0)] foo: uint = 0..4")
+                .unwrap()
+                .attribute_list,
+            AttributeList {
+                attributes: vec![Attribute::BitOrder(BitOrder::MSB0, Span::call_site())]
+            }
+        );
+        assert_eq!(
+            syn::parse_str::<Field>("#[bit_order(XSB0)] foo: uint = 0..4")
+                .unwrap_err()
+                .to_string(),
+            "expected `LSB0` or `MSB0`"
+        );
+        assert_eq!(
+            syn::parse_str::<AttributeList>("#[bit_order(MSB0)]")
+                .unwrap_err()
+                .to_string(),
+            "Unsupported attribute 'bit_order'. Only `doc` and `cfg` attributes are allowed"
+        );
+    }
+
     #[test]
     fn parse_ref_object() {
         assert_eq!(
@@ -1972,6 +3391,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_alias_object() {
+        assert_eq!(
+            syn::parse_str::<AliasObject>("/// Hi!\nalias FooV2 = Foo @ 0x20").unwrap(),
+            AliasObject {
+                attribute_list: AttributeList {
+                    attributes: vec![Attribute::Doc(" Hi!".into())]
+                },
+                identifier: Ident::new("FooV2", Span::call_site()),
+                target: Ident::new("Foo", Span::call_site()),
+                address: syn::LitInt::new("0x20", Span::call_site()),
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<AliasObject>("alias FooV2 = Foo 0x20")
+                .unwrap_err()
+                .to_string(),
+            "expected `@`"
+        );
+    }
+
     #[test]
     fn parse_register() {
         assert_eq!(
@@ -2098,13 +3539,19 @@ mod tests {
                         Object::Command(Command {
                             attribute_list: AttributeList::new(),
                             identifier: Ident::new("A", Span::call_site()),
-                            value: Some(CommandValue::Basic(LitInt::new("5", Span::call_site())))
+                            value: Some(CommandValue::Basic(CommandAddress::Integer(LitInt::new(
+                    "5",
+                    Span::call_site()
+                ))))
                         }),
                         Object::Buffer(Buffer {
                             attribute_list: AttributeList::new(),
                             identifier: Ident::new("B", Span::call_site()),
                             access: None,
-                            address: Some(LitInt::new("6", Span::call_site()))
+                            address: Some(LitInt::new("6", Span::call_site())),
+                            frame: None,
+                            max_size: None,
+                            length_field: None,
                         })
                     ]
                 }
@@ -2207,11 +3654,162 @@ mod tests {
             }
         );
 
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type AutoAddress = true; }").unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::AutoAddress(LitBool::new(
+                    true,
+                    Span::call_site()
+                ))]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>(
+                "config { type ResetSequence = [(Foo, 0x01), (Bar, 255),]; }"
+            )
+            .unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::ResetSequence(vec![
+                    (
+                        Ident::new("Foo", Span::call_site()),
+                        LitInt::new("0x01", Span::call_site())
+                    ),
+                    (
+                        Ident::new("Bar", Span::call_site()),
+                        LitInt::new("255", Span::call_site())
+                    ),
+                ])]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type ResetSequence = [(Foo)]; }")
+                .unwrap_err()
+                .to_string(),
+            "expected `,`"
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>(
+                "config { type IdRegister = (WhoAmI, chip_id, 0x42); }"
+            )
+            .unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::IdRegister(
+                    Ident::new("WhoAmI", Span::call_site()),
+                    Ident::new("chip_id", Span::call_site()),
+                    LitInt::new("0x42", Span::call_site())
+                )]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type IdRegister = (WhoAmI, 0x42); }")
+                .unwrap_err()
+                .to_string(),
+            "expected identifier"
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type AddressUnit = Word16; }").unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::AddressUnit(AddressUnit::Word16)]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type AddressUnit = Word32; }")
+                .unwrap_err()
+                .to_string(),
+            "expected `Byte` or `Word16`"
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type ChipVariants = [A, B,]; }").unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::ChipVariants(vec![
+                    Ident::new("A", Span::call_site()),
+                    Ident::new("B", Span::call_site())
+                ])]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type DynAccess = true; }").unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::DynAccess(LitBool::new(
+                    true,
+                    Span::call_site()
+                ))]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type SharedCommandInterface = true; }")
+                .unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::SharedCommandInterface(LitBool::new(
+                    true,
+                    Span::call_site()
+                ))]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type PostRead = crate::on_read; }")
+                .unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::PostRead(syn::parse_quote!(crate::on_read))]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type EnumDerives = [Clone, Debug]; }")
+                .unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::EnumDerives(vec![
+                    Ident::new("Clone", Span::call_site()),
+                    Ident::new("Debug", Span::call_site()),
+                ])]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type DetectBusStuck = true; }").unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::DetectBusStuck(LitBool::new(
+                    true,
+                    Span::call_site()
+                ))]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>(
+                "config { type_alias Temp = crate::units::Temperature; }"
+            )
+            .unwrap(),
+            GlobalConfigList {
+                configs: vec![GlobalConfig::TypeAlias(
+                    Ident::new("Temp", Span::call_site()),
+                    syn::parse_quote!(crate::units::Temperature)
+                )]
+            }
+        );
+
+        assert_eq!(
+            syn::parse_str::<GlobalConfigList>("config { type_alias Temp crate::Temperature; }")
+                .unwrap_err()
+                .to_string(),
+            "expected `=`"
+        );
+
         assert_eq!(
             syn::parse_str::<GlobalConfigList>("config { type DefaultRegisterAccesssss = RW; }")
                 .unwrap_err()
                 .to_string(),
-            "expected one of: `DefaultRegisterAccess`, `DefaultFieldAccess`, `DefaultBufferAccess`, `DefaultByteOrder`, `DefaultBitOrder`, `RegisterAddressType`, `CommandAddressType`, `BufferAddressType`, `NameWordBoundaries`, `DefmtFeature`"
+            "expected one of: `DefaultRegisterAccess`, `DefaultFieldAccess`, `DefaultBufferAccess`, `DefaultByteOrder`, `DefaultRegisterByteOrder`, `DefaultCommandByteOrder`, `DefaultBitOrder`, `RegisterAddressType`, `CommandAddressType`, `BufferAddressType`, `NameWordBoundaries`, `DefmtFeature`, `UfmtFeature`, `AutoAddress`, `ResetSequence`, `Visibility`, `MaxFields`, `FlatAccessors`, `CrcAlgorithm`, `IdRegister`, `AddressUnit`, `Mmio`, `ResetHoldUs`, `GroupByKind`, `GenerateBitOps`, `FeatureGate`, `ChipVariants`, `DynAccess`, `SharedCommandInterface`, `PostRead`, `EnumDerives`, `DetectBusStuck`"
         );
     }
 
@@ -2221,7 +3819,7 @@ mod tests {
             syn::parse_str::<Object>("config { }")
                 .unwrap_err()
                 .to_string(),
-            "expected one of: `block`, `register`, `command`, `buffer`, `ref`"
+            "expected one of: `block`, `register`, `command`, `buffer`, `ref`, `alias`"
         );
 
         assert_eq!(
@@ -2264,6 +3862,9 @@ mod tests {
                 identifier: Ident::new("Foo", Span::call_site()),
                 access: None,
                 address: None,
+                frame: None,
+                max_size: None,
+                length_field: None,
             }),
         );
 
@@ -2277,6 +3878,9 @@ mod tests {
                     identifier: Ident::new("Foo", Span::call_site()),
                     access: None,
                     address: None,
+                    frame: None,
+                    max_size: None,
+                    length_field: None,
                 }))
             }),
         );
@@ -2290,6 +3894,9 @@ mod tests {
                 identifier: Ident::new("Foo", Span::call_site()),
                 access: None,
                 address: None,
+                frame: None,
+                max_size: None,
+                length_field: None,
             }),
         );
     }
@@ -2324,6 +3931,9 @@ mod tests {
                         identifier: Ident::new("Foo", Span::call_site()),
                         access: None,
                         address: None,
+                        frame: None,
+                        max_size: None,
+                        length_field: None,
                     })]
                 }
             }
@@ -2342,6 +3952,9 @@ mod tests {
                         identifier: Ident::new("Foo", Span::call_site()),
                         access: None,
                         address: None,
+                        frame: None,
+                        max_size: None,
+                        length_field: None,
                     })]
                 }
             }
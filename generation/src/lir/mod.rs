@@ -1,4 +1,4 @@
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 
 use proc_macro2::{Ident, Literal, TokenStream};
 
@@ -10,10 +10,215 @@ pub mod token_transform;
 pub struct Device {
     pub internal_address_type: Ident,
     pub register_address_type: Ident,
+    /// The sorted and merged address ranges that are taken up by registers
+    pub defined_register_addresses: Vec<RangeInclusive<i64>>,
     pub blocks: Vec<Block>,
     pub field_sets: Vec<FieldSet>,
     pub enums: Vec<Enum>,
+    pub newtypes: Vec<Newtype>,
     pub defmt_feature: Option<String>,
+    /// The feature that gates the generated `ufmt::uDebug` impls
+    pub ufmt_feature: Option<String>,
+    /// The visibility of the generated types and accessor methods
+    pub visibility: TokenStream,
+    /// Generate getters and setters for the fields of the registers directly on the blocks
+    pub flat_accessors: bool,
+    pub reset_sequence: Vec<ResetSequenceWrite>,
+    pub snapshot: Snapshot,
+    pub configure: Configure,
+    pub id_check: Option<IdCheck>,
+    /// Set when the registers are addressed in 16-bit words
+    pub word_interface: Option<WordInterface>,
+    /// Set when the registers are memory-mapped
+    pub mmio: Option<Mmio>,
+    /// The time in microseconds the reset is held by the generated `reset_with_delay` function
+    pub reset_hold_us: Option<Literal>,
+    /// The submodules of the field sets module the field sets are put in.
+    /// Field sets not in any group stay in the field sets module itself.
+    pub field_set_groups: Vec<FieldSetGroup>,
+    /// Generate the bitwise operator impls on the field sets
+    pub generate_bit_ops: bool,
+    /// The registers with values from the datasheet that are checked by generated tests
+    pub register_expectations: Vec<RegisterExpectation>,
+    /// The field sets that are read as the length prefix of a buffer
+    pub length_prefixes: Vec<LengthPrefix>,
+    /// The registers of which the size is read at runtime from a length field
+    pub variable_size_registers: Vec<VariableSizeRegister>,
+    /// The commands of which the in and out field sets have the same layout
+    pub command_echoes: Vec<CommandEcho>,
+    /// Every register address with the field set that is decoded from it by `decode_any`
+    pub decodable_registers: Vec<DecodableRegister>,
+    /// The registers of which the fields can be read and written by name with `read_field` and `write_field`
+    pub dyn_access: Option<Vec<DynAccessRegister>>,
+    /// Commands and registers share an address space, so command addresses may not be register addresses
+    pub commands_share_register_addresses: bool,
+    /// The hook that is run after every successful read of a register
+    pub read_hook: Option<ReadHook>,
+    /// The derives that are put on every generated enum
+    pub enum_derives: Vec<Ident>,
+}
+
+/// The generated type that implements the read hook of the register operations
+pub struct ReadHook {
+    pub name: Ident,
+    /// The function that is called with the address after every successful read of a register
+    pub post_read: Option<TokenStream>,
+    /// Fail the reads that read all `0x00` or all `0xFF` while the reset value isn't
+    pub detect_bus_stuck: bool,
+}
+
+/// A register address and the field set of the register at that address
+pub struct DecodableRegister {
+    pub cfg_attr: TokenStream,
+    pub field_set_name: Ident,
+    pub address: i64,
+    pub size_bytes: Literal,
+}
+
+/// A register of the root block that can be accessed by name
+pub struct DynAccessRegister {
+    pub cfg_attr: TokenStream,
+    pub name: Ident,
+    pub readable: bool,
+    /// The register can be read and written, so its fields can be written with a read-modify-write
+    pub modifiable: bool,
+    pub fields: Vec<DynAccessField>,
+}
+
+/// A field that can be accessed by name. The field is accessed with its raw value, bypassing any conversion
+pub struct DynAccessField {
+    pub cfg_attr: TokenStream,
+    pub name: Ident,
+    pub readable: bool,
+    pub writable: bool,
+    pub getter: Ident,
+    pub setter: Ident,
+    /// The type of the raw value, or `None` for bool fields
+    pub base_type: Option<Ident>,
+    pub signed: bool,
+    pub size_bits: u32,
+}
+
+/// A command that echoes its input, so its out field set can be created from its in field set
+pub struct CommandEcho {
+    pub cfg_attr: TokenStream,
+    pub field_set_name_in: Ident,
+    pub field_set_name_out: Ident,
+    pub size_bytes: Literal,
+}
+
+/// A register with a length field that holds the amount of bytes that follow the bytes of the field
+pub struct VariableSizeRegister {
+    pub cfg_attr: TokenStream,
+    pub field_set_name: Ident,
+    pub length_field_name: Ident,
+    /// The size of the part of the register with the length field, in whole bytes
+    pub prefix_size_bits: Literal,
+}
+
+/// A field set that holds the length of the payload that follows it in a buffer
+pub struct LengthPrefix {
+    pub cfg_attr: TokenStream,
+    pub field_set_name: Ident,
+    /// The field that holds the payload length in bytes
+    pub field_name: Ident,
+}
+
+/// The values the datasheet gives for a register, which are checked against the computed values by generated tests
+pub struct RegisterExpectation {
+    pub cfg_attr: TokenStream,
+    pub register_name: Ident,
+    pub field_set_name: Ident,
+    /// The computed address of the (first instance of the) register and the address from the datasheet
+    pub address: Option<(Literal, Literal)>,
+    /// The size in bits from the datasheet
+    pub size_bits: Option<Literal>,
+}
+
+/// A submodule of the field sets module with the field sets of one object kind
+pub struct FieldSetGroup {
+    pub module_name: Ident,
+    pub doc_attr: TokenStream,
+    pub field_set_names: Vec<Ident>,
+}
+
+/// The word interface the device can be created with when the registers are addressed in 16-bit words
+pub struct WordInterface {
+    pub byte_order: ByteOrder,
+    /// The size of the biggest register in words
+    pub max_words: Literal,
+}
+
+/// A struct containing the values of all readable registers of the root block
+pub struct Snapshot {
+    pub name: Ident,
+    pub registers: Vec<SnapshotRegister>,
+}
+
+pub struct SnapshotRegister {
+    pub cfg_attr: TokenStream,
+    pub name: Ident,
+    pub field_set_name: Ident,
+    /// The amount of registers when the register is repeated
+    pub repeat_count: Option<u64>,
+}
+
+/// A struct in which writes to the registers of the root block are staged by the generated `configure` function
+pub struct Configure {
+    pub name: Ident,
+    /// The registers sorted by address
+    pub registers: Vec<ConfigureRegister>,
+    /// The summed size of the registers in bytes
+    pub buffer_size: Literal,
+}
+
+pub struct ConfigureRegister {
+    pub cfg_attr: TokenStream,
+    pub name: Ident,
+    pub field_set_name: Ident,
+    pub reset_value_function: Ident,
+    pub address: Literal,
+    /// The register is located right after the previous register, so they can be written in one go
+    pub follows_previous: bool,
+}
+
+/// A `#[repr(C)]` struct that lays out the registers of the root block at their addresses
+pub struct Mmio {
+    pub name: Ident,
+    /// The fields sorted by offset, including the padding between the registers
+    pub fields: Vec<MmioField>,
+}
+
+pub struct MmioField {
+    pub name: Ident,
+    pub offset: Literal,
+    pub size_bytes: Literal,
+    /// The register at this field or `None` when it's padding
+    pub register: Option<MmioRegister>,
+}
+
+pub struct MmioRegister {
+    pub cfg_attr: TokenStream,
+    pub doc_attr: TokenStream,
+    pub field_set_name: Ident,
+    pub access: Access,
+    pub reset_value_function: Ident,
+}
+
+/// A register write of the reset sequence, done by the generated `init` function
+pub struct ResetSequenceWrite {
+    pub cfg_attr: TokenStream,
+    pub register_name: Ident,
+    pub field_set_name: Ident,
+    pub value: Vec<u8>,
+}
+
+/// The id register field that is compared to the expected value by the generated `check_id` function
+pub struct IdCheck {
+    pub cfg_attr: TokenStream,
+    pub register_name: Ident,
+    pub field_name: Ident,
+    pub expected: Literal,
 }
 
 pub struct Block {
@@ -50,28 +255,49 @@ pub enum BlockMethodType {
         access: Access,
         address_type: Ident,
         reset_value_function: Ident,
+        /// When not empty, the register is virtual and made up of parts at these offsets from the address
+        span_offsets: Vec<Literal>,
+        /// The last written value is kept on the root block and reads return it
+        shadowed: bool,
     },
     Command {
         field_set_name_in: Option<Ident>,
         field_set_name_out: Option<Ident>,
         address_type: Ident,
+        /// The register field that is polled after dispatching, if any
+        poll_until: Option<CommandPoll>,
     },
     Buffer {
         access: Access,
         address_type: Ident,
+        /// The field set of the frames in the buffer, if any
+        frame_field_set: Option<Ident>,
+        /// The field set of the length prefix in front of the payloads, if any
+        length_prefix_field_set: Option<Ident>,
+        /// The maximum amount of bytes `write_checked` accepts, if any
+        max_size: Option<Literal>,
     },
 }
 
+/// A register field that is polled after dispatching a command until it's set
+pub struct CommandPoll {
+    /// The method name of the register on the block
+    pub register_name: Ident,
+    pub field_set_name: Ident,
+    pub field_name: Ident,
+}
+
 /// A set of fields, like a register or command in/out
 pub struct FieldSet {
     pub cfg_attr: TokenStream,
     pub doc_attr: TokenStream,
     pub name: Ident,
     pub byte_order: ByteOrder,
-    pub bit_order: BitOrder,
     pub size_bits: u32,
     pub reset_value: Vec<u8>,
     pub ref_reset_overrides: Vec<(String, Vec<u8>)>,
+    /// The reset values per chip variant
+    pub chip_variant_reset_values: Vec<(String, Vec<u8>)>,
     pub fields: Vec<Field>,
 }
 
@@ -79,18 +305,46 @@ pub struct Field {
     pub cfg_attr: TokenStream,
     pub doc_attr: TokenStream,
     pub name: Ident,
+    /// Old names of the field for which deprecated aliases are generated
+    pub renamed_from: Vec<Ident>,
+    /// The unit of the value of the field, used for documentation
+    pub unit: Option<String>,
+    /// The condition on another field that must hold for this field to be valid
+    pub valid_when: Option<ValidWhen>,
+    pub bit_order: BitOrder,
     pub address: Range<Literal>,
     pub base_type: Ident,
     pub conversion_method: FieldConversionMethod,
     pub access: mir::Access,
 }
 
+/// A comparison of the raw value of another field in the same field set
+pub struct ValidWhen {
+    /// The condition as written by the user, like `mode == Output`
+    pub description: String,
+    pub field_name: Ident,
+    pub negated: bool,
+    pub raw_value: i128,
+}
+
 pub enum FieldConversionMethod {
     None,
     Into(TokenStream),
     UnsafeInto(TokenStream),
     TryInto(TokenStream),
     Bool,
+    /// The field is a crc calculated with the algorithm over the bits in the range
+    Crc {
+        algorithm: TokenStream,
+        over: Range<u32>,
+    },
+    /// The field is read with `read_fn` into `read_type` and written with `write_fn` from `write_type`
+    Functions {
+        read_type: TokenStream,
+        read_fn: TokenStream,
+        write_type: TokenStream,
+        write_fn: TokenStream,
+    },
 }
 
 impl FieldConversionMethod {
@@ -101,6 +355,16 @@ impl FieldConversionMethod {
             FieldConversionMethod::UnsafeInto(token_stream) => Some(token_stream),
             FieldConversionMethod::TryInto(token_stream) => Some(token_stream),
             FieldConversionMethod::Bool => None,
+            FieldConversionMethod::Crc { .. } => None,
+            FieldConversionMethod::Functions { read_type, .. } => Some(read_type),
+        }
+    }
+
+    /// The type the setter of the field takes. Only differs from the [Self::conversion_type] for asymmetric conversions
+    pub fn input_conversion_type(&self) -> Option<&TokenStream> {
+        match self {
+            FieldConversionMethod::Functions { write_type, .. } => Some(write_type),
+            _ => self.conversion_type(),
         }
     }
 }
@@ -111,6 +375,19 @@ pub struct Enum {
     pub name: Ident,
     pub base_type: Ident,
     pub variants: Vec<EnumVariant>,
+    /// Extra attributes (like `allow`, `derive` or `repr`) that are put on the enum definition
+    pub passthrough_attrs: TokenStream,
+}
+
+/// A tuple struct that wraps the value of a field
+pub struct Newtype {
+    pub cfg_attr: TokenStream,
+    pub doc_attr: TokenStream,
+    pub name: Ident,
+    /// The type of the value in the tuple struct
+    pub inner: Ident,
+    /// The type of the field the newtype converts from and into
+    pub base_type: Ident,
 }
 
 pub struct EnumVariant {
@@ -120,4 +397,6 @@ pub struct EnumVariant {
     pub number: Literal,
     pub default: bool,
     pub catch_all: bool,
+    /// The inclusive end of the range of raw values that map to this variant. The range starts at the number
+    pub range_end: Option<Literal>,
 }
@@ -15,11 +15,21 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
     for (i, claimed_address) in claimed_addresses.iter().enumerate() {
         for other_claimed_address in claimed_addresses.get(i + 1..).unwrap_or_default() {
             let types_same = claimed_address.address_type == other_claimed_address.address_type;
+            // On a shared interface, a command with the address of a register can't be told apart from it
+            let types_share_addresses = device.commands_share_register_addresses
+                && matches!(
+                    (
+                        claimed_address.address_type,
+                        other_claimed_address.address_type
+                    ),
+                    (ClaimedAddressType::Register, ClaimedAddressType::Command)
+                        | (ClaimedAddressType::Command, ClaimedAddressType::Register)
+                );
             let both_allow_overlap =
                 claimed_address.allow_overlap && other_claimed_address.allow_overlap;
             let address_same = claimed_address.address == other_claimed_address.address;
 
-            if address_same && types_same && !both_allow_overlap {
+            if address_same && (types_same || types_share_addresses) && !both_allow_overlap {
                 let mut name0 = claimed_address.name.clone();
                 if let Some(repeat_index) = claimed_address.repeat_index {
                     name0 += &format!(" (index: {repeat_index})");
@@ -28,6 +38,13 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                 if let Some(repeat_index) = other_claimed_address.repeat_index {
                     name1 += &format!(" (index: {repeat_index})");
                 }
+
+                if !types_same {
+                    bail!(
+                        "Objects \"{name0}\" and \"{name1}\" use the same address ({}) and can't be told apart, because commands and registers share the interface. If this is intended, then allow address overlap on both objects.",
+                        claimed_address.address
+                    );
+                }
                 bail!(
                     "Objects \"{name0}\" and \"{name1}\" use the same address ({}). If this is intended, then allow address overlap on both objects.",
                     claimed_address.address
@@ -92,20 +109,32 @@ fn get_block_claimed_addresses(
             BlockMethodType::Buffer { .. } => ClaimedAddressType::Buffer,
         };
 
+        let span_offsets = match &method.method_type {
+            BlockMethodType::Register { span_offsets, .. } if !span_offsets.is_empty() => {
+                span_offsets
+                    .iter()
+                    .map(|offset| offset.to_string().parse::<i64>().unwrap())
+                    .collect()
+            }
+            _ => vec![0],
+        };
+
         for i in 0..repeat_count {
             use itertools::Itertools;
 
-            claimed_adresses.push(ClaimedAddress {
-                name: name_stack
-                    .iter()
-                    .cloned()
-                    .chain([method.name.to_string().to_case(Case::Pascal)])
-                    .join("::"),
-                repeat_index: repeat.then_some(i),
-                address: current_address_offset + i * repeat_stride,
-                allow_overlap: method.allow_address_overlap,
-                address_type: claimed_address_type,
-            });
+            for span_offset in &span_offsets {
+                claimed_adresses.push(ClaimedAddress {
+                    name: name_stack
+                        .iter()
+                        .cloned()
+                        .chain([method.name.to_string().to_case(Case::Pascal)])
+                        .join("::"),
+                    repeat_index: repeat.then_some(i),
+                    address: current_address_offset + i * repeat_stride + span_offset,
+                    allow_overlap: method.allow_address_overlap,
+                    address_type: claimed_address_type,
+                });
+            }
         }
     }
 
@@ -129,18 +158,19 @@ struct ClaimedAddress {
 
 #[cfg(test)]
 mod tests {
-    use crate::lir::BlockMethod;
+    use crate::lir::{BlockMethod, Configure, Snapshot};
 
     use super::*;
     use indoc::indoc;
     use proc_macro2::Literal;
-    use quote::format_ident;
+    use quote::{format_ident, quote};
 
     #[test]
     fn deep_overlap_detected() {
         let mut device = Device {
             internal_address_type: format_ident!("u8"),
             register_address_type: format_ident!("u8"),
+            defined_register_addresses: Vec::new(),
             blocks: vec![
                 Block {
                     cfg_attr: Default::default(),
@@ -177,6 +207,8 @@ mod tests {
                                 access: crate::mir::Access::RW,
                                 address_type: format_ident!("bla"),
                                 reset_value_function: format_ident!("new"),
+                                span_offsets: Vec::new(),
+                                shadowed: false,
                             },
                         },
                     ],
@@ -198,13 +230,44 @@ mod tests {
                             access: crate::mir::Access::RW,
                             address_type: format_ident!("bla"),
                             reset_value_function: format_ident!("new"),
+                            span_offsets: Vec::new(),
+                            shadowed: false,
                         },
                     }],
                 },
             ],
             field_sets: Vec::new(),
             enums: Vec::new(),
+            newtypes: Vec::new(),
             defmt_feature: None,
+            ufmt_feature: None,
+            visibility: quote! { pub },
+            flat_accessors: false,
+            reset_sequence: Vec::new(),
+            snapshot: Snapshot {
+                name: format_ident!("RootSnapshot"),
+                registers: Vec::new(),
+            },
+            configure: Configure {
+                name: format_ident!("RootConfigure"),
+                registers: Vec::new(),
+                buffer_size: Literal::u32_unsuffixed(0),
+            },
+            id_check: None,
+            word_interface: None,
+            mmio: None,
+            reset_hold_us: None,
+            field_set_groups: Vec::new(),
+            generate_bit_ops: true,
+            register_expectations: Vec::new(),
+            length_prefixes: Vec::new(),
+            variable_size_registers: Vec::new(),
+            command_echoes: Vec::new(),
+            decodable_registers: Vec::new(),
+            dyn_access: None,
+            commands_share_register_addresses: false,
+            read_hook: None,
+            enum_derives: Vec::new(),
         };
 
         pretty_assertions::assert_eq!(
@@ -214,4 +277,60 @@ mod tests {
             )
         );
     }
+
+    fn run_dsl(config: &str, allow_overlap: &str) -> anyhow::Result<()> {
+        let mut mir = crate::dsl_hir::mir_transform::transform(
+            syn::parse_str::<crate::dsl_hir::Device>(&format!(
+                "
+                config {{
+                    type RegisterAddressType = u8;
+                    type DefaultByteOrder = LE;
+                    {config}
+                }}
+                register Status {{
+                    const ADDRESS = 0x05;
+                    const SIZE_BITS = 8;
+                    {allow_overlap}
+
+                    value: uint = 0..8,
+                }},
+                command Reset {{
+                    const ADDRESS = 0x05;
+                    {allow_overlap}
+                }},
+                "
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        crate::mir::passes::run_passes(&mut mir)?;
+        let mut lir = crate::mir::lir_transform::transform(mir, "Device")?;
+        run_pass(&mut lir)
+    }
+
+    #[test]
+    fn command_colliding_with_register_on_shared_interface() {
+        run_dsl("type CommandAddressType = u8;", "").unwrap();
+        run_dsl(
+            "type SharedCommandInterface = true; type CommandAddressType = u16;",
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(
+            run_dsl(
+                "type SharedCommandInterface = true; type CommandAddressType = u8;",
+                ""
+            )
+            .unwrap_err()
+            .to_string(),
+            "Objects \"Status\" and \"Reset\" use the same address (5) and can't be told apart, because commands and registers share the interface. If this is intended, then allow address overlap on both objects."
+        );
+
+        run_dsl(
+            "type SharedCommandInterface = true; type CommandAddressType = u8;",
+            "const ALLOW_ADDRESS_OVERLAP = true;",
+        )
+        .unwrap();
+    }
 }
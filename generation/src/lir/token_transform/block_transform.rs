@@ -1,8 +1,15 @@
+use std::ops::RangeInclusive;
+
 use proc_macro2::{Ident, Literal, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 
+use super::field_set_transform::{get_field_input_type, get_field_return_type};
 use crate::{
-    lir::{Block, BlockMethod, BlockMethodKind, BlockMethodType},
+    lir::{
+        Block, BlockMethod, BlockMethodKind, BlockMethodType, CommandPoll, Configure,
+        ConfigureRegister, FieldConversionMethod, FieldSet, IdCheck, Mmio, MmioField, MmioRegister,
+        ReadHook, ResetSequenceWrite, Snapshot, SnapshotRegister, WordInterface,
+    },
     mir,
 };
 
@@ -10,6 +17,8 @@ pub fn generate_block(
     value: &Block,
     internal_address_type: &Ident,
     register_address_type: &Ident,
+    read_hook: Option<&ReadHook>,
+    visibility: &TokenStream,
 ) -> TokenStream {
     let Block {
         cfg_attr,
@@ -38,9 +47,46 @@ pub fn generate_block(
             )
         };
 
-    let method_impls = methods
+    let read_error = read_error(
+        read_hook.is_some_and(|read_hook| read_hook.detect_bus_stuck),
+        quote! { I::Error },
+    );
+    let method_impls = methods.iter().map(|m| {
+        generate_method(
+            m,
+            internal_address_type,
+            read_hook.map(|read_hook| &read_hook.name),
+            visibility,
+        )
+    });
+
+    let (shadow_fields, shadow_inits): (Vec<_>, Vec<_>) = methods
         .iter()
-        .map(|m| generate_method(m, internal_address_type));
+        .filter_map(|m| match &m.method_type {
+            BlockMethodType::Register {
+                field_set_name,
+                reset_value_function,
+                shadowed: true,
+                ..
+            } => {
+                let cfg_attr = &m.cfg_attr;
+                let shadow_name = shadow_field_name(&m.name);
+
+                Some((
+                    quote! {
+                        #cfg_attr
+                        #[doc(hidden)]
+                        #shadow_name: field_sets::#field_set_name,
+                    },
+                    quote! {
+                        #cfg_attr
+                        #shadow_name: field_sets::#field_set_name::#reset_value_function(),
+                    },
+                ))
+            }
+            _ => None,
+        })
+        .unzip();
 
     let (new_hidden_if_not_root, new_access, new_const) = if *root {
         (quote! {}, quote! { pub }, quote! { const })
@@ -117,10 +163,11 @@ pub fn generate_block(
         #doc_attr
         #cfg_attr
         #[derive(Debug)]
-        pub struct #name<#generics> {
+        #visibility struct #name<#generics> {
             pub(crate) interface: #interface_declaration,
             #[doc(hidden)]
             base_address: #internal_address_type,
+            #(#shadow_fields)*
         }
 
         #cfg_attr
@@ -131,6 +178,7 @@ pub fn generate_block(
                 Self {
                     interface,
                     base_address: #address_specifier,
+                    #(#shadow_inits)*
                 }
             }
 
@@ -140,10 +188,10 @@ pub fn generate_block(
             }
 
             #read_all_docs
-            pub fn read_all_registers(
+            #visibility fn read_all_registers(
                 &mut self,
                 mut callback: impl FnMut(#register_address_type, &'static str, field_sets::FieldSetValue)
-            ) -> Result<(), I::Error>
+            ) -> Result<(), #read_error>
                 where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
             {
                 #(#read_all_registers_items)*
@@ -151,10 +199,10 @@ pub fn generate_block(
             }
 
             #read_all_docs
-            pub async fn read_all_registers_async(
+            #visibility async fn read_all_registers_async(
                 &mut self,
                 mut callback: impl FnMut(#register_address_type, &'static str, field_sets::FieldSetValue)
-            ) -> Result<(), I::Error>
+            ) -> Result<(), #read_error>
                 where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
             {
                 #(#read_async_all_registers_items)*
@@ -166,160 +214,1534 @@ pub fn generate_block(
     }
 }
 
-fn generate_method(method: &BlockMethod, internal_address_type: &Ident) -> TokenStream {
-    let BlockMethod {
-        cfg_attr,
-        doc_attr,
-        name,
-        address,
-        allow_address_overlap: _,
-        kind,
-        method_type,
-    } = method;
+/// Generate the `is_defined_address` function on the root block
+pub fn generate_defined_address_check(
+    root_block: &Block,
+    register_address_type: &Ident,
+    defined_register_addresses: &[RangeInclusive<i64>],
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Block { cfg_attr, name, .. } = root_block;
 
-    let (return_type, address_conversion, default_arg) = match method_type {
-        BlockMethodType::Block { name } => (quote! { #name::<'_, I> }, quote! {}, quote! {}),
-        BlockMethodType::Register {
-            field_set_name,
-            access,
-            address_type,
-            reset_value_function: default_value_function_name,
-        } => (
-            quote! { ::device_driver::RegisterOperation::<'_, I, #address_type, field_sets::#field_set_name, ::device_driver::#access>  },
-            quote! { as #address_type },
-            quote! { , field_sets::#field_set_name::#default_value_function_name },
-        ),
-        BlockMethodType::Command {
-            field_set_name_in,
-            field_set_name_out,
-            address_type,
-        } => {
-            let field_set_name_in = match field_set_name_in {
-                Some(val) => quote! { field_sets::#val },
-                None => quote! { () },
-            };
-            let field_set_name_out = match field_set_name_out {
-                Some(val) => quote! { field_sets::#val },
-                None => quote! { () },
-            };
-            (
-                quote! { ::device_driver::CommandOperation::<'_, I, #address_type, #field_set_name_in, #field_set_name_out>  },
-                quote! { as #address_type },
-                quote! {},
-            )
+    let address_literal = |address: i64| {
+        let literal = Literal::u64_unsuffixed(address.unsigned_abs());
+        if address.is_negative() {
+            quote! { -#literal }
+        } else {
+            quote! { #literal }
         }
-        BlockMethodType::Buffer {
-            access,
-            address_type,
-        } => (
-            quote! { ::device_driver::BufferOperation::<'_, I, #address_type, ::device_driver::#access>  },
-            quote! { as #address_type },
-            quote! {},
-        ),
     };
 
-    let (index_param, address_calc, index_doc) = match kind {
-        BlockMethodKind::Normal => (None, quote! { self.base_address + #address }, None),
-        BlockMethodKind::Repeated { count, stride } => {
-            let doc = format!("Valid index range: 0..{count}");
+    let patterns = defined_register_addresses.iter().map(|range| {
+        let start = address_literal(*range.start());
+        let end = address_literal(*range.end());
 
-            let stride = stride.to_string().parse::<i64>().unwrap();
+        if range.start() == range.end() {
+            start
+        } else {
+            quote! { #start..=#end }
+        }
+    });
 
-            let operator = if stride.is_negative() {
-                quote! { - }
-            } else {
-                quote! { + }
+    let body = if defined_register_addresses.is_empty() {
+        quote! {
+            let _ = address;
+            false
+        }
+    } else {
+        quote! {
+            match address {
+                #(#patterns)|* => true,
+                _ => false,
+            }
+        }
+    };
+
+    quote! {
+        #cfg_attr
+        impl<I> #name<I> {
+            /// Returns true if the given address falls within any of the registers of the device,
+            /// including the registers in (repeated) child blocks.
+            ///
+            /// A register takes up one address for every address unit (a byte or a 16-bit word) of its size.
+            #visibility const fn is_defined_address(address: #register_address_type) -> bool {
+                #body
+            }
+        }
+    }
+}
+
+/// Generate the `DEVICE_SPAN` const and the `from_bytes` constructor on the root block
+/// that creates the device on top of an in-memory interface
+pub fn generate_memory_constructor(
+    root_block: &Block,
+    register_address_type: &Ident,
+    defined_register_addresses: &[RangeInclusive<i64>],
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Block { cfg_attr, name, .. } = root_block;
+
+    // Negative addresses can't be mapped to memory
+    let (Some(first), Some(last)) = (
+        defined_register_addresses.first(),
+        defined_register_addresses.last(),
+    ) else {
+        return TokenStream::new();
+    };
+    if first.start().is_negative() {
+        return TokenStream::new();
+    }
+
+    let device_span = Literal::u64_unsuffixed(*last.end() as u64 + 1);
+
+    quote! {
+        #cfg_attr
+        impl<I> #name<I> {
+            /// The number of bytes spanned by the registers of the device, starting at address 0.
+            ///
+            /// Registers are assumed to be byte-addressed, so a register takes up one address for every byte of its size.
+            #visibility const DEVICE_SPAN: usize = #device_span;
+        }
+
+        #cfg_attr
+        impl #name<::device_driver::MemoryInterface<#register_address_type, #device_span>> {
+            /// Create the device on top of an in-memory interface that is seeded with the given bytes.
+            /// The byte at index `a` is the value at address `a`.
+            ///
+            /// This fails when the number of bytes doesn't match [Self::DEVICE_SPAN].
+            #visibility fn from_bytes(bytes: &[u8]) -> Result<Self, ::device_driver::MemoryInterfaceError> {
+                Ok(Self::new(::device_driver::MemoryInterface::try_from(bytes)?))
+            }
+        }
+    }
+}
+
+/// Generate the `init` functions on the root block that write the reset sequence
+pub fn generate_init(
+    root_block: &Block,
+    register_address_type: &Ident,
+    reset_sequence: &[ResetSequenceWrite],
+    visibility: &TokenStream,
+) -> TokenStream {
+    if reset_sequence.is_empty() {
+        return TokenStream::new();
+    }
+
+    let Block { cfg_attr, name, .. } = root_block;
+
+    let generate_writes = |use_async: bool| {
+        reset_sequence.iter().map(move |write| {
+            let ResetSequenceWrite {
+                cfg_attr,
+                register_name,
+                field_set_name,
+                value,
+            } = write;
+
+            let write_function = match use_async {
+                true => quote! { write_with_zero_async(|reg| *reg = field_sets::#field_set_name::from([#(#value),*])).await },
+                false => quote! { write_with_zero(|reg| *reg = field_sets::#field_set_name::from([#(#value),*])) },
             };
 
-            let stride = Literal::u64_unsuffixed(stride.unsigned_abs());
+            quote! {
+                #cfg_attr
+                self.#register_name().#write_function?;
+            }
+        })
+    };
 
-            (
-                Some(quote! { index: usize, }),
-                quote! { {
-                    assert!(index < #count);
-                    self.base_address + #address #operator index as #internal_address_type * #stride
-                } },
-                Some(quote! {
-                    #[doc = ""]
-                    #[doc = #doc]
-                }),
-            )
+    let writes = generate_writes(false);
+    let async_writes = generate_writes(true);
+
+    quote! {
+        #cfg_attr
+        impl<I> #name<I> {
+            /// Initialize the device by writing the reset sequence to the registers in order.
+            /// The whole register is written with the configured value.
+            #visibility fn init(&mut self) -> Result<(), I::Error>
+                where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+            {
+                #(#writes)*
+                Ok(())
+            }
+
+            /// Initialize the device by writing the reset sequence to the registers in order.
+            /// The whole register is written with the configured value.
+            #visibility async fn init_async(&mut self) -> Result<(), I::Error>
+                where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+            {
+                #(#async_writes)*
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn generate_reset_with_delay(
+    root_block: &Block,
+    reset_hold_us: Option<&Literal>,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Some(reset_hold_us) = reset_hold_us else {
+        return TokenStream::new();
+    };
+
+    let Block { cfg_attr, name, .. } = root_block;
+
+    let function_doc = format!(
+        "Reset the device by asserting the reset, waiting {reset_hold_us} µs and then releasing it."
+    );
+
+    quote! {
+        #cfg_attr
+        impl<I> #name<I> {
+            #[doc = #function_doc]
+            ///
+            /// The delay is called with the number of microseconds to wait, e.g. `|us| delay.delay_us(us)` with `embedded-hal`.
+            #visibility fn reset_with_delay(&mut self, mut delay: impl FnMut(u32)) -> Result<(), <I as ::device_driver::ResetInterface>::Error>
+                where I: ::device_driver::ResetInterface
+            {
+                ::device_driver::ResetInterface::set_reset(&mut self.interface, true)?;
+                delay(#reset_hold_us);
+                ::device_driver::ResetInterface::set_reset(&mut self.interface, false)
+            }
+
+            #[doc = #function_doc]
+            ///
+            /// The delay is called with the number of microseconds to wait, e.g. `|us| delay.delay_us(us)` with `embedded-hal-async`.
+            #visibility async fn reset_with_delay_async<DelayFuture: Future<Output = ()>>(
+                &mut self,
+                mut delay: impl FnMut(u32) -> DelayFuture,
+            ) -> Result<(), <I as ::device_driver::AsyncResetInterface>::Error>
+                where I: ::device_driver::AsyncResetInterface
+            {
+                ::device_driver::AsyncResetInterface::set_reset(&mut self.interface, true).await?;
+                delay(#reset_hold_us).await;
+                ::device_driver::AsyncResetInterface::set_reset(&mut self.interface, false).await
+            }
+        }
+    }
+}
+
+/// Generate the `new_word` constructor on the root block that creates the device on top of a word-addressed interface
+pub fn generate_word_constructor(
+    root_block: &Block,
+    word_interface: Option<&WordInterface>,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Some(WordInterface {
+        byte_order,
+        max_words,
+    }) = word_interface
+    else {
+        return TokenStream::new();
+    };
+
+    let Block { cfg_attr, name, .. } = root_block;
+
+    let byte_order = match byte_order {
+        mir::ByteOrder::LE => quote! { ::device_driver::WordByteOrder::LE },
+        // Every pair of bytes is a big endian word
+        mir::ByteOrder::BE | mir::ByteOrder::SwapPairs => {
+            quote! { ::device_driver::WordByteOrder::BE }
         }
     };
 
     quote! {
-        #doc_attr
-        #index_doc
         #cfg_attr
-        pub fn #name(&mut self, #index_param) -> #return_type {
-            let address = #address_calc;
-            #return_type::new(self.interface(), address #address_conversion #default_arg)
+        impl<I> #name<::device_driver::WordInterface<I, #max_words>> {
+            /// Create the device on top of an interface that addresses the registers in 16-bit words.
+            /// The bytes of the registers are combined into words using the byte order of the registers.
+            #visibility const fn new_word(interface: I) -> Self {
+                Self::new(::device_driver::WordInterface::new(interface, #byte_order))
+            }
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use indoc::indoc;
-    use proc_macro2::Literal;
-    use quote::format_ident;
+/// Generate the `check_id` functions on the root block that compare the id register field to the expected value
+pub fn generate_id_check(
+    root_block: &Block,
+    register_address_type: &Ident,
+    id_check: Option<&IdCheck>,
+    detect_bus_stuck: bool,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Some(IdCheck {
+        cfg_attr: register_cfg_attr,
+        register_name,
+        field_name,
+        expected,
+    }) = id_check
+    else {
+        return TokenStream::new();
+    };
 
-    #[test]
-    fn root_block_correct() {
-        let output = generate_block(
-            &Block {
-                cfg_attr: quote! { #[cfg(unix)] },
-                doc_attr: quote! { #[doc = "Hello!"] },
-                root: true,
-                name: format_ident!("RootBlock"),
-                methods: vec![BlockMethod {
-                    cfg_attr: quote! { #[cfg(unix)] },
-                    doc_attr: quote! { #[doc = "42 is the answer"] },
-                    name: format_ident!("my_register1"),
-                    address: Literal::i64_unsuffixed(5),
-                    allow_address_overlap: false,
-                    kind: BlockMethodKind::Normal,
-                    method_type: BlockMethodType::Register {
-                        field_set_name: format_ident!("MyRegister"),
-                        access: crate::mir::Access::RW,
-                        address_type: format_ident!("u8"),
-                        reset_value_function: format_ident!("new"),
-                    },
-                }],
-            },
-            &format_ident!("u8"),
-            &format_ident!("u8"),
-        );
+    let Block { cfg_attr, name, .. } = root_block;
+    let read_error = read_error(detect_bus_stuck, quote! { I::Error });
 
-        pretty_assertions::assert_eq!(
-            prettyplease::unparse(&syn::parse2(output).unwrap()),
-            indoc! {"
-                ///Hello!
-                #[cfg(unix)]
-                #[derive(Debug)]
-                pub struct RootBlock<I> {
-                    pub(crate) interface: I,
-                    #[doc(hidden)]
-                    base_address: u8,
-                }
-                #[cfg(unix)]
-                impl<I> RootBlock<I> {
-                    /// Create a new instance of the block based on device interface
-                    pub const fn new(interface: I) -> Self {
-                        Self { interface, base_address: 0 }
+    let doc = format!(
+        "Read the `{field_name}` field of the `{register_name}` register and check if it's the expected id `{expected}`.\n\n\
+        This can be used to verify the device is present and is the right device."
+    );
+
+    quote! {
+        #cfg_attr
+        impl<I> #name<I> {
+            #[doc = #doc]
+            #register_cfg_attr
+            #visibility fn check_id(&mut self) -> Result<bool, #read_error>
+                where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+            {
+                Ok(self.#register_name().read()?.#field_name() == #expected)
+            }
+
+            #[doc = #doc]
+            #register_cfg_attr
+            #visibility async fn check_id_async(&mut self) -> Result<bool, #read_error>
+                where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+            {
+                Ok(self.#register_name().read_async().await?.#field_name() == #expected)
+            }
+        }
+    }
+}
+
+/// Generate getters and setters on the block for every field of the registers of the block.
+/// The getters read the register, the setters do a read-modify-write of the register.
+pub fn generate_flat_accessors(
+    block: &Block,
+    field_sets: &[FieldSet],
+    register_address_type: &Ident,
+    detect_bus_stuck: bool,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Block {
+        cfg_attr,
+        root,
+        name,
+        methods,
+        ..
+    } = block;
+
+    let generics = match root {
+        true => quote! { I },
+        false => quote! { 'i, I },
+    };
+
+    let read_error = &read_error(detect_bus_stuck, quote! { I::Error });
+
+    let accessors = methods.iter().filter_map(|method| {
+        let BlockMethodType::Register {
+            field_set_name,
+            access,
+            ..
+        } = &method.method_type
+        else {
+            return None;
+        };
+
+        let field_set = field_sets.iter().find(|fs| &fs.name == field_set_name)?;
+        let register_name = &method.name;
+        let register_cfg_attr = &method.cfg_attr;
+
+        let (index_param, index_arg) = match method.kind {
+            BlockMethodKind::Normal => (None, None),
+            BlockMethodKind::Repeated { .. } => (Some(quote! { index: usize, }), Some(quote! { index })),
+        };
+
+        let readable_register = matches!(access, mir::Access::RW | mir::Access::RO | mir::Access::RC);
+        let modifiable_register = matches!(access, mir::Access::RW);
+
+        Some(field_set.fields.iter().map(move |field| {
+            let field_name = &field.name;
+            let field_cfg_attr = &field.cfg_attr;
+
+            let getters = (readable_register && matches!(field.access, mir::Access::RW | mir::Access::RO | mir::Access::RC)).then(|| {
+                let getter_name = format_ident!("{register_name}_{field_name}");
+                let async_getter_name = format_ident!("{register_name}_{field_name}_async");
+                let return_type = get_field_return_type(field, &quote! {});
+                let doc = format!("Read the `{register_name}` register and get its `{field_name}` field");
+
+                quote! {
+                    #[doc = #doc]
+                    #register_cfg_attr
+                    #field_cfg_attr
+                    #visibility fn #getter_name(&mut self, #index_param) -> Result<#return_type, #read_error>
+                        where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+                    {
+                        Ok(self.#register_name(#index_arg).read()?.#field_name())
                     }
-                    /// A reference to the interface used to communicate with the device
-                    pub(crate) fn interface(&mut self) -> &mut I {
-                        &mut self.interface
+
+                    #[doc = #doc]
+                    #register_cfg_attr
+                    #field_cfg_attr
+                    #visibility async fn #async_getter_name(&mut self, #index_param) -> Result<#return_type, #read_error>
+                        where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+                    {
+                        Ok(self.#register_name(#index_arg).read_async().await?.#field_name())
                     }
-                    /// Read all readable register values in this block from the device.
-                    /// The callback is called for each of them.
-                    /// Any registers in child blocks are not included.
-                    ///
-                    /// The callback has three arguments:
-                    ///
-                    /// - The address of the register
+                }
+            });
+
+            // The setter of a crc field takes no value, so it's left out
+            let settable_field = matches!(field.access, mir::Access::RW | mir::Access::WO)
+                && !matches!(field.conversion_method, FieldConversionMethod::Crc { .. });
+
+            let setters = (modifiable_register && settable_field).then(|| {
+                let setter_name = format_ident!("set_{register_name}_{field_name}");
+                let async_setter_name = format_ident!("set_{register_name}_{field_name}_async");
+                let field_setter_name = format_ident!("set_{field_name}");
+                let input_type = get_field_input_type(field);
+                let doc = format!("Set the `{field_name}` field of the `{register_name}` register with a read-modify-write");
+
+                quote! {
+                    #[doc = #doc]
+                    #register_cfg_attr
+                    #field_cfg_attr
+                    #visibility fn #setter_name(&mut self, #index_param value: #input_type) -> Result<(), #read_error>
+                        where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+                    {
+                        self.#register_name(#index_arg).modify(|reg| reg.#field_setter_name(value))
+                    }
+
+                    #[doc = #doc]
+                    #register_cfg_attr
+                    #field_cfg_attr
+                    #visibility async fn #async_setter_name(&mut self, #index_param value: #input_type) -> Result<(), #read_error>
+                        where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+                    {
+                        self.#register_name(#index_arg).modify_async(|reg| reg.#field_setter_name(value)).await
+                    }
+                }
+            });
+
+            quote! {
+                #getters
+                #setters
+            }
+        }))
+    }).flatten();
+
+    quote! {
+        #cfg_attr
+        impl<#generics> #name<#generics> {
+            #(#accessors)*
+        }
+    }
+}
+
+/// Generate a `touch_<register>` function on the block for every register that has read-to-clear fields.
+/// It reads the register and discards the value, so only the clearing side effect remains.
+pub fn generate_touch_methods(
+    block: &Block,
+    field_sets: &[FieldSet],
+    register_address_type: &Ident,
+    detect_bus_stuck: bool,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Block {
+        cfg_attr,
+        root,
+        name,
+        methods,
+        ..
+    } = block;
+
+    let generics = match root {
+        true => quote! { I },
+        false => quote! { 'i, I },
+    };
+
+    let read_error = read_error(detect_bus_stuck, quote! { I::Error });
+
+    let touch_methods = methods
+        .iter()
+        .filter_map(|method| {
+            // A shadowed register is read from the shadow, so there's no read to touch it with
+            let BlockMethodType::Register {
+                field_set_name,
+                access,
+                shadowed: false,
+                ..
+            } = &method.method_type
+            else {
+                return None;
+            };
+
+            let field_set = field_sets.iter().find(|fs| &fs.name == field_set_name)?;
+            let clears_on_read = *access == mir::Access::RC
+                || (*access != mir::Access::WO
+                    && field_set
+                        .fields
+                        .iter()
+                        .any(|field| field.access == mir::Access::RC));
+
+            clears_on_read.then_some(method)
+        })
+        .map(|method| {
+            let register_name = &method.name;
+            let register_cfg_attr = &method.cfg_attr;
+            let touch_name = format_ident!("touch_{register_name}");
+            let async_touch_name = format_ident!("touch_{register_name}_async");
+
+            let (index_param, index_arg) = match method.kind {
+                BlockMethodKind::Normal => (None, None),
+                BlockMethodKind::Repeated { .. } => {
+                    (Some(quote! { index: usize }), Some(quote! { index }))
+                }
+            };
+
+            let doc = format!(
+                "Read the `{register_name}` register and discard the value. This is done for the side effect of clearing its read-to-clear fields."
+            );
+
+            quote! {
+                #[doc = #doc]
+                #register_cfg_attr
+                #visibility fn #touch_name(&mut self, #index_param) -> Result<(), #read_error>
+                    where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+                {
+                    self.#register_name(#index_arg).read().map(|_| ())
+                }
+
+                #[doc = #doc]
+                #register_cfg_attr
+                #visibility async fn #async_touch_name(&mut self, #index_param) -> Result<(), #read_error>
+                    where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+                {
+                    self.#register_name(#index_arg).read_async().await.map(|_| ())
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if touch_methods.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        #cfg_attr
+        impl<#generics> #name<#generics> {
+            #(#touch_methods)*
+        }
+    }
+}
+
+/// Generate a `<block>_indices` function on the block for every repeated child block.
+/// The blocks borrow the interface mutably, so they can't be yielded by an iterator themselves.
+/// The returned range doesn't borrow the block, so the indices can be used to get every instance in a loop.
+pub fn generate_repeat_indices(block: &Block, visibility: &TokenStream) -> TokenStream {
+    let Block {
+        cfg_attr,
+        root,
+        name,
+        methods,
+        ..
+    } = block;
+
+    let generics = match root {
+        true => quote! { I },
+        false => quote! { 'i, I },
+    };
+
+    let index_methods = methods
+        .iter()
+        .filter_map(|method| {
+            let BlockMethodKind::Repeated { count, .. } = &method.kind else {
+                return None;
+            };
+            let BlockMethodType::Block { .. } = &method.method_type else {
+                return None;
+            };
+
+            let block_name = &method.name;
+            let block_cfg_attr = &method.cfg_attr;
+            let indices_name = format_ident!("{block_name}_indices");
+            let count = Literal::usize_unsuffixed(count.to_string().parse().unwrap());
+
+            let doc = format!(
+                "The indices of all {count} instances of the `{block_name}` block, in order.\n\n\
+                Use it to go over every instance, like `for index in self.{indices_name}() {{ self.{block_name}(index); }}`."
+            );
+
+            Some(quote! {
+                #[doc = #doc]
+                #block_cfg_attr
+                #visibility fn #indices_name(&self) -> core::ops::Range<usize> {
+                    0..#count
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if index_methods.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        #cfg_attr
+        impl<#generics> #name<#generics> {
+            #(#index_methods)*
+        }
+    }
+}
+
+/// Generate a `fold_<register>` function on the block for every readable repeated register.
+/// It reads all instances of the register in order and folds them into an accumulator.
+pub fn generate_fold_methods(
+    block: &Block,
+    register_address_type: &Ident,
+    detect_bus_stuck: bool,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Block {
+        cfg_attr,
+        root,
+        name,
+        methods,
+        ..
+    } = block;
+
+    let generics = match root {
+        true => quote! { I },
+        false => quote! { 'i, I },
+    };
+
+    let read_error = read_error(detect_bus_stuck, quote! { I::Error });
+
+    let fold_methods = methods
+        .iter()
+        .filter_map(|method| {
+            let BlockMethodKind::Repeated { count, .. } = &method.kind else {
+                return None;
+            };
+            let BlockMethodType::Register {
+                field_set_name,
+                access,
+                ..
+            } = &method.method_type
+            else {
+                return None;
+            };
+
+            if *access == mir::Access::WO {
+                return None;
+            }
+
+            let register_name = &method.name;
+            let register_cfg_attr = &method.cfg_attr;
+            let fold_name = format_ident!("fold_{register_name}");
+            let async_fold_name = format_ident!("fold_{register_name}_async");
+
+            let doc = format!(
+                "Read all {count} instances of the `{register_name}` register in order and fold them into an accumulator.\n\n\
+                The closure is called with the accumulator and the read register and returns the new accumulator."
+            );
+
+            Some(quote! {
+                #[doc = #doc]
+                #register_cfg_attr
+                #visibility fn #fold_name<Acc>(
+                    &mut self,
+                    init: Acc,
+                    mut f: impl FnMut(Acc, field_sets::#field_set_name) -> Acc,
+                ) -> Result<Acc, #read_error>
+                    where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+                {
+                    let mut acc = init;
+                    for index in 0..#count {
+                        acc = f(acc, self.#register_name(index).read()?);
+                    }
+                    Ok(acc)
+                }
+
+                #[doc = #doc]
+                #register_cfg_attr
+                #visibility async fn #async_fold_name<Acc>(
+                    &mut self,
+                    init: Acc,
+                    mut f: impl FnMut(Acc, field_sets::#field_set_name) -> Acc,
+                ) -> Result<Acc, #read_error>
+                    where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+                {
+                    let mut acc = init;
+                    for index in 0..#count {
+                        acc = f(acc, self.#register_name(index).read_async().await?);
+                    }
+                    Ok(acc)
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if fold_methods.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        #cfg_attr
+        impl<#generics> #name<#generics> {
+            #(#fold_methods)*
+        }
+    }
+}
+
+/// Generate a `<command>_and_wait` function on the block for every command that polls a register field.
+/// It dispatches the command and then reads the register until the field is set.
+pub fn generate_command_poll_methods(
+    block: &Block,
+    register_address_type: &Ident,
+    detect_bus_stuck: bool,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Block {
+        cfg_attr,
+        root,
+        name,
+        methods,
+        ..
+    } = block;
+
+    let generics = match root {
+        true => quote! { I },
+        false => quote! { 'i, I },
+    };
+
+    let async_read_error = read_error(
+        detect_bus_stuck,
+        quote! { <I as ::device_driver::AsyncRegisterInterface>::Error },
+    );
+    let read_error = read_error(
+        detect_bus_stuck,
+        quote! { <I as ::device_driver::RegisterInterface>::Error },
+    );
+    // The poll error only converts from the read error, so the dispatch error needs to be wrapped the same way
+    let dispatch_error_conversion =
+        detect_bus_stuck.then(|| quote! { .map_err(::device_driver::BusStuckError::Interface) });
+
+    let poll_methods = methods
+        .iter()
+        .filter_map(|method| {
+            let BlockMethodType::Command {
+                field_set_name_in,
+                address_type: command_address_type,
+                poll_until: Some(CommandPoll {
+                    register_name,
+                    field_set_name,
+                    field_name,
+                }),
+                ..
+            } = &method.method_type
+            else {
+                return None;
+            };
+
+            let command_name = &method.name;
+            let command_cfg_attr = &method.cfg_attr;
+            let wait_name = format_ident!("{command_name}_and_wait");
+            let async_wait_name = format_ident!("{command_name}_and_wait_async");
+
+            let (in_param, in_arg) = match field_set_name_in {
+                Some(field_set_name_in) => (
+                    quote! { f: impl FnOnce(&mut field_sets::#field_set_name_in), },
+                    quote! { f },
+                ),
+                None => (quote! {}, quote! {}),
+            };
+
+            let doc = format!(
+                "Dispatch the `{command_name}` command and then read the `{register_name}` register until `{field_name}` is set.\n\n\
+                The register is read at most `max_attempts` times. When the field isn't set in any of them, [::device_driver::PollError::Timeout] is returned."
+            );
+
+            Some(quote! {
+                #[doc = #doc]
+                #command_cfg_attr
+                #visibility fn #wait_name(
+                    &mut self,
+                    #in_param
+                    max_attempts: u32,
+                ) -> Result<field_sets::#field_set_name, ::device_driver::PollError<#read_error>>
+                    where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+                        + ::device_driver::CommandInterface<AddressType = #command_address_type, Error = <I as ::device_driver::RegisterInterface>::Error>
+                {
+                    self.#command_name().dispatch(#in_arg)#dispatch_error_conversion?;
+                    self.#register_name().poll_until(|register| register.#field_name(), max_attempts)
+                }
+
+                #[doc = #doc]
+                ///
+                /// The delay is awaited between the reads.
+                #command_cfg_attr
+                #visibility async fn #async_wait_name<DelayFuture: Future<Output = ()>>(
+                    &mut self,
+                    #in_param
+                    max_attempts: u32,
+                    delay: impl FnMut() -> DelayFuture,
+                ) -> Result<field_sets::#field_set_name, ::device_driver::PollError<#async_read_error>>
+                    where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+                        + ::device_driver::AsyncCommandInterface<AddressType = #command_address_type, Error = <I as ::device_driver::AsyncRegisterInterface>::Error>
+                {
+                    self.#command_name().dispatch_async(#in_arg).await #dispatch_error_conversion?;
+                    self.#register_name()
+                        .poll_until_async(|register| register.#field_name(), max_attempts, delay)
+                        .await
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if poll_methods.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        #cfg_attr
+        impl<#generics> #name<#generics> {
+            #(#poll_methods)*
+        }
+    }
+}
+
+/// Generate the snapshot struct and the `snapshot` functions on the root block
+pub fn generate_snapshot(
+    root_block: &Block,
+    register_address_type: &Ident,
+    snapshot: &Snapshot,
+    defmt_feature: Option<&str>,
+    detect_bus_stuck: bool,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Snapshot {
+        name: snapshot_name,
+        registers,
+    } = snapshot;
+
+    if registers.is_empty() {
+        return TokenStream::new();
+    }
+
+    let Block { cfg_attr, name, .. } = root_block;
+    let read_error = read_error(detect_bus_stuck, quote! { I::Error });
+
+    let struct_fields = registers.iter().map(|register| {
+        let SnapshotRegister {
+            cfg_attr,
+            name,
+            field_set_name,
+            repeat_count,
+        } = register;
+
+        let field_type = match repeat_count {
+            Some(count) => {
+                let count = Literal::u64_unsuffixed(*count);
+                quote! { [field_sets::#field_set_name; #count] }
+            }
+            None => quote! { field_sets::#field_set_name },
+        };
+
+        let doc = format!("The value of the `{name}` register");
+
+        quote! {
+            #[doc = #doc]
+            #cfg_attr
+            pub #name: #field_type
+        }
+    });
+
+    let generate_field_reads = |use_async: bool| {
+        registers.iter().map(move |register| {
+            let SnapshotRegister {
+                cfg_attr,
+                name,
+                repeat_count,
+                ..
+            } = register;
+
+            let read_function = match use_async {
+                true => quote! { read_async().await },
+                false => quote! { read() },
+            };
+
+            let value = match repeat_count {
+                Some(count) => {
+                    let reads = (0..*count).map(|index| {
+                        let index = Literal::u64_unsuffixed(index);
+                        quote! { self.#name(#index).#read_function? }
+                    });
+                    quote! { [#(#reads),*] }
+                }
+                None => quote! { self.#name().#read_function? },
+            };
+
+            quote! {
+                #cfg_attr
+                #name: #value
+            }
+        })
+    };
+
+    let field_reads = generate_field_reads(false);
+    let async_field_reads = generate_field_reads(true);
+
+    let defmt_derive = match defmt_feature {
+        Some(feature_name) => {
+            quote! { #[cfg_attr(feature = #feature_name, derive(defmt::Format))] }
+        }
+        None => quote! {},
+    };
+
+    let snapshot_doc = format!(
+        "The values of all readable registers in the root block of [{name}], created with [{name}::snapshot]"
+    );
+
+    quote! {
+        #[doc = #snapshot_doc]
+        #cfg_attr
+        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        #defmt_derive
+        #visibility struct #snapshot_name {
+            #(#struct_fields),*
+        }
+
+        #cfg_attr
+        impl<I> #name<I> {
+            /// Read all readable registers in the root block once and return their values.
+            /// Any registers in child blocks are not included.
+            ///
+            /// This is useful for e.g. diagnostics and bug reports.
+            #visibility fn snapshot(&mut self) -> Result<#snapshot_name, #read_error>
+                where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+            {
+                Ok(#snapshot_name {
+                    #(#field_reads),*
+                })
+            }
+
+            /// Read all readable registers in the root block once and return their values.
+            /// Any registers in child blocks are not included.
+            ///
+            /// This is useful for e.g. diagnostics and bug reports.
+            #visibility async fn snapshot_async(&mut self) -> Result<#snapshot_name, #read_error>
+                where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+            {
+                Ok(#snapshot_name {
+                    #(#async_field_reads),*
+                })
+            }
+        }
+    }
+}
+
+/// Generate the `configure` functions on the root block and the struct in which the register writes are staged
+pub fn generate_configure(
+    root_block: &Block,
+    register_address_type: &Ident,
+    configure: &Configure,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Configure {
+        name: configure_name,
+        registers,
+        buffer_size,
+    } = configure;
+
+    if registers.is_empty() {
+        return TokenStream::new();
+    }
+
+    let Block { cfg_attr, name, .. } = root_block;
+
+    let struct_fields = registers.iter().map(|register| {
+        let ConfigureRegister {
+            cfg_attr,
+            name,
+            field_set_name,
+            ..
+        } = register;
+
+        quote! {
+            #cfg_attr
+            #name: Option<field_sets::#field_set_name>
+        }
+    });
+
+    let stage_functions = registers.iter().map(|register| {
+        let ConfigureRegister {
+            cfg_attr,
+            name,
+            field_set_name,
+            reset_value_function,
+            ..
+        } = register;
+
+        let doc = format!(
+            "Stage a write to the `{name}` register. The value starts at the reset value of the register."
+        );
+
+        quote! {
+            #[doc = #doc]
+            #cfg_attr
+            #visibility fn #name(&mut self) -> &mut field_sets::#field_set_name {
+                self.#name.get_or_insert_with(field_sets::#field_set_name::#reset_value_function)
+            }
+        }
+    });
+
+    let staged_writes = registers.iter().map(|register| {
+        let ConfigureRegister {
+            cfg_attr,
+            name,
+            field_set_name,
+            address,
+            follows_previous,
+            ..
+        } = register;
+
+        quote! {
+            #cfg_attr
+            ::device_driver::StagedWrite {
+                address: #address,
+                size_bits: <field_sets::#field_set_name as ::device_driver::FieldSet>::SIZE_BITS,
+                value: staged.#name.as_ref().map(::device_driver::FieldSet::get_inner_buffer),
+                follows_previous: #follows_previous,
+            }
+        }
+    });
+    let staged_writes = quote! { #(#staged_writes),* };
+
+    let configure_doc = format!(
+        "Register writes that are staged by [{name}::configure] and then written to the device at once"
+    );
+    let function_doc = quote! {
+        /// Stage writes to the registers in the root block and write them to the device at once.
+        /// The registers are written in address order and registers located right after each other are combined into a single write.
+        ///
+        /// The staged values start at the reset value of the register, like with a normal `write`.
+        /// Any registers in child blocks and registers that are repeated, shadowed or span multiple addresses can't be staged.
+    };
+
+    quote! {
+        #[doc = #configure_doc]
+        #cfg_attr
+        #[derive(Debug, Default)]
+        #visibility struct #configure_name {
+            #(#struct_fields),*
+        }
+
+        #cfg_attr
+        impl #configure_name {
+            #(#stage_functions)*
+        }
+
+        #cfg_attr
+        impl<I> #name<I> {
+            #function_doc
+            #visibility fn configure(&mut self, f: impl FnOnce(&mut #configure_name)) -> Result<(), I::Error>
+                where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+            {
+                let mut staged = #configure_name::default();
+                f(&mut staged);
+                ::device_driver::write_staged::<_, #buffer_size>(&mut self.interface, &[#staged_writes])
+            }
+
+            #function_doc
+            #visibility async fn configure_async(&mut self, f: impl FnOnce(&mut #configure_name)) -> Result<(), I::Error>
+                where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+            {
+                let mut staged = #configure_name::default();
+                f(&mut staged);
+                ::device_driver::write_staged_async::<_, #buffer_size>(&mut self.interface, &[#staged_writes]).await
+            }
+        }
+    }
+}
+
+pub fn generate_mmio(
+    root_block: &Block,
+    mmio: Option<&Mmio>,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Some(Mmio {
+        name: mmio_name,
+        fields,
+    }) = mmio
+    else {
+        return TokenStream::new();
+    };
+
+    let Block { cfg_attr, name, .. } = root_block;
+
+    let struct_fields = fields.iter().map(|field| {
+        let MmioField {
+            name, size_bytes, ..
+        } = field;
+
+        quote! {
+            #name: ::core::cell::UnsafeCell<[u8; #size_bytes]>
+        }
+    });
+
+    let offset_asserts = fields.iter().map(|field| {
+        let MmioField { name, offset, .. } = field;
+
+        quote! {
+            assert!(::core::mem::offset_of!(#mmio_name, #name) == #offset);
+        }
+    });
+
+    let register_functions = fields.iter().filter_map(|field| {
+        let MmioField {
+            name,
+            register:
+                Some(MmioRegister {
+                    cfg_attr,
+                    doc_attr,
+                    field_set_name,
+                    access,
+                    reset_value_function,
+                }),
+            ..
+        } = field
+        else {
+            return None;
+        };
+
+        Some(quote! {
+            #doc_attr
+            #cfg_attr
+            #visibility fn #name(&self) -> ::device_driver::MmioRegisterOperation<'_, field_sets::#field_set_name, ::device_driver::#access> {
+                ::device_driver::MmioRegisterOperation::new(&self.#name, field_sets::#field_set_name::#reset_value_function)
+            }
+        })
+    });
+
+    let mmio_doc = format!(
+        "The registers in the root block of [{name}] laid out at their addresses, to be placed over the memory-mapped registers of the device."
+    );
+
+    quote! {
+        #[doc = #mmio_doc]
+        ///
+        /// The registers are accessed with volatile reads and writes.
+        /// Any registers in child blocks and registers that are repeated or span multiple addresses are not included.
+        #cfg_attr
+        #[repr(C)]
+        #visibility struct #mmio_name {
+            #(#struct_fields),*
+        }
+
+        #cfg_attr
+        const _: () = {
+            #(#offset_asserts)*
+        };
+
+        #cfg_attr
+        impl #mmio_name {
+            /// Get the registers located at the given pointer.
+            ///
+            /// # Safety
+            ///
+            /// The pointer must point to the memory-mapped registers of the device and stay valid for the lifetime `'a`.
+            #visibility const unsafe fn from_ptr<'a>(ptr: *mut Self) -> &'a Self {
+                unsafe { &*ptr }
+            }
+
+            #(#register_functions)*
+        }
+    }
+}
+
+fn generate_method(
+    method: &BlockMethod,
+    internal_address_type: &Ident,
+    read_hook: Option<&Ident>,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let BlockMethod {
+        cfg_attr,
+        doc_attr,
+        name,
+        address,
+        allow_address_overlap: _,
+        kind,
+        method_type,
+    } = method;
+
+    let mut interface_arg = quote! { self.interface() };
+    // The hook is only passed when there is one, so the default of the operation is used otherwise
+    let read_hook = read_hook.map(|read_hook| quote! { , #read_hook });
+
+    let (return_type, address_arg, default_arg) = match method_type {
+        BlockMethodType::Block { name } => {
+            (quote! { #name::<'_, I> }, quote! { address }, quote! {})
+        }
+        BlockMethodType::Register {
+            field_set_name,
+            access,
+            address_type,
+            reset_value_function: default_value_function_name,
+            shadowed: true,
+            ..
+        } => {
+            let shadow_name = shadow_field_name(name);
+            // Borrow the fields separately so the interface and the shadow can both be borrowed mutably
+            interface_arg = quote! { &mut self.interface };
+
+            (
+                quote! { ::device_driver::ShadowedRegisterOperation::<'_, I, #address_type, field_sets::#field_set_name, ::device_driver::#access #read_hook>  },
+                quote! { address as #address_type },
+                quote! { , field_sets::#field_set_name::#default_value_function_name, &mut self.#shadow_name },
+            )
+        }
+        BlockMethodType::Register {
+            field_set_name,
+            access,
+            address_type,
+            reset_value_function: default_value_function_name,
+            span_offsets,
+            shadowed: _,
+        } if !span_offsets.is_empty() => {
+            let parts = Literal::usize_unsuffixed(span_offsets.len());
+            let part_addresses = span_offsets.iter().map(|offset| {
+                let offset = offset.to_string().parse::<i64>().unwrap();
+                let offset_literal = Literal::u64_unsuffixed(offset.unsigned_abs());
+
+                if offset.is_negative() {
+                    quote! { (address - #offset_literal) as #address_type }
+                } else {
+                    quote! { (address + #offset_literal) as #address_type }
+                }
+            });
+
+            (
+                quote! { ::device_driver::SpannedRegisterOperation::<'_, I, #address_type, field_sets::#field_set_name, ::device_driver::#access, #parts #read_hook>  },
+                quote! { [#(#part_addresses),*] },
+                quote! { , field_sets::#field_set_name::#default_value_function_name },
+            )
+        }
+        BlockMethodType::Register {
+            field_set_name,
+            access,
+            address_type,
+            reset_value_function: default_value_function_name,
+            span_offsets: _,
+            shadowed: _,
+        } => (
+            quote! { ::device_driver::RegisterOperation::<'_, I, #address_type, field_sets::#field_set_name, ::device_driver::#access #read_hook>  },
+            quote! { address as #address_type },
+            quote! { , field_sets::#field_set_name::#default_value_function_name },
+        ),
+        BlockMethodType::Command {
+            field_set_name_in,
+            field_set_name_out,
+            address_type,
+            poll_until: _,
+        } => {
+            let field_set_name_in = match field_set_name_in {
+                Some(val) => quote! { field_sets::#val },
+                None => quote! { () },
+            };
+            let field_set_name_out = match field_set_name_out {
+                Some(val) => quote! { field_sets::#val },
+                None => quote! { () },
+            };
+            (
+                quote! { ::device_driver::CommandOperation::<'_, I, #address_type, #field_set_name_in, #field_set_name_out>  },
+                quote! { address as #address_type },
+                quote! {},
+            )
+        }
+        BlockMethodType::Buffer {
+            access,
+            address_type,
+            frame_field_set,
+            length_prefix_field_set,
+            max_size: _,
+        } => {
+            let frame = frame_field_set
+                .as_ref()
+                .map(|frame| quote! { , field_sets::#frame });
+            // The prefix comes after the frame, so the frame must be filled in with the default if there's none
+            let frame_and_prefix = match length_prefix_field_set {
+                Some(prefix) => {
+                    let frame = frame.unwrap_or_else(|| quote! { , () });
+                    quote! { #frame, field_sets::#prefix }
+                }
+                None => quote! { #frame },
+            };
+
+            (
+                quote! { ::device_driver::BufferOperation::<'_, I, #address_type, ::device_driver::#access #frame_and_prefix>  },
+                quote! { address as #address_type },
+                quote! {},
+            )
+        }
+    };
+
+    let (index_param, address_calc, index_doc) = match kind {
+        BlockMethodKind::Normal => (None, quote! { self.base_address + #address }, None),
+        BlockMethodKind::Repeated { count, stride } => {
+            let doc = format!("Valid index range: 0..{count}");
+
+            let stride = stride.to_string().parse::<i64>().unwrap();
+
+            let operator = if stride.is_negative() {
+                quote! { - }
+            } else {
+                quote! { + }
+            };
+
+            let stride = Literal::u64_unsuffixed(stride.unsigned_abs());
+
+            (
+                Some(quote! { index: usize, }),
+                quote! { {
+                    assert!(index < #count);
+                    self.base_address + #address #operator index as #internal_address_type * #stride
+                } },
+                Some(quote! {
+                    #[doc = ""]
+                    #[doc = #doc]
+                }),
+            )
+        }
+    };
+
+    // Configure the operation further where needed
+    let builder_call = match method_type {
+        BlockMethodType::Buffer {
+            max_size: Some(max_size),
+            ..
+        } => Some(quote! { .with_max_size(#max_size) }),
+        _ => None,
+    };
+
+    quote! {
+        #doc_attr
+        #index_doc
+        #cfg_attr
+        #visibility fn #name(&mut self, #index_param) -> #return_type {
+            let address = #address_calc;
+            #return_type::new(#interface_arg, #address_arg #default_arg) #builder_call
+        }
+    }
+}
+
+/// The error of the generated functions that read registers.
+/// With bus stuck detection, the reads fail with a `BusStuckError` that wraps the interface error.
+pub(super) fn read_error(detect_bus_stuck: bool, interface_error: TokenStream) -> TokenStream {
+    match detect_bus_stuck {
+        true => quote! { ::device_driver::BusStuckError<#interface_error> },
+        false => interface_error,
+    }
+}
+
+/// The name of the field on the root block that holds the shadow of a register
+fn shadow_field_name(register_name: &Ident) -> Ident {
+    format_ident!("{register_name}_shadow")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use proc_macro2::Literal;
+    use quote::format_ident;
+
+    #[test]
+    fn root_block_correct() {
+        let output = generate_block(
+            &Block {
+                cfg_attr: quote! { #[cfg(unix)] },
+                doc_attr: quote! { #[doc = "Hello!"] },
+                root: true,
+                name: format_ident!("RootBlock"),
+                methods: vec![BlockMethod {
+                    cfg_attr: quote! { #[cfg(unix)] },
+                    doc_attr: quote! { #[doc = "42 is the answer"] },
+                    name: format_ident!("my_register1"),
+                    address: Literal::i64_unsuffixed(5),
+                    allow_address_overlap: false,
+                    kind: BlockMethodKind::Normal,
+                    method_type: BlockMethodType::Register {
+                        field_set_name: format_ident!("MyRegister"),
+                        access: crate::mir::Access::RW,
+                        address_type: format_ident!("u8"),
+                        reset_value_function: format_ident!("new"),
+                        span_offsets: Vec::new(),
+                        shadowed: false,
+                    },
+                }],
+            },
+            &format_ident!("u8"),
+            &format_ident!("u8"),
+            None,
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                ///Hello!
+                #[cfg(unix)]
+                #[derive(Debug)]
+                pub struct RootBlock<I> {
+                    pub(crate) interface: I,
+                    #[doc(hidden)]
+                    base_address: u8,
+                }
+                #[cfg(unix)]
+                impl<I> RootBlock<I> {
+                    /// Create a new instance of the block based on device interface
+                    pub const fn new(interface: I) -> Self {
+                        Self { interface, base_address: 0 }
+                    }
+                    /// A reference to the interface used to communicate with the device
+                    pub(crate) fn interface(&mut self) -> &mut I {
+                        &mut self.interface
+                    }
+                    /// Read all readable register values in this block from the device.
+                    /// The callback is called for each of them.
+                    /// Any registers in child blocks are not included.
+                    ///
+                    /// The callback has three arguments:
+                    ///
+                    /// - The address of the register
+                    /// - The name of the register (with index for repeated registers)
+                    /// - The read value from the register
+                    ///
+                    /// This is useful for e.g. debug printing all values.
+                    /// The given [field_sets::FieldSetValue] has a Debug and Format implementation that forwards to the concrete type
+                    /// the lies within so it can be printed without matching on it.
+                    pub fn read_all_registers(
+                        &mut self,
+                        mut callback: impl FnMut(u8, &'static str, field_sets::FieldSetValue),
+                    ) -> Result<(), I::Error>
+                    where
+                        I: ::device_driver::RegisterInterface<AddressType = u8>,
+                    {
+                        #[cfg(unix)]
+                        let reg = self.my_register1().read()?;
+                        #[cfg(unix)] callback(5 + 0 * 0, \"my_register1\", reg.into());
+                        Ok(())
+                    }
+                    /// Read all readable register values in this block from the device.
+                    /// The callback is called for each of them.
+                    /// Any registers in child blocks are not included.
+                    ///
+                    /// The callback has three arguments:
+                    ///
+                    /// - The address of the register
+                    /// - The name of the register (with index for repeated registers)
+                    /// - The read value from the register
+                    ///
+                    /// This is useful for e.g. debug printing all values.
+                    /// The given [field_sets::FieldSetValue] has a Debug and Format implementation that forwards to the concrete type
+                    /// the lies within so it can be printed without matching on it.
+                    pub async fn read_all_registers_async(
+                        &mut self,
+                        mut callback: impl FnMut(u8, &'static str, field_sets::FieldSetValue),
+                    ) -> Result<(), I::Error>
+                    where
+                        I: ::device_driver::AsyncRegisterInterface<AddressType = u8>,
+                    {
+                        #[cfg(unix)]
+                        let reg = self.my_register1().read_async().await?;
+                        #[cfg(unix)] callback(5 + 0 * 0, \"my_register1\", reg.into());
+                        Ok(())
+                    }
+                    ///42 is the answer
+                    #[cfg(unix)]
+                    pub fn my_register1(
+                        &mut self,
+                    ) -> ::device_driver::RegisterOperation<
+                        '_,
+                        I,
+                        u8,
+                        field_sets::MyRegister,
+                        ::device_driver::RW,
+                    > {
+                        let address = self.base_address + 5;
+                        ::device_driver::RegisterOperation::<
+                            '_,
+                            I,
+                            u8,
+                            field_sets::MyRegister,
+                            ::device_driver::RW,
+                        >::new(self.interface(), address as u8, field_sets::MyRegister::new)
+                    }
+                }
+            "}
+        )
+    }
+
+    #[test]
+    fn non_root_block_correct() {
+        let output = generate_block(
+            &Block {
+                cfg_attr: quote! { #[cfg(unix)] },
+                doc_attr: quote! { #[doc = "Hello!"] },
+                root: false,
+                name: format_ident!("AnyBlock"),
+                methods: vec![BlockMethod {
+                    cfg_attr: quote! { #[cfg(unix)] },
+                    doc_attr: quote! { #[doc = "42 is the answer"] },
+                    name: format_ident!("my_buffer"),
+                    address: Literal::i64_unsuffixed(5),
+                    allow_address_overlap: false,
+                    kind: BlockMethodKind::Repeated {
+                        count: Literal::i64_unsuffixed(4),
+                        stride: Literal::i64_unsuffixed(1),
+                    },
+                    method_type: BlockMethodType::Buffer {
+                        access: crate::mir::Access::RO,
+                        address_type: format_ident!("i16"),
+                        frame_field_set: None,
+                        length_prefix_field_set: None,
+                        max_size: None,
+                    },
+                }],
+            },
+            &format_ident!("u8"),
+            &format_ident!("u8"),
+            None,
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                ///Hello!
+                #[cfg(unix)]
+                #[derive(Debug)]
+                pub struct AnyBlock<'i, I> {
+                    pub(crate) interface: &'i mut I,
+                    #[doc(hidden)]
+                    base_address: u8,
+                }
+                #[cfg(unix)]
+                impl<'i, I> AnyBlock<'i, I> {
+                    /// Create a new instance of the block based on device interface
+                    #[doc(hidden)]
+                    fn new(interface: &'i mut I, base_address: u8) -> Self {
+                        Self {
+                            interface,
+                            base_address: base_address,
+                        }
+                    }
+                    /// A reference to the interface used to communicate with the device
+                    pub(crate) fn interface(&mut self) -> &mut I {
+                        self.interface
+                    }
+                    /// Read all readable register values in this block from the device.
+                    /// The callback is called for each of them.
+                    /// Any registers in child blocks are not included.
+                    ///
+                    /// The callback has three arguments:
+                    ///
+                    /// - The address of the register
                     /// - The name of the register (with index for repeated registers)
                     /// - The read value from the register
                     ///
@@ -328,184 +1750,785 @@ mod tests {
                     /// the lies within so it can be printed without matching on it.
                     pub fn read_all_registers(
                         &mut self,
-                        mut callback: impl FnMut(u8, &'static str, field_sets::FieldSetValue),
-                    ) -> Result<(), I::Error>
+                        mut callback: impl FnMut(u8, &'static str, field_sets::FieldSetValue),
+                    ) -> Result<(), I::Error>
+                    where
+                        I: ::device_driver::RegisterInterface<AddressType = u8>,
+                    {
+                        Ok(())
+                    }
+                    /// Read all readable register values in this block from the device.
+                    /// The callback is called for each of them.
+                    /// Any registers in child blocks are not included.
+                    ///
+                    /// The callback has three arguments:
+                    ///
+                    /// - The address of the register
+                    /// - The name of the register (with index for repeated registers)
+                    /// - The read value from the register
+                    ///
+                    /// This is useful for e.g. debug printing all values.
+                    /// The given [field_sets::FieldSetValue] has a Debug and Format implementation that forwards to the concrete type
+                    /// the lies within so it can be printed without matching on it.
+                    pub async fn read_all_registers_async(
+                        &mut self,
+                        mut callback: impl FnMut(u8, &'static str, field_sets::FieldSetValue),
+                    ) -> Result<(), I::Error>
+                    where
+                        I: ::device_driver::AsyncRegisterInterface<AddressType = u8>,
+                    {
+                        Ok(())
+                    }
+                    ///42 is the answer
+                    ///
+                    ///Valid index range: 0..4
+                    #[cfg(unix)]
+                    pub fn my_buffer(
+                        &mut self,
+                        index: usize,
+                    ) -> ::device_driver::BufferOperation<'_, I, i16, ::device_driver::RO> {
+                        let address = {
+                            assert!(index < 4);
+                            self.base_address + 5 + index as u8 * 1
+                        };
+                        ::device_driver::BufferOperation::<
+                            '_,
+                            I,
+                            i16,
+                            ::device_driver::RO,
+                        >::new(self.interface(), address as i16)
+                    }
+                }
+            "}
+        )
+    }
+
+    #[test]
+    fn defined_address_check_correct() {
+        let root_block = Block {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            root: true,
+            name: format_ident!("RootBlock"),
+            methods: Vec::new(),
+        };
+
+        let output = generate_defined_address_check(
+            &root_block,
+            &format_ident!("i16"),
+            &[-4..=-3, 0..=2, 5..=5],
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                impl<I> RootBlock<I> {
+                    /// Returns true if the given address falls within any of the registers of the device,
+                    /// including the registers in (repeated) child blocks.
+                    ///
+                    /// A register takes up one address for every address unit (a byte or a 16-bit word) of its size.
+                    pub const fn is_defined_address(address: i16) -> bool {
+                        match address {
+                            -4..=-3 | 0..=2 | 5 => true,
+                            _ => false,
+                        }
+                    }
+                }
+            "}
+        );
+
+        let output =
+            generate_defined_address_check(&root_block, &format_ident!("u8"), &[], &quote! { pub });
+
+        assert!(
+            prettyplease::unparse(&syn::parse2(output).unwrap())
+                .contains("let _ = address;\n        false")
+        );
+    }
+
+    #[test]
+    fn spanned_register_method_correct() {
+        let output = generate_method(
+            &BlockMethod {
+                cfg_attr: quote! {},
+                doc_attr: quote! { #[doc = "A 24 bit value"] },
+                name: format_ident!("my_register"),
+                address: Literal::i64_unsuffixed(0x10),
+                allow_address_overlap: false,
+                kind: BlockMethodKind::Normal,
+                method_type: BlockMethodType::Register {
+                    field_set_name: format_ident!("MyRegister"),
+                    access: crate::mir::Access::RO,
+                    address_type: format_ident!("u8"),
+                    reset_value_function: format_ident!("new"),
+                    span_offsets: vec![
+                        Literal::i64_unsuffixed(0),
+                        Literal::i64_unsuffixed(1),
+                        Literal::i64_unsuffixed(2),
+                    ],
+                    shadowed: false,
+                },
+            },
+            &format_ident!("u8"),
+            None,
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(quote! { impl Foo { #output } }).unwrap()),
+            indoc! {"
+                impl Foo {
+                    ///A 24 bit value
+                    pub fn my_register(
+                        &mut self,
+                    ) -> ::device_driver::SpannedRegisterOperation<
+                        '_,
+                        I,
+                        u8,
+                        field_sets::MyRegister,
+                        ::device_driver::RO,
+                        3,
+                    > {
+                        let address = self.base_address + 16;
+                        ::device_driver::SpannedRegisterOperation::<
+                            '_,
+                            I,
+                            u8,
+                            field_sets::MyRegister,
+                            ::device_driver::RO,
+                            3,
+                        >::new(
+                            self.interface(),
+                            [(address + 0) as u8, (address + 1) as u8, (address + 2) as u8],
+                            field_sets::MyRegister::new,
+                        )
+                    }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn read_hook_passed_to_register_operations() {
+        let register = |name: &str, span_offsets: Vec<Literal>| BlockMethod {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("{name}"),
+            address: Literal::i64_unsuffixed(0x10),
+            allow_address_overlap: false,
+            kind: BlockMethodKind::Normal,
+            method_type: BlockMethodType::Register {
+                field_set_name: format_ident!("MyRegister"),
+                access: crate::mir::Access::RW,
+                address_type: format_ident!("u8"),
+                reset_value_function: format_ident!("new"),
+                span_offsets,
+                shadowed: false,
+            },
+        };
+        let read_hook = format_ident!("DeviceReadHook");
+
+        for (method, expected_type) in [
+            (
+                register("plain", Vec::new()),
+                "::device_driver::RegisterOperation::<'_,I,u8,field_sets::MyRegister,::device_driver::RW,DeviceReadHook>",
+            ),
+            (
+                register(
+                    "spanned",
+                    vec![Literal::i64_unsuffixed(0), Literal::i64_unsuffixed(1)],
+                ),
+                "::device_driver::SpannedRegisterOperation::<'_,I,u8,field_sets::MyRegister,::device_driver::RW,2,DeviceReadHook>",
+            ),
+        ] {
+            let output = generate_method(
+                &method,
+                &format_ident!("u8"),
+                Some(&read_hook),
+                &quote! { pub },
+            );
+
+            // Compare without whitespace, since the formatting of the long types differs
+            let output = output.to_string().replace(' ', "");
+            assert!(output.contains(expected_type), "{output}");
+        }
+    }
+
+    #[test]
+    fn shadowed_register_correct() {
+        let method = BlockMethod {
+            cfg_attr: quote! {},
+            doc_attr: quote! { #[doc = "A write-only register"] },
+            name: format_ident!("my_register"),
+            address: Literal::i64_unsuffixed(0x10),
+            allow_address_overlap: false,
+            kind: BlockMethodKind::Normal,
+            method_type: BlockMethodType::Register {
+                field_set_name: format_ident!("MyRegister"),
+                access: crate::mir::Access::WO,
+                address_type: format_ident!("u8"),
+                reset_value_function: format_ident!("new"),
+                span_offsets: Vec::new(),
+                shadowed: true,
+            },
+        };
+
+        let output = generate_method(&method, &format_ident!("u8"), None, &quote! { pub });
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(quote! { impl Foo { #output } }).unwrap()),
+            indoc! {"
+                impl Foo {
+                    ///A write-only register
+                    pub fn my_register(
+                        &mut self,
+                    ) -> ::device_driver::ShadowedRegisterOperation<
+                        '_,
+                        I,
+                        u8,
+                        field_sets::MyRegister,
+                        ::device_driver::WO,
+                    > {
+                        let address = self.base_address + 16;
+                        ::device_driver::ShadowedRegisterOperation::<
+                            '_,
+                            I,
+                            u8,
+                            field_sets::MyRegister,
+                            ::device_driver::WO,
+                        >::new(
+                            &mut self.interface,
+                            address as u8,
+                            field_sets::MyRegister::new,
+                            &mut self.my_register_shadow,
+                        )
+                    }
+                }
+            "}
+        );
+
+        let output = prettyplease::unparse(
+            &syn::parse2(generate_block(
+                &Block {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    root: true,
+                    name: format_ident!("RootBlock"),
+                    methods: vec![method],
+                },
+                &format_ident!("u8"),
+                &format_ident!("u8"),
+                None,
+                &quote! { pub },
+            ))
+            .unwrap(),
+        );
+
+        assert!(output.contains(indoc! {"
+            pub struct RootBlock<I> {
+                pub(crate) interface: I,
+                #[doc(hidden)]
+                base_address: u8,
+                #[doc(hidden)]
+                my_register_shadow: field_sets::MyRegister,
+            }
+        "}));
+        assert!(output.contains("my_register_shadow: field_sets::MyRegister::new(),"));
+    }
+
+    #[test]
+    fn buffer_with_frame_method_correct() {
+        let output = generate_method(
+            &BlockMethod {
+                cfg_attr: quote! {},
+                doc_attr: quote! { #[doc = "A fifo of samples"] },
+                name: format_ident!("fifo"),
+                address: Literal::i64_unsuffixed(0x10),
+                allow_address_overlap: false,
+                kind: BlockMethodKind::Normal,
+                method_type: BlockMethodType::Buffer {
+                    access: crate::mir::Access::RO,
+                    address_type: format_ident!("u8"),
+                    frame_field_set: Some(format_ident!("Sample")),
+                    length_prefix_field_set: None,
+                    max_size: None,
+                },
+            },
+            &format_ident!("u8"),
+            None,
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(quote! { impl Foo { #output } }).unwrap()),
+            indoc! {"
+                impl Foo {
+                    ///A fifo of samples
+                    pub fn fifo(
+                        &mut self,
+                    ) -> ::device_driver::BufferOperation<
+                        '_,
+                        I,
+                        u8,
+                        ::device_driver::RO,
+                        field_sets::Sample,
+                    > {
+                        let address = self.base_address + 16;
+                        ::device_driver::BufferOperation::<
+                            '_,
+                            I,
+                            u8,
+                            ::device_driver::RO,
+                            field_sets::Sample,
+                        >::new(self.interface(), address as u8)
+                    }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn buffer_with_length_prefix_method_correct() {
+        let output = generate_method(
+            &BlockMethod {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("packets"),
+                address: Literal::i64_unsuffixed(0x20),
+                allow_address_overlap: false,
+                kind: BlockMethodKind::Normal,
+                method_type: BlockMethodType::Buffer {
+                    access: crate::mir::Access::RO,
+                    address_type: format_ident!("u8"),
+                    frame_field_set: None,
+                    length_prefix_field_set: Some(format_ident!("PacketsLength")),
+                    max_size: None,
+                },
+            },
+            &format_ident!("u8"),
+            None,
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(quote! { impl Foo { #output } }).unwrap()),
+            indoc! {"
+                impl Foo {
+                    pub fn packets(
+                        &mut self,
+                    ) -> ::device_driver::BufferOperation<
+                        '_,
+                        I,
+                        u8,
+                        ::device_driver::RO,
+                        (),
+                        field_sets::PacketsLength,
+                    > {
+                        let address = self.base_address + 32;
+                        ::device_driver::BufferOperation::<
+                            '_,
+                            I,
+                            u8,
+                            ::device_driver::RO,
+                            (),
+                            field_sets::PacketsLength,
+                        >::new(self.interface(), address as u8)
+                    }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn memory_constructor_correct() {
+        let root_block = Block {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            root: true,
+            name: format_ident!("RootBlock"),
+            methods: Vec::new(),
+        };
+
+        assert!(
+            generate_memory_constructor(&root_block, &format_ident!("u8"), &[], &quote! { pub })
+                .is_empty()
+        );
+        assert!(
+            generate_memory_constructor(
+                &root_block,
+                &format_ident!("i8"),
+                &[-2..=-1, 0..=3],
+                &quote! { pub },
+            )
+            .is_empty()
+        );
+
+        let output = generate_memory_constructor(
+            &root_block,
+            &format_ident!("u8"),
+            &[0..=2, 5..=6],
+            &quote! { pub(crate) },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                impl<I> RootBlock<I> {
+                    /// The number of bytes spanned by the registers of the device, starting at address 0.
+                    ///
+                    /// Registers are assumed to be byte-addressed, so a register takes up one address for every byte of its size.
+                    pub(crate) const DEVICE_SPAN: usize = 7;
+                }
+                impl RootBlock<::device_driver::MemoryInterface<u8, 7>> {
+                    /// Create the device on top of an in-memory interface that is seeded with the given bytes.
+                    /// The byte at index `a` is the value at address `a`.
+                    ///
+                    /// This fails when the number of bytes doesn't match [Self::DEVICE_SPAN].
+                    pub(crate) fn from_bytes(
+                        bytes: &[u8],
+                    ) -> Result<Self, ::device_driver::MemoryInterfaceError> {
+                        Ok(Self::new(::device_driver::MemoryInterface::try_from(bytes)?))
+                    }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn init_correct() {
+        let root_block = Block {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            root: true,
+            name: format_ident!("RootBlock"),
+            methods: Vec::new(),
+        };
+
+        assert!(generate_init(&root_block, &format_ident!("u8"), &[], &quote! { pub }).is_empty());
+
+        let output = generate_init(
+            &root_block,
+            &format_ident!("u8"),
+            &[ResetSequenceWrite {
+                cfg_attr: quote! { #[cfg(unix)] },
+                register_name: format_ident!("foo"),
+                field_set_name: format_ident!("Foo"),
+                value: vec![1, 2],
+            }],
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                impl<I> RootBlock<I> {
+                    /// Initialize the device by writing the reset sequence to the registers in order.
+                    /// The whole register is written with the configured value.
+                    pub fn init(&mut self) -> Result<(), I::Error>
                     where
                         I: ::device_driver::RegisterInterface<AddressType = u8>,
                     {
                         #[cfg(unix)]
-                        let reg = self.my_register1().read()?;
-                        #[cfg(unix)] callback(5 + 0 * 0, \"my_register1\", reg.into());
+                        self.foo().write_with_zero(|reg| *reg = field_sets::Foo::from([1u8, 2u8]))?;
                         Ok(())
                     }
-                    /// Read all readable register values in this block from the device.
-                    /// The callback is called for each of them.
-                    /// Any registers in child blocks are not included.
-                    ///
-                    /// The callback has three arguments:
-                    ///
-                    /// - The address of the register
-                    /// - The name of the register (with index for repeated registers)
-                    /// - The read value from the register
-                    ///
-                    /// This is useful for e.g. debug printing all values.
-                    /// The given [field_sets::FieldSetValue] has a Debug and Format implementation that forwards to the concrete type
-                    /// the lies within so it can be printed without matching on it.
-                    pub async fn read_all_registers_async(
-                        &mut self,
-                        mut callback: impl FnMut(u8, &'static str, field_sets::FieldSetValue),
-                    ) -> Result<(), I::Error>
+                    /// Initialize the device by writing the reset sequence to the registers in order.
+                    /// The whole register is written with the configured value.
+                    pub async fn init_async(&mut self) -> Result<(), I::Error>
                     where
                         I: ::device_driver::AsyncRegisterInterface<AddressType = u8>,
                     {
                         #[cfg(unix)]
-                        let reg = self.my_register1().read_async().await?;
-                        #[cfg(unix)] callback(5 + 0 * 0, \"my_register1\", reg.into());
+                        self.foo()
+                            .write_with_zero_async(|reg| *reg = field_sets::Foo::from([1u8, 2u8]))
+                            .await?;
                         Ok(())
                     }
-                    ///42 is the answer
-                    #[cfg(unix)]
-                    pub fn my_register1(
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn flat_accessors_correct() {
+        let root_block = Block {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            root: true,
+            name: format_ident!("Device"),
+            methods: vec![BlockMethod {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("foo"),
+                address: Literal::i64_unsuffixed(0),
+                allow_address_overlap: false,
+                kind: BlockMethodKind::Normal,
+                method_type: BlockMethodType::Register {
+                    field_set_name: format_ident!("Foo"),
+                    access: crate::mir::Access::RW,
+                    address_type: format_ident!("u8"),
+                    reset_value_function: format_ident!("new"),
+                    span_offsets: Vec::new(),
+                    shadowed: false,
+                },
+            }],
+        };
+
+        let output = generate_flat_accessors(
+            &root_block,
+            &[FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("Foo"),
+                byte_order: crate::mir::ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![crate::lir::Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("value"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: crate::mir::BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(8),
+                    base_type: format_ident!("u8"),
+                    conversion_method: crate::lir::FieldConversionMethod::None,
+                    access: crate::mir::Access::RW,
+                }],
+            }],
+            &format_ident!("u8"),
+            true,
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                impl<I> Device<I> {
+                    ///Read the `foo` register and get its `value` field
+                    pub fn foo_value(&mut self) -> Result<u8, ::device_driver::BusStuckError<I::Error>>
+                    where
+                        I: ::device_driver::RegisterInterface<AddressType = u8>,
+                    {
+                        Ok(self.foo().read()?.value())
+                    }
+                    ///Read the `foo` register and get its `value` field
+                    pub async fn foo_value_async(
                         &mut self,
-                    ) -> ::device_driver::RegisterOperation<
-                        '_,
-                        I,
-                        u8,
-                        field_sets::MyRegister,
-                        ::device_driver::RW,
-                    > {
-                        let address = self.base_address + 5;
-                        ::device_driver::RegisterOperation::<
-                            '_,
-                            I,
-                            u8,
-                            field_sets::MyRegister,
-                            ::device_driver::RW,
-                        >::new(self.interface(), address as u8, field_sets::MyRegister::new)
+                    ) -> Result<u8, ::device_driver::BusStuckError<I::Error>>
+                    where
+                        I: ::device_driver::AsyncRegisterInterface<AddressType = u8>,
+                    {
+                        Ok(self.foo().read_async().await?.value())
+                    }
+                    ///Set the `value` field of the `foo` register with a read-modify-write
+                    pub fn set_foo_value(
+                        &mut self,
+                        value: u8,
+                    ) -> Result<(), ::device_driver::BusStuckError<I::Error>>
+                    where
+                        I: ::device_driver::RegisterInterface<AddressType = u8>,
+                    {
+                        self.foo().modify(|reg| reg.set_value(value))
+                    }
+                    ///Set the `value` field of the `foo` register with a read-modify-write
+                    pub async fn set_foo_value_async(
+                        &mut self,
+                        value: u8,
+                    ) -> Result<(), ::device_driver::BusStuckError<I::Error>>
+                    where
+                        I: ::device_driver::AsyncRegisterInterface<AddressType = u8>,
+                    {
+                        self.foo().modify_async(|reg| reg.set_value(value)).await
                     }
                 }
             "}
-        )
+        );
     }
 
     #[test]
-    fn non_root_block_correct() {
-        let output = generate_block(
-            &Block {
-                cfg_attr: quote! { #[cfg(unix)] },
-                doc_attr: quote! { #[doc = "Hello!"] },
-                root: false,
-                name: format_ident!("AnyBlock"),
-                methods: vec![BlockMethod {
-                    cfg_attr: quote! { #[cfg(unix)] },
-                    doc_attr: quote! { #[doc = "42 is the answer"] },
-                    name: format_ident!("my_buffer"),
-                    address: Literal::i64_unsuffixed(5),
-                    allow_address_overlap: false,
-                    kind: BlockMethodKind::Repeated {
-                        count: Literal::i64_unsuffixed(4),
-                        stride: Literal::i64_unsuffixed(1),
+    fn snapshot_correct() {
+        let root_block = Block {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            root: true,
+            name: format_ident!("RootBlock"),
+            methods: Vec::new(),
+        };
+
+        let output = generate_snapshot(
+            &root_block,
+            &format_ident!("u8"),
+            &Snapshot {
+                name: format_ident!("RootBlockSnapshot"),
+                registers: vec![
+                    SnapshotRegister {
+                        cfg_attr: quote! { #[cfg(unix)] },
+                        name: format_ident!("foo"),
+                        field_set_name: format_ident!("Foo"),
+                        repeat_count: None,
                     },
-                    method_type: BlockMethodType::Buffer {
-                        access: crate::mir::Access::RO,
-                        address_type: format_ident!("i16"),
+                    SnapshotRegister {
+                        cfg_attr: quote! {},
+                        name: format_ident!("bar"),
+                        field_set_name: format_ident!("Bar"),
+                        repeat_count: Some(2),
                     },
-                }],
+                ],
             },
-            &format_ident!("u8"),
-            &format_ident!("u8"),
+            Some("defmt-03"),
+            false,
+            &quote! { pub },
         );
 
         pretty_assertions::assert_eq!(
             prettyplease::unparse(&syn::parse2(output).unwrap()),
             indoc! {"
-                ///Hello!
-                #[cfg(unix)]
-                #[derive(Debug)]
-                pub struct AnyBlock<'i, I> {
-                    pub(crate) interface: &'i mut I,
-                    #[doc(hidden)]
-                    base_address: u8,
+                ///The values of all readable registers in the root block of [RootBlock], created with [RootBlock::snapshot]
+                #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+                #[cfg_attr(feature = \"defmt-03\", derive(defmt::Format))]
+                pub struct RootBlockSnapshot {
+                    ///The value of the `foo` register
+                    #[cfg(unix)]
+                    pub foo: field_sets::Foo,
+                    ///The value of the `bar` register
+                    pub bar: [field_sets::Bar; 2],
                 }
-                #[cfg(unix)]
-                impl<'i, I> AnyBlock<'i, I> {
-                    /// Create a new instance of the block based on device interface
-                    #[doc(hidden)]
-                    fn new(interface: &'i mut I, base_address: u8) -> Self {
-                        Self {
-                            interface,
-                            base_address: base_address,
-                        }
-                    }
-                    /// A reference to the interface used to communicate with the device
-                    pub(crate) fn interface(&mut self) -> &mut I {
-                        self.interface
-                    }
-                    /// Read all readable register values in this block from the device.
-                    /// The callback is called for each of them.
+                impl<I> RootBlock<I> {
+                    /// Read all readable registers in the root block once and return their values.
                     /// Any registers in child blocks are not included.
                     ///
-                    /// The callback has three arguments:
-                    ///
-                    /// - The address of the register
-                    /// - The name of the register (with index for repeated registers)
-                    /// - The read value from the register
-                    ///
-                    /// This is useful for e.g. debug printing all values.
-                    /// The given [field_sets::FieldSetValue] has a Debug and Format implementation that forwards to the concrete type
-                    /// the lies within so it can be printed without matching on it.
-                    pub fn read_all_registers(
-                        &mut self,
-                        mut callback: impl FnMut(u8, &'static str, field_sets::FieldSetValue),
-                    ) -> Result<(), I::Error>
+                    /// This is useful for e.g. diagnostics and bug reports.
+                    pub fn snapshot(&mut self) -> Result<RootBlockSnapshot, I::Error>
                     where
                         I: ::device_driver::RegisterInterface<AddressType = u8>,
                     {
-                        Ok(())
+                        Ok(RootBlockSnapshot {
+                            #[cfg(unix)]
+                            foo: self.foo().read()?,
+                            bar: [self.bar(0).read()?, self.bar(1).read()?],
+                        })
                     }
-                    /// Read all readable register values in this block from the device.
-                    /// The callback is called for each of them.
+                    /// Read all readable registers in the root block once and return their values.
                     /// Any registers in child blocks are not included.
                     ///
-                    /// The callback has three arguments:
-                    ///
-                    /// - The address of the register
-                    /// - The name of the register (with index for repeated registers)
-                    /// - The read value from the register
-                    ///
-                    /// This is useful for e.g. debug printing all values.
-                    /// The given [field_sets::FieldSetValue] has a Debug and Format implementation that forwards to the concrete type
-                    /// the lies within so it can be printed without matching on it.
-                    pub async fn read_all_registers_async(
-                        &mut self,
-                        mut callback: impl FnMut(u8, &'static str, field_sets::FieldSetValue),
-                    ) -> Result<(), I::Error>
+                    /// This is useful for e.g. diagnostics and bug reports.
+                    pub async fn snapshot_async(&mut self) -> Result<RootBlockSnapshot, I::Error>
                     where
                         I: ::device_driver::AsyncRegisterInterface<AddressType = u8>,
                     {
-                        Ok(())
+                        Ok(RootBlockSnapshot {
+                            #[cfg(unix)]
+                            foo: self.foo().read_async().await?,
+                            bar: [self.bar(0).read_async().await?, self.bar(1).read_async().await?],
+                        })
                     }
-                    ///42 is the answer
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn mmio_offsets_match_addresses() {
+        let output = generate_mmio(
+            &Block {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                root: true,
+                name: format_ident!("Device"),
+                methods: Vec::new(),
+            },
+            Some(&Mmio {
+                name: format_ident!("DeviceMmio"),
+                fields: vec![
+                    MmioField {
+                        name: format_ident!("foo"),
+                        offset: Literal::i64_unsuffixed(0),
+                        size_bytes: Literal::i64_unsuffixed(2),
+                        register: Some(MmioRegister {
+                            cfg_attr: quote! {},
+                            doc_attr: quote! { #[doc = "Foo"] },
+                            field_set_name: format_ident!("Foo"),
+                            access: mir::Access::RW,
+                            reset_value_function: format_ident!("new"),
+                        }),
+                    },
+                    MmioField {
+                        name: format_ident!("_reserved_2"),
+                        offset: Literal::i64_unsuffixed(2),
+                        size_bytes: Literal::i64_unsuffixed(2),
+                        register: None,
+                    },
+                    MmioField {
+                        name: format_ident!("bar"),
+                        offset: Literal::i64_unsuffixed(4),
+                        size_bytes: Literal::i64_unsuffixed(1),
+                        register: Some(MmioRegister {
+                            cfg_attr: quote! { #[cfg(unix)] },
+                            doc_attr: quote! {},
+                            field_set_name: format_ident!("Bar"),
+                            access: mir::Access::RO,
+                            reset_value_function: format_ident!("new"),
+                        }),
+                    },
+                ],
+            }),
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                ///The registers in the root block of [Device] laid out at their addresses, to be placed over the memory-mapped registers of the device.
+                ///
+                /// The registers are accessed with volatile reads and writes.
+                /// Any registers in child blocks and registers that are repeated or span multiple addresses are not included.
+                #[repr(C)]
+                pub struct DeviceMmio {
+                    foo: ::core::cell::UnsafeCell<[u8; 2]>,
+                    _reserved_2: ::core::cell::UnsafeCell<[u8; 2]>,
+                    bar: ::core::cell::UnsafeCell<[u8; 1]>,
+                }
+                const _: () = {
+                    assert!(::core::mem::offset_of!(DeviceMmio, foo) == 0);
+                    assert!(::core::mem::offset_of!(DeviceMmio, _reserved_2) == 2);
+                    assert!(::core::mem::offset_of!(DeviceMmio, bar) == 4);
+                };
+                impl DeviceMmio {
+                    /// Get the registers located at the given pointer.
                     ///
-                    ///Valid index range: 0..4
+                    /// # Safety
+                    ///
+                    /// The pointer must point to the memory-mapped registers of the device and stay valid for the lifetime `'a`.
+                    pub const unsafe fn from_ptr<'a>(ptr: *mut Self) -> &'a Self {
+                        unsafe { &*ptr }
+                    }
+                    ///Foo
+                    pub fn foo(
+                        &self,
+                    ) -> ::device_driver::MmioRegisterOperation<
+                        '_,
+                        field_sets::Foo,
+                        ::device_driver::RW,
+                    > {
+                        ::device_driver::MmioRegisterOperation::new(&self.foo, field_sets::Foo::new)
+                    }
                     #[cfg(unix)]
-                    pub fn my_buffer(
-                        &mut self,
-                        index: usize,
-                    ) -> ::device_driver::BufferOperation<'_, I, i16, ::device_driver::RO> {
-                        let address = {
-                            assert!(index < 4);
-                            self.base_address + 5 + index as u8 * 1
-                        };
-                        ::device_driver::BufferOperation::<
-                            '_,
-                            I,
-                            i16,
-                            ::device_driver::RO,
-                        >::new(self.interface(), address as i16)
+                    pub fn bar(
+                        &self,
+                    ) -> ::device_driver::MmioRegisterOperation<
+                        '_,
+                        field_sets::Bar,
+                        ::device_driver::RO,
+                    > {
+                        ::device_driver::MmioRegisterOperation::new(&self.bar, field_sets::Bar::new)
                     }
                 }
             "}
-        )
+        );
     }
 }
@@ -0,0 +1,60 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::lir::CommandEcho;
+
+/// Generate the `From` impls from the in to the out field set of the commands that echo their input.
+/// The conversion goes through the bytes, so it also works when the field sets are in different modules.
+pub fn generate_command_echoes(command_echoes: &[CommandEcho]) -> TokenStream {
+    command_echoes
+        .iter()
+        .map(|command_echo| {
+            let CommandEcho {
+                cfg_attr,
+                field_set_name_in,
+                field_set_name_out,
+                size_bytes,
+            } = command_echo;
+
+            quote! {
+                #cfg_attr
+                impl From<#field_set_name_in> for #field_set_name_out {
+                    fn from(value: #field_set_name_in) -> Self {
+                        Self::from(<[u8; #size_bytes]>::from(value))
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use proc_macro2::Literal;
+    use quote::format_ident;
+
+    use super::*;
+
+    #[test]
+    fn command_echoes_correct() {
+        let output = generate_command_echoes(&[CommandEcho {
+            cfg_attr: quote! { #[cfg(unix)] },
+            field_set_name_in: format_ident!("EchoFieldsIn"),
+            field_set_name_out: format_ident!("EchoFieldsOut"),
+            size_bytes: Literal::usize_unsuffixed(2),
+        }]);
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                #[cfg(unix)]
+                impl From<EchoFieldsIn> for EchoFieldsOut {
+                    fn from(value: EchoFieldsIn) -> Self {
+                        Self::from(<[u8; 2]>::from(value))
+                    }
+                }
+            "}
+        );
+    }
+}
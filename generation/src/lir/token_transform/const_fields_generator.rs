@@ -0,0 +1,170 @@
+use convert_case::Casing;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{
+    lir::{FieldConversionMethod, FieldSet},
+    mir::Access,
+};
+
+use super::{field_set_transform::get_field_return_type, nested_visibility};
+
+/// Generate the `const_fields` module with a free const function per field that has a const getter.
+/// They take the raw bits of the field set, so the value of a field can be computed in a const context.
+pub fn generate_const_fields(field_sets: &[FieldSet], visibility: &TokenStream) -> TokenStream {
+    // The functions are in a module per field set inside the `const_fields` module
+    let visibility = &nested_visibility(visibility, 2);
+
+    let modules = field_sets
+        .iter()
+        .filter(|fs| fs.size_bits > 0)
+        .filter_map(|fs| {
+            let name = &fs.name;
+            let cfg_attr = &fs.cfg_attr;
+            let module_name = format_ident!("{}", name.to_string().to_case(convert_case::Case::Snake));
+            let size_bytes = proc_macro2::Literal::u32_unsuffixed(fs.size_bits.div_ceil(8));
+
+            let functions = fs
+                .fields
+                .iter()
+                .filter(|field| matches!(field.access, Access::RW | Access::RO | Access::RC))
+                .filter(|field| {
+                    matches!(
+                        field.conversion_method,
+                        FieldConversionMethod::None | FieldConversionMethod::Bool
+                    )
+                })
+                .map(|field| {
+                    let field_name = &field.name;
+                    let field_cfg_attr = &field.cfg_attr;
+                    let return_type = get_field_return_type(field, &quote! {});
+                    let doc = format!("Get the `{field_name}` field from the raw bits of [{name}](super::super::{name})");
+
+                    quote! {
+                        #[doc = #doc]
+                        #field_cfg_attr
+                        #visibility const fn #field_name(bits: [u8; #size_bytes]) -> #return_type {
+                            super::super::#name::from_bits(bits).#field_name()
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            if functions.is_empty() {
+                return None;
+            }
+
+            let doc = format!("The const field getters of [{name}](super::super::{name})");
+
+            Some(quote! {
+                #[doc = #doc]
+                #cfg_attr
+                pub mod #module_name {
+                    #(#functions)*
+                }
+            })
+        });
+
+    quote! {
+        /// Module containing const functions that get the value of a field from the raw bits of a field set.
+        ///
+        /// This can be used in const contexts, e.g. to check a configuration at compile time.
+        pub mod const_fields {
+            #(#modules)*
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Literal;
+
+    use super::*;
+    use crate::{
+        lir::Field,
+        mir::{BitOrder, ByteOrder},
+    };
+
+    #[test]
+    fn const_fields_correct() {
+        let field = |name: &str, base_type: &str, conversion_method, access| Field {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("{name}"),
+            renamed_from: Vec::new(),
+            unit: None,
+            valid_when: None,
+            bit_order: BitOrder::LSB0,
+            address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(8),
+            base_type: format_ident!("{base_type}"),
+            conversion_method,
+            access,
+        };
+
+        let output = generate_const_fields(
+            &[
+                FieldSet {
+                    cfg_attr: quote! { #[cfg(unix)] },
+                    doc_attr: quote! {},
+                    name: format_ident!("MyReg"),
+                    byte_order: ByteOrder::LE,
+                    size_bits: 16,
+                    reset_value: vec![0, 0],
+                    ref_reset_overrides: Vec::new(),
+                    chip_variant_reset_values: Vec::new(),
+                    fields: vec![
+                        field("value", "u8", FieldConversionMethod::None, Access::RW),
+                        field("flag", "u8", FieldConversionMethod::Bool, Access::RO),
+                        field(
+                            "mode",
+                            "u8",
+                            FieldConversionMethod::Into(quote! { Mode }),
+                            Access::RW,
+                        ),
+                        field("command", "u8", FieldConversionMethod::None, Access::WO),
+                    ],
+                },
+                FieldSet {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("OnlyEnum"),
+                    byte_order: ByteOrder::LE,
+                    size_bits: 8,
+                    reset_value: vec![0],
+                    ref_reset_overrides: Vec::new(),
+                    chip_variant_reset_values: Vec::new(),
+                    fields: vec![field(
+                        "mode",
+                        "u8",
+                        FieldConversionMethod::Into(quote! { Mode }),
+                        Access::RW,
+                    )],
+                },
+            ],
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc::indoc! {"
+                /// Module containing const functions that get the value of a field from the raw bits of a field set.
+                ///
+                /// This can be used in const contexts, e.g. to check a configuration at compile time.
+                pub mod const_fields {
+                    ///The const field getters of [MyReg](super::super::MyReg)
+                    #[cfg(unix)]
+                    pub mod my_reg {
+                        ///Get the `value` field from the raw bits of [MyReg](super::super::MyReg)
+                        pub const fn value(bits: [u8; 2]) -> u8 {
+                            super::super::MyReg::from_bits(bits).value()
+                        }
+                        ///Get the `flag` field from the raw bits of [MyReg](super::super::MyReg)
+                        pub const fn flag(bits: [u8; 2]) -> bool {
+                            super::super::MyReg::from_bits(bits).flag()
+                        }
+                    }
+                }
+            "}
+        );
+    }
+}
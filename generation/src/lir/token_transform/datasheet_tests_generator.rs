@@ -0,0 +1,150 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::lir::RegisterExpectation;
+
+/// Generate a test module with a test for every address and size of a register that is given by the datasheet.
+/// This catches transcription errors, like a register that got placed at the wrong address.
+pub fn generate_datasheet_tests(
+    cfg_attr: &TokenStream,
+    expectations: &[RegisterExpectation],
+) -> TokenStream {
+    if expectations.is_empty() {
+        return TokenStream::new();
+    }
+
+    let tests = expectations.iter().map(|expectation| {
+        let RegisterExpectation {
+            cfg_attr,
+            register_name,
+            field_set_name,
+            address,
+            size_bits,
+        } = expectation;
+
+        let address_test = address.as_ref().map(|(computed, expected)| {
+            let test_name = format_ident!("{register_name}_address_matches_datasheet");
+            let message = format!("The address of register `{field_set_name}` doesn't match the datasheet");
+
+            quote! {
+                #[test]
+                #cfg_attr
+                fn #test_name() {
+                    let computed = #computed;
+                    let expected = #expected;
+                    assert_eq!(computed, expected, #message);
+                }
+            }
+        });
+
+        let size_test = size_bits.as_ref().map(|expected| {
+            let test_name = format_ident!("{register_name}_size_matches_datasheet");
+            let message = format!("The size of register `{field_set_name}` doesn't match the datasheet");
+
+            quote! {
+                #[test]
+                #cfg_attr
+                fn #test_name() {
+                    let computed = <field_sets::#field_set_name as ::device_driver::FieldSet>::SIZE_BITS;
+                    let expected = #expected;
+                    assert_eq!(computed, expected, #message);
+                }
+            }
+        });
+
+        quote! {
+            #address_test
+            #size_test
+        }
+    });
+
+    quote! {
+        /// Tests that check the registers against the values given by the datasheet
+        #[cfg(test)]
+        #cfg_attr
+        mod datasheet_tests {
+            use super::*;
+
+            #(#tests)*
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use proc_macro2::Literal;
+
+    use super::*;
+
+    #[test]
+    fn datasheet_tests_correct() {
+        let output = generate_datasheet_tests(
+            &quote! {},
+            &[
+                RegisterExpectation {
+                    cfg_attr: quote! { #[cfg(unix)] },
+                    register_name: format_ident!("foo"),
+                    field_set_name: format_ident!("Foo"),
+                    address: Some((Literal::i64_suffixed(18), Literal::i64_suffixed(16))),
+                    size_bits: Some(Literal::u32_unsuffixed(8)),
+                },
+                RegisterExpectation {
+                    cfg_attr: quote! {},
+                    register_name: format_ident!("bar"),
+                    field_set_name: format_ident!("Bar"),
+                    address: None,
+                    size_bits: Some(Literal::u32_unsuffixed(16)),
+                },
+            ],
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                /// Tests that check the registers against the values given by the datasheet
+                #[cfg(test)]
+                mod datasheet_tests {
+                    use super::*;
+                    #[test]
+                    #[cfg(unix)]
+                    fn foo_address_matches_datasheet() {
+                        let computed = 18i64;
+                        let expected = 16i64;
+                        assert_eq!(
+                            computed,
+                            expected,
+                            \"The address of register `Foo` doesn't match the datasheet\",
+                        );
+                    }
+                    #[test]
+                    #[cfg(unix)]
+                    fn foo_size_matches_datasheet() {
+                        let computed = <field_sets::Foo as ::device_driver::FieldSet>::SIZE_BITS;
+                        let expected = 8;
+                        assert_eq!(
+                            computed,
+                            expected,
+                            \"The size of register `Foo` doesn't match the datasheet\",
+                        );
+                    }
+                    #[test]
+                    fn bar_size_matches_datasheet() {
+                        let computed = <field_sets::Bar as ::device_driver::FieldSet>::SIZE_BITS;
+                        let expected = 16;
+                        assert_eq!(
+                            computed,
+                            expected,
+                            \"The size of register `Bar` doesn't match the datasheet\",
+                        );
+                    }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn no_expectations_no_tests() {
+        assert!(generate_datasheet_tests(&quote! {}, &[]).is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::quote;
+
+use crate::lir::DecodableRegister;
+
+/// Generate the `decode_any` function that decodes the bytes of any register given its address.
+/// This is a single entry point into all field sets, which is useful for fuzzing or for a protocol analyzer.
+pub fn generate_decode_any(
+    registers: &[DecodableRegister],
+    register_address_type: &Ident,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let arms = registers.iter().map(|register| {
+        let DecodableRegister {
+            cfg_attr,
+            field_set_name,
+            address,
+            size_bytes,
+        } = register;
+
+        let address_literal = Literal::u64_unsuffixed(address.unsigned_abs());
+        let address = if address.is_negative() {
+            quote! { -#address_literal }
+        } else {
+            quote! { #address_literal }
+        };
+
+        quote! {
+            #cfg_attr
+            #address => <[u8; #size_bytes]>::try_from(bytes)
+                .ok()
+                .map(|bits| FieldSetValue::#field_set_name(#field_set_name::from(bits)))
+        }
+    });
+
+    // Without registers the bytes are never looked at
+    let ignore_bytes = registers.is_empty().then(|| quote! { let _ = bytes; });
+
+    quote! {
+        /// Decode the bytes of the register at the given address into its field set.
+        ///
+        /// Returns `None` when there's no register at the address or when the amount of bytes doesn't match the size of the register.
+        /// When multiple registers share an address, the first one that is defined is used.
+        #[allow(unreachable_patterns)]
+        #visibility fn decode_any(address: #register_address_type, bytes: &[u8]) -> Option<FieldSetValue> {
+            #ignore_bytes
+            match address {
+                #(#arms,)*
+                _ => None,
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::quote;
+
+use super::block_transform::read_error;
+use crate::lir::{Block, DynAccessField, DynAccessRegister};
+
+/// Generate the `read_field` and `write_field` functions on the root block that access the fields of the registers by name.
+/// This is useful for scripting or a REPL, where the register and field aren't known at compile time.
+pub fn generate_dyn_access(
+    root_block: &Block,
+    register_address_type: &Ident,
+    registers: Option<&[DynAccessRegister]>,
+    detect_bus_stuck: bool,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Some(registers) = registers else {
+        return TokenStream::new();
+    };
+
+    let Block { cfg_attr, name, .. } = root_block;
+    let read_error = read_error(detect_bus_stuck, quote! { I::Error });
+
+    let read_arms = get_read_arms(registers, false);
+    let read_arms_async = get_read_arms(registers, true);
+    let write_arms = get_write_arms(registers, false);
+    let write_arms_async = get_write_arms(registers, true);
+
+    // Without writable fields the value is never looked at
+    let ignore_value = registers
+        .iter()
+        .all(|register| !register.modifiable || register.fields.iter().all(|field| !field.writable))
+        .then(|| quote! { let _ = value; });
+
+    let read_doc = "Read the register with the given name and get the raw value of the field with the given name.\n\n\
+        The raw value bypasses any conversion of the field. Bools are `0` or `1` and signed values are sign extended.";
+    let write_doc = "Write the raw value to the field with the given name of the register with the given name.\n\n\
+        The register is read, the field is changed and the register is written back. \
+        The raw value bypasses any conversion of the field. Signed values are given as their sign extended bits.";
+
+    quote! {
+        #cfg_attr
+        impl<I> #name<I> {
+            #[doc = #read_doc]
+            #visibility fn read_field(&mut self, register: &str, field: &str) -> Result<u64, ::device_driver::DynAccessError<#read_error>>
+                where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+            {
+                match (register, field) {
+                    #(#read_arms)*
+                    _ => Err(::device_driver::DynAccessError::UnknownRegister),
+                }
+            }
+
+            #[doc = #read_doc]
+            #visibility async fn read_field_async(&mut self, register: &str, field: &str) -> Result<u64, ::device_driver::DynAccessError<#read_error>>
+                where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+            {
+                match (register, field) {
+                    #(#read_arms_async)*
+                    _ => Err(::device_driver::DynAccessError::UnknownRegister),
+                }
+            }
+
+            #[doc = #write_doc]
+            #visibility fn write_field(&mut self, register: &str, field: &str, value: u64) -> Result<(), ::device_driver::DynAccessError<#read_error>>
+                where I: ::device_driver::RegisterInterface<AddressType = #register_address_type>
+            {
+                #ignore_value
+                match (register, field) {
+                    #(#write_arms)*
+                    _ => Err(::device_driver::DynAccessError::UnknownRegister),
+                }
+            }
+
+            #[doc = #write_doc]
+            #visibility async fn write_field_async(&mut self, register: &str, field: &str, value: u64) -> Result<(), ::device_driver::DynAccessError<#read_error>>
+                where I: ::device_driver::AsyncRegisterInterface<AddressType = #register_address_type>
+            {
+                #ignore_value
+                match (register, field) {
+                    #(#write_arms_async)*
+                    _ => Err(::device_driver::DynAccessError::UnknownRegister),
+                }
+            }
+        }
+    }
+}
+
+fn get_read_arms(registers: &[DynAccessRegister], is_async: bool) -> Vec<TokenStream> {
+    let read = match is_async {
+        true => quote! { read_async().await },
+        false => quote! { read() },
+    };
+
+    registers
+        .iter()
+        .map(|register| {
+            let DynAccessRegister {
+                cfg_attr: register_cfg_attr,
+                name: register_name,
+                readable: register_readable,
+                modifiable: _,
+                fields,
+            } = register;
+            let register_string = register_name
+                .to_string()
+                .trim_start_matches("r#")
+                .to_owned();
+
+            let field_arms = fields.iter().map(|field| {
+                let DynAccessField {
+                    cfg_attr: field_cfg_attr,
+                    name: field_name,
+                    readable,
+                    getter,
+                    ..
+                } = field;
+                let field_string = field_name.to_string().trim_start_matches("r#").to_owned();
+
+                let result = match *register_readable && *readable {
+                    // The raw value of a narrow signed field isn't sign extended by the getter
+                    true if field.signed && field.size_bits < 64 => {
+                        let shift = Literal::u32_unsuffixed(64 - field.size_bits);
+                        quote! { Ok((((self.#register_name().#read?.#getter() as i64) << #shift) >> #shift) as u64) }
+                    }
+                    true => quote! { Ok(self.#register_name().#read?.#getter() as u64) },
+                    false => quote! { Err(::device_driver::DynAccessError::NotReadable) },
+                };
+
+                quote! {
+                    #register_cfg_attr
+                    #field_cfg_attr
+                    (#register_string, #field_string) => #result,
+                }
+            });
+
+            quote! {
+                #(#field_arms)*
+                #register_cfg_attr
+                (#register_string, _) => Err(::device_driver::DynAccessError::UnknownField),
+            }
+        })
+        .collect()
+}
+
+fn get_write_arms(registers: &[DynAccessRegister], is_async: bool) -> Vec<TokenStream> {
+    let modify = match is_async {
+        true => quote! { modify_async },
+        false => quote! { modify },
+    };
+    let await_token = is_async.then(|| quote! { .await });
+
+    registers
+        .iter()
+        .map(|register| {
+            let DynAccessRegister {
+                cfg_attr: register_cfg_attr,
+                name: register_name,
+                readable: _,
+                modifiable,
+                fields,
+            } = register;
+            let register_string = register_name.to_string().trim_start_matches("r#").to_owned();
+
+            let field_arms = fields.iter().map(|field| {
+                let DynAccessField {
+                    cfg_attr: field_cfg_attr,
+                    name: field_name,
+                    writable,
+                    setter,
+                    ..
+                } = field;
+                let field_string = field_name.to_string().trim_start_matches("r#").to_owned();
+
+                let body = match *modifiable && *writable {
+                    true => {
+                        let (range_check, value) = get_range_check(field);
+
+                        quote! {
+                            {
+                                #range_check
+                                self.#register_name().#modify(|reg| reg.#setter(#value))#await_token?;
+                                Ok(())
+                            }
+                        }
+                    }
+                    false => quote! { Err(::device_driver::DynAccessError::NotWritable) },
+                };
+
+                quote! {
+                    #register_cfg_attr
+                    #field_cfg_attr
+                    (#register_string, #field_string) => #body,
+                }
+            });
+
+            quote! {
+                #(#field_arms)*
+                #register_cfg_attr
+                (#register_string, _) => Err(::device_driver::DynAccessError::UnknownField),
+            }
+        })
+        .collect()
+}
+
+/// Get the check that the value fits in the field and the value converted to the type the setter takes
+fn get_range_check(field: &DynAccessField) -> (TokenStream, TokenStream) {
+    let DynAccessField {
+        base_type,
+        signed,
+        size_bits,
+        ..
+    } = field;
+
+    let out_of_range = quote! { return Err(::device_driver::DynAccessError::ValueOutOfRange); };
+
+    match base_type {
+        None => (
+            quote! { if value > 1 { #out_of_range } },
+            quote! { value != 0 },
+        ),
+        // A field of 64 bits can hold any value
+        Some(base_type) if *size_bits == 64 => (quote! {}, quote! { value as #base_type }),
+        Some(base_type) if *signed => {
+            let min = Literal::i64_unsuffixed(-(1 << (size_bits - 1)));
+            let max = Literal::i64_unsuffixed((1 << (size_bits - 1)) - 1);
+            (
+                quote! { if !(#min..=#max).contains(&(value as i64)) { #out_of_range } },
+                quote! { value as i64 as #base_type },
+            )
+        }
+        Some(base_type) => {
+            let max = Literal::u64_unsuffixed((1 << size_bits) - 1);
+            (
+                quote! { if value > #max { #out_of_range } },
+                quote! { value as #base_type },
+            )
+        }
+    }
+}
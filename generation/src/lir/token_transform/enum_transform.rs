@@ -1,17 +1,23 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
 use crate::lir::EnumVariant;
 
 use super::Enum;
 
-pub fn generate_enum(value: &Enum, defmt_feature: Option<&str>) -> TokenStream {
+pub fn generate_enum(
+    value: &Enum,
+    derives: &[Ident],
+    defmt_feature: Option<&str>,
+    visibility: &TokenStream,
+) -> TokenStream {
     let Enum {
         cfg_attr,
         doc_attr,
         name,
         base_type,
         variants,
+        passthrough_attrs,
     } = value;
     let default_variant = variants.iter().find(|v| v.default);
     let catch_all_variant = variants.iter().find(|v| v.catch_all);
@@ -75,19 +81,13 @@ pub fn generate_enum(value: &Enum, defmt_feature: Option<&str>) -> TokenStream {
         let from_variants = variants
             .iter()
             .filter(|v| !v.catch_all)
-            .map(
-                |EnumVariant {
-                     name,
-                     number,
-                     cfg_attr,
-                     ..
-                 }| {
-                    quote! {
-                        #cfg_attr
-                        #number => Self::#name
-                    }
-                },
-            )
+            .map(|variant @ EnumVariant { name, cfg_attr, .. }| {
+                let pattern = get_match_pattern(variant);
+                quote! {
+                    #cfg_attr
+                    #pattern => Self::#name
+                }
+            })
             .chain(Some(from_fallback_variant));
 
         quote! {
@@ -106,19 +106,13 @@ pub fn generate_enum(value: &Enum, defmt_feature: Option<&str>) -> TokenStream {
         let try_from_variants = variants
             .iter()
             .filter(|v| !v.catch_all)
-            .map(
-                |EnumVariant {
-                     name,
-                     number,
-                     cfg_attr,
-                     ..
-                 }| {
-                    quote! {
-                        #cfg_attr
-                        #number => Ok(Self::#name)
-                    }
-                },
-            )
+            .map(|variant @ EnumVariant { name, cfg_attr, .. }| {
+                let pattern = get_match_pattern(variant);
+                quote! {
+                    #cfg_attr
+                    #pattern => Ok(Self::#name)
+                }
+            })
             .chain(Some(try_from_fallback_variant));
 
         quote! {
@@ -169,6 +163,44 @@ pub fn generate_enum(value: &Enum, defmt_feature: Option<&str>) -> TokenStream {
         }
     };
 
+    let variant_name_impl = {
+        let name_variants = variants.iter().map(
+            |EnumVariant {
+                 name: var_name,
+                 catch_all,
+                 cfg_attr,
+                 ..
+             }| {
+                if *catch_all {
+                    quote! {
+                        #cfg_attr
+                        Self::#var_name(_) => "<unknown>"
+                    }
+                } else {
+                    let var_name_string = var_name.to_string();
+                    quote! {
+                        #cfg_attr
+                        Self::#var_name => #var_name_string
+                    }
+                }
+            },
+        );
+
+        quote! {
+            #cfg_attr
+            impl #name {
+                /// Get the name of the variant. The catch-all variant returns `"<unknown>"`.
+                ///
+                /// This is useful for logging without the overhead of `Debug` formatting.
+                #visibility const fn variant_name(self) -> &'static str {
+                    match self {
+                        #(#name_variants),*
+                    }
+                }
+            }
+        }
+    };
+
     let defmt_attr = match defmt_feature {
         Some(feature_name) => {
             quote! { #[cfg_attr(feature = #feature_name, derive(defmt::Format))] }
@@ -180,9 +212,10 @@ pub fn generate_enum(value: &Enum, defmt_feature: Option<&str>) -> TokenStream {
         #doc_attr
         #cfg_attr
         #[repr(#base_type)]
-        #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+        #[derive(#(#derives),*)]
         #defmt_attr
-        pub enum #name {
+        #passthrough_attrs
+        #visibility enum #name {
             #(#variant_quotes),*
         }
 
@@ -191,6 +224,17 @@ pub fn generate_enum(value: &Enum, defmt_feature: Option<&str>) -> TokenStream {
         #from_impl
 
         #into_impl
+
+        #variant_name_impl
+    }
+}
+
+/// The pattern that matches the raw values of the variant
+fn get_match_pattern(variant: &EnumVariant) -> TokenStream {
+    let number = &variant.number;
+    match &variant.range_end {
+        Some(range_end) => quote! { #number..=#range_end },
+        None => quote! { #number },
     }
 }
 
@@ -203,6 +247,13 @@ mod tests {
     use proc_macro2::Literal;
     use quote::format_ident;
 
+    fn default_derives() -> Vec<Ident> {
+        ["Debug", "Copy", "Clone", "Eq", "PartialEq", "Hash"]
+            .into_iter()
+            .map(|derive| format_ident!("{derive}"))
+            .collect()
+    }
+
     #[test]
     fn enum_correct() {
         let output = generate_enum(
@@ -211,6 +262,7 @@ mod tests {
                 doc_attr: quote! { #[doc = "Docs are important!"] },
                 name: format_ident!("MyEnum"),
                 base_type: format_ident!("u8"),
+                passthrough_attrs: quote! {},
                 variants: vec![
                     EnumVariant {
                         cfg_attr: quote! {#[cfg(unix)]},
@@ -219,6 +271,7 @@ mod tests {
                         number: Literal::u8_unsuffixed(0),
                         default: false,
                         catch_all: false,
+                        range_end: None,
                     },
                     EnumVariant {
                         cfg_attr: quote! {},
@@ -227,6 +280,7 @@ mod tests {
                         number: Literal::u8_unsuffixed(1),
                         default: true,
                         catch_all: false,
+                        range_end: None,
                     },
                     EnumVariant {
                         cfg_attr: quote! {},
@@ -235,10 +289,13 @@ mod tests {
                         number: Literal::u8_unsuffixed(4),
                         default: false,
                         catch_all: true,
+                        range_end: None,
                     },
                 ],
             },
+            &default_derives(),
             Some("defmt-03"),
+            &quote! { pub },
         );
 
         pretty_assertions::assert_eq!(
@@ -284,7 +341,210 @@ mod tests {
                         }
                     }
                 }
+                #[cfg(windows)]
+                impl MyEnum {
+                    /// Get the name of the variant. The catch-all variant returns `\"<unknown>\"`.
+                    ///
+                    /// This is useful for logging without the overhead of `Debug` formatting.
+                    pub const fn variant_name(self) -> &'static str {
+                        match self {
+                            #[cfg(unix)]
+                            Self::MyField => \"MyField\",
+                            Self::MyField1 => \"MyField1\",
+                            Self::MyField2(_) => \"<unknown>\",
+                        }
+                    }
+                }
+            "}
+        )
+    }
+
+    #[test]
+    fn enum_defmt_derive_only_with_feature() {
+        let generate = |defmt_feature| {
+            let output = generate_enum(
+                &Enum {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("MyEnum"),
+                    base_type: format_ident!("u8"),
+                    passthrough_attrs: quote! {},
+                    variants: vec![EnumVariant {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("MyField"),
+                        number: Literal::u8_unsuffixed(0),
+                        default: false,
+                        catch_all: false,
+                        range_end: None,
+                    }],
+                },
+                &default_derives(),
+                defmt_feature,
+                &quote! { pub },
+            );
+
+            prettyplease::unparse(&syn::parse2(output).unwrap())
+        };
+
+        assert!(
+            generate(Some("my-defmt"))
+                .contains("#[cfg_attr(feature = \"my-defmt\", derive(defmt::Format))]")
+        );
+        assert!(!generate(None).contains("defmt"));
+    }
+
+    #[test]
+    fn enum_derives_applied() {
+        let generate = |derives: &[Ident]| {
+            let output = generate_enum(
+                &Enum {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("MyEnum"),
+                    base_type: format_ident!("u8"),
+                    passthrough_attrs: quote! {},
+                    variants: vec![EnumVariant {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("MyField"),
+                        number: Literal::u8_unsuffixed(0),
+                        default: false,
+                        catch_all: false,
+                        range_end: None,
+                    }],
+                },
+                derives,
+                None,
+                &quote! { pub },
+            );
+
+            prettyplease::unparse(&syn::parse2(output).unwrap())
+        };
+
+        assert!(
+            generate(&default_derives())
+                .contains("#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]")
+        );
+
+        let without_copy = default_derives()
+            .into_iter()
+            .filter(|derive| derive != "Copy")
+            .collect::<Vec<_>>();
+        assert!(generate(&without_copy).contains("#[derive(Debug, Clone, Eq, PartialEq, Hash)]"));
+    }
+
+    #[test]
+    fn enum_passthrough_attributes() {
+        let output = generate_enum(
+            &Enum {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyEnum"),
+                base_type: format_ident!("u8"),
+                passthrough_attrs: quote! {
+                    #[allow(non_camel_case_types)]
+                    #[derive(PartialOrd, Ord)]
+                },
+                variants: vec![EnumVariant {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("my_field"),
+                    number: Literal::u8_unsuffixed(0),
+                    default: false,
+                    catch_all: false,
+                    range_end: None,
+                }],
+            },
+            &default_derives(),
+            None,
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                #[repr(u8)]
+                #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+                #[allow(non_camel_case_types)]
+                #[derive(PartialOrd, Ord)]
+                pub enum MyEnum {
+                    my_field = 0,
+                }
+                impl core::convert::TryFrom<u8> for MyEnum {
+                    type Error = ::device_driver::ConversionError<u8>;
+                    fn try_from(val: u8) -> Result<Self, Self::Error> {
+                        match val {
+                            0 => Ok(Self::my_field),
+                            val => {
+                                Err(::device_driver::ConversionError {
+                                    source: val,
+                                    target: \"MyEnum\",
+                                })
+                            }
+                        }
+                    }
+                }
+                impl From<MyEnum> for u8 {
+                    fn from(val: MyEnum) -> Self {
+                        match val {
+                            MyEnum::my_field => 0,
+                        }
+                    }
+                }
+                impl MyEnum {
+                    /// Get the name of the variant. The catch-all variant returns `\"<unknown>\"`.
+                    ///
+                    /// This is useful for logging without the overhead of `Debug` formatting.
+                    pub const fn variant_name(self) -> &'static str {
+                        match self {
+                            Self::my_field => \"my_field\",
+                        }
+                    }
+                }
             "}
         )
     }
+
+    #[test]
+    fn enum_range_variant_matches_range() {
+        let output = generate_enum(
+            &Enum {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyEnum"),
+                base_type: format_ident!("u8"),
+                passthrough_attrs: quote! {},
+                variants: vec![
+                    EnumVariant {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("Off"),
+                        number: Literal::u8_unsuffixed(0),
+                        default: false,
+                        catch_all: false,
+                        range_end: None,
+                    },
+                    EnumVariant {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("Reserved"),
+                        number: Literal::u8_unsuffixed(1),
+                        default: false,
+                        catch_all: false,
+                        range_end: Some(Literal::u8_unsuffixed(7)),
+                    },
+                ],
+            },
+            &default_derives(),
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains("Reserved = 1,"));
+        assert!(output.contains("1..=7 => Ok(Self::Reserved),"));
+        assert!(output.contains("MyEnum::Reserved => 1,"));
+    }
 }
@@ -8,6 +8,7 @@ use crate::lir::FieldSet;
 pub fn generate_field_set_enum(
     field_sets: &[FieldSet],
     defmt_feature: Option<&str>,
+    visibility: &TokenStream,
 ) -> TokenStream {
     let filter = |fs: &&FieldSet| fs.size_bits > 0;
 
@@ -88,7 +89,7 @@ pub fn generate_field_set_enum(
 
     quote! {
         /// Enum containing all possible field set types
-        pub enum FieldSetValue {
+        #visibility enum FieldSetValue {
             #(#fields),*
         }
 
@@ -1,6 +1,8 @@
 use convert_case::Casing;
 use itertools::Itertools;
-use proc_macro2::{Literal, TokenStream};
+use std::ops::Range;
+
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{ToTokens, format_ident, quote};
 
 use crate::{
@@ -8,16 +10,20 @@ use crate::{
     mir::{Access, BitOrder, ByteOrder},
 };
 
-pub fn generate_field_set(value: &FieldSet, defmt_feature: Option<&str>) -> TokenStream {
+pub fn generate_field_set(
+    value: &FieldSet,
+    defmt_feature: Option<&str>,
+    visibility: &TokenStream,
+) -> TokenStream {
     let FieldSet {
         cfg_attr,
         doc_attr,
         name,
         byte_order,
-        bit_order,
         size_bits,
         reset_value,
         ref_reset_overrides,
+        chip_variant_reset_values,
         fields,
     } = value;
 
@@ -29,12 +35,101 @@ pub fn generate_field_set(value: &FieldSet, defmt_feature: Option<&str>) -> Toke
     let size_bytes = Literal::u32_unsuffixed(size_bits.div_ceil(8));
     let size_bits = Literal::u32_unsuffixed(*size_bits);
 
-    let read_functions = fields
+    let read_functions = fields.iter().map(|field| {
+        get_read_function(
+            field,
+            fields,
+            *byte_order,
+            value.size_bits.div_ceil(8),
+            visibility,
+        )
+    });
+    let write_functions = fields.iter().map(|field| {
+        get_write_function(
+            field,
+            fields,
+            *byte_order,
+            value.size_bits.div_ceil(8),
+            visibility,
+        )
+    });
+    let renamed_functions = fields
+        .iter()
+        .map(|field| get_renamed_functions(field, visibility));
+    let byte_range_functions = fields.iter().map(|field| {
+        get_byte_range_function(field, *byte_order, value.size_bits.div_ceil(8), visibility)
+    });
+    let bits_functions = fields
+        .iter()
+        .map(|field| get_bits_function(field, visibility));
+
+    let readable_field_names = fields
         .iter()
-        .map(|field| get_read_function(field, *byte_order, *bit_order));
-    let write_functions = fields
+        .filter(|field| matches!(field.access, Access::RW | Access::RO | Access::RC))
+        .map(|field| {
+            let cfg_attr = &field.cfg_attr;
+            let name = field.name.to_string();
+            quote! { #cfg_attr #name }
+        });
+    let writable_field_names = fields
         .iter()
-        .map(|field| get_write_function(field, *byte_order, *bit_order));
+        .filter(|field| matches!(field.access, Access::RW | Access::WO))
+        .map(|field| {
+            let cfg_attr = &field.cfg_attr;
+            let name = field.name.to_string();
+            quote! { #cfg_attr #name }
+        });
+
+    let layout = get_layout(fields);
+
+    let unit_consts = fields.iter().filter_map(|field| {
+        let unit = field.unit.as_ref()?;
+        let cfg_attr = &field.cfg_attr;
+        let const_name = get_unit_const_name(&field.name);
+        let description = format!("The unit of the value of the `{}` field", field.name);
+
+        Some(quote! {
+            #[doc = #description]
+            #cfg_attr
+            #visibility const #const_name: &str = #unit;
+        })
+    });
+
+    // Every field set that fits can be represented as a single integer
+    let to_u128_function = (value.size_bits <= 128).then(|| {
+        let accumulate = match byte_order {
+            ByteOrder::LE => quote! { value |= (self.bits[i] as u128) << (i * 8); },
+            ByteOrder::BE => quote! { value = (value << 8) | self.bits[i] as u128; },
+            ByteOrder::SwapPairs => quote! { value |= (self.bits[i ^ 1] as u128) << (i * 8); },
+        };
+
+        quote! {
+            /// Get the raw value of the field set as an integer.
+            /// The bytes are combined using the byte order of the field set.
+            #visibility const fn to_u128(&self) -> u128 {
+                let mut value = 0;
+                let mut i = 0;
+                while i < #size_bytes {
+                    #accumulate
+                    i += 1;
+                }
+                value
+            }
+        }
+    });
+
+    let field_masks = fields.iter().map(|field| {
+        let cfg_attr = &field.cfg_attr;
+        let name = field.name.to_string();
+        let mask = get_field_mask(field, *byte_order, value.size_bits.div_ceil(8))
+            .into_iter()
+            .map(Literal::u8_unsuffixed);
+        quote! { #cfg_attr (#name, &[#(#mask),*]) }
+    });
+
+    let readable_mask = get_readable_mask(fields, *byte_order, value.size_bits.div_ceil(8))
+        .into_iter()
+        .map(Literal::u8_unsuffixed);
 
     let from_impl = {
         quote! {
@@ -49,6 +144,52 @@ pub fn generate_field_set(value: &FieldSet, defmt_feature: Option<&str>) -> Toke
         }
     };
 
+    // Small field sets can be parsed from a single word, e.g. from a test vector
+    let try_from_u32_impl = (value.size_bits <= 32).then(|| {
+        let bytes_len = value.size_bits.div_ceil(8);
+        let byte_indices = (0..bytes_len).map(|i| {
+            Literal::u32_unsuffixed(match byte_order {
+                ByteOrder::LE => i,
+                ByteOrder::BE => 4 - bytes_len + i,
+                ByteOrder::SwapPairs => i ^ 1,
+            })
+        });
+        let to_bytes = match byte_order {
+            ByteOrder::LE | ByteOrder::SwapPairs => quote! { to_le_bytes },
+            ByteOrder::BE => quote! { to_be_bytes },
+        };
+        let name_string = name.to_string();
+        // Shifting by 32 would overflow, but then all values fit anyways
+        let overflow_check = (value.size_bits < 32).then(|| {
+            quote! {
+                if value >> #size_bits != 0 {
+                    return Err(::device_driver::ConversionError {
+                        source: value,
+                        target: #name_string,
+                    });
+                }
+            }
+        });
+
+        quote! {
+            #cfg_attr
+            impl TryFrom<u32> for #name {
+                type Error = ::device_driver::ConversionError<u32>;
+
+                /// Lay out the value in the field set using its byte order.
+                /// Returns an error when the value has bits set above the size of the field set.
+                fn try_from(value: u32) -> Result<Self, Self::Error> {
+                    #overflow_check
+
+                    let bytes = value.#to_bytes();
+                    Ok(Self {
+                        bits: [#(bytes[#byte_indices]),*],
+                    })
+                }
+            }
+        }
+    });
+
     let into_impl = {
         quote! {
             #cfg_attr
@@ -60,7 +201,15 @@ pub fn generate_field_set(value: &FieldSet, defmt_feature: Option<&str>) -> Toke
         }
     };
 
-    let debug_impl = {
+    // Without fields there's nothing to show but the raw bytes, so the derive is good enough
+    let debug_derive = match fields.is_empty() {
+        true => quote! { Debug, },
+        false => quote! {},
+    };
+
+    let debug_impl = if fields.is_empty() {
+        quote! {}
+    } else {
         let debug_field_calls = fields.iter().map(|f| {
             let name = &f.name;
             let name_string = name.to_string();
@@ -137,7 +286,7 @@ pub fn generate_field_set(value: &FieldSet, defmt_feature: Option<&str>) -> Toke
 
             quote! {
                 #[doc = #docs]
-                pub const fn #name() -> Self {
+                #visibility const fn #name() -> Self {
                     Self {
                         bits: [#(#reset_value),*],
                     }
@@ -147,11 +296,30 @@ pub fn generate_field_set(value: &FieldSet, defmt_feature: Option<&str>) -> Toke
         })
     };
 
+    let chip_variant_constructors =
+        chip_variant_reset_values
+            .iter()
+            .map(|(chip_variant, reset_value)| {
+                let name = format_ident!("new_as_variant_{chip_variant}");
+                let docs = format!(
+                    "Create a new instance, loaded with the reset value of chip variant `{chip_variant}`"
+                );
+
+                quote! {
+                    #[doc = #docs]
+                    #visibility const fn #name() -> Self {
+                        Self {
+                            bits: [#(#reset_value),*],
+                        }
+                    }
+                }
+            });
+
     quote! {
         #doc_attr
         #cfg_attr
-        #[derive(Copy, Clone, Eq, PartialEq)]
-        pub struct #name {
+        #[derive(#debug_derive Copy, Clone, Eq, PartialEq)]
+        #visibility struct #name {
             /// The internal bits
             bits: [u8; #size_bytes],
         }
@@ -170,36 +338,177 @@ pub fn generate_field_set(value: &FieldSet, defmt_feature: Option<&str>) -> Toke
             fn get_inner_buffer_mut(&mut self) -> &mut [u8] {
                 &mut self.bits
             }
+
+            const FIELD_MASKS: &'static [(&'static str, &'static [u8])] = &[#(#field_masks),*];
         }
 
         #cfg_attr
         impl #name {
+            /// A compact map of the fields in the field set, from the highest bit to the lowest.
+            /// Every field is shown as `[end:start name type access]`.
+            #visibility const LAYOUT: &str = #layout;
+
+            #(#unit_consts)*
+
+            /// The reset value of the field set as bytes in the byte order of the field set.
+            /// This is all zeroes when no reset value is specified.
+            #visibility const RESET_VALUE: [u8; #size_bytes] = [#(#reset_value),*];
+
             /// Create a new instance, loaded with the reset value (if any)
-            pub const fn new() -> Self {
+            #visibility const fn new() -> Self {
                 Self {
-                    bits: [#(#reset_value),*],
+                    bits: Self::RESET_VALUE,
                 }
             }
 
             /// Create a new instance, loaded with all zeroes
-            pub const fn new_zero() -> Self {
+            #visibility const fn new_zero() -> Self {
                 Self {
                     bits: [0; #size_bytes],
                 }
             }
 
+            /// Create a new instance from the raw bits, the same as the `From` impl but usable in const contexts
+            #visibility const fn from_bits(bits: [u8; #size_bytes]) -> Self {
+                Self { bits }
+            }
+
+            /// Get mutable access to the raw bytes of the field set, e.g. to let a DMA fill it in place.
+            /// Any byte pattern is valid.
+            #visibility const fn as_mut_bytes(&mut self) -> &mut [u8; #size_bytes] {
+                &mut self.bits
+            }
+
+            /// Get the raw bytes of the field set for logging.
+            /// This works without any formatting feature, so a generic logger can always consume it.
+            #visibility const fn log_bytes(&self) -> &[u8] {
+                &self.bits
+            }
+
+            /// Pack the bits of the field sets one after the other into the buffer, e.g. for a burst write to a register bank.
+            /// Returns the number of bytes written or an error when the buffer is too small to hold all field sets.
+            #visibility fn pack_into(items: &[Self], out: &mut [u8]) -> Result<usize, ::device_driver::BufferTooSmall> {
+                let required = items.len() * #size_bytes;
+                if out.len() < required {
+                    return Err(::device_driver::BufferTooSmall {
+                        required,
+                        actual: out.len(),
+                    });
+                }
+
+                for (item, chunk) in items.iter().zip(out.chunks_exact_mut(#size_bytes)) {
+                    chunk.copy_from_slice(&item.bits);
+                }
+                Ok(required)
+            }
+
+            /// Get the bytes in the order they are transmitted to and from the device.
+            ///
+            /// The bytes are stored in the byte order of the field set, so this is the same as the internal layout.
+            /// With the `BE` byte order the most significant byte comes first, with `LE` the least significant byte.
+            #visibility const fn to_wire_bytes(&self) -> [u8; #size_bytes] {
+                self.bits
+            }
+
+            #to_u128_function
+
+            /// Check if the field set holds its reset value.
+            /// Only the bits of the readable fields are compared, all other bits are ignored.
+            #visibility const fn is_reset(&self) -> bool {
+                let readable_mask: [u8; #size_bytes] = [#(#readable_mask),*];
+
+                let mut i = 0;
+                while i < #size_bytes {
+                    if (self.bits[i] ^ Self::RESET_VALUE[i]) & readable_mask[i] != 0 {
+                        return false;
+                    }
+                    i += 1;
+                }
+                true
+            }
+
+            /// Merge the bits of `other` into this field set where the bits of `mask` are set.
+            /// All other bits keep their value, so e.g. a read value can be combined with staged field writes.
+            #visibility const fn merge(&mut self, other: &Self, mask: &Self) {
+                let mut i = 0;
+                while i < #size_bytes {
+                    self.bits[i] = (self.bits[i] & !mask.bits[i]) | (other.bits[i] & mask.bits[i]);
+                    i += 1;
+                }
+            }
+
+            /// The names of all fields that can be read
+            #visibility const fn readable_field_names() -> &'static [&'static str] {
+                &[#(#readable_field_names),*]
+            }
+
+            /// The names of all fields that can be written
+            #visibility const fn writable_field_names() -> &'static [&'static str] {
+                &[#(#writable_field_names),*]
+            }
+
             #(#ref_value_constructors)*
 
+            #(#chip_variant_constructors)*
+
             #(#read_functions)*
 
             #(#write_functions)*
+
+            #(#renamed_functions)*
+
+            #(#byte_range_functions)*
+
+            #(#bits_functions)*
         }
 
         #from_impl
+        #try_from_u32_impl
         #into_impl
         #debug_impl
         #defmt_impl
+    }
+}
+
+/// Build the textual bit map of the fields, like `[23:16 value2 int RO][15:1 value1 uint RW][0 value0 bool RW]`
+fn get_layout(fields: &[Field]) -> String {
+    let mut fields = fields
+        .iter()
+        .map(|field| {
+            let start: u32 = field.address.start.to_string().parse().unwrap();
+            let end: u32 = field.address.end.to_string().parse().unwrap();
+            (start, end, field)
+        })
+        .collect::<Vec<_>>();
+    fields.sort_by_key(|(start, _, _)| core::cmp::Reverse(*start));
+
+    fields
+        .into_iter()
+        .map(|(start, end, field)| {
+            let bits = match end - start {
+                1 => format!("{start}"),
+                _ => format!("{}:{start}", end - 1),
+            };
+            let base_type = match &field.conversion_method {
+                FieldConversionMethod::Bool => "bool",
+                _ if field.base_type.to_string().starts_with('i') => "int",
+                _ => "uint",
+            };
+            format!("[{bits} {} {base_type} {:?}]", field.name, field.access)
+        })
+        .collect()
+}
+
+/// Generate the bitwise operator impls of a field set.
+/// They operate on all bits of the field set at once.
+pub fn generate_field_set_bit_ops(value: &FieldSet, generate_bit_ops: bool) -> TokenStream {
+    if !generate_bit_ops || value.size_bits == 0 {
+        return TokenStream::new();
+    }
 
+    let FieldSet { cfg_attr, name, .. } = value;
+
+    quote! {
         #cfg_attr
         impl core::ops::BitAnd for #name {
             type Output = Self;
@@ -271,49 +580,97 @@ pub fn generate_field_set(value: &FieldSet, defmt_feature: Option<&str>) -> Toke
     }
 }
 
-fn get_read_function(field: &Field, byte_order: ByteOrder, bit_order: BitOrder) -> TokenStream {
+/// Generate the `ufmt::uDebug` impl of a field set.
+///
+/// The fields with a conversion are printed as their raw value,
+/// so the conversion types don't need to implement `uDebug` themselves.
+pub fn generate_field_set_ufmt(value: &FieldSet, ufmt_feature: Option<&str>) -> TokenStream {
+    let Some(feature_name) = ufmt_feature else {
+        return TokenStream::new();
+    };
+
+    if value.size_bits == 0 {
+        return TokenStream::new();
+    }
+
+    let FieldSet {
+        cfg_attr,
+        name,
+        fields,
+        ..
+    } = value;
+
+    let name_string = name.to_string();
+    let field_calls = fields
+        .iter()
+        .filter(|field| matches!(field.access, Access::RW | Access::RO | Access::RC))
+        .map(|field| {
+            let cfg_attr = &field.cfg_attr;
+            let field_name = &field.name;
+            let field_name_string = field_name.to_string();
+            let getter = match field.conversion_method {
+                FieldConversionMethod::None | FieldConversionMethod::Bool => field_name.clone(),
+                _ => format_ident!("{field_name}_raw"),
+            };
+
+            quote! {
+                #cfg_attr
+                debug_struct.field(#field_name_string, &self.#getter())?;
+            }
+        });
+
+    quote! {
+        #cfg_attr
+        #[cfg(feature = #feature_name)]
+        impl ufmt::uDebug for #name {
+            fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+            where
+                W: ufmt::uWrite + ?Sized,
+            {
+                let mut debug_struct = f.debug_struct(#name_string)?;
+                #(#field_calls)*
+                debug_struct.finish()
+            }
+        }
+    }
+}
+
+fn get_read_function(
+    field: &Field,
+    fields: &[Field],
+    byte_order: ByteOrder,
+    size_bytes: u32,
+    visibility: &TokenStream,
+) -> TokenStream {
     let Field {
         cfg_attr,
         doc_attr,
         name,
+        renamed_from: _,
+        unit,
+        valid_when: _,
+        bit_order,
         address,
         base_type,
         conversion_method,
         access,
     } = field;
 
-    if !matches!(access, Access::RW | Access::RO) {
+    if !matches!(access, Access::RW | Access::RO | Access::RC) {
         return TokenStream::new();
     }
 
-    let load_function = match (byte_order, bit_order) {
-        (ByteOrder::LE, BitOrder::LSB0) => {
-            quote! { ::device_driver::ops::load_lsb0::<#base_type, ::device_driver::ops::LE> }
+    let byte_order_type = get_byte_order_type(byte_order);
+    let load_function = match bit_order {
+        BitOrder::LSB0 => {
+            quote! { ::device_driver::ops::load_lsb0::<#base_type, #byte_order_type> }
         }
-        (ByteOrder::LE, BitOrder::MSB0) => {
-            quote! { ::device_driver::ops::load_msb0::<#base_type, ::device_driver::ops::LE> }
-        }
-        (ByteOrder::BE, BitOrder::LSB0) => {
-            quote! { ::device_driver::ops::load_lsb0::<#base_type, ::device_driver::ops::BE> }
-        }
-        (ByteOrder::BE, BitOrder::MSB0) => {
-            quote! { ::device_driver::ops::load_msb0::<#base_type, ::device_driver::ops::BE> }
+        BitOrder::MSB0 => {
+            quote! { ::device_driver::ops::load_msb0::<#base_type, #byte_order_type> }
         }
     };
 
-    let super_token = get_super_token(conversion_method);
-
-    let return_type = match conversion_method {
-        FieldConversionMethod::None => base_type.to_token_stream(),
-        FieldConversionMethod::Into(conversion_type)
-        | FieldConversionMethod::UnsafeInto(conversion_type) => {
-            quote! { #super_token #conversion_type }
-        }
-        FieldConversionMethod::TryInto(conversion_type) => {
-            quote! { Result<#super_token #conversion_type, <#super_token #conversion_type as TryFrom<#base_type>>::Error> }
-        }
-        FieldConversionMethod::Bool => format_ident!("bool").into_token_stream(),
-    };
+    let return_type = get_field_return_type(field, &get_super_token(conversion_method));
 
     let start_bit = &address.start;
     let end_bit = &address.end;
@@ -326,27 +683,246 @@ fn get_read_function(field: &Field, byte_order: ByteOrder, bit_order: BitOrder)
         }
         FieldConversionMethod::TryInto(_) => quote! { raw.try_into() },
         FieldConversionMethod::Bool => quote! { raw > 0 },
+        FieldConversionMethod::Functions { read_fn, .. } => {
+            let read_fn_super_token = get_path_super_token(Some(read_fn));
+            quote! { #read_fn_super_token #read_fn(raw) }
+        }
+        FieldConversionMethod::Crc { algorithm, over } => {
+            let over = get_byte_range(over, byte_order, size_bytes);
+            let (start_byte, end_byte) = (
+                Literal::u32_unsuffixed(over.start),
+                Literal::u32_unsuffixed(over.end),
+            );
+
+            quote! {
+                let calculated = #algorithm.checksum(&self.bits[#start_byte..#end_byte]);
+                if raw == calculated {
+                    Ok(raw)
+                } else {
+                    Err(::device_driver::CrcMismatch { stored: raw, calculated })
+                }
+            }
+        }
     };
 
     let function_description = format!("Read the `{name}` field of the register.");
+    let (valid_when_doc, valid_when_check) =
+        get_valid_when_check(field, fields, byte_order, size_bytes);
+    let unit_doc = unit.as_ref().map(|unit| {
+        let unit_description = format!(
+            "The value is in `{unit}`. See [Self::{}].",
+            get_unit_const_name(name)
+        );
+        quote! {
+            #[doc = ""]
+            #[doc = #unit_description]
+        }
+    });
+
+    let raw_read_function = match conversion_method {
+        FieldConversionMethod::Into(_)
+        | FieldConversionMethod::UnsafeInto(_)
+        | FieldConversionMethod::TryInto(_)
+        | FieldConversionMethod::Crc { .. }
+        | FieldConversionMethod::Functions { .. } => {
+            let raw_function_description = format!(
+                "Read the `{name}` field of the register as a raw value, bypassing the conversion."
+            );
+            let raw_function_name = format_ident!("{name}_raw");
+
+            quote! {
+                #[doc = #raw_function_description]
+                #[doc = ""]
+                #doc_attr
+                #cfg_attr
+                #visibility fn #raw_function_name(&self) -> #base_type {
+                    unsafe { #load_function(&self.bits, #start_bit, #end_bit) }
+                }
+            }
+        }
+        FieldConversionMethod::None | FieldConversionMethod::Bool => quote! {},
+    };
+
+    // Without a conversion the bits can be loaded inline, which makes the getter usable in const contexts
+    let (constness, load) = match conversion_method {
+        FieldConversionMethod::None | FieldConversionMethod::Bool => (
+            quote! { const },
+            get_const_load(field, byte_order, size_bytes),
+        ),
+        _ => (
+            quote! {},
+            quote! { unsafe { #load_function(&self.bits, #start_bit, #end_bit) } },
+        ),
+    };
 
     quote! {
         #[doc = #function_description]
         #[doc = ""]
         #doc_attr
+        #unit_doc
+        #valid_when_doc
         #cfg_attr
-        pub fn #name(&self) -> #return_type {
-            let raw = unsafe { #load_function(&self.bits, #start_bit, #end_bit) };
+        #visibility #constness fn #name(&self) -> #return_type {
+            #valid_when_check
+            let raw = #load;
             #conversion
         }
+
+        #raw_read_function
+    }
+}
+
+/// Get the doc and the debug assertion for the condition under which the field is valid, if any
+fn get_valid_when_check(
+    field: &Field,
+    fields: &[Field],
+    byte_order: ByteOrder,
+    size_bytes: u32,
+) -> (TokenStream, TokenStream) {
+    let Some(valid_when) = &field.valid_when else {
+        return (TokenStream::new(), TokenStream::new());
+    };
+
+    let condition_field = fields
+        .iter()
+        .find(|f| f.name == valid_when.field_name)
+        .expect("The condition field is checked to exist in the same field set");
+    let load = get_const_load(condition_field, byte_order, size_bytes);
+
+    let value = Literal::i128_unsuffixed(valid_when.raw_value.abs());
+    let value = match valid_when.raw_value.is_negative() {
+        true => quote! { -#value },
+        false => quote! { #value },
+    };
+    let operator = match valid_when.negated {
+        true => quote! { != },
+        false => quote! { == },
+    };
+
+    let doc = format!("Only valid when `{}`.", valid_when.description);
+    let message = format!(
+        "The `{}` field is only valid when `{}`",
+        field.name, valid_when.description
+    );
+
+    (
+        quote! {
+            #[doc = ""]
+            #[doc = #doc]
+        },
+        quote! {
+            debug_assert!((#load) #operator #value, #message);
+        },
+    )
+}
+
+/// Get the expression that loads the bits of the field as its base type without calling into the ops module.
+///
+/// This does the same as the load functions, but all indices and shifts are calculated here.
+fn get_const_load(field: &Field, byte_order: ByteOrder, size_bytes: u32) -> TokenStream {
+    let base_type = &field.base_type;
+    let start: u32 = field.address.start.to_string().parse().unwrap();
+    let end: u32 = field.address.end.to_string().parse().unwrap();
+
+    let byte_index = |i: u32| {
+        Literal::u32_unsuffixed(match byte_order {
+            ByteOrder::LE => i / 8,
+            ByteOrder::BE => size_bytes - (i / 8) - 1,
+            ByteOrder::SwapPairs => (i / 8) ^ 1,
+        })
+    };
+    let shifted = |value: TokenStream, shift: u32| match shift {
+        0 => value,
+        shift => {
+            let shift = Literal::u32_unsuffixed(shift);
+            quote! { (#value) << #shift }
+        }
+    };
+    let shifted_right = |value: TokenStream, shift: u32| match shift {
+        0 => value,
+        shift => {
+            let shift = Literal::u32_unsuffixed(shift);
+            quote! { (#value >> #shift) }
+        }
+    };
+
+    // Casting a byte to a u8 is a lint warning in the user crate, so that's skipped
+    let mut parts = Vec::new();
+    let mut i = start;
+    while i < end {
+        let byte_index = byte_index(i);
+
+        if i.is_multiple_of(8) && i + 8 <= end {
+            parts.push(shifted(
+                match base_type == "u8" {
+                    true => quote! { self.bits[#byte_index] },
+                    false => quote! { self.bits[#byte_index] as #base_type },
+                },
+                i - start,
+            ));
+            i += 8;
+        } else {
+            let (bit, target) = match field.bit_order {
+                BitOrder::LSB0 => (i % 8, i),
+                BitOrder::MSB0 => (7 - i % 8, pivot_msb0(start, end, i)),
+            };
+            let bit = shifted_right(quote! { self.bits[#byte_index] }, bit);
+            parts.push(shifted(
+                match base_type == "u8" {
+                    true => quote! { #bit & 1 },
+                    false => quote! { (#bit & 1) as #base_type },
+                },
+                target - start,
+            ));
+            i += 1;
+        }
+    }
+
+    quote! { #(#parts)|* }
+}
+
+/// Get the index the bit at index `i` is stored at in a msb0 field.
+/// This mirrors the function in the ops module of the runtime crate.
+fn pivot_msb0(start: u32, end: u32, i: u32) -> u32 {
+    let (num_bits, pivot) = if i / 8 == start / 8 {
+        let num_bits = (start + 1).next_multiple_of(8).min(end) - start;
+        (num_bits, start + num_bits / 2)
+    } else {
+        let num_bits = end - (end - 8).next_multiple_of(8);
+        (num_bits, end - num_bits.div_ceil(2))
+    };
+    let num_bits_even = (num_bits % 2 == 0) as i64;
+
+    let mut diff = pivot as i64 - i as i64;
+    if diff <= 0 {
+        diff -= num_bits_even;
+    }
+
+    let mut j = i as i64 + diff * 2;
+    if diff > 0 {
+        j -= num_bits_even;
+    } else {
+        j += num_bits_even;
     }
+
+    j as u32
 }
 
-fn get_write_function(field: &Field, byte_order: ByteOrder, bit_order: BitOrder) -> TokenStream {
+fn get_write_function(
+    field: &Field,
+    fields: &[Field],
+    byte_order: ByteOrder,
+    size_bytes: u32,
+    visibility: &TokenStream,
+) -> TokenStream {
     let Field {
         cfg_attr,
         doc_attr,
         name,
+        renamed_from: _,
+        unit: _,
+        valid_when: _,
+        bit_order,
         address,
         base_type,
         conversion_method,
@@ -357,30 +933,18 @@ fn get_write_function(field: &Field, byte_order: ByteOrder, bit_order: BitOrder)
         return TokenStream::new();
     }
 
-    let store_function = match (byte_order, bit_order) {
-        (ByteOrder::LE, BitOrder::LSB0) => {
-            quote! { ::device_driver::ops::store_lsb0::<#base_type, ::device_driver::ops::LE> }
-        }
-        (ByteOrder::LE, BitOrder::MSB0) => {
-            quote! { ::device_driver::ops::store_msb0::<#base_type, ::device_driver::ops::LE> }
+    let byte_order_type = get_byte_order_type(byte_order);
+    let store_function = match bit_order {
+        BitOrder::LSB0 => {
+            quote! { ::device_driver::ops::store_lsb0::<#base_type, #byte_order_type> }
         }
-        (ByteOrder::BE, BitOrder::LSB0) => {
-            quote! { ::device_driver::ops::store_lsb0::<#base_type, ::device_driver::ops::BE> }
-        }
-        (ByteOrder::BE, BitOrder::MSB0) => {
-            quote! { ::device_driver::ops::store_msb0::<#base_type, ::device_driver::ops::BE> }
+        BitOrder::MSB0 => {
+            quote! { ::device_driver::ops::store_msb0::<#base_type, #byte_order_type> }
         }
     };
 
-    let super_token = get_super_token(conversion_method);
-
-    let input_type = match conversion_method {
-        FieldConversionMethod::None => &base_type.to_token_stream(),
-        FieldConversionMethod::Into(conversion_type)
-        | FieldConversionMethod::UnsafeInto(conversion_type)
-        | FieldConversionMethod::TryInto(conversion_type) => conversion_type,
-        FieldConversionMethod::Bool => &quote! { bool },
-    };
+    let super_token = get_input_super_token(conversion_method);
+    let input_type = get_field_input_type(field);
 
     let start_bit = &address.start;
     let end_bit = &address.end;
@@ -388,63 +952,397 @@ fn get_write_function(field: &Field, byte_order: ByteOrder, bit_order: BitOrder)
     let conversion = match conversion_method {
         FieldConversionMethod::None => quote! { value },
         FieldConversionMethod::Bool => quote! { value as _ },
+        FieldConversionMethod::Functions { write_fn, .. } => {
+            let write_fn_super_token = get_path_super_token(Some(write_fn));
+            quote! { #write_fn_super_token #write_fn(value) }
+        }
         _ => quote! { value.into() },
     };
 
     let function_description = format!("Write the `{name}` field of the register.");
     let function_name = format_ident!("set_{name}");
 
+    let raw_write_function = match conversion_method {
+        FieldConversionMethod::Into(_)
+        | FieldConversionMethod::UnsafeInto(_)
+        | FieldConversionMethod::TryInto(_)
+        | FieldConversionMethod::Crc { .. }
+        | FieldConversionMethod::Functions { .. } => {
+            let raw_function_description = format!(
+                "Write the `{name}` field of the register with a raw value, bypassing the conversion."
+            );
+            let raw_function_name = format_ident!("set_{name}_raw");
+
+            quote! {
+                #[doc = #raw_function_description]
+                #[doc = ""]
+                #doc_attr
+                #cfg_attr
+                #visibility fn #raw_function_name(&mut self, raw: #base_type) {
+                    unsafe { #store_function(raw, #start_bit, #end_bit, &mut self.bits) };
+                }
+            }
+        }
+        FieldConversionMethod::None | FieldConversionMethod::Bool => quote! {},
+    };
+
+    if let FieldConversionMethod::Crc { algorithm, over } = conversion_method {
+        let over = get_byte_range(over, byte_order, size_bytes);
+        let (start_byte, end_byte) = (
+            Literal::u32_unsuffixed(over.start),
+            Literal::u32_unsuffixed(over.end),
+        );
+        let function_description = format!(
+            "Calculate the crc and write it to the `{name}` field of the register. Set the other fields first."
+        );
+
+        return quote! {
+            #[doc = #function_description]
+            #[doc = ""]
+            #doc_attr
+            #cfg_attr
+            #visibility fn #function_name(&mut self) {
+                let raw = #algorithm.checksum(&self.bits[#start_byte..#end_byte]);
+                unsafe { #store_function(raw, #start_bit, #end_bit, &mut self.bits) };
+            }
+
+            #raw_write_function
+        };
+    }
+
+    // A bool field that can be read and written can be flipped in place
+    let toggle_function = (matches!(conversion_method, FieldConversionMethod::Bool)
+        && *access == Access::RW)
+        .then(|| {
+            let toggle_function_description = format!("Flip the `{name}` field of the register.");
+            let toggle_function_name = format_ident!("toggle_{name}");
+
+            quote! {
+                #[doc = #toggle_function_description]
+                #[doc = ""]
+                #doc_attr
+                #cfg_attr
+                #visibility fn #toggle_function_name(&mut self) {
+                    self.#function_name(!self.#name());
+                }
+            }
+        });
+
+    let (valid_when_doc, valid_when_check) =
+        get_valid_when_check(field, fields, byte_order, size_bytes);
+
+    let with_function_description =
+        format!("Return a copy of the register with the `{name}` field set to the given value.");
+    let with_function_name = format_ident!("with_{name}");
+
     quote! {
         #[doc = #function_description]
         #[doc = ""]
         #doc_attr
+        #valid_when_doc
         #cfg_attr
-        pub fn #function_name(&mut self, value: #super_token #input_type) {
+        #visibility fn #function_name(&mut self, value: #super_token #input_type) {
+            #valid_when_check
             let raw = #conversion;
             unsafe { #store_function(raw, #start_bit, #end_bit, &mut self.bits) };
         }
-    }
-}
 
-fn get_super_token(conversion_method: &FieldConversionMethod) -> TokenStream {
-    match conversion_method.conversion_type() {
-        Some(ct)
-            if syn::parse2::<syn::TypePath>(ct.clone())
-                .map(|tp| {
-                    tp.path.leading_colon.is_none()
-                        && tp.path.segments.first().unwrap().ident != format_ident!("crate")
-                })
-                .unwrap_or_default() =>
-        {
-            quote! { super:: }
+        #[doc = #with_function_description]
+        #[doc = ""]
+        #doc_attr
+        #valid_when_doc
+        #cfg_attr
+        #[must_use]
+        #visibility fn #with_function_name(mut self, value: #super_token #input_type) -> Self {
+            self.#function_name(value);
+            self
         }
-        _ => quote! {},
+
+        #raw_write_function
+        #toggle_function
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use indoc::indoc;
-    use proc_macro2::Literal;
+/// Get the name of the constant holding the unit of a field, e.g. `TEMPERATURE_UNIT`
+fn get_unit_const_name(name: &Ident) -> Ident {
+    format_ident!(
+        "{}_UNIT",
+        name.to_string().trim_start_matches("r#").to_uppercase()
+    )
+}
 
-    #[test]
-    fn field_set_correct() {
-        let output = generate_field_set(
-            &FieldSet {
-                cfg_attr: quote! { #[cfg(windows)] },
+/// Generate deprecated getters and setters under the old names of the field that forward to the current ones
+fn get_renamed_functions(field: &Field, visibility: &TokenStream) -> TokenStream {
+    let Field {
+        cfg_attr,
+        name,
+        renamed_from,
+        conversion_method,
+        access,
+        ..
+    } = field;
+
+    let return_type = get_field_return_type(field, &get_super_token(conversion_method));
+    let input_super_token = get_input_super_token(conversion_method);
+    let input_type = get_field_input_type(field);
+    let setter_name = format_ident!("set_{name}");
+
+    renamed_from
+        .iter()
+        .map(|old_name| {
+            let getter = matches!(access, Access::RW | Access::RO | Access::RC).then(|| {
+                let note = format!("Renamed to `{name}`");
+                let function_description =
+                    format!("Read the `{name}` field of the register. This is the old name of [Self::{name}].");
+
+                quote! {
+                    #[doc = #function_description]
+                    #[deprecated(note = #note)]
+                    #cfg_attr
+                    #visibility fn #old_name(&self) -> #return_type {
+                        self.#name()
+                    }
+                }
+            });
+
+            let setter = matches!(access, Access::RW | Access::WO).then(|| {
+                let old_setter_name = format_ident!("set_{old_name}");
+                let note = format!("Renamed to `{setter_name}`");
+                let function_description =
+                    format!("Write the `{name}` field of the register. This is the old name of [Self::{setter_name}].");
+
+                quote! {
+                    #[doc = #function_description]
+                    #[deprecated(note = #note)]
+                    #cfg_attr
+                    #visibility fn #old_setter_name(&mut self, value: #input_super_token #input_type) {
+                        self.#setter_name(value)
+                    }
+                }
+            });
+
+            quote! {
+                #getter
+                #setter
+            }
+        })
+        .collect()
+}
+
+fn get_byte_range_function(
+    field: &Field,
+    byte_order: ByteOrder,
+    size_bytes: u32,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Field {
+        cfg_attr,
+        name,
+        address,
+        ..
+    } = field;
+
+    let start_bit: u32 = address.start.to_string().parse().unwrap();
+    let end_bit: u32 = address.end.to_string().parse().unwrap();
+
+    let byte_range = get_byte_range(&(start_bit..end_bit), byte_order, size_bytes);
+    let start_byte = Literal::u32_unsuffixed(byte_range.start);
+    let end_byte = Literal::u32_unsuffixed(byte_range.end);
+
+    let function_name = format_ident!("{name}_byte_range");
+    let function_description =
+        format!("The range of bytes in the field set that is occupied by the `{name}` field.");
+
+    quote! {
+        #[doc = #function_description]
+        #cfg_attr
+        #visibility const fn #function_name() -> core::ops::Range<usize> {
+            #start_byte..#end_byte
+        }
+    }
+}
+
+/// Generate the function that returns the width of the field in bits.
+/// The base type of the getter can be wider than the field, so this tells the range of valid values.
+fn get_bits_function(field: &Field, visibility: &TokenStream) -> TokenStream {
+    let Field {
+        cfg_attr,
+        name,
+        address,
+        ..
+    } = field;
+
+    let start_bit: u32 = address.start.to_string().parse().unwrap();
+    let end_bit: u32 = address.end.to_string().parse().unwrap();
+    let width = Literal::u32_unsuffixed(end_bit - start_bit);
+
+    let function_name = format_ident!("{name}_bits");
+    let function_description = format!("The width in bits of the `{name}` field.");
+
+    quote! {
+        #[doc = #function_description]
+        #cfg_attr
+        #visibility const fn #function_name() -> u32 {
+            #width
+        }
+    }
+}
+
+/// The range of bytes in the field set that the range of bits occupies
+fn get_byte_range(bits: &Range<u32>, byte_order: ByteOrder, size_bytes: u32) -> Range<u32> {
+    // The bits are indexed from the back of the buffer for big endian
+    match byte_order {
+        ByteOrder::LE => bits.start / 8..bits.end.div_ceil(8),
+        ByteOrder::BE => size_bytes - bits.end.div_ceil(8)..size_bytes - bits.start / 8,
+        // The bytes are swapped within the pairs, so the range covers whole pairs
+        ByteOrder::SwapPairs => (bits.start / 8) & !1..bits.end.div_ceil(8).next_multiple_of(2),
+    }
+}
+
+/// The type in the ops module that implements the byte order
+fn get_byte_order_type(byte_order: ByteOrder) -> TokenStream {
+    match byte_order {
+        ByteOrder::LE => quote! { ::device_driver::ops::LE },
+        ByteOrder::BE => quote! { ::device_driver::ops::BE },
+        ByteOrder::SwapPairs => quote! { ::device_driver::ops::SwapPairs },
+    }
+}
+
+/// The type that is returned by the getter of the field.
+/// The super token is put in front of the conversion type.
+pub fn get_field_return_type(field: &Field, super_token: &TokenStream) -> TokenStream {
+    let base_type = &field.base_type;
+
+    match &field.conversion_method {
+        FieldConversionMethod::None => base_type.to_token_stream(),
+        FieldConversionMethod::Into(conversion_type)
+        | FieldConversionMethod::UnsafeInto(conversion_type) => {
+            quote! { #super_token #conversion_type }
+        }
+        FieldConversionMethod::TryInto(conversion_type) => {
+            quote! { Result<#super_token #conversion_type, <#super_token #conversion_type as TryFrom<#base_type>>::Error> }
+        }
+        FieldConversionMethod::Bool => format_ident!("bool").into_token_stream(),
+        FieldConversionMethod::Crc { .. } => {
+            quote! { Result<#base_type, ::device_driver::CrcMismatch> }
+        }
+        FieldConversionMethod::Functions { read_type, .. } => {
+            quote! { #super_token #read_type }
+        }
+    }
+}
+
+/// The type that is taken by the setter of the field (without any super token)
+pub fn get_field_input_type(field: &Field) -> TokenStream {
+    match &field.conversion_method {
+        FieldConversionMethod::None => field.base_type.to_token_stream(),
+        FieldConversionMethod::Into(conversion_type)
+        | FieldConversionMethod::UnsafeInto(conversion_type)
+        | FieldConversionMethod::TryInto(conversion_type) => conversion_type.clone(),
+        FieldConversionMethod::Bool => quote! { bool },
+        FieldConversionMethod::Crc { .. } => field.base_type.to_token_stream(),
+        FieldConversionMethod::Functions { write_type, .. } => write_type.clone(),
+    }
+}
+
+fn get_super_token(conversion_method: &FieldConversionMethod) -> TokenStream {
+    get_path_super_token(conversion_method.conversion_type())
+}
+
+/// The super token for the type taken by the setter of the field
+fn get_input_super_token(conversion_method: &FieldConversionMethod) -> TokenStream {
+    get_path_super_token(conversion_method.input_conversion_type())
+}
+
+/// Paths that are relative to the crate root need a `super::` in front of them,
+/// because the field sets are generated in their own module
+fn get_path_super_token(path: Option<&TokenStream>) -> TokenStream {
+    match path {
+        Some(ct)
+            if syn::parse2::<syn::TypePath>(ct.clone())
+                .map(|tp| {
+                    tp.path.leading_colon.is_none()
+                        && tp.path.segments.first().unwrap().ident != format_ident!("crate")
+                })
+                .unwrap_or_default() =>
+        {
+            quote! { super:: }
+        }
+        _ => quote! {},
+    }
+}
+
+/// Get the bits of the field set that belong to a readable field.
+/// The bits are located the same way as the `load` functions of the `ops` module do it.
+fn get_readable_mask(fields: &[Field], byte_order: ByteOrder, size_bytes: u32) -> Vec<u8> {
+    let mut mask = vec![0; size_bytes as usize];
+
+    for field in fields
+        .iter()
+        .filter(|field| matches!(field.access, Access::RW | Access::RO | Access::RC))
+    {
+        for (mask, field_mask) in mask
+            .iter_mut()
+            .zip(get_field_mask(field, byte_order, size_bytes))
+        {
+            *mask |= field_mask;
+        }
+    }
+
+    mask
+}
+
+/// Get the mask over the inner buffer of the bits the field occupies
+fn get_field_mask(field: &Field, byte_order: ByteOrder, size_bytes: u32) -> Vec<u8> {
+    let mut mask = vec![0; size_bytes as usize];
+
+    let start = field.address.start.to_string().parse::<u32>().unwrap();
+    let end = field.address.end.to_string().parse::<u32>().unwrap();
+
+    for bit in start..end {
+        let byte_index = match byte_order {
+            ByteOrder::LE => bit / 8,
+            ByteOrder::BE => size_bytes - bit / 8 - 1,
+            ByteOrder::SwapPairs => (bit / 8) ^ 1,
+        };
+        let bit_index = match field.bit_order {
+            BitOrder::LSB0 => bit % 8,
+            BitOrder::MSB0 => 7 - bit % 8,
+        };
+
+        mask[byte_index as usize] |= 1 << bit_index;
+    }
+
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use proc_macro2::Literal;
+
+    #[test]
+    fn field_set_correct() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! { #[cfg(windows)] },
                 doc_attr: quote! { #[doc = "Hiya!"] },
                 name: format_ident!("MyRegister"),
                 byte_order: ByteOrder::BE,
-                bit_order: BitOrder::LSB0,
                 size_bits: 20,
                 reset_value: vec![1, 2, 3],
                 ref_reset_overrides: vec![("MyRef".into(), vec![0, 1, 2])],
+                chip_variant_reset_values: Vec::new(),
                 fields: vec![
                     Field {
                         cfg_attr: quote! { #[cfg(linux)] },
                         doc_attr: quote! { #[doc = "Hiya again!"] },
                         name: format_ident!("my_field"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
                         address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(4),
                         base_type: format_ident!("u8"),
                         conversion_method: FieldConversionMethod::UnsafeInto(quote! { FieldEnum }),
@@ -454,6 +1352,10 @@ mod tests {
                         cfg_attr: quote! {},
                         doc_attr: quote! {},
                         name: format_ident!("my_field2"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
                         address: Literal::u64_unsuffixed(4)..Literal::u64_unsuffixed(16),
                         base_type: format_ident!("i16"),
                         conversion_method: FieldConversionMethod::None,
@@ -462,6 +1364,7 @@ mod tests {
                 ],
             },
             Some("defmt-03"),
+            &quote! { pub },
         );
 
         pretty_assertions::assert_eq!(
@@ -486,17 +1389,109 @@ mod tests {
                 fn get_inner_buffer_mut(&mut self) -> &mut [u8] {
                     &mut self.bits
                 }
+                const FIELD_MASKS: &'static [(&'static str, &'static [u8])] = &[
+                    #[cfg(linux)]
+                    (\"my_field\", &[0, 0, 15]),
+                    (\"my_field2\", &[0, 255, 240]),
+                ];
             }
             #[cfg(windows)]
             impl MyRegister {
+                /// A compact map of the fields in the field set, from the highest bit to the lowest.
+                /// Every field is shown as `[end:start name type access]`.
+                pub const LAYOUT: &str = \"[15:4 my_field2 int WO][3:0 my_field uint RW]\";
+                /// The reset value of the field set as bytes in the byte order of the field set.
+                /// This is all zeroes when no reset value is specified.
+                pub const RESET_VALUE: [u8; 3] = [1u8, 2u8, 3u8];
                 /// Create a new instance, loaded with the reset value (if any)
                 pub const fn new() -> Self {
-                    Self { bits: [1u8, 2u8, 3u8] }
+                    Self { bits: Self::RESET_VALUE }
                 }
                 /// Create a new instance, loaded with all zeroes
                 pub const fn new_zero() -> Self {
                     Self { bits: [0; 3] }
                 }
+                /// Create a new instance from the raw bits, the same as the `From` impl but usable in const contexts
+                pub const fn from_bits(bits: [u8; 3]) -> Self {
+                    Self { bits }
+                }
+                /// Get mutable access to the raw bytes of the field set, e.g. to let a DMA fill it in place.
+                /// Any byte pattern is valid.
+                pub const fn as_mut_bytes(&mut self) -> &mut [u8; 3] {
+                    &mut self.bits
+                }
+                /// Get the raw bytes of the field set for logging.
+                /// This works without any formatting feature, so a generic logger can always consume it.
+                pub const fn log_bytes(&self) -> &[u8] {
+                    &self.bits
+                }
+                /// Pack the bits of the field sets one after the other into the buffer, e.g. for a burst write to a register bank.
+                /// Returns the number of bytes written or an error when the buffer is too small to hold all field sets.
+                pub fn pack_into(
+                    items: &[Self],
+                    out: &mut [u8],
+                ) -> Result<usize, ::device_driver::BufferTooSmall> {
+                    let required = items.len() * 3;
+                    if out.len() < required {
+                        return Err(::device_driver::BufferTooSmall {
+                            required,
+                            actual: out.len(),
+                        });
+                    }
+                    for (item, chunk) in items.iter().zip(out.chunks_exact_mut(3)) {
+                        chunk.copy_from_slice(&item.bits);
+                    }
+                    Ok(required)
+                }
+                /// Get the bytes in the order they are transmitted to and from the device.
+                ///
+                /// The bytes are stored in the byte order of the field set, so this is the same as the internal layout.
+                /// With the `BE` byte order the most significant byte comes first, with `LE` the least significant byte.
+                pub const fn to_wire_bytes(&self) -> [u8; 3] {
+                    self.bits
+                }
+                /// Get the raw value of the field set as an integer.
+                /// The bytes are combined using the byte order of the field set.
+                pub const fn to_u128(&self) -> u128 {
+                    let mut value = 0;
+                    let mut i = 0;
+                    while i < 3 {
+                        value = (value << 8) | self.bits[i] as u128;
+                        i += 1;
+                    }
+                    value
+                }
+                /// Check if the field set holds its reset value.
+                /// Only the bits of the readable fields are compared, all other bits are ignored.
+                pub const fn is_reset(&self) -> bool {
+                    let readable_mask: [u8; 3] = [0, 0, 15];
+                    let mut i = 0;
+                    while i < 3 {
+                        if (self.bits[i] ^ Self::RESET_VALUE[i]) & readable_mask[i] != 0 {
+                            return false;
+                        }
+                        i += 1;
+                    }
+                    true
+                }
+                /// Merge the bits of `other` into this field set where the bits of `mask` are set.
+                /// All other bits keep their value, so e.g. a read value can be combined with staged field writes.
+                pub const fn merge(&mut self, other: &Self, mask: &Self) {
+                    let mut i = 0;
+                    while i < 3 {
+                        self.bits[i] = (self.bits[i] & !mask.bits[i])
+                            | (other.bits[i] & mask.bits[i]);
+                        i += 1;
+                    }
+                }
+                /// The names of all fields that can be read
+                pub const fn readable_field_names() -> &'static [&'static str] {
+                    &[#[cfg(linux)] \"my_field\"]
+                }
+                /// The names of all fields that can be written
+                pub const fn writable_field_names() -> &'static [&'static str] {
+                    &[#[cfg(linux)] \"my_field\", \"my_field2\"]
+                }
                 ///Create a new instance, loaded with the reset value of the `MyRef` ref
                 pub const fn new_as_my_ref() -> Self {
                     Self { bits: [0u8, 1u8, 2u8] }
@@ -514,6 +1509,18 @@ mod tests {
                     };
                     unsafe { raw.try_into().unwrap_unchecked() }
                 }
+                ///Read the `my_field` field of the register as a raw value, bypassing the conversion.
+                ///
+                ///Hiya again!
+                #[cfg(linux)]
+                pub fn my_field_raw(&self) -> u8 {
+                    unsafe {
+                        ::device_driver::ops::load_lsb0::<
+                            u8,
+                            ::device_driver::ops::BE,
+                        >(&self.bits, 0, 4)
+                    }
+                }
                 ///Write the `my_field` field of the register.
                 ///
                 ///Hiya again!
@@ -527,6 +1534,27 @@ mod tests {
                         >(raw, 0, 4, &mut self.bits)
                     };
                 }
+                ///Return a copy of the register with the `my_field` field set to the given value.
+                ///
+                ///Hiya again!
+                #[cfg(linux)]
+                #[must_use]
+                pub fn with_my_field(mut self, value: super::FieldEnum) -> Self {
+                    self.set_my_field(value);
+                    self
+                }
+                ///Write the `my_field` field of the register with a raw value, bypassing the conversion.
+                ///
+                ///Hiya again!
+                #[cfg(linux)]
+                pub fn set_my_field_raw(&mut self, raw: u8) {
+                    unsafe {
+                        ::device_driver::ops::store_lsb0::<
+                            u8,
+                            ::device_driver::ops::BE,
+                        >(raw, 0, 4, &mut self.bits)
+                    };
+                }
                 ///Write the `my_field2` field of the register.
                 ///
                 pub fn set_my_field2(&mut self, value: i16) {
@@ -538,6 +1566,31 @@ mod tests {
                         >(raw, 4, 16, &mut self.bits)
                     };
                 }
+                ///Return a copy of the register with the `my_field2` field set to the given value.
+                ///
+                #[must_use]
+                pub fn with_my_field2(mut self, value: i16) -> Self {
+                    self.set_my_field2(value);
+                    self
+                }
+                ///The range of bytes in the field set that is occupied by the `my_field` field.
+                #[cfg(linux)]
+                pub const fn my_field_byte_range() -> core::ops::Range<usize> {
+                    2..3
+                }
+                ///The range of bytes in the field set that is occupied by the `my_field2` field.
+                pub const fn my_field2_byte_range() -> core::ops::Range<usize> {
+                    1..3
+                }
+                ///The width in bits of the `my_field` field.
+                #[cfg(linux)]
+                pub const fn my_field_bits() -> u32 {
+                    4
+                }
+                ///The width in bits of the `my_field2` field.
+                pub const fn my_field2_bits() -> u32 {
+                    12
+                }
             }
             #[cfg(windows)]
             impl From<[u8; 3]> for MyRegister {
@@ -546,6 +1599,24 @@ mod tests {
                 }
             }
             #[cfg(windows)]
+            impl TryFrom<u32> for MyRegister {
+                type Error = ::device_driver::ConversionError<u32>;
+                /// Lay out the value in the field set using its byte order.
+                /// Returns an error when the value has bits set above the size of the field set.
+                fn try_from(value: u32) -> Result<Self, Self::Error> {
+                    if value >> 20 != 0 {
+                        return Err(::device_driver::ConversionError {
+                            source: value,
+                            target: \"MyRegister\",
+                        });
+                    }
+                    let bytes = value.to_be_bytes();
+                    Ok(Self {
+                        bits: [bytes[1], bytes[2], bytes[3]],
+                    })
+                }
+            }
+            #[cfg(windows)]
             impl From<MyRegister> for [u8; 3] {
                 fn from(val: MyRegister) -> Self {
                     val.bits
@@ -572,65 +1643,1214 @@ mod tests {
                     )
                 }
             }
-            #[cfg(windows)]
-            impl core::ops::BitAnd for MyRegister {
-                type Output = Self;
-                fn bitand(mut self, rhs: Self) -> Self::Output {
-                    self &= rhs;
-                    self
-                }
-            }
-            #[cfg(windows)]
-            impl core::ops::BitAndAssign for MyRegister {
-                fn bitand_assign(&mut self, rhs: Self) {
-                    for (l, r) in self.bits.iter_mut().zip(&rhs.bits) {
-                        *l &= *r;
-                    }
-                }
-            }
-            #[cfg(windows)]
-            impl core::ops::BitOr for MyRegister {
-                type Output = Self;
-                fn bitor(mut self, rhs: Self) -> Self::Output {
-                    self |= rhs;
-                    self
-                }
-            }
-            #[cfg(windows)]
-            impl core::ops::BitOrAssign for MyRegister {
-                fn bitor_assign(&mut self, rhs: Self) {
-                    for (l, r) in self.bits.iter_mut().zip(&rhs.bits) {
-                        *l |= *r;
-                    }
-                }
-            }
-            #[cfg(windows)]
-            impl core::ops::BitXor for MyRegister {
-                type Output = Self;
-                fn bitxor(mut self, rhs: Self) -> Self::Output {
-                    self ^= rhs;
-                    self
-                }
-            }
-            #[cfg(windows)]
-            impl core::ops::BitXorAssign for MyRegister {
-                fn bitxor_assign(&mut self, rhs: Self) {
-                    for (l, r) in self.bits.iter_mut().zip(&rhs.bits) {
-                        *l ^= *r;
-                    }
-                }
-            }
-            #[cfg(windows)]
-            impl core::ops::Not for MyRegister {
-                type Output = Self;
-                fn not(mut self) -> Self::Output {
-                    for val in self.bits.iter_mut() {
-                        *val = !*val;
-                    }
-                    self
-                }
-            }
             "}
         )
     }
+
+    #[test]
+    fn enum_field_has_raw_accessors() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("mode"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(2),
+                    base_type: format_ident!("u8"),
+                    conversion_method: FieldConversionMethod::TryInto(quote! { Mode }),
+                    access: Access::RW,
+                }],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains("pub fn set_mode(&mut self, value: super::Mode)"));
+        assert!(output.contains("pub fn set_mode_raw(&mut self, raw: u8)"));
+        assert!(output.contains(
+            "pub fn mode(&self) -> Result<super::Mode, <super::Mode as TryFrom<u8>>::Error>"
+        ));
+        assert!(output.contains("pub fn mode_raw(&self) -> u8"));
+    }
+
+    #[test]
+    fn defmt_enum_fields_have_no_type_hint() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("mode"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
+                        address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(2),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::Into(quote! { Mode }),
+                        access: Access::RW,
+                    },
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("level"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
+                        address: Literal::u64_unsuffixed(2)..Literal::u64_unsuffixed(8),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::None,
+                        access: Access::RW,
+                    },
+                ],
+            },
+            Some("defmt-03"),
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        // The enum is formatted with its own `defmt::Format` impl, so it shows the variant name
+        assert!(output.contains("\"MyRegister {{ mode: {}, level: {=u8} }}\""));
+    }
+
+    #[test]
+    fn visibility_is_applied() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("level"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(8),
+                    base_type: format_ident!("u8"),
+                    conversion_method: FieldConversionMethod::None,
+                    access: Access::RW,
+                }],
+            },
+            None,
+            &quote! { pub(crate) },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains("pub(crate) struct MyRegister {"));
+        assert!(output.contains("pub(crate) const fn level(&self) -> u8"));
+        assert!(output.contains("pub(crate) fn set_level(&mut self, value: u8)"));
+        assert!(output.contains("pub(crate) const fn new() -> Self"));
+        assert!(output.contains("pub(crate) const RESET_VALUE: [u8; 1]"));
+        // Nothing on the field set is more visible than the field set itself
+        assert!(
+            !output.contains("pub const") && !output.contains("pub fn"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn field_less_field_set_derives_debug() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 16,
+                reset_value: vec![0, 0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: Vec::new(),
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(
+            output
+                .contains("#[derive(Debug, Copy, Clone, Eq, PartialEq)]\npub struct MyRegister {")
+        );
+        assert!(!output.contains("impl core::fmt::Debug for MyRegister"));
+    }
+
+    #[test]
+    fn to_u128_only_up_to_128_bits() {
+        let field_set = |size_bits| FieldSet {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("MyRegister"),
+            byte_order: ByteOrder::LE,
+            size_bits,
+            reset_value: vec![0; size_bits.div_ceil(8) as usize],
+            ref_reset_overrides: Vec::new(),
+            chip_variant_reset_values: Vec::new(),
+            fields: Vec::new(),
+        };
+
+        let output = generate_field_set(&field_set(100), None, &quote! { pub });
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+        assert!(output.contains(concat!(
+            "    pub const fn to_u128(&self) -> u128 {\n",
+            "        let mut value = 0;\n",
+            "        let mut i = 0;\n",
+            "        while i < 13 {\n",
+            "            value |= (self.bits[i] as u128) << (i * 8);\n",
+        )));
+
+        let output = generate_field_set(&field_set(129), None, &quote! { pub });
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+        assert!(!output.contains("to_u128"));
+    }
+
+    #[test]
+    fn readable_mask_follows_byte_and_bit_order() {
+        let field = |bit_order, range: Range<u64>, access| Field {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("my_field"),
+            renamed_from: Vec::new(),
+            unit: None,
+            valid_when: None,
+            bit_order,
+            address: Literal::u64_unsuffixed(range.start)..Literal::u64_unsuffixed(range.end),
+            base_type: format_ident!("u16"),
+            conversion_method: FieldConversionMethod::None,
+            access,
+        };
+
+        let fields = [
+            field(BitOrder::LSB0, 0..3, Access::RO),
+            field(BitOrder::LSB0, 6..10, Access::RW),
+            field(BitOrder::LSB0, 12..16, Access::WO),
+        ];
+        assert_eq!(
+            get_readable_mask(&fields, ByteOrder::LE, 2),
+            [0b1100_0111, 0b0000_0011]
+        );
+        assert_eq!(
+            get_readable_mask(&fields, ByteOrder::BE, 2),
+            [0b0000_0011, 0b1100_0111]
+        );
+
+        let fields = [field(BitOrder::MSB0, 0..3, Access::RC)];
+        assert_eq!(
+            get_readable_mask(&fields, ByteOrder::LE, 2),
+            [0b1110_0000, 0]
+        );
+    }
+
+    #[test]
+    fn swap_pairs_field_set_uses_swap_pairs_ops() {
+        let field = |name: &str, range: Range<u64>, access| Field {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("{name}"),
+            renamed_from: Vec::new(),
+            unit: None,
+            valid_when: None,
+            bit_order: BitOrder::LSB0,
+            address: Literal::u64_unsuffixed(range.start)..Literal::u64_unsuffixed(range.end),
+            base_type: format_ident!("u16"),
+            conversion_method: FieldConversionMethod::None,
+            access,
+        };
+
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MySample"),
+                byte_order: ByteOrder::SwapPairs,
+                size_bits: 32,
+                reset_value: vec![0; 4],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    field("low", 0..16, Access::RW),
+                    field("high", 18..30, Access::RW),
+                ],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        // The getters index the bytes with the pairs swapped, the setters use the ops
+        assert!(output.contains("let raw = self.bits[1] as u16 | (self.bits[0] as u16) << 8;"));
+        assert!(output.contains("let raw = ((self.bits[3] >> 2) & 1) as u16"));
+        assert_eq!(output.matches("::device_driver::ops::SwapPairs").count(), 2);
+        assert!(!output.contains("::device_driver::ops::LE"));
+
+        // Bits 0..3 live in byte 1, bits 8..12 in byte 0
+        let fields = [
+            field("a", 0..3, Access::RO),
+            field("b", 8..12, Access::RW),
+            field("c", 16..32, Access::WO),
+        ];
+        assert_eq!(
+            get_readable_mask(&fields, ByteOrder::SwapPairs, 4),
+            [0b0000_1111, 0b0000_0111, 0, 0]
+        );
+    }
+
+    #[test]
+    fn chip_variant_constructors_generated() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![1],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: vec![("a".into(), vec![1]), ("b".into(), vec![2])],
+                fields: vec![Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("level"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(8),
+                    base_type: format_ident!("u8"),
+                    conversion_method: FieldConversionMethod::None,
+                    access: Access::RW,
+                }],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains(concat!(
+            "    ///Create a new instance, loaded with the reset value of chip variant `a`\n",
+            "    pub const fn new_as_variant_a() -> Self {\n",
+            "        Self { bits: [1u8] }\n",
+            "    }\n",
+        )));
+        assert!(output.contains(concat!(
+            "    ///Create a new instance, loaded with the reset value of chip variant `b`\n",
+            "    pub const fn new_as_variant_b() -> Self {\n",
+            "        Self { bits: [2u8] }\n",
+            "    }\n",
+        )));
+    }
+
+    #[test]
+    fn renamed_field_has_deprecated_aliases() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("level"),
+                    renamed_from: vec![format_ident!("lvl")],
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(8),
+                    base_type: format_ident!("u8"),
+                    conversion_method: FieldConversionMethod::None,
+                    access: Access::RW,
+                }],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains("pub const fn level(&self) -> u8"));
+        assert!(output.contains("pub fn set_level(&mut self, value: u8)"));
+        assert!(output.contains(concat!(
+            "    #[deprecated(note = \"Renamed to `level`\")]\n",
+            "    pub fn lvl(&self) -> u8 {\n",
+            "        self.level()\n",
+            "    }\n",
+        )));
+        assert!(output.contains(concat!(
+            "    #[deprecated(note = \"Renamed to `set_level`\")]\n",
+            "    pub fn set_lvl(&mut self, value: u8) {\n",
+            "        self.set_level(value)\n",
+            "    }\n",
+        )));
+    }
+
+    #[test]
+    fn field_unit_documented() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 16,
+                reset_value: vec![0, 0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("temperature"),
+                    renamed_from: Vec::new(),
+                    unit: Some("°C".into()),
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(16),
+                    base_type: format_ident!("i16"),
+                    conversion_method: FieldConversionMethod::None,
+                    access: Access::RO,
+                }],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains(concat!(
+            "    ///The unit of the value of the `temperature` field\n",
+            "    pub const TEMPERATURE_UNIT: &str = \"°C\";\n",
+        )));
+        assert!(output.contains(concat!(
+            "    ///The value is in `°C`. See [Self::TEMPERATURE_UNIT].\n",
+            "    pub const fn temperature(&self) -> i16 {\n",
+        )));
+    }
+
+    #[test]
+    fn read_write_bool_field_has_toggle() {
+        let bool_field = |name: &str, access| Field {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("{name}"),
+            renamed_from: Vec::new(),
+            unit: None,
+            valid_when: None,
+            bit_order: BitOrder::LSB0,
+            address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(1),
+            base_type: format_ident!("u8"),
+            conversion_method: FieldConversionMethod::Bool,
+            access,
+        };
+
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    bool_field("enabled", Access::RW),
+                    bool_field("start", Access::WO),
+                ],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains(concat!(
+            "    pub fn toggle_enabled(&mut self) {\n",
+            "        self.set_enabled(!self.enabled());\n",
+            "    }\n",
+        )));
+        assert!(!output.contains("toggle_start"));
+    }
+
+    #[test]
+    fn writable_field_has_with_builder() {
+        let field = |name: &str, access| Field {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("{name}"),
+            renamed_from: Vec::new(),
+            unit: None,
+            valid_when: None,
+            bit_order: BitOrder::LSB0,
+            address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(4),
+            base_type: format_ident!("u8"),
+            conversion_method: FieldConversionMethod::None,
+            access,
+        };
+
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    field("value", Access::RW),
+                    field("command", Access::WO),
+                    field("status", Access::RO),
+                ],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains(concat!(
+            "    #[must_use]\n",
+            "    pub fn with_value(mut self, value: u8) -> Self {\n",
+            "        self.set_value(value);\n",
+            "        self\n",
+            "    }\n",
+        )));
+        assert!(output.contains("pub fn with_command(mut self, value: u8) -> Self"));
+        assert!(!output.contains("with_status"));
+    }
+
+    #[test]
+    fn valid_when_field_has_debug_assertion() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("mode"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
+                        address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(2),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::None,
+                        access: Access::RW,
+                    },
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("level"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: Some(crate::lir::ValidWhen {
+                            description: "mode == Output".into(),
+                            field_name: format_ident!("mode"),
+                            negated: false,
+                            raw_value: 2,
+                        }),
+                        bit_order: BitOrder::LSB0,
+                        address: Literal::u64_unsuffixed(2)..Literal::u64_unsuffixed(8),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::None,
+                        access: Access::RW,
+                    },
+                ],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains(concat!(
+            "    ///Only valid when `mode == Output`.\n",
+            "    pub const fn level(&self) -> u8 {\n",
+            "        debug_assert!(\n",
+            "            (self.bits[0] & 1 | ((self.bits[0] >> 1) & 1) << 1) == 2,\n",
+            "            \"The `level` field is only valid when `mode == Output`\",\n",
+            "        );\n",
+        )));
+        assert!(output.contains(concat!(
+            "    ///Only valid when `mode == Output`.\n",
+            "    pub fn set_level(&mut self, value: u8) {\n",
+            "        debug_assert!(\n",
+            "            (self.bits[0] & 1 | ((self.bits[0] >> 1) & 1) << 1) == 2,\n",
+            "            \"The `level` field is only valid when `mode == Output`\",\n",
+            "        );\n",
+        )));
+    }
+
+    #[test]
+    fn mixed_bit_order_fields_use_own_bit_order() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("low"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
+                        address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(4),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::None,
+                        access: Access::RW,
+                    },
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("high"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::MSB0,
+                        address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(4),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::None,
+                        access: Access::RW,
+                    },
+                ],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        // The getters load the bits inline, so check which bit of the byte ends up at the top of the value
+        assert!(output.contains("((self.bits[0] >> 3) & 1) << 3"));
+        assert!(output.contains("((self.bits[0] >> 7) & 1) << 3"));
+        assert_eq!(
+            output
+                .matches("::device_driver::ops::store_lsb0::<")
+                .count(),
+            1
+        );
+        assert_eq!(
+            output
+                .matches("::device_driver::ops::store_msb0::<")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn field_names_partitioned_by_access() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("both"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
+                        address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(2),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::None,
+                        access: Access::RW,
+                    },
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("read"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
+                        address: Literal::u64_unsuffixed(2)..Literal::u64_unsuffixed(4),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::None,
+                        access: Access::RO,
+                    },
+                    Field {
+                        cfg_attr: quote! {},
+                        doc_attr: quote! {},
+                        name: format_ident!("write"),
+                        renamed_from: Vec::new(),
+                        unit: None,
+                        valid_when: None,
+                        bit_order: BitOrder::LSB0,
+                        address: Literal::u64_unsuffixed(4)..Literal::u64_unsuffixed(6),
+                        base_type: format_ident!("u8"),
+                        conversion_method: FieldConversionMethod::None,
+                        access: Access::WO,
+                    },
+                ],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains(concat!(
+            "    pub const fn readable_field_names() -> &'static [&'static str] {\n",
+            "        &[\"both\", \"read\"]\n",
+            "    }\n",
+        )));
+        assert!(output.contains(concat!(
+            "    pub const fn writable_field_names() -> &'static [&'static str] {\n",
+            "        &[\"both\", \"write\"]\n",
+            "    }\n",
+        )));
+    }
+
+    #[test]
+    fn crc_field_correct() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::BE,
+                size_bits: 32,
+                reset_value: vec![0, 0, 0, 0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("checksum"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(8),
+                    base_type: format_ident!("u8"),
+                    conversion_method: FieldConversionMethod::Crc {
+                        algorithm: quote! { ::device_driver::Crc8::SMBUS },
+                        over: 8..32,
+                    },
+                    access: Access::RW,
+                }],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        // The crc is over the first three bytes of the big endian buffer
+        assert!(output.contains(concat!(
+            "        let calculated = ::device_driver::Crc8::SMBUS.checksum(&self.bits[0..3]);\n",
+            "        if raw == calculated {\n",
+            "            Ok(raw)\n",
+            "        } else {\n",
+            "            Err(::device_driver::CrcMismatch {\n",
+            "                stored: raw,\n",
+            "                calculated,\n",
+            "            })\n",
+            "        }\n",
+        )));
+        assert!(
+            output.contains("pub fn checksum(&self) -> Result<u8, ::device_driver::CrcMismatch>")
+        );
+        assert!(output.contains(concat!(
+            "    pub fn set_checksum(&mut self) {\n",
+            "        let raw = ::device_driver::Crc8::SMBUS.checksum(&self.bits[0..3]);\n",
+        )));
+        assert!(output.contains("pub fn set_checksum_raw(&mut self, raw: u8)"));
+    }
+
+    #[test]
+    fn byte_range_correct() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 32,
+                reset_value: vec![0, 0, 0, 0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("value"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(4)..Literal::u64_unsuffixed(20),
+                    base_type: format_ident!("u16"),
+                    conversion_method: FieldConversionMethod::None,
+                    access: Access::RW,
+                }],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        // Bits 4..20 touch the first three bytes
+        assert!(output.contains(
+            "pub const fn value_byte_range() -> core::ops::Range<usize> {\n        0..3\n    }"
+        ));
+    }
+
+    #[test]
+    fn ufmt_impl_only_with_feature() {
+        let field_set = FieldSet {
+            cfg_attr: quote! { #[cfg(windows)] },
+            doc_attr: quote! {},
+            name: format_ident!("MyRegister"),
+            byte_order: ByteOrder::LE,
+            size_bits: 8,
+            reset_value: vec![0],
+            ref_reset_overrides: Vec::new(),
+            chip_variant_reset_values: Vec::new(),
+            fields: vec![
+                Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("enabled"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(1),
+                    base_type: format_ident!("bool"),
+                    conversion_method: FieldConversionMethod::Bool,
+                    access: Access::RW,
+                },
+                Field {
+                    cfg_attr: quote! { #[cfg(unix)] },
+                    doc_attr: quote! {},
+                    name: format_ident!("mode"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(1)..Literal::u64_unsuffixed(3),
+                    base_type: format_ident!("u8"),
+                    conversion_method: FieldConversionMethod::Into(quote! { Mode }),
+                    access: Access::RO,
+                },
+                Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("trigger"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(3)..Literal::u64_unsuffixed(4),
+                    base_type: format_ident!("bool"),
+                    conversion_method: FieldConversionMethod::Bool,
+                    access: Access::WO,
+                },
+            ],
+        };
+
+        assert!(generate_field_set_ufmt(&field_set, None).is_empty());
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(
+                &syn::parse2(generate_field_set_ufmt(&field_set, Some("ufmt"))).unwrap()
+            ),
+            indoc! {"
+                #[cfg(windows)]
+                #[cfg(feature = \"ufmt\")]
+                impl ufmt::uDebug for MyRegister {
+                    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+                    where
+                        W: ufmt::uWrite + ?Sized,
+                    {
+                        let mut debug_struct = f.debug_struct(\"MyRegister\")?;
+                        debug_struct.field(\"enabled\", &self.enabled())?;
+                        #[cfg(unix)] debug_struct.field(\"mode\", &self.mode_raw())?;
+                        debug_struct.finish()
+                    }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn getters_without_conversion_are_const() {
+        let field = |name: &str, address: Range<u64>, base_type: &str, conversion_method| Field {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("{name}"),
+            renamed_from: Vec::new(),
+            unit: None,
+            valid_when: None,
+            bit_order: BitOrder::LSB0,
+            address: Literal::u64_unsuffixed(address.start)..Literal::u64_unsuffixed(address.end),
+            base_type: format_ident!("{base_type}"),
+            conversion_method,
+            access: Access::RO,
+        };
+
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::BE,
+                size_bits: 24,
+                reset_value: vec![0, 0, 0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    field("enabled", 0..1, "u8", FieldConversionMethod::Bool),
+                    field("value", 6..16, "u16", FieldConversionMethod::None),
+                    field(
+                        "mode",
+                        16..18,
+                        "u8",
+                        FieldConversionMethod::Into(quote! { Mode }),
+                    ),
+                ],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        assert!(output.contains(concat!(
+            "    pub const fn enabled(&self) -> bool {\n",
+            "        let raw = self.bits[2] & 1;\n",
+            "        raw > 0\n",
+            "    }\n",
+        )));
+        assert!(output.contains(concat!(
+            "    pub const fn value(&self) -> u16 {\n",
+            "        let raw = ((self.bits[2] >> 6) & 1) as u16\n",
+            "            | (((self.bits[2] >> 7) & 1) as u16) << 1 | (self.bits[1] as u16) << 2;\n",
+            "        raw\n",
+            "    }\n",
+        )));
+        assert!(output.contains("pub fn mode(&self) -> super::Mode"));
+    }
+
+    #[test]
+    fn conversion_getters_only_load_and_convert() {
+        let field = |name: &str, address: Range<u64>, conversion_method| Field {
+            cfg_attr: quote! {},
+            doc_attr: quote! {},
+            name: format_ident!("{name}"),
+            renamed_from: Vec::new(),
+            unit: None,
+            valid_when: None,
+            bit_order: BitOrder::LSB0,
+            address: Literal::u64_unsuffixed(address.start)..Literal::u64_unsuffixed(address.end),
+            base_type: format_ident!("u8"),
+            conversion_method,
+            access: Access::RO,
+        };
+
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![
+                    field("mode", 0..2, FieldConversionMethod::Into(quote! { Mode })),
+                    field(
+                        "state",
+                        2..4,
+                        FieldConversionMethod::UnsafeInto(quote! { State }),
+                    ),
+                    field(
+                        "kind",
+                        4..8,
+                        FieldConversionMethod::TryInto(quote! { Kind }),
+                    ),
+                ],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+
+        // The value is decoded from the bits on every call and returned by value.
+        // There's no cache, clone or anything else in between.
+        assert!(output.contains(concat!(
+            "    pub fn mode(&self) -> super::Mode {\n",
+            "        let raw = unsafe {\n",
+            "            ::device_driver::ops::load_lsb0::<\n",
+            "                u8,\n",
+            "                ::device_driver::ops::LE,\n",
+            "            >(&self.bits, 0, 2)\n",
+            "        };\n",
+            "        raw.into()\n",
+            "    }\n",
+        )));
+        assert!(output.contains(concat!(
+            "    pub fn state(&self) -> super::State {\n",
+            "        let raw = unsafe {\n",
+            "            ::device_driver::ops::load_lsb0::<\n",
+            "                u8,\n",
+            "                ::device_driver::ops::LE,\n",
+            "            >(&self.bits, 2, 4)\n",
+            "        };\n",
+            "        unsafe { raw.try_into().unwrap_unchecked() }\n",
+            "    }\n",
+        )));
+        assert!(output.contains(concat!(
+            "    pub fn kind(&self) -> Result<super::Kind, <super::Kind as TryFrom<u8>>::Error> {\n",
+            "        let raw = unsafe {\n",
+            "            ::device_driver::ops::load_lsb0::<\n",
+            "                u8,\n",
+            "                ::device_driver::ops::LE,\n",
+            "            >(&self.bits, 4, 8)\n",
+            "        };\n",
+            "        raw.try_into()\n",
+            "    }\n",
+        )));
+    }
+
+    #[test]
+    fn asymmetric_conversion_uses_functions() {
+        let output = generate_field_set(
+            &FieldSet {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("MyRegister"),
+                byte_order: ByteOrder::LE,
+                size_bits: 8,
+                reset_value: vec![0],
+                ref_reset_overrides: Vec::new(),
+                chip_variant_reset_values: Vec::new(),
+                fields: vec![Field {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("gain"),
+                    renamed_from: Vec::new(),
+                    unit: None,
+                    valid_when: None,
+                    bit_order: BitOrder::LSB0,
+                    address: Literal::u64_unsuffixed(0)..Literal::u64_unsuffixed(4),
+                    base_type: format_ident!("u8"),
+                    conversion_method: FieldConversionMethod::Functions {
+                        read_type: quote! { Gain },
+                        read_fn: quote! { crate::gain::decode },
+                        write_type: quote! { crate::GainSetting },
+                        write_fn: quote! { encode_gain },
+                    },
+                    access: Access::RW,
+                }],
+            },
+            None,
+            &quote! { pub },
+        );
+
+        let output = prettyplease::unparse(&syn::parse2(output).unwrap());
+        // The read function and type are relative to the crate root, the write function and type are not
+        assert!(output.contains(concat!(
+            "    pub fn gain(&self) -> super::Gain {\n",
+            "        let raw = unsafe {\n",
+            "            ::device_driver::ops::load_lsb0::<\n",
+            "                u8,\n",
+            "                ::device_driver::ops::LE,\n",
+            "            >(&self.bits, 0, 4)\n",
+            "        };\n",
+            "        crate::gain::decode(raw)\n",
+            "    }\n",
+        )));
+        assert!(output.contains(concat!(
+            "    pub fn set_gain(&mut self, value: crate::GainSetting) {\n",
+            "        let raw = super::encode_gain(value);\n",
+        )));
+        assert!(output.contains("pub fn gain_raw(&self) -> u8"));
+        assert!(output.contains("pub fn set_gain_raw(&mut self, raw: u8)"));
+    }
+
+    #[test]
+    fn bit_ops_correct() {
+        let field_set = FieldSet {
+            cfg_attr: quote! { #[cfg(windows)] },
+            doc_attr: quote! {},
+            name: format_ident!("MyRegister"),
+            byte_order: ByteOrder::LE,
+            size_bits: 8,
+            reset_value: vec![0],
+            ref_reset_overrides: Vec::new(),
+            chip_variant_reset_values: Vec::new(),
+            fields: Vec::new(),
+        };
+
+        assert!(generate_field_set_bit_ops(&field_set, false).is_empty());
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(
+                &syn::parse2(generate_field_set_bit_ops(&field_set, true)).unwrap()
+            ),
+            indoc! {"
+                #[cfg(windows)]
+                impl core::ops::BitAnd for MyRegister {
+                    type Output = Self;
+                    fn bitand(mut self, rhs: Self) -> Self::Output {
+                        self &= rhs;
+                        self
+                    }
+                }
+                #[cfg(windows)]
+                impl core::ops::BitAndAssign for MyRegister {
+                    fn bitand_assign(&mut self, rhs: Self) {
+                        for (l, r) in self.bits.iter_mut().zip(&rhs.bits) {
+                            *l &= *r;
+                        }
+                    }
+                }
+                #[cfg(windows)]
+                impl core::ops::BitOr for MyRegister {
+                    type Output = Self;
+                    fn bitor(mut self, rhs: Self) -> Self::Output {
+                        self |= rhs;
+                        self
+                    }
+                }
+                #[cfg(windows)]
+                impl core::ops::BitOrAssign for MyRegister {
+                    fn bitor_assign(&mut self, rhs: Self) {
+                        for (l, r) in self.bits.iter_mut().zip(&rhs.bits) {
+                            *l |= *r;
+                        }
+                    }
+                }
+                #[cfg(windows)]
+                impl core::ops::BitXor for MyRegister {
+                    type Output = Self;
+                    fn bitxor(mut self, rhs: Self) -> Self::Output {
+                        self ^= rhs;
+                        self
+                    }
+                }
+                #[cfg(windows)]
+                impl core::ops::BitXorAssign for MyRegister {
+                    fn bitxor_assign(&mut self, rhs: Self) {
+                        for (l, r) in self.bits.iter_mut().zip(&rhs.bits) {
+                            *l ^= *r;
+                        }
+                    }
+                }
+                #[cfg(windows)]
+                impl core::ops::Not for MyRegister {
+                    type Output = Self;
+                    fn not(mut self) -> Self::Output {
+                        for val in self.bits.iter_mut() {
+                            *val = !*val;
+                        }
+                        self
+                    }
+                }
+            "}
+        )
+    }
+
+    #[test]
+    fn try_from_u32_only_for_small_field_sets() {
+        let generate = |size_bits: u32| {
+            let output = generate_field_set(
+                &FieldSet {
+                    cfg_attr: quote! {},
+                    doc_attr: quote! {},
+                    name: format_ident!("MyRegister"),
+                    byte_order: ByteOrder::LE,
+                    size_bits,
+                    reset_value: vec![0; size_bits.div_ceil(8) as usize],
+                    ref_reset_overrides: Vec::new(),
+                    chip_variant_reset_values: Vec::new(),
+                    fields: Vec::new(),
+                },
+                None,
+                &quote! { pub },
+            );
+            prettyplease::unparse(&syn::parse2(output).unwrap())
+        };
+
+        let output = generate(16);
+        assert!(output.contains("impl TryFrom<u32> for MyRegister {"));
+        assert!(output.contains("if value >> 16 != 0 {"));
+        assert!(output.contains("let bytes = value.to_le_bytes();"));
+        assert!(output.contains("bits: [bytes[0], bytes[1]]"));
+
+        let output = generate(32);
+        assert!(output.contains("impl TryFrom<u32> for MyRegister {"));
+        assert!(!output.contains("if value >>"));
+
+        assert!(!generate(40).contains("TryFrom<u32>"));
+    }
 }
@@ -0,0 +1,56 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::lir::LengthPrefix;
+
+/// Generate the `LengthPrefix` impls so the buffers can read how many payload bytes follow the prefix
+pub fn generate_length_prefixes(length_prefixes: &[LengthPrefix]) -> TokenStream {
+    length_prefixes
+        .iter()
+        .map(|length_prefix| {
+            let LengthPrefix {
+                cfg_attr,
+                field_set_name,
+                field_name,
+            } = length_prefix;
+
+            quote! {
+                #cfg_attr
+                impl ::device_driver::LengthPrefix for #field_set_name {
+                    fn payload_len(&self) -> usize {
+                        self.#field_name() as usize
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use quote::format_ident;
+
+    use super::*;
+
+    #[test]
+    fn length_prefixes_correct() {
+        let output = generate_length_prefixes(&[LengthPrefix {
+            cfg_attr: quote! { #[cfg(unix)] },
+            field_set_name: format_ident!("PacketsLength"),
+            field_name: format_ident!("length"),
+        }]);
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                #[cfg(unix)]
+                impl ::device_driver::LengthPrefix for PacketsLength {
+                    fn payload_len(&self) -> usize {
+                        self.length() as usize
+                    }
+                }
+            "}
+        );
+    }
+}
@@ -1,43 +1,245 @@
-use block_transform::generate_block;
+use block_transform::{
+    generate_block, generate_command_poll_methods, generate_configure,
+    generate_defined_address_check, generate_flat_accessors, generate_fold_methods,
+    generate_id_check, generate_init, generate_memory_constructor, generate_mmio,
+    generate_repeat_indices, generate_reset_with_delay, generate_snapshot, generate_touch_methods,
+    generate_word_constructor,
+};
+use command_echo_generator::generate_command_echoes;
+use const_fields_generator::generate_const_fields;
+use datasheet_tests_generator::generate_datasheet_tests;
+use decode_any_generator::generate_decode_any;
+use dyn_access_generator::generate_dyn_access;
 use enum_transform::generate_enum;
 use field_set_enum_generator::generate_field_set_enum;
-use field_set_transform::generate_field_set;
+use field_set_transform::{
+    generate_field_set, generate_field_set_bit_ops, generate_field_set_ufmt,
+};
+use length_prefix_generator::generate_length_prefixes;
+use newtype_transform::generate_newtype;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{ToTokens, format_ident, quote};
+use read_hook_generator::generate_read_hook;
+use variable_size_generator::generate_variable_size_registers;
 
-use super::{Device, Enum};
+use super::{Device, Enum, FieldSetGroup, Newtype};
 
 mod block_transform;
+mod command_echo_generator;
+mod const_fields_generator;
+mod datasheet_tests_generator;
+mod decode_any_generator;
+mod dyn_access_generator;
 mod enum_transform;
 mod field_set_enum_generator;
 mod field_set_transform;
+mod length_prefix_generator;
+mod newtype_transform;
+mod read_hook_generator;
+mod variable_size_generator;
 
 pub fn transform(device: Device) -> TokenStream {
     let mut tokens = TokenStream::new();
+    let detect_bus_stuck = device
+        .read_hook
+        .as_ref()
+        .is_some_and(|read_hook| read_hook.detect_bus_stuck);
 
     for block in &device.blocks {
         tokens.extend(generate_block(
             block,
             &device.internal_address_type,
             &device.register_address_type,
+            device.read_hook.as_ref(),
+            &device.visibility,
+        ));
+
+        if device.flat_accessors {
+            tokens.extend(generate_flat_accessors(
+                block,
+                &device.field_sets,
+                &device.register_address_type,
+                detect_bus_stuck,
+                &device.visibility,
+            ));
+        }
+
+        tokens.extend(generate_touch_methods(
+            block,
+            &device.field_sets,
+            &device.register_address_type,
+            detect_bus_stuck,
+            &device.visibility,
+        ));
+        tokens.extend(generate_fold_methods(
+            block,
+            &device.register_address_type,
+            detect_bus_stuck,
+            &device.visibility,
+        ));
+        tokens.extend(generate_repeat_indices(block, &device.visibility));
+        tokens.extend(generate_command_poll_methods(
+            block,
+            &device.register_address_type,
+            detect_bus_stuck,
+            &device.visibility,
+        ));
+    }
+
+    let root_block = device
+        .blocks
+        .iter()
+        .find(|b| b.root)
+        .expect("There's always a root block");
+    tokens.extend(generate_defined_address_check(
+        root_block,
+        &device.register_address_type,
+        &device.defined_register_addresses,
+        &device.visibility,
+    ));
+    // The memory interface is byte-addressed, so it can't be used for word-addressed devices
+    if device.word_interface.is_none() {
+        tokens.extend(generate_memory_constructor(
+            root_block,
+            &device.register_address_type,
+            &device.defined_register_addresses,
+            &device.visibility,
         ));
     }
+    tokens.extend(generate_word_constructor(
+        root_block,
+        device.word_interface.as_ref(),
+        &device.visibility,
+    ));
+    tokens.extend(generate_init(
+        root_block,
+        &device.register_address_type,
+        &device.reset_sequence,
+        &device.visibility,
+    ));
+    tokens.extend(generate_reset_with_delay(
+        root_block,
+        device.reset_hold_us.as_ref(),
+        &device.visibility,
+    ));
+    tokens.extend(generate_id_check(
+        root_block,
+        &device.register_address_type,
+        device.id_check.as_ref(),
+        detect_bus_stuck,
+        &device.visibility,
+    ));
+    tokens.extend(generate_configure(
+        root_block,
+        &device.register_address_type,
+        &device.configure,
+        &device.visibility,
+    ));
+    tokens.extend(generate_mmio(
+        root_block,
+        device.mmio.as_ref(),
+        &device.visibility,
+    ));
+    tokens.extend(generate_dyn_access(
+        root_block,
+        &device.register_address_type,
+        device.dyn_access.as_deref(),
+        detect_bus_stuck,
+        &device.visibility,
+    ));
+    tokens.extend(generate_read_hook(
+        root_block,
+        &device.register_address_type,
+        device.read_hook.as_ref(),
+        &device.visibility,
+    ));
+    tokens.extend(generate_snapshot(
+        root_block,
+        &device.register_address_type,
+        &device.snapshot,
+        device.defmt_feature.as_deref(),
+        detect_bus_stuck,
+        &device.visibility,
+    ));
+
+    // The field sets are one module deeper than the device and the grouped ones two
+    let field_set_visibility = nested_visibility(&device.visibility, 1);
+    let grouped_field_set_visibility = nested_visibility(&device.visibility, 2);
 
     let mut field_set_tokens = TokenStream::new();
+    let mut grouped_field_set_tokens = vec![TokenStream::new(); device.field_set_groups.len()];
     for field_set in &device.field_sets {
-        field_set_tokens.extend(generate_field_set(
+        let group_index = device
+            .field_set_groups
+            .iter()
+            .position(|group| group.field_set_names.contains(&field_set.name));
+        let (tokens, visibility) = match group_index {
+            Some(index) => (
+                &mut grouped_field_set_tokens[index],
+                &grouped_field_set_visibility,
+            ),
+            None => (&mut field_set_tokens, &field_set_visibility),
+        };
+
+        tokens.extend(generate_field_set(
             field_set,
             device.defmt_feature.as_deref(),
+            visibility,
+        ));
+        tokens.extend(generate_field_set_bit_ops(
+            field_set,
+            device.generate_bit_ops,
+        ));
+        tokens.extend(generate_field_set_ufmt(
+            field_set,
+            device.ufmt_feature.as_deref(),
         ));
     }
 
+    // The groups are re-exported so the field sets can be used by the same path as without grouping
+    for (group, group_tokens) in device.field_set_groups.iter().zip(grouped_field_set_tokens) {
+        let FieldSetGroup {
+            module_name,
+            doc_attr,
+            field_set_names: _,
+        } = group;
+
+        field_set_tokens.extend(quote! {
+            #doc_attr
+            pub mod #module_name {
+                use super::*;
+
+                #group_tokens
+            }
+
+            pub use #module_name::*;
+        });
+    }
+
     field_set_tokens.extend(generate_field_set_enum(
         &device.field_sets,
         device.defmt_feature.as_deref(),
+        &field_set_visibility,
+    ));
+    field_set_tokens.extend(generate_decode_any(
+        &device.decodable_registers,
+        &device.register_address_type,
+        &field_set_visibility,
+    ));
+    field_set_tokens.extend(generate_const_fields(
+        &device.field_sets,
+        &field_set_visibility,
+    ));
+    field_set_tokens.extend(generate_length_prefixes(&device.length_prefixes));
+    field_set_tokens.extend(generate_variable_size_registers(
+        &device.variable_size_registers,
     ));
+    field_set_tokens.extend(generate_command_echoes(&device.command_echoes));
 
+    let root_cfg_attr = &root_block.cfg_attr;
     tokens.extend(quote! {
         /// Module containing the generated fieldsets of the registers and commands
+        #root_cfg_attr
         pub mod field_sets {
             use super::*;
 
@@ -46,8 +248,94 @@ pub fn transform(device: Device) -> TokenStream {
     });
 
     for enum_value in &device.enums {
-        tokens.extend(generate_enum(enum_value, device.defmt_feature.as_deref()));
+        tokens.extend(generate_enum(
+            enum_value,
+            &device.enum_derives,
+            device.defmt_feature.as_deref(),
+            &device.visibility,
+        ));
+    }
+
+    for newtype in &device.newtypes {
+        tokens.extend(generate_newtype(
+            newtype,
+            device.defmt_feature.as_deref(),
+            &device.visibility,
+        ));
     }
 
+    tokens.extend(generate_datasheet_tests(
+        root_cfg_attr,
+        &device.register_expectations,
+    ));
+
     tokens
 }
+
+/// Get the visibility for items that are `levels` modules deeper than the device,
+/// so they are visible from the same place as the items next to the device.
+///
+/// A relative visibility like `pub(super)` would otherwise be relative to the generated module instead.
+pub(crate) fn nested_visibility(visibility: &TokenStream, levels: usize) -> TokenStream {
+    let visibility = syn::parse2::<syn::Visibility>(visibility.clone())
+        .expect("The visibility has been validated already");
+
+    let path = match &visibility {
+        syn::Visibility::Public(_) => return visibility.into_token_stream(),
+        syn::Visibility::Inherited => syn::parse_quote! { self },
+        syn::Visibility::Restricted(restricted) => (*restricted.path).clone(),
+    };
+
+    let is_relative = path.leading_colon.is_none()
+        && path
+            .segments
+            .first()
+            .is_some_and(|segment| segment.ident == "self" || segment.ident == "super");
+    if !is_relative || levels == 0 {
+        return visibility.into_token_stream();
+    }
+
+    // `self` is the module of the device, which is `super` from one level deeper
+    let segments = path
+        .segments
+        .iter()
+        .skip_while(|segment| segment.ident == "self")
+        .map(|segment| &segment.ident);
+    let supers = std::iter::repeat_n(format_ident!("super"), levels);
+    let mut segments = supers.chain(segments.cloned()).collect::<Vec<_>>();
+
+    match segments.as_slice() {
+        [single] if single == "super" => quote! { pub(super) },
+        _ => {
+            let first = segments.remove(0);
+            quote! { pub(in #first #(::#segments)*) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_visibility_correct() {
+        let nested = |visibility: &str, levels| {
+            nested_visibility(&visibility.parse().unwrap(), levels)
+                .to_string()
+                .replace(' ', "")
+        };
+
+        assert_eq!(nested("pub", 2), "pub");
+        assert_eq!(nested("pub(crate)", 2), "pub(crate)");
+        assert_eq!(nested("pub(in crate::foo)", 1), "pub(incrate::foo)");
+        assert_eq!(nested("pub(super)", 0), "pub(super)");
+        assert_eq!(nested("", 1), "pub(super)");
+        assert_eq!(nested("pub(self)", 1), "pub(super)");
+        assert_eq!(nested("pub(self)", 2), "pub(insuper::super)");
+        assert_eq!(nested("pub(super)", 1), "pub(insuper::super)");
+        assert_eq!(
+            nested("pub(in super::super)", 2),
+            "pub(insuper::super::super::super)"
+        );
+    }
+}
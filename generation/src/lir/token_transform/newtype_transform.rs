@@ -0,0 +1,135 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::Newtype;
+
+pub fn generate_newtype(
+    value: &Newtype,
+    defmt_feature: Option<&str>,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Newtype {
+        cfg_attr,
+        doc_attr,
+        name,
+        inner,
+        base_type,
+    } = value;
+
+    let defmt_attr = match defmt_feature {
+        Some(feature_name) => {
+            quote! { #[cfg_attr(feature = #feature_name, derive(defmt::Format))] }
+        }
+        None => quote! {},
+    };
+
+    // A cast to the same type is a lint warning in the user crate
+    let (from_base, into_base) = match inner == base_type {
+        true => (quote! { Self(val) }, quote! { val.0 }),
+        false => (
+            quote! { Self(val as #inner) },
+            quote! { val.0 as #base_type },
+        ),
+    };
+
+    quote! {
+        #doc_attr
+        #cfg_attr
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+        #defmt_attr
+        #visibility struct #name(pub #inner);
+
+        #cfg_attr
+        impl From<#base_type> for #name {
+            fn from(val: #base_type) -> Self {
+                #from_base
+            }
+        }
+
+        #cfg_attr
+        impl From<#name> for #base_type {
+            fn from(val: #name) -> Self {
+                #into_base
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use quote::format_ident;
+
+    use super::*;
+
+    #[test]
+    fn newtype_correct() {
+        let output = generate_newtype(
+            &Newtype {
+                cfg_attr: quote! { #[cfg(unix)] },
+                doc_attr: quote! { #[doc = "A temperature"] },
+                name: format_ident!("Temperature"),
+                inner: format_ident!("u16"),
+                base_type: format_ident!("u16"),
+            },
+            Some("defmt-03"),
+            &quote! { pub },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                ///A temperature
+                #[cfg(unix)]
+                #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+                #[cfg_attr(feature = \"defmt-03\", derive(defmt::Format))]
+                pub struct Temperature(pub u16);
+                #[cfg(unix)]
+                impl From<u16> for Temperature {
+                    fn from(val: u16) -> Self {
+                        Self(val)
+                    }
+                }
+                #[cfg(unix)]
+                impl From<Temperature> for u16 {
+                    fn from(val: Temperature) -> Self {
+                        val.0
+                    }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn newtype_with_different_inner_type_casts() {
+        let output = generate_newtype(
+            &Newtype {
+                cfg_attr: quote! {},
+                doc_attr: quote! {},
+                name: format_ident!("Voltage"),
+                inner: format_ident!("i32"),
+                base_type: format_ident!("i16"),
+            },
+            None,
+            &quote! { pub(crate) },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+                pub(crate) struct Voltage(pub i32);
+                impl From<i16> for Voltage {
+                    fn from(val: i16) -> Self {
+                        Self(val as i32)
+                    }
+                }
+                impl From<Voltage> for i16 {
+                    fn from(val: Voltage) -> Self {
+                        val.0 as i16
+                    }
+                }
+            "}
+        );
+    }
+}
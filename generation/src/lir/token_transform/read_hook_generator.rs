@@ -0,0 +1,128 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+use crate::lir::{Block, ReadHook};
+
+/// Generate the type that implements the read hook of the register operations.
+/// The register accessors only name this type when there is a hook, so without one nothing is generated or called.
+pub fn generate_read_hook(
+    root_block: &Block,
+    register_address_type: &Ident,
+    read_hook: Option<&ReadHook>,
+    visibility: &TokenStream,
+) -> TokenStream {
+    let Some(ReadHook {
+        name,
+        post_read,
+        detect_bus_stuck,
+    }) = read_hook
+    else {
+        return TokenStream::new();
+    };
+
+    let cfg_attr = &root_block.cfg_attr;
+
+    let check_read = match detect_bus_stuck {
+        true => quote! {
+            type Error<E> = ::device_driver::BusStuckError<E>;
+
+            #[inline]
+            fn check_read<Register: ::device_driver::FieldSet, E>(
+                read: &[u8],
+                reset: fn() -> Register,
+            ) -> Result<(), Self::Error<E>> {
+                ::device_driver::check_bus_stuck(read, reset)
+            }
+        },
+        false => quote! {
+            type Error<E> = E;
+
+            #[inline]
+            fn check_read<Register: ::device_driver::FieldSet, E>(
+                _read: &[u8],
+                _reset: fn() -> Register,
+            ) -> Result<(), Self::Error<E>> {
+                Ok(())
+            }
+        },
+    };
+
+    let after_read = match post_read {
+        Some(post_read) => quote! {
+            #[inline]
+            fn after_read(address: #register_address_type) {
+                #post_read(address)
+            }
+        },
+        None => quote! {
+            #[inline]
+            fn after_read(_address: #register_address_type) {}
+        },
+    };
+
+    quote! {
+        /// The hook that is run by the register operations after every successful read
+        #cfg_attr
+        #visibility struct #name;
+
+        #cfg_attr
+        impl ::device_driver::ReadHook<#register_address_type> for #name {
+            #check_read
+            #after_read
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use quote::format_ident;
+
+    use super::*;
+
+    #[test]
+    fn read_hook_correct() {
+        let root_block = Block {
+            cfg_attr: quote! { #[cfg(unix)] },
+            doc_attr: quote! {},
+            root: true,
+            name: format_ident!("Device"),
+            methods: Vec::new(),
+        };
+
+        let output = generate_read_hook(
+            &root_block,
+            &format_ident!("u8"),
+            Some(&ReadHook {
+                name: format_ident!("DeviceReadHook"),
+                post_read: Some(quote! { crate::on_read }),
+                detect_bus_stuck: true,
+            }),
+            &quote! { pub(crate) },
+        );
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                /// The hook that is run by the register operations after every successful read
+                #[cfg(unix)]
+                pub(crate) struct DeviceReadHook;
+                #[cfg(unix)]
+                impl ::device_driver::ReadHook<u8> for DeviceReadHook {
+                    type Error<E> = ::device_driver::BusStuckError<E>;
+                    #[inline]
+                    fn check_read<Register: ::device_driver::FieldSet, E>(
+                        read: &[u8],
+                        reset: fn() -> Register,
+                    ) -> Result<(), Self::Error<E>> {
+                        ::device_driver::check_bus_stuck(read, reset)
+                    }
+                    #[inline]
+                    fn after_read(address: u8) {
+                        crate::on_read(address)
+                    }
+                }
+            "}
+        );
+    }
+}
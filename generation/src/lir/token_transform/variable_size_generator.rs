@@ -0,0 +1,62 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::lir::VariableSizeRegister;
+
+/// Generate the `VariableSize` impls so the registers can be read with the size their length field indicates
+pub fn generate_variable_size_registers(registers: &[VariableSizeRegister]) -> TokenStream {
+    registers
+        .iter()
+        .map(|register| {
+            let VariableSizeRegister {
+                cfg_attr,
+                field_set_name,
+                length_field_name,
+                prefix_size_bits,
+            } = register;
+
+            quote! {
+                #cfg_attr
+                impl ::device_driver::VariableSize for #field_set_name {
+                    const LENGTH_PREFIX_SIZE_BITS: u32 = #prefix_size_bits;
+
+                    fn size_bytes(&self) -> usize {
+                        Self::LENGTH_PREFIX_SIZE_BITS as usize / 8 + self.#length_field_name() as usize
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use proc_macro2::Literal;
+    use quote::format_ident;
+
+    use super::*;
+
+    #[test]
+    fn variable_size_registers_correct() {
+        let output = generate_variable_size_registers(&[VariableSizeRegister {
+            cfg_attr: quote! { #[cfg(unix)] },
+            field_set_name: format_ident!("Message"),
+            length_field_name: format_ident!("len"),
+            prefix_size_bits: Literal::u32_unsuffixed(16),
+        }]);
+
+        pretty_assertions::assert_eq!(
+            prettyplease::unparse(&syn::parse2(output).unwrap()),
+            indoc! {"
+                #[cfg(unix)]
+                impl ::device_driver::VariableSize for Message {
+                    const LENGTH_PREFIX_SIZE_BITS: u32 = 16;
+                    fn size_bytes(&self) -> usize {
+                        Self::LENGTH_PREFIX_SIZE_BITS as usize / 8 + self.len() as usize
+                    }
+                }
+            "}
+        );
+    }
+}
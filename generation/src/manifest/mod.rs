@@ -15,11 +15,10 @@ pub fn transform(value: impl Value) -> anyhow::Result<mir::Device> {
         Default::default()
     };
 
-    let objects = device_map
-        .iter()
-        .filter(|(k, _)| *k != "config")
-        .map(transform_object)
-        .collect::<Result<_, _>>()?;
+    let objects = transform_objects(
+        device_map.iter().filter(|(k, _)| *k != "config"),
+        &global_config,
+    )?;
 
     Ok(mir::Device {
         global_config,
@@ -51,6 +50,18 @@ fn transform_global_config(value: &impl Value) -> anyhow::Result<mir::GlobalConf
                         .with_context(|| format!("Parsing error for {key}"))?,
                 )
             }
+            "default_register_byte_order" => {
+                global_config.default_register_byte_order = Some(
+                    transform_byte_order(value)
+                        .with_context(|| format!("Parsing error for {key}"))?,
+                )
+            }
+            "default_command_byte_order" => {
+                global_config.default_command_byte_order = Some(
+                    transform_byte_order(value)
+                        .with_context(|| format!("Parsing error for {key}"))?,
+                )
+            }
             "default_bit_order" => {
                 global_config.default_bit_order = transform_bit_order(value)
                     .with_context(|| format!("Parsing error for {key}"))?
@@ -85,6 +96,137 @@ fn transform_global_config(value: &impl Value) -> anyhow::Result<mir::GlobalConf
                         .into(),
                 )
             }
+            "feature_gate" => {
+                global_config.feature_gate = Some(
+                    value
+                        .as_string()
+                        .with_context(|| format!("Parsing error for {key}"))?
+                        .into(),
+                )
+            }
+            "chip_variants" => {
+                global_config.chip_variants = value
+                    .as_array()
+                    .and_then(|variants| {
+                        variants
+                            .iter()
+                            .map(|variant| Ok(variant.as_string()?.into()))
+                            .collect()
+                    })
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "dyn_access" => {
+                global_config.dyn_access = value
+                    .as_bool()
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "shared_command_interface" => {
+                global_config.shared_command_interface = value
+                    .as_bool()
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "post_read" => {
+                global_config.post_read = Some(
+                    value
+                        .as_string()
+                        .with_context(|| format!("Parsing error for {key}"))?
+                        .into(),
+                )
+            }
+            "enum_derives" => {
+                global_config.enum_derives = Some(
+                    value
+                        .as_array()
+                        .and_then(|derives| {
+                            derives
+                                .iter()
+                                .map(|derive| Ok(derive.as_string()?.into()))
+                                .collect()
+                        })
+                        .with_context(|| format!("Parsing error for {key}"))?,
+                )
+            }
+            "detect_bus_stuck" => {
+                global_config.detect_bus_stuck = value
+                    .as_bool()
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "ufmt_feature" => {
+                global_config.ufmt_feature = Some(
+                    value
+                        .as_string()
+                        .with_context(|| format!("Parsing error for {key}"))?
+                        .into(),
+                )
+            }
+            "auto_address" => {
+                global_config.auto_address = value
+                    .as_bool()
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "reset_sequence" => {
+                global_config.reset_sequence = transform_reset_sequence(value)
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "visibility" => {
+                global_config.visibility = Some(
+                    value
+                        .as_string()
+                        .with_context(|| format!("Parsing error for {key}"))?
+                        .into(),
+                )
+            }
+            "flat_accessors" => {
+                global_config.flat_accessors = value
+                    .as_bool()
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "crc_algorithm" => {
+                global_config.crc_algorithm = transform_crc_algorithm(value)
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "address_unit" => {
+                global_config.address_unit = transform_address_unit(value)
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "mmio" => {
+                global_config.mmio = value
+                    .as_bool()
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "generate_bit_ops" => {
+                global_config.generate_bit_ops = value
+                    .as_bool()
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "group_by_kind" => {
+                global_config.group_by_kind = value
+                    .as_bool()
+                    .with_context(|| format!("Parsing error for {key}"))?
+            }
+            "reset_hold_us" => {
+                global_config.reset_hold_us = Some(
+                    value
+                        .as_uint()
+                        .map_err(anyhow::Error::from)
+                        .and_then(|value| Ok(u32::try_from(value)?))
+                        .with_context(|| format!("Parsing error for {key}"))?,
+                )
+            }
+            "id_register" => {
+                global_config.id_register = Some(
+                    transform_id_register(value)
+                        .with_context(|| format!("Parsing error for {key}"))?,
+                )
+            }
+            "max_fields" => {
+                global_config.max_fields = Some(
+                    value
+                        .as_uint()
+                        .with_context(|| format!("Parsing error for {key}"))?
+                        as usize,
+                )
+            }
             _ => bail!("No config with key `{key}` is recognized"),
         }
     }
@@ -92,13 +234,60 @@ fn transform_global_config(value: &impl Value) -> anyhow::Result<mir::GlobalConf
     Ok(global_config)
 }
 
+fn transform_reset_sequence(value: &impl Value) -> anyhow::Result<Vec<(String, mir::ResetValue)>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|entry| {
+            let entry = entry.as_array()?;
+            ensure!(
+                entry.len() == 2,
+                "Each entry of the reset sequence must be an array of a register name and a value"
+            );
+
+            Ok((
+                entry[0].as_string()?.into(),
+                mir::ResetValue::Integer(entry[1].as_uint()? as u128),
+            ))
+        })
+        .collect()
+}
+
+fn transform_id_register(value: &impl Value) -> anyhow::Result<mir::IdRegister> {
+    let entry = value.as_array()?;
+    ensure!(
+        entry.len() == 3,
+        "The id register must be an array of a register name, a field name and the expected value"
+    );
+
+    Ok(mir::IdRegister {
+        register: entry[0].as_string()?.into(),
+        field: entry[1].as_string()?.into(),
+        expected: entry[2].as_uint()? as u128,
+    })
+}
+
+fn transform_poll_until(value: &impl Value) -> anyhow::Result<mir::PollUntil> {
+    let entry = value.as_array()?;
+    ensure!(
+        entry.len() == 2,
+        "The poll must be an array of a register name and a field name"
+    );
+
+    Ok(mir::PollUntil {
+        register: entry[0].as_string()?.into(),
+        field: entry[1].as_string()?.into(),
+    })
+}
+
 fn transform_access(value: &impl Value) -> anyhow::Result<mir::Access> {
     match value.as_string()? {
         "ReadWrite" | "RW" => Ok(mir::Access::RW),
         "ReadOnly" | "RO" => Ok(mir::Access::RO),
         "WriteOnly" | "WO" => Ok(mir::Access::WO),
+        "ReadClear" | "RC" => Ok(mir::Access::RC),
         val => Err(anyhow::anyhow!(
-            "No access value `{val}` exists. Values are limited to \"ReadWrite\", \"RW\", \"ReadOnly\", \"RO\", \"WriteOnly\", \"WO\""
+            "No access value `{val}` exists. Values are limited to \"ReadWrite\", \"RW\", \"ReadOnly\", \"RO\", \"WriteOnly\", \"WO\", \"ReadClear\", \"RC\""
         )),
     }
 }
@@ -107,8 +296,9 @@ fn transform_byte_order(value: &impl Value) -> anyhow::Result<mir::ByteOrder> {
     match value.as_string()? {
         "LE" => Ok(mir::ByteOrder::LE),
         "BE" => Ok(mir::ByteOrder::BE),
+        "SwapPairs" => Ok(mir::ByteOrder::SwapPairs),
         val => Err(anyhow::anyhow!(
-            "No byte order value `{val}` exists. Values are limited to \"LE\" and \"BE\""
+            "No byte order value `{val}` exists. Values are limited to \"LE\", \"BE\" and \"SwapPairs\""
         )),
     }
 }
@@ -123,6 +313,27 @@ fn transform_bit_order(value: &impl Value) -> anyhow::Result<mir::BitOrder> {
     }
 }
 
+fn transform_crc_algorithm(value: &impl Value) -> anyhow::Result<mir::CrcAlgorithm> {
+    match value.as_string()? {
+        "Crc8Smbus" => Ok(mir::CrcAlgorithm::Crc8Smbus),
+        "Crc8Nrsc5" => Ok(mir::CrcAlgorithm::Crc8Nrsc5),
+        "Crc8Autosar" => Ok(mir::CrcAlgorithm::Crc8Autosar),
+        val => Err(anyhow::anyhow!(
+            "No crc algorithm value `{val}` exists. Values are limited to \"Crc8Smbus\", \"Crc8Nrsc5\" and \"Crc8Autosar\""
+        )),
+    }
+}
+
+fn transform_address_unit(value: &impl Value) -> anyhow::Result<mir::AddressUnit> {
+    match value.as_string()? {
+        "Byte" => Ok(mir::AddressUnit::Byte),
+        "Word16" => Ok(mir::AddressUnit::Word16),
+        val => Err(anyhow::anyhow!(
+            "No address unit value `{val}` exists. Values are limited to \"Byte\" and \"Word16\""
+        )),
+    }
+}
+
 fn transform_integer_type(value: &impl Value) -> anyhow::Result<mir::Integer> {
     match value.as_string()? {
         "u8" => Ok(mir::Integer::U8),
@@ -165,7 +376,31 @@ fn transform_name_word_boundaries(value: &impl Value) -> anyhow::Result<Vec<Boun
     }
 }
 
-fn transform_object((key, value): (&str, &impl Value)) -> anyhow::Result<mir::Object> {
+fn transform_objects<'a, V: Value + 'a>(
+    objects: impl Iterator<Item = (&'a str, &'a V)>,
+    global_config: &mir::GlobalConfig,
+) -> anyhow::Result<Vec<mir::Object>> {
+    // The address the next register gets when it has no address specified and auto addressing is on
+    let mut auto_address = 0;
+
+    objects
+        .map(|object| {
+            let object = transform_object(object, global_config, auto_address)?;
+
+            if let mir::Object::Register(register) = &object {
+                auto_address = register.next_auto_address(global_config.address_unit);
+            }
+
+            Ok(object)
+        })
+        .collect()
+}
+
+fn transform_object(
+    (key, value): (&str, &impl Value),
+    global_config: &mir::GlobalConfig,
+    auto_address: i64,
+) -> anyhow::Result<mir::Object> {
     let try_ = || {
         let object_map = value.as_map()?;
 
@@ -175,13 +410,23 @@ fn transform_object((key, value): (&str, &impl Value)) -> anyhow::Result<mir::Ob
             .as_string()?;
 
         match object_type {
-            "block" => Ok(mir::Object::Block(transform_block(key, object_map)?)),
-            "register" => Ok(mir::Object::Register(transform_register(key, object_map)?)),
+            "block" => Ok(mir::Object::Block(transform_block(
+                key,
+                object_map,
+                global_config,
+            )?)),
+            "register" => Ok(mir::Object::Register(transform_register(
+                key,
+                object_map,
+                global_config,
+                auto_address,
+            )?)),
             "command" => Ok(mir::Object::Command(transform_command(key, object_map)?)),
             "buffer" => Ok(mir::Object::Buffer(transform_buffer(key, object_map)?)),
             "ref" => Ok(mir::Object::Ref(transform_ref(key, object_map)?)),
+            "alias" => Ok(mir::Object::Ref(transform_alias(key, object_map)?)),
             val => Err(anyhow!(
-                "Unexpected object type '{val}'. Select one of \"block\", \"register\", \"command\", \"buffer\" or \"ref\""
+                "Unexpected object type '{val}'. Select one of \"block\", \"register\", \"command\", \"buffer\", \"ref\" or \"alias\""
             )),
         }
     };
@@ -189,7 +434,11 @@ fn transform_object((key, value): (&str, &impl Value)) -> anyhow::Result<mir::Ob
     try_().with_context(|| format!("Parsing object `{key}`"))
 }
 
-fn transform_block(name: &str, map: &impl Map) -> anyhow::Result<mir::Block> {
+fn transform_block(
+    name: &str,
+    map: &impl Map,
+    global_config: &mir::GlobalConfig,
+) -> anyhow::Result<mir::Block> {
     let mut block = mir::Block {
         name: name.into(),
         ..Default::default()
@@ -200,12 +449,7 @@ fn transform_block(name: &str, map: &impl Map) -> anyhow::Result<mir::Block> {
             value
                 .as_map()
                 .map_err(anyhow::Error::from)
-                .map(|object_map| {
-                    object_map
-                        .iter()
-                        .map(transform_object)
-                        .collect::<Result<Vec<mir::Object>, anyhow::Error>>()
-                })
+                .map(|object_map| transform_objects(object_map.iter(), global_config))
                 .and_then(std::convert::identity)
                 .context("Parsing error for 'objects'")
         };
@@ -240,13 +484,23 @@ fn transform_block(name: &str, map: &impl Map) -> anyhow::Result<mir::Block> {
     Ok(block)
 }
 
-fn transform_register(name: &str, map: &impl Map) -> anyhow::Result<mir::Register> {
+fn transform_register(
+    name: &str,
+    map: &impl Map,
+    global_config: &mir::GlobalConfig,
+    auto_address: i64,
+) -> anyhow::Result<mir::Register> {
     let mut register = mir::Register {
         name: name.into(),
+        address: auto_address,
         ..Default::default()
     };
 
     for required_key in ["address", "size_bits"] {
+        if required_key == "address" && global_config.auto_address {
+            continue;
+        }
+
         if !map.contains_key(required_key) {
             bail!("Register definition must contain the '{required_key}' field");
         }
@@ -277,7 +531,22 @@ fn transform_register(name: &str, map: &impl Map) -> anyhow::Result<mir::Registe
                     transform_bit_order(value).context("Parsing error for 'bit_order'")?;
             }
             "address" => {
-                register.address = value.as_int().context("Parsing error for 'address'")?;
+                if let Ok(addresses) = value.as_array() {
+                    let addresses = addresses
+                        .iter()
+                        .map(|address| address.as_int())
+                        .collect::<Result<Vec<_>, _>>()
+                        .context("Parsing error for 'address'")?;
+                    let Some(first) = addresses.first().copied() else {
+                        bail!("Parsing error for 'address': the address span must not be empty");
+                    };
+
+                    register.address = first;
+                    register.span_offsets =
+                        addresses.iter().map(|address| address - first).collect();
+                } else {
+                    register.address = value.as_int().context("Parsing error for 'address'")?;
+                }
             }
             "size_bits" => {
                 register.size_bits = value
@@ -304,8 +573,10 @@ fn transform_register(name: &str, map: &impl Map) -> anyhow::Result<mir::Registe
                         Ok(val) => mir::ResetValue::Array(val),
                         Err(e) => return Err(e.context("Parsing error for 'reset_value")),
                     }
+                } else if let Ok(rv) = value.as_map() {
+                    transform_reset_value_fields(rv).context("Parsing error for 'reset_value")?
                 } else {
-                    return Err(anyhow!("Field must be an integer or an array")
+                    return Err(anyhow!("Field must be an integer, an array or a map")
                         .context("Parsing error for 'reset_value"));
                 })
             }
@@ -323,6 +594,38 @@ fn transform_register(name: &str, map: &impl Map) -> anyhow::Result<mir::Registe
                     .as_bool()
                     .context("Parsing error for 'allow_address_overlap'")?;
             }
+            "reserved_rest" => {
+                register.reserved_rest = value
+                    .as_bool()
+                    .context("Parsing error for 'reserved_rest'")?;
+            }
+            "shadowed" => {
+                register.shadowed = value.as_bool().context("Parsing error for 'shadowed'")?;
+            }
+            "expect_address" => {
+                register.expect_address = Some(
+                    value
+                        .as_int()
+                        .context("Parsing error for 'expect_address'")?,
+                );
+            }
+            "length_field" => {
+                register.length_field = Some(
+                    value
+                        .as_string()
+                        .context("Parsing error for 'length_field'")?
+                        .into(),
+                );
+            }
+            "expect_size" => {
+                register.expect_size_bits = Some(
+                    value
+                        .as_uint()
+                        .context("Parsing error for 'expect_size'")?
+                        .try_into()
+                        .context("Parsing error for 'expect_size'")?,
+                );
+            }
             "fields" => {
                 register.fields = transform_fields(value).context("Parsing error for 'fields'")?;
             }
@@ -369,7 +672,20 @@ fn transform_command(name: &str, map: &impl Map) -> anyhow::Result<mir::Command>
                     transform_bit_order(value).context("Parsing error for 'bit_order'")?;
             }
             "address" => {
-                command.address = value.as_int().context("Parsing error for 'address'")?;
+                if let Ok(bytes) = value.as_array() {
+                    command.opcode = bytes
+                        .iter()
+                        .map(|byte| {
+                            u8::try_from(byte.as_uint()?).context("Opcode must contain bytes")
+                        })
+                        .collect::<Result<_, _>>()
+                        .context("Parsing error for 'address'")?;
+                    command.address = mir::Command::opcode_address(&command.opcode)
+                        .context("An opcode must be 1 to 4 bytes long")
+                        .context("Parsing error for 'address'")?;
+                } else {
+                    command.address = value.as_int().context("Parsing error for 'address'")?;
+                }
             }
             "size_bits_in" => {
                 command.size_bits_in = value
@@ -407,6 +723,10 @@ fn transform_command(name: &str, map: &impl Map) -> anyhow::Result<mir::Command>
                 command.out_fields =
                     transform_fields(value).context("Parsing error for 'fields_out'")?;
             }
+            "poll_until" => {
+                command.poll_until =
+                    Some(transform_poll_until(value).context("Parsing error for 'poll_until'")?);
+            }
             val => {
                 bail!("Unexpected key: '{val}'")
             }
@@ -447,6 +767,32 @@ fn transform_buffer(name: &str, map: &impl Map) -> anyhow::Result<mir::Buffer> {
             "address" => {
                 buffer.address = value.as_int().context("Parsing error for 'address'")?;
             }
+            "frame" => {
+                buffer.frame = Some(
+                    value
+                        .as_string()
+                        .context("Parsing error for 'frame'")?
+                        .into(),
+                );
+            }
+            "max_size" => {
+                buffer.max_size = Some(
+                    value
+                        .as_uint()
+                        .context("Parsing error for 'max_size'")?
+                        .try_into()
+                        .context("Parsing error for 'max_size'")?,
+                );
+            }
+            "length_field" => {
+                let [length_field] = <[_; 1]>::try_from(
+                    transform_fields(value).context("Parsing error for 'length_field'")?,
+                )
+                .map_err(|_| {
+                    anyhow::anyhow!("The 'length_field' must contain exactly one field")
+                })?;
+                buffer.length_field = Some(Box::new(length_field));
+            }
             val => {
                 bail!("Unexpected key: '{val}'")
             }
@@ -502,6 +848,54 @@ fn transform_ref(name: &str, map: &impl Map) -> anyhow::Result<mir::RefObject> {
     Ok(ref_object)
 }
 
+/// An alias is a ref to a register that only overrides the address
+fn transform_alias(name: &str, map: &impl Map) -> anyhow::Result<mir::RefObject> {
+    let mut register_override = mir::RegisterOverride::default();
+    let mut ref_object = mir::RefObject {
+        name: name.into(),
+        ..Default::default()
+    };
+
+    for required_key in ["target", "address"] {
+        if !map.contains_key(required_key) {
+            bail!("Alias definition must contain the '{required_key}' field");
+        }
+    }
+
+    for (key, value) in map.iter() {
+        match key {
+            "type" => {}
+            "cfg" => {
+                ref_object.cfg_attr =
+                    mir::Cfg::new(Some(value.as_string().context("Parsing error for 'cfg'")?))
+            }
+            "description" => {
+                ref_object.description = value
+                    .as_string()
+                    .context("Parsing error for 'description'")?
+                    .into()
+            }
+            "target" => {
+                register_override.name = value
+                    .as_string()
+                    .context("Parsing error for 'target'")?
+                    .into()
+            }
+            "address" => {
+                register_override.address =
+                    Some(value.as_int().context("Parsing error for 'address'")?);
+            }
+            val => {
+                bail!("Unexpected key: '{val}'")
+            }
+        }
+    }
+
+    ref_object.object_override = mir::ObjectOverride::Register(register_override);
+
+    Ok(ref_object)
+}
+
 fn transform_object_override(
     target: &str,
     override_value: &impl Value,
@@ -599,8 +993,10 @@ fn transform_register_override(
                         Ok(val) => mir::ResetValue::Array(val),
                         Err(e) => return Err(e.context("Parsing error for 'reset_value")),
                     }
+                } else if let Ok(rv) = value.as_map() {
+                    transform_reset_value_fields(rv).context("Parsing error for 'reset_value")?
                 } else {
-                    return Err(anyhow!("Field must be an integer or an array")
+                    return Err(anyhow!("Field must be an integer, an array or a map")
                         .context("Parsing error for 'reset_value"));
                 })
             }
@@ -652,6 +1048,27 @@ fn transform_command_override(name: &str, map: &impl Map) -> anyhow::Result<mir:
     Ok(command)
 }
 
+fn transform_reset_value_fields(map: &impl Map) -> anyhow::Result<mir::ResetValue> {
+    map.iter()
+        .map(|(field_name, value)| {
+            let value = if let Ok(value) = value.as_int() {
+                mir::FieldResetValue::Integer(value.into())
+            } else if let Ok(value) = value.as_bool() {
+                mir::FieldResetValue::Bool(value)
+            } else if let Ok(variant) = value.as_string() {
+                mir::FieldResetValue::Variant(variant.into())
+            } else {
+                bail!(
+                    "The value of field '{field_name}' must be an integer, a bool or a variant name"
+                );
+            };
+
+            Ok((field_name.into(), value))
+        })
+        .collect::<anyhow::Result<_>>()
+        .map(mir::ResetValue::Fields)
+}
+
 fn transform_repeat(value: &impl Value) -> anyhow::Result<mir::Repeat> {
     let map = value.as_map()?;
 
@@ -714,6 +1131,33 @@ fn transform_field((field_name, field_value): (&str, &impl Value)) -> anyhow::Re
             "access" => {
                 field.access = transform_access(value).context("Parsing error for 'access'")?
             }
+            "renamed_from" => {
+                field.renamed_from = if let Ok(names) = value.as_array() {
+                    names
+                        .iter()
+                        .map(|name| name.as_string().map(Into::into))
+                        .collect::<Result<_, _>>()
+                } else {
+                    value.as_string().map(|name| vec![name.into()])
+                }
+                .context("Parsing error for 'renamed_from'")?
+            }
+            "bit_order" => {
+                field.bit_order =
+                    Some(transform_bit_order(value).context("Parsing error for 'bit_order'")?);
+            }
+            "valid_when" => {
+                field.valid_when =
+                    Some(transform_valid_when(value).context("Parsing error for 'valid_when'")?)
+            }
+            "unit" => {
+                field.unit = Some(
+                    value
+                        .as_string()
+                        .context("Parsing error for 'unit'")?
+                        .into(),
+                )
+            }
             "base" => {
                 field.base_type = transform_base_type(value).context("Parsing error for 'base'")?
             }
@@ -734,6 +1178,41 @@ fn transform_field((field_name, field_value): (&str, &impl Value)) -> anyhow::Re
                         .context("Parsing error for 'try_conversion'")?,
                 )
             }
+            "crc" => {
+                ensure!(
+                    !field_map.contains_key("conversion")
+                        && !field_map.contains_key("try_conversion"),
+                    "Cannot have both 'crc' and a conversion on a field. Pick one."
+                );
+
+                field.field_conversion =
+                    Some(transform_crc(value).context("Parsing error for 'crc'")?)
+            }
+            "newtype" => {
+                ensure!(
+                    !field_map.contains_key("conversion")
+                        && !field_map.contains_key("try_conversion")
+                        && !field_map.contains_key("crc"),
+                    "Cannot have both 'newtype' and a conversion or crc on a field. Pick one."
+                );
+
+                field.field_conversion =
+                    Some(transform_newtype(value).context("Parsing error for 'newtype'")?)
+            }
+            "conversion_functions" => {
+                ensure!(
+                    !field_map.contains_key("conversion")
+                        && !field_map.contains_key("try_conversion")
+                        && !field_map.contains_key("crc")
+                        && !field_map.contains_key("newtype"),
+                    "Cannot have both 'conversion_functions' and a conversion, crc or newtype on a field. Pick one."
+                );
+
+                field.field_conversion = Some(
+                    transform_conversion_functions(value)
+                        .context("Parsing error for 'conversion_functions'")?,
+                )
+            }
             "start" => {
                 field.field_address.start = value
                     .as_uint()
@@ -814,6 +1293,92 @@ fn transform_field_conversion(
     }
 }
 
+fn transform_crc(value: &impl Value) -> anyhow::Result<mir::FieldConversion> {
+    let crc_map = value.as_map()?;
+
+    let start = crc_map
+        .get("start")
+        .ok_or_else(|| anyhow!("Missing 'start' field"))?
+        .as_uint()?
+        .try_into()?;
+    let end = crc_map
+        .get("end")
+        .ok_or_else(|| anyhow!("Missing 'end' field"))?
+        .as_uint()?
+        .try_into()?;
+
+    if let Some((key, _)) = crc_map
+        .iter()
+        .find(|(key, _)| *key != "start" && *key != "end")
+    {
+        bail!("Unexpected key: '{key}'");
+    }
+
+    Ok(mir::FieldConversion::Crc { over: start..end })
+}
+
+fn transform_conversion_functions(value: &impl Value) -> anyhow::Result<mir::FieldConversion> {
+    let functions_map = value.as_map()?;
+
+    let get = |key: &str| -> anyhow::Result<String> {
+        Ok(functions_map
+            .get(key)
+            .ok_or_else(|| anyhow!("Missing '{key}' field"))?
+            .as_string()?
+            .into())
+    };
+
+    let conversion = mir::FieldConversion::Functions {
+        read_type: get("read_type")?,
+        read_fn: get("read_fn")?,
+        write_type: get("write_type")?,
+        write_fn: get("write_fn")?,
+    };
+
+    if let Some((key, _)) = functions_map
+        .iter()
+        .find(|(key, _)| !["read_type", "read_fn", "write_type", "write_fn"].contains(key))
+    {
+        bail!("Unexpected key: '{key}'");
+    }
+
+    Ok(conversion)
+}
+
+fn transform_newtype(value: &impl Value) -> anyhow::Result<mir::FieldConversion> {
+    let newtype_map = value.as_map()?;
+
+    let name = newtype_map
+        .get("name")
+        .ok_or_else(|| anyhow!("Missing 'name' field"))?
+        .as_string()?;
+    let inner = transform_integer_type(
+        newtype_map
+            .get("inner")
+            .ok_or_else(|| anyhow!("Missing 'inner' field"))?,
+    )?;
+    let description = newtype_map
+        .get("description")
+        .map(|description| description.as_string())
+        .transpose()?;
+
+    if let Some((key, _)) = newtype_map
+        .iter()
+        .find(|(key, _)| !["name", "inner", "description"].contains(key))
+    {
+        bail!("Unexpected key: '{key}'");
+    }
+
+    Ok(mir::FieldConversion::Newtype {
+        newtype: mir::Newtype {
+            cfg_attr: Cfg::default(),
+            description: description.unwrap_or_default().into(),
+            name: name.into(),
+            inner,
+        },
+    })
+}
+
 fn transform_enum_variant(
     (variant_name, variant_value): (&str, &impl Value),
 ) -> anyhow::Result<mir::EnumVariant> {
@@ -854,18 +1419,86 @@ fn transform_enum_variant(
     }
 }
 
-fn transform_enum_value(value: &impl Value) -> anyhow::Result<mir::EnumValue> {
-    if value.as_null().is_ok() {
-        Ok(mir::EnumValue::Unspecified)
-    } else if let Ok(specified) = value.as_int() {
+/// Parse a comparison of a field with a value, like `mode == Output`
+fn transform_valid_when(value: &impl Value) -> anyhow::Result<mir::ValidWhen> {
+    let condition = value.as_string()?;
+
+    let (field, negated, value) = if let Some((field, value)) = condition.split_once("!=") {
+        (field, true, value)
+    } else if let Some((field, value)) = condition.split_once("==") {
+        (field, false, value)
+    } else {
+        bail!("'{condition}' is not a comparison like \"mode == Output\" or \"mode != Output\"");
+    };
+
+    let (field, value) = (field.trim(), value.trim());
+    ensure!(!field.is_empty(), "'{condition}' doesn't name a field");
+
+    let value = match value {
+        "true" => mir::FieldResetValue::Bool(true),
+        "false" => mir::FieldResetValue::Bool(false),
+        value if value.starts_with(|c: char| c.is_ascii_digit() || c == '-') => {
+            let parsed = if let Some(hex) = value.strip_prefix("0x") {
+                i128::from_str_radix(hex, 16)
+            } else if let Some(bin) = value.strip_prefix("0b") {
+                i128::from_str_radix(bin, 2)
+            } else {
+                value.parse()
+            };
+            mir::FieldResetValue::Integer(
+                parsed.with_context(|| format!("Could not parse '{value}' as an integer"))?,
+            )
+        }
+        "" => bail!("'{condition}' doesn't compare with a value"),
+        variant => mir::FieldResetValue::Variant(variant.into()),
+    };
+
+    Ok(mir::ValidWhen {
+        field: field.into(),
+        negated,
+        value,
+        raw_value: 0,
+    })
+}
+
+fn parse_enum_range_bound(bound: &str) -> anyhow::Result<i128> {
+    let bound = bound.trim();
+    let parsed = if let Some(hex) = bound.strip_prefix("0x") {
+        i128::from_str_radix(hex, 16)
+    } else if let Some(bin) = bound.strip_prefix("0b") {
+        i128::from_str_radix(bin, 2)
+    } else {
+        bound.parse()
+    };
+
+    parsed.with_context(|| format!("Could not parse '{bound}' as the bound of an enum range"))
+}
+
+fn transform_enum_value(value: &impl Value) -> anyhow::Result<mir::EnumValue> {
+    if value.as_null().is_ok() {
+        Ok(mir::EnumValue::Unspecified)
+    } else if let Ok(specified) = value.as_int() {
         Ok(mir::EnumValue::Specified(specified as i128))
     } else if let Ok(specified) = value.as_string() {
         match specified {
             "default" => Ok(mir::EnumValue::Default),
             "catch_all" => Ok(mir::EnumValue::CatchAll),
-            val => Err(anyhow!(
-                "Unexpected string value: '{val}'. Choose one of 'default' or 'catch_all'"
-            )),
+            "fallback" => Ok(mir::EnumValue::Fallback),
+            val => {
+                if let Some((start, end)) = val.split_once("..=") {
+                    Ok(mir::EnumValue::Range(
+                        parse_enum_range_bound(start)?..=parse_enum_range_bound(end)?,
+                    ))
+                } else if let Some((start, end)) = val.split_once("..") {
+                    Ok(mir::EnumValue::Range(
+                        parse_enum_range_bound(start)?..=(parse_enum_range_bound(end)? - 1),
+                    ))
+                } else {
+                    Err(anyhow!(
+                        "Unexpected string value: '{val}'. Choose one of 'default', 'catch_all' or a range like '1..=7'"
+                    ))
+                }
+            }
         }
     } else {
         Err(anyhow!(
@@ -907,7 +1540,9 @@ mod tests {
                     "
                         default_field_access = \"RO\"
                         default_byte_order = \"BE\"
+                        default_command_byte_order = \"LE\"
                         default_bit_order =  \"MSB0\"
+                        auto_address = true
                     "
                 )
                 .unwrap()
@@ -916,7 +1551,9 @@ mod tests {
             mir::GlobalConfig {
                 default_field_access: mir::Access::RO,
                 default_byte_order: Some(mir::ByteOrder::BE),
+                default_command_byte_order: Some(mir::ByteOrder::LE),
                 default_bit_order: mir::BitOrder::MSB0,
+                auto_address: true,
                 ..Default::default()
             }
         );
@@ -927,7 +1564,8 @@ mod tests {
                     "{
                         \"name_word_boundaries\": \"aA\",
                         \"register_address_type\": \"i16\",
-                        \"command_address_type\":  \"u32\"
+                        \"command_address_type\":  \"u32\",
+                        \"reset_sequence\": [[\"Foo\", 1], [\"Bar\", 255]]
                     }"
                 )
                 .unwrap()
@@ -937,6 +1575,10 @@ mod tests {
                 name_word_boundaries: Boundary::list_from("aA"),
                 register_address_type: Some(mir::Integer::I16),
                 command_address_type: Some(mir::Integer::U32),
+                reset_sequence: vec![
+                    ("Foo".into(), mir::ResetValue::Integer(1)),
+                    ("Bar".into(), mir::ResetValue::Integer(255)),
+                ],
                 ..Default::default()
             }
         );
@@ -956,6 +1598,264 @@ mod tests {
             "No config with key `test` is recognized"
         );
 
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        id_register: [WhoAmI, chip_id, 0x42]
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                id_register: Some(mir::IdRegister {
+                    register: "WhoAmI".into(),
+                    field: "chip_id".into(),
+                    expected: 0x42,
+                }),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        id_register: [WhoAmI, 0x42]
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .root_cause()
+            .to_string(),
+            "The id register must be an array of a register name, a field name and the expected value"
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        address_unit: Word16
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                address_unit: mir::AddressUnit::Word16,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        address_unit: Word32
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+            .root_cause()
+            .to_string(),
+            "No address unit value `Word32` exists. Values are limited to \"Byte\" and \"Word16\""
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        mmio: true
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                mmio: true,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        reset_hold_us: 10
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                reset_hold_us: Some(10),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        group_by_kind: true
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                group_by_kind: true,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        generate_bit_ops: false
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                generate_bit_ops: false,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        feature_gate: my-driver
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                feature_gate: Some("my-driver".into()),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        chip_variants: [A, B]
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                chip_variants: vec!["A".into(), "B".into()],
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        dyn_access: true
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                dyn_access: true,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        shared_command_interface: true
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                shared_command_interface: true,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        post_read: crate::on_read
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                post_read: Some("crate::on_read".into()),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        enum_derives: [Clone, Debug]
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                enum_derives: Some(vec!["Clone".into(), "Debug".into()]),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        detect_bus_stuck: true
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                detect_bus_stuck: true,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            transform_global_config(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                        ufmt_feature: ufmt
+                    "
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            mir::GlobalConfig {
+                ufmt_feature: Some("ufmt".into()),
+                ..Default::default()
+            }
+        );
+
         assert_eq!(
             transform_global_config(
                 &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
@@ -968,7 +1868,7 @@ mod tests {
             .unwrap_err()
             .root_cause()
             .to_string(),
-            "No access value `Blah` exists. Values are limited to \"ReadWrite\", \"RW\", \"ReadOnly\", \"RO\", \"WriteOnly\", \"WO\""
+            "No access value `Blah` exists. Values are limited to \"ReadWrite\", \"RW\", \"ReadOnly\", \"RO\", \"WriteOnly\", \"WO\", \"ReadClear\", \"RC\""
         );
 
         assert_eq!(
@@ -990,15 +1890,19 @@ mod tests {
     #[test]
     fn register_parsed() {
         assert_eq!(
-            transform_object((
-                "my_register",
-                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
-                    "
+            transform_object(
+                (
+                    "my_register",
+                    &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                        "
                         type: register
                     "
-                )
-                .unwrap()
-            ))
+                    )
+                    .unwrap()
+                ),
+                &Default::default(),
+                0,
+            )
             .unwrap_err()
             .root_cause()
             .to_string(),
@@ -1006,16 +1910,20 @@ mod tests {
         );
 
         assert_eq!(
-            transform_object((
-                "my_register",
-                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
-                    "
+            transform_object(
+                (
+                    "my_register",
+                    &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                        "
                         type: register
                         address: 42
                     "
-                )
-                .unwrap()
-            ))
+                    )
+                    .unwrap()
+                ),
+                &Default::default(),
+                0,
+            )
             .unwrap_err()
             .root_cause()
             .to_string(),
@@ -1023,17 +1931,21 @@ mod tests {
         );
 
         assert_eq!(
-            transform_object((
-                "my_register",
-                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
-                    "
+            transform_object(
+                (
+                    "my_register",
+                    &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                        "
                         type: register
                         address: 42
                         size_bits: 8
                     "
-                )
-                .unwrap()
-            ))
+                    )
+                    .unwrap()
+                ),
+                &Default::default(),
+                0,
+            )
             .unwrap(),
             Object::Register(Register {
                 name: "my_register".into(),
@@ -1044,10 +1956,11 @@ mod tests {
         );
 
         assert_eq!(
-            transform_object((
-                "my_register",
-                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
-                    "
+            transform_object(
+                (
+                    "my_register",
+                    &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                        "
                         type: register
                         address: 42
                         size_bits: 8
@@ -1062,10 +1975,16 @@ mod tests {
                         reset_value: [1, 2, 3]
                         description: hello!
                         cfg: windows
+                        expect_address: 42
+                        expect_size: 8
+                        length_field: len
                     "
-                )
-                .unwrap()
-            ))
+                    )
+                    .unwrap()
+                ),
+                &Default::default(),
+                0,
+            )
             .unwrap(),
             Object::Register(Register {
                 name: "my_register".into(),
@@ -1083,15 +2002,19 @@ mod tests {
                 reset_value: Some(ResetValue::Array(vec![1, 2, 3])),
                 description: "hello!".into(),
                 cfg_attr: Cfg::new(Some("windows")),
+                expect_address: Some(42),
+                expect_size_bits: Some(8),
+                length_field: Some("len".into()),
                 ..Default::default()
             })
         );
 
         pretty_assertions::assert_eq!(
-            transform_object((
-                "my_register",
-                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
-                    "
+            transform_object(
+                (
+                    "my_register",
+                    &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                        "
                         type: register
                         address: 42
                         size_bits: 9
@@ -1123,9 +2046,12 @@ mod tests {
                                         description: This one is documented
                                         value: catch_all
                     "
-                )
-                .unwrap()
-            ))
+                    )
+                    .unwrap()
+                ),
+                &Default::default(),
+                0,
+            )
             .unwrap(),
             Object::Register(Register {
                 name: "my_register".into(),
@@ -1136,6 +2062,10 @@ mod tests {
                         cfg_attr: Cfg::new(Some("unix")),
                         description: "The test field".into(),
                         name: "test".into(),
+                        renamed_from: Vec::new(),
+                        bit_order: None,
+                        unit: None,
+                        valid_when: None,
                         access: mir::Access::RO,
                         base_type: mir::BaseType::Int,
                         field_conversion: None,
@@ -1145,6 +2075,10 @@ mod tests {
                         cfg_attr: Default::default(),
                         description: Default::default(),
                         name: "test2".into(),
+                        renamed_from: Vec::new(),
+                        bit_order: None,
+                        unit: None,
+                        valid_when: None,
                         access: Default::default(),
                         base_type: mir::BaseType::Uint,
                         field_conversion: Some(mir::FieldConversion::Direct {
@@ -1157,6 +2091,10 @@ mod tests {
                         cfg_attr: Default::default(),
                         description: Default::default(),
                         name: "test3".into(),
+                        renamed_from: Vec::new(),
+                        bit_order: None,
+                        unit: None,
+                        valid_when: None,
                         access: Default::default(),
                         base_type: mir::BaseType::Int,
                         field_conversion: Some(mir::FieldConversion::Enum {
@@ -1199,4 +2137,316 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn newtype_field_parsed() {
+        pretty_assertions::assert_eq!(
+            transform_field((
+                "temperature",
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                    base: uint
+                    start: 0
+                    end: 12
+                    newtype:
+                        name: Temperature
+                        inner: u16
+                        description: The temperature
+                    "
+                )
+                .unwrap()
+            ))
+            .unwrap()
+            .field_conversion,
+            Some(mir::FieldConversion::Newtype {
+                newtype: mir::Newtype {
+                    cfg_attr: Cfg::default(),
+                    description: "The temperature".into(),
+                    name: "Temperature".into(),
+                    inner: mir::Integer::U16,
+                }
+            })
+        );
+
+        assert_eq!(
+            transform_field((
+                "temperature",
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                    base: uint
+                    start: 0
+                    end: 12
+                    newtype:
+                        name: Temperature
+                    "
+                )
+                .unwrap()
+            ))
+            .unwrap_err()
+            .root_cause()
+            .to_string(),
+            "Missing 'inner' field"
+        );
+    }
+
+    #[test]
+    fn reset_value_fields_parsed() {
+        pretty_assertions::assert_eq!(
+            transform_reset_value_fields(
+                dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                    value: 5
+                    offset: -2
+                    enabled: true
+                    mode: Output
+                    "
+                )
+                .unwrap()
+                .as_map()
+                .unwrap()
+            )
+            .unwrap(),
+            mir::ResetValue::Fields(vec![
+                ("value".into(), mir::FieldResetValue::Integer(5)),
+                ("offset".into(), mir::FieldResetValue::Integer(-2)),
+                ("enabled".into(), mir::FieldResetValue::Bool(true)),
+                (
+                    "mode".into(),
+                    mir::FieldResetValue::Variant("Output".into())
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn valid_when_field_parsed() {
+        let valid_when = |condition: &str| {
+            transform_field((
+                "level",
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(&format!(
+                    "
+                    base: uint
+                    start: 0
+                    end: 4
+                    valid_when: \"{condition}\"
+                    "
+                ))
+                .unwrap(),
+            ))
+            .map(|field| field.valid_when.unwrap())
+        };
+
+        pretty_assertions::assert_eq!(
+            valid_when("mode == Output").unwrap(),
+            mir::ValidWhen {
+                field: "mode".into(),
+                negated: false,
+                value: mir::FieldResetValue::Variant("Output".into()),
+                raw_value: 0,
+            }
+        );
+        pretty_assertions::assert_eq!(
+            valid_when("speed != 0x10").unwrap(),
+            mir::ValidWhen {
+                field: "speed".into(),
+                negated: true,
+                value: mir::FieldResetValue::Integer(16),
+                raw_value: 0,
+            }
+        );
+        assert_eq!(
+            format!("{:#}", valid_when("mode = Output").unwrap_err()),
+            "Parsing error for 'valid_when': 'mode = Output' is not a comparison like \"mode == Output\" or \"mode != Output\""
+        );
+    }
+
+    #[test]
+    fn conversion_functions_field_parsed() {
+        pretty_assertions::assert_eq!(
+            transform_field((
+                "gain",
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                    base: uint
+                    start: 0
+                    end: 4
+                    conversion_functions:
+                        read_type: Gain
+                        read_fn: gain::decode
+                        write_type: GainSetting
+                        write_fn: gain::encode
+                    "
+                )
+                .unwrap()
+            ))
+            .unwrap()
+            .field_conversion,
+            Some(mir::FieldConversion::Functions {
+                read_type: "Gain".into(),
+                read_fn: "gain::decode".into(),
+                write_type: "GainSetting".into(),
+                write_fn: "gain::encode".into(),
+            })
+        );
+
+        assert_eq!(
+            transform_field((
+                "gain",
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                    base: uint
+                    start: 0
+                    end: 4
+                    conversion_functions:
+                        read_type: Gain
+                        read_fn: gain::decode
+                        write_type: GainSetting
+                    "
+                )
+                .unwrap()
+            ))
+            .unwrap_err()
+            .root_cause()
+            .to_string(),
+            "Missing 'write_fn' field"
+        );
+    }
+
+    #[test]
+    fn buffer_length_field_parsed() {
+        let parse = |text: &str| {
+            transform_buffer(
+                "Packets",
+                dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(text)
+                    .unwrap()
+                    .as_map()
+                    .unwrap(),
+            )
+            .map_err(|e| format!("{e:#}"))
+        };
+
+        pretty_assertions::assert_eq!(
+            parse(
+                "
+                type: buffer
+                address: 3
+                length_field:
+                    length:
+                        base: uint
+                        start: 0
+                        end: 8
+                "
+            )
+            .unwrap()
+            .length_field,
+            Some(Box::new(mir::Field {
+                name: "length".into(),
+                base_type: mir::BaseType::Uint,
+                field_address: 0..8,
+                ..Default::default()
+            }))
+        );
+
+        assert_eq!(
+            parse(
+                "
+                type: buffer
+                address: 3
+                length_field:
+                    length:
+                        base: uint
+                        start: 0
+                        end: 8
+                    other:
+                        base: uint
+                        start: 8
+                        end: 16
+                "
+            )
+            .unwrap_err(),
+            "The 'length_field' must contain exactly one field"
+        );
+
+        assert_eq!(
+            parse(
+                "
+                type: buffer
+                address: 3
+                max_size: 64
+                "
+            )
+            .unwrap()
+            .max_size,
+            Some(64)
+        );
+    }
+
+    #[test]
+    fn alias_parsed() {
+        pretty_assertions::assert_eq!(
+            transform_alias(
+                "FooV2",
+                dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                    type: alias
+                    description: The foo register of the v2 chip
+                    target: Foo
+                    address: 0x20
+                    "
+                )
+                .unwrap()
+                .as_map()
+                .unwrap()
+            )
+            .unwrap(),
+            mir::RefObject {
+                cfg_attr: Cfg::default(),
+                description: "The foo register of the v2 chip".into(),
+                name: "FooV2".into(),
+                object_override: mir::ObjectOverride::Register(mir::RegisterOverride {
+                    name: "Foo".into(),
+                    address: Some(0x20),
+                    ..Default::default()
+                }),
+            }
+        );
+
+        assert_eq!(
+            transform_alias(
+                "FooV2",
+                dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(
+                    "
+                    type: alias
+                    target: Foo
+                    "
+                )
+                .unwrap()
+                .as_map()
+                .unwrap()
+            )
+            .unwrap_err()
+            .to_string(),
+            "Alias definition must contain the 'address' field"
+        );
+    }
+
+    #[test]
+    fn enum_value_ranges() {
+        let parse = |text: &str| {
+            transform_enum_value(
+                &dd_manifest_tree::parse_manifest::<dd_manifest_tree::YamlValue>(text).unwrap(),
+            )
+            .map_err(|e| format!("{e:#}"))
+        };
+
+        assert_eq!(parse("'1..=7'"), Ok(mir::EnumValue::Range(1..=7)));
+        assert_eq!(parse("'0x10..0x20'"), Ok(mir::EnumValue::Range(16..=31)));
+        assert_eq!(
+            parse("'1..=x'"),
+            Err(
+                "Could not parse 'x' as the bound of an enum range: invalid digit found in string"
+                    .into()
+            )
+        );
+    }
 }
@@ -1,18 +1,19 @@
-use std::ops::{Add, Not};
+use std::ops::{Add, Not, RangeInclusive};
 
 use anyhow::ensure;
+use itertools::Itertools;
 use proc_macro2::{Literal, TokenStream};
-use quote::{format_ident, quote};
+use quote::{ToTokens, format_ident, quote};
 use syn::Ident;
 
 use crate::{
     lir,
-    mir::{self, passes::search_object},
+    mir::{self, Unique, passes::search_object},
 };
 
-use super::passes::{find_min_max_addresses, recurse_objects};
+use super::passes::{find_min_max_addresses, recurse_objects, recurse_objects_mut};
 
-pub fn transform(device: mir::Device, driver_name: &str) -> anyhow::Result<lir::Device> {
+pub fn transform(mut device: mir::Device, driver_name: &str) -> anyhow::Result<lir::Device> {
     let lenient_pascal_converter = convert_case::Converter::new()
         .set_boundaries(&convert_case::Boundary::list_from("aA:AAa:_:-: :a1:A1"))
         .set_pattern(convert_case::Pattern::Capital);
@@ -24,18 +25,44 @@ pub fn transform(device: mir::Device, driver_name: &str) -> anyhow::Result<lir::
         converted_driver_name
     );
 
+    let feature_gate = mir::Cfg::new(
+        device
+            .global_config
+            .feature_gate
+            .as_ref()
+            .map(|feature| format!("feature = \"{feature}\""))
+            .as_deref(),
+    );
+    apply_feature_gate(&mut device, &feature_gate)?;
+
+    let enum_derives = transform_enum_derives(device.global_config.enum_derives.as_deref())?;
     let mir_enums = collect_enums(&device)?;
     let lir_enums = mir_enums
         .iter()
-        .map(|(e, base_type, size_bits)| transform_enum(e, *base_type, *size_bits))
+        .map(|(e, base_type, size_bits)| transform_enum(e, *base_type, *size_bits, &enum_derives))
+        .collect::<Result<_, anyhow::Error>>()?;
+
+    let newtypes = collect_newtypes(&device)?
+        .iter()
+        .map(|(newtype, base_type, size_bits)| transform_newtype(newtype, *base_type, *size_bits))
         .collect::<Result<_, anyhow::Error>>()?;
 
     let field_sets = transform_field_sets(&device, mir_enums.iter().map(|(e, _, _)| e))?;
+    let field_set_groups = create_field_set_groups(&device)?;
+    let reset_sequence = transform_reset_sequence(&device)?;
+    let id_check = transform_id_register(&device)?;
+    let mut register_expectations = Vec::new();
+    collect_register_expectations(&device.objects, 0, &mut register_expectations)?;
+    let length_prefixes = collect_length_prefixes(&device)?;
+    let variable_size_registers = collect_variable_size_registers(&device)?;
+    let command_echoes = collect_command_echoes(&device)?;
+    let mut decodable_registers = Vec::new();
+    collect_decodable_registers(&device.objects, 0, &mut decodable_registers)?;
 
     // Create a root block and pass the device objects to it
     let blocks = collect_into_blocks(
         BorrowedBlock {
-            cfg_attr: &mir::Cfg::new(None),
+            cfg_attr: &feature_gate,
             description: &format!("Root block of the {driver_name} driver"),
             name: &driver_name.into(),
             address_offset: &0,
@@ -47,20 +74,489 @@ pub fn transform(device: mir::Device, driver_name: &str) -> anyhow::Result<lir::
         &device.objects,
     )?;
 
+    let register_address_type = device
+        .global_config
+        .register_address_type
+        .unwrap_or(mir::Integer::U8);
+    let defined_register_addresses = find_defined_register_addresses(
+        &blocks,
+        &field_sets,
+        register_address_type,
+        device.global_config.address_unit,
+    );
+    let word_interface = transform_word_interface(&device)?;
+    let snapshot = create_snapshot(&blocks, driver_name);
+    let configure = create_configure(
+        &blocks,
+        &field_sets,
+        driver_name,
+        device.global_config.address_unit,
+    );
+    let mmio = create_mmio(&blocks, &field_sets, driver_name, &device.global_config)?;
+    let visibility = transform_visibility(device.global_config.visibility.as_deref())?;
+    let dyn_access = transform_dyn_access(&device, &field_sets)?;
+    let read_hook = transform_read_hook(&device, driver_name)?;
+
     Ok(lir::Device {
         internal_address_type: find_best_internal_address(&device),
-        register_address_type: device
-            .global_config
-            .register_address_type
-            .unwrap_or(mir::Integer::U8)
-            .into(),
+        register_address_type: register_address_type.into(),
+        defined_register_addresses,
         blocks,
         field_sets,
         enums: lir_enums,
+        newtypes,
         defmt_feature: device.global_config.defmt_feature,
+        ufmt_feature: device.global_config.ufmt_feature,
+        visibility,
+        flat_accessors: device.global_config.flat_accessors,
+        reset_sequence,
+        snapshot,
+        configure,
+        id_check,
+        word_interface,
+        mmio,
+        reset_hold_us: device
+            .global_config
+            .reset_hold_us
+            .map(Literal::u32_unsuffixed),
+        field_set_groups,
+        generate_bit_ops: device.global_config.generate_bit_ops,
+        register_expectations,
+        length_prefixes,
+        variable_size_registers,
+        command_echoes,
+        decodable_registers,
+        dyn_access,
+        commands_share_register_addresses: device.global_config.shared_command_interface
+            && device.global_config.command_address_type
+                == device.global_config.register_address_type,
+        read_hook,
+        enum_derives,
     })
 }
 
+/// Prepend the feature gate to the cfg of every object and of the enums and newtypes of their fields.
+/// The root block gets the gate as well, so everything that is generated is behind it.
+fn apply_feature_gate(device: &mut mir::Device, feature_gate: &mir::Cfg) -> anyhow::Result<()> {
+    if feature_gate.inner().is_none() {
+        return Ok(());
+    }
+
+    recurse_objects_mut(&mut device.objects, &mut |object| {
+        let cfg_attr = object.cfg_attr_mut();
+        *cfg_attr = feature_gate.combine(cfg_attr);
+
+        for field in object.field_sets_mut().flatten() {
+            match field.field_conversion.as_mut() {
+                Some(mir::FieldConversion::Enum { enum_value, .. }) => {
+                    enum_value.cfg_attr = feature_gate.combine(&enum_value.cfg_attr);
+                }
+                Some(mir::FieldConversion::Newtype { newtype }) => {
+                    newtype.cfg_attr = feature_gate.combine(&newtype.cfg_attr);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// With `group_by_kind`, the field sets of the registers and commands each get their own submodule
+fn create_field_set_groups(device: &mir::Device) -> anyhow::Result<Vec<lir::FieldSetGroup>> {
+    if !device.global_config.group_by_kind {
+        return Ok(Vec::new());
+    }
+
+    let mut register_field_sets = Vec::new();
+    let mut command_field_sets = Vec::new();
+
+    recurse_objects(&device.objects, &mut |object| {
+        match object {
+            mir::Object::Register(r) => register_field_sets.push(format_ident!("{}", r.name)),
+            mir::Object::Command(c) => {
+                command_field_sets.push(format_ident!("{}FieldsIn", c.name));
+                command_field_sets.push(format_ident!("{}FieldsOut", c.name));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    })?;
+
+    Ok(vec![
+        lir::FieldSetGroup {
+            module_name: format_ident!("registers"),
+            doc_attr: quote! { #[doc = "Module containing the generated fieldsets of the registers"] },
+            field_set_names: register_field_sets,
+        },
+        lir::FieldSetGroup {
+            module_name: format_ident!("commands"),
+            doc_attr: quote! { #[doc = "Module containing the generated fieldsets of the commands"] },
+            field_set_names: command_field_sets,
+        },
+    ])
+}
+
+/// With 16-bit word addressing, the device gets a constructor for the [lir::WordInterface].
+/// The byte order is the same for all registers, which is checked in an earlier pass.
+fn transform_word_interface(device: &mir::Device) -> anyhow::Result<Option<lir::WordInterface>> {
+    if device.global_config.address_unit == mir::AddressUnit::Byte {
+        return Ok(None);
+    }
+
+    let mut byte_order = None;
+    let mut max_words = 1;
+
+    recurse_objects(&device.objects, &mut |object| {
+        if let mir::Object::Register(register) = object {
+            byte_order = byte_order.or(register.byte_order);
+            max_words = max_words.max(register.size_bits.div_ceil(16));
+        }
+
+        Ok(())
+    })?;
+
+    Ok(Some(lir::WordInterface {
+        byte_order: byte_order.unwrap_or(mir::ByteOrder::LE),
+        max_words: Literal::u32_unsuffixed(max_words),
+    }))
+}
+
+fn transform_visibility(visibility: Option<&str>) -> anyhow::Result<TokenStream> {
+    let Some(visibility) = visibility else {
+        return Ok(quote! { pub });
+    };
+
+    match syn::parse_str::<syn::Visibility>(visibility) {
+        Ok(visibility) => Ok(visibility.into_token_stream()),
+        Err(_) => anyhow::bail!("The visibility \"{visibility}\" is not a valid Rust visibility"),
+    }
+}
+
+/// Create the read hook when the post read function is set or a stuck bus is detected.
+///
+/// Reading a shadowed register doesn't access the device, so those can't be combined with the post read function.
+fn transform_read_hook(
+    device: &mir::Device,
+    driver_name: &str,
+) -> anyhow::Result<Option<lir::ReadHook>> {
+    let name = format_ident!("{driver_name}ReadHook");
+    let detect_bus_stuck = device.global_config.detect_bus_stuck;
+    let Some(post_read) = device.global_config.post_read.as_deref() else {
+        return Ok(detect_bus_stuck.then_some(lir::ReadHook {
+            name,
+            post_read: None,
+            detect_bus_stuck,
+        }));
+    };
+
+    let Ok(post_read) = syn::parse_str::<syn::Path>(post_read) else {
+        anyhow::bail!("The post read hook \"{post_read}\" is not a valid Rust path");
+    };
+
+    // Shadowed registers can only be in the root block
+    if let Some(mir::Object::Register(register)) = device
+        .objects
+        .iter()
+        .find(|object| matches!(object, mir::Object::Register(register) if register.shadowed))
+    {
+        anyhow::bail!(
+            "Register \"{}\" is shadowed, so reading it never accesses the device. This can't be combined with the post read hook",
+            register.name
+        );
+    }
+
+    Ok(Some(lir::ReadHook {
+        name,
+        post_read: Some(post_read.into_token_stream()),
+        detect_bus_stuck,
+    }))
+}
+
+/// The derives that can be put on the generated enums, with the derives they need
+const ENUM_DERIVES: &[(&str, &[&str])] = &[
+    ("Debug", &[]),
+    ("Copy", &["Clone"]),
+    ("Clone", &[]),
+    ("Eq", &["PartialEq"]),
+    ("PartialEq", &[]),
+    ("Hash", &[]),
+    ("PartialOrd", &["PartialEq"]),
+    ("Ord", &["Eq", "PartialOrd"]),
+];
+
+/// The derives of the generated enums when none are specified
+const DEFAULT_ENUM_DERIVES: &[&str] = &["Debug", "Copy", "Clone", "Eq", "PartialEq", "Hash"];
+
+fn transform_enum_derives(enum_derives: Option<&[String]>) -> anyhow::Result<Vec<Ident>> {
+    let Some(enum_derives) = enum_derives else {
+        return Ok(DEFAULT_ENUM_DERIVES
+            .iter()
+            .map(|derive| format_ident!("{derive}"))
+            .collect());
+    };
+
+    for derive in enum_derives {
+        let Some((_, required)) = ENUM_DERIVES.iter().find(|(name, _)| name == derive) else {
+            anyhow::bail!(
+                "The enum derive \"{derive}\" is not supported. Options are: {}",
+                ENUM_DERIVES.iter().map(|(name, _)| name).join(", ")
+            );
+        };
+
+        for required in required.iter() {
+            ensure!(
+                enum_derives.iter().any(|derive| derive == required),
+                "The enum derive \"{derive}\" requires \"{required}\" to be derived as well"
+            );
+        }
+    }
+
+    // The Debug impl of the field sets prints the value of every field
+    ensure!(
+        enum_derives.iter().any(|derive| derive == "Debug"),
+        "The enum derives must include \"Debug\", because the Debug impl of the field sets prints the enum fields"
+    );
+
+    Ok(enum_derives
+        .iter()
+        .map(|derive| format_ident!("{derive}"))
+        .collect())
+}
+
+fn transform_reset_sequence(device: &mir::Device) -> anyhow::Result<Vec<lir::ResetSequenceWrite>> {
+    use convert_case::Casing;
+
+    device
+        .global_config
+        .reset_sequence
+        .iter()
+        .map(|(register_name, value)| {
+            let Some(mir::Object::Register(register)) = device
+                .objects
+                .iter()
+                .find(|object| object.name() == register_name)
+            else {
+                anyhow::bail!(
+                    "Register \"{register_name}\" of the reset sequence must be defined in the root of the device"
+                );
+            };
+
+            ensure!(
+                register.repeat.is_none(),
+                "Register \"{register_name}\" of the reset sequence is repeated, which is not supported"
+            );
+            ensure!(
+                matches!(register.access, mir::Access::RW | mir::Access::WO),
+                "Register \"{register_name}\" of the reset sequence is not writable"
+            );
+
+            Ok(lir::ResetSequenceWrite {
+                cfg_attr: cfg_attr_string_to_tokens(&register.cfg_attr)?,
+                register_name: format_ident!(
+                    "{}",
+                    register_name.to_case(convert_case::Case::Snake)
+                ),
+                field_set_name: format_ident!("{register_name}"),
+                value: value
+                    .as_array()
+                    .expect("Reset sequence values are converted in a mir pass")
+                    .clone(),
+            })
+        })
+        .collect()
+}
+
+fn transform_id_register(device: &mir::Device) -> anyhow::Result<Option<lir::IdCheck>> {
+    use convert_case::Casing;
+
+    let Some(mir::IdRegister {
+        register: register_name,
+        field: field_name,
+        expected,
+    }) = &device.global_config.id_register
+    else {
+        return Ok(None);
+    };
+
+    let Some(mir::Object::Register(register)) = device
+        .objects
+        .iter()
+        .find(|object| object.name() == register_name)
+    else {
+        anyhow::bail!("Id register \"{register_name}\" must be defined in the root of the device");
+    };
+
+    ensure!(
+        register.repeat.is_none(),
+        "Id register \"{register_name}\" is repeated, which is not supported"
+    );
+    ensure!(
+        register.access != mir::Access::WO,
+        "Id register \"{register_name}\" is not readable"
+    );
+    ensure!(
+        !register.shadowed,
+        "Id register \"{register_name}\" is shadowed, so it's never read from the device"
+    );
+
+    let Some(field) = register
+        .fields
+        .iter()
+        .find(|field| &field.name == field_name)
+    else {
+        anyhow::bail!("Id register \"{register_name}\" has no field \"{field_name}\"");
+    };
+
+    ensure!(
+        field.access != mir::Access::WO,
+        "Field \"{field_name}\" of id register \"{register_name}\" is not readable"
+    );
+    ensure!(
+        field.base_type == mir::BaseType::Uint && field.field_conversion.is_none(),
+        "Field \"{field_name}\" of id register \"{register_name}\" must be a uint without conversion"
+    );
+    ensure!(
+        field.field_address.len() >= 128 || *expected >> field.field_address.len() == 0,
+        "The expected id {expected:#X} doesn't fit in field \"{field_name}\" of id register \"{register_name}\""
+    );
+
+    Ok(Some(lir::IdCheck {
+        cfg_attr: cfg_attr_string_to_tokens(&register.cfg_attr)?,
+        register_name: format_ident!("{}", register_name.to_case(convert_case::Case::Snake)),
+        field_name: format_ident!("{field_name}"),
+        expected: Literal::u128_unsuffixed(*expected),
+    }))
+}
+
+/// Collect the registers of the root block of which the fields can be accessed by name.
+///
+/// Registers that are repeated, span multiple addresses, have a variable size or are shadowed are left out.
+/// Fields that are wider than 64 bits are left out as well, since their raw value doesn't fit the `u64` value.
+fn transform_dyn_access(
+    device: &mir::Device,
+    field_sets: &[lir::FieldSet],
+) -> anyhow::Result<Option<Vec<lir::DynAccessRegister>>> {
+    use convert_case::Casing;
+
+    if !device.global_config.dyn_access {
+        return Ok(None);
+    }
+
+    device
+        .objects
+        .iter()
+        .filter_map(|object| match object {
+            mir::Object::Register(register)
+                if register.repeat.is_none()
+                    && register.span_offsets.is_empty()
+                    && register.length_field.is_none()
+                    && !register.shadowed =>
+            {
+                Some(register)
+            }
+            _ => None,
+        })
+        .map(|register| {
+            let field_set = field_sets
+                .iter()
+                .find(|field_set| field_set.name == register.name)
+                .expect("All field sets are collected");
+
+            let fields = field_set
+                .fields
+                .iter()
+                .filter_map(|field| {
+                    let size_bits = field.address.end.to_string().parse::<u32>().unwrap()
+                        - field.address.start.to_string().parse::<u32>().unwrap();
+                    if size_bits > 64 {
+                        return None;
+                    }
+
+                    let name = &field.name;
+                    let (getter, setter) = match field.conversion_method {
+                        lir::FieldConversionMethod::None | lir::FieldConversionMethod::Bool => {
+                            (name.clone(), format_ident!("set_{name}"))
+                        }
+                        _ => (format_ident!("{name}_raw"), format_ident!("set_{name}_raw")),
+                    };
+
+                    Some(lir::DynAccessField {
+                        cfg_attr: field.cfg_attr.clone(),
+                        name: name.clone(),
+                        readable: matches!(
+                            field.access,
+                            mir::Access::RW | mir::Access::RO | mir::Access::RC
+                        ),
+                        writable: matches!(field.access, mir::Access::RW | mir::Access::WO),
+                        getter,
+                        setter,
+                        base_type: (!matches!(
+                            field.conversion_method,
+                            lir::FieldConversionMethod::Bool
+                        ))
+                        .then(|| field.base_type.clone()),
+                        signed: field.base_type.to_string().starts_with('i'),
+                        size_bits,
+                    })
+                })
+                .collect();
+
+            Ok(lir::DynAccessRegister {
+                cfg_attr: cfg_attr_string_to_tokens(&register.cfg_attr)?,
+                name: format_ident!("{}", register.name.to_case(convert_case::Case::Snake)),
+                readable: register.access != mir::Access::WO,
+                modifiable: register.access == mir::Access::RW,
+                fields,
+            })
+        })
+        .collect::<anyhow::Result<_>>()
+        .map(Some)
+}
+
+/// Collect the registers with an expected address or size. The address of a register is computed by adding up
+/// the offsets of the blocks it's in. For repeated blocks and registers the first instance is used.
+fn collect_register_expectations(
+    objects: &[mir::Object],
+    address_offset: i64,
+    expectations: &mut Vec<lir::RegisterExpectation>,
+) -> anyhow::Result<()> {
+    use convert_case::Casing;
+
+    for object in objects {
+        match object {
+            mir::Object::Block(block) => collect_register_expectations(
+                &block.objects,
+                address_offset + block.address_offset,
+                expectations,
+            )?,
+            mir::Object::Register(register)
+                if register.expect_address.is_some() || register.expect_size_bits.is_some() =>
+            {
+                expectations.push(lir::RegisterExpectation {
+                    cfg_attr: cfg_attr_string_to_tokens(&register.cfg_attr)?,
+                    register_name: format_ident!(
+                        "{}",
+                        register.name.to_case(convert_case::Case::Snake)
+                    ),
+                    field_set_name: format_ident!("{}", register.name),
+                    address: register.expect_address.map(|expected| {
+                        (
+                            Literal::i64_suffixed(address_offset + register.address),
+                            Literal::i64_suffixed(expected),
+                        )
+                    }),
+                    size_bits: register.expect_size_bits.map(Literal::u32_unsuffixed),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
 fn collect_into_blocks(
     block: BorrowedBlock,
     is_root: bool,
@@ -152,12 +648,30 @@ fn get_method(
             name,
             allow_address_overlap,
             address,
+            span_offsets,
             access,
             repeat,
+            shadowed,
+            byte_order,
+            reset_value,
             ..
         }) => lir::BlockMethod {
             cfg_attr: cfg_attr_string_to_tokens(cfg_attr)?,
-            doc_attr: quote! { #[doc = #description] },
+            doc_attr: {
+                let doc = match reset_value {
+                    Some(reset_value) => {
+                        let reset_value = reset_value_to_hex(
+                            reset_value
+                                .as_array()
+                                .expect("Reset values are converted in a mir pass"),
+                            byte_order.expect("The byte order is specified in a mir pass"),
+                        );
+                        format!("{description}\n\nReset value: `{reset_value}`")
+                    }
+                    None => description.clone(),
+                };
+                quote! { #[doc = #doc] }
+            },
             name: format_ident!("{}", name.to_case(convert_case::Case::Snake)),
             address: Literal::i64_unsuffixed(*address),
             allow_address_overlap: *allow_address_overlap,
@@ -170,6 +684,11 @@ fn get_method(
                     .expect("The presence of the address type is already checked in a mir pass")
                     .into(),
                 reset_value_function: register_reset_value_function.clone(),
+                span_offsets: span_offsets
+                    .iter()
+                    .map(|offset| Literal::i64_unsuffixed(*offset))
+                    .collect(),
+                shadowed: *shadowed,
             },
         },
         mir::Object::Command(mir::Command {
@@ -181,6 +700,7 @@ fn get_method(
             repeat,
             in_fields,
             out_fields,
+            poll_until,
             ..
         }) => lir::BlockMethod {
             cfg_attr: cfg_attr_string_to_tokens(cfg_attr)?,
@@ -202,6 +722,14 @@ fn get_method(
                     .command_address_type
                     .expect("The presence of the address type is already checked in a mir pass")
                     .into(),
+                poll_until: poll_until.as_ref().map(|poll_until| lir::CommandPoll {
+                    register_name: format_ident!(
+                        "{}",
+                        poll_until.register.to_case(convert_case::Case::Snake)
+                    ),
+                    field_set_name: format_ident!("{}", poll_until.register),
+                    field_name: format_ident!("{}", poll_until.field),
+                }),
             },
         },
         mir::Object::Buffer(mir::Buffer {
@@ -210,6 +738,9 @@ fn get_method(
             name,
             access,
             address,
+            frame,
+            max_size,
+            length_field,
         }) => lir::BlockMethod {
             cfg_attr: cfg_attr_string_to_tokens(cfg_attr)?,
             doc_attr: quote! { #[doc = #description] },
@@ -223,6 +754,19 @@ fn get_method(
                     .buffer_address_type
                     .expect("The presence of the address type is already checked in a mir pass")
                     .into(),
+                frame_field_set: frame
+                    .as_ref()
+                    .map(|frame| match search_object(frame, device_objects) {
+                        Some(mir::Object::Register(_)) => Ok(format_ident!("{frame}")),
+                        _ => Err(anyhow::anyhow!(
+                            "Buffer \"{name}\" has frame \"{frame}\", which is not a register"
+                        )),
+                    })
+                    .transpose()?,
+                length_prefix_field_set: length_field
+                    .as_ref()
+                    .map(|_| format_ident!("{name}Length")),
+                max_size: max_size.map(Literal::u32_unsuffixed),
             },
         },
         mir::Object::Ref(mir::RefObject {
@@ -285,6 +829,8 @@ fn get_method(
                     if let Some(repeat) = override_values.repeat {
                         reffed_object.repeat = Some(repeat);
                     }
+                    // The polled register is only validated to be next to the original command
+                    reffed_object.poll_until = None;
                 }
             }
 
@@ -305,6 +851,18 @@ fn get_method(
     })
 }
 
+/// Format the reset value array as a hex number, most significant byte first
+fn reset_value_to_hex(reset_value: &[u8], byte_order: mir::ByteOrder) -> String {
+    let mut bytes = reset_value.to_vec();
+    byte_order.reorder_from_le(&mut bytes);
+    bytes.reverse();
+
+    format!(
+        "0x{}",
+        bytes.iter().map(|byte| format!("{byte:02X}")).join("")
+    )
+}
+
 fn transform_field_sets<'a>(
     device: &mir::Device,
     mir_enums: impl Iterator<Item = &'a mir::Enum> + Clone,
@@ -331,11 +889,25 @@ fn transform_field_sets<'a>(
                     })
                     .collect();
 
-                field_sets.push(transform_field_set(
+                let reserved_bits = r.reserved_bits();
+                let description = if reserved_bits.is_empty() {
+                    r.description.clone()
+                } else {
+                    format!(
+                        "{}\n\nReserved bits (must be zero): {}",
+                        r.description,
+                        reserved_bits
+                            .iter()
+                            .map(|bits| format!("{bits:?}"))
+                            .join(", ")
+                    )
+                };
+
+                let mut field_set = transform_field_set(
                     &r.fields,
                     format_ident!("{}", r.name),
                     &r.cfg_attr,
-                    &r.description,
+                    &description,
                     r.byte_order.unwrap(),
                     r.bit_order,
                     r.size_bits,
@@ -344,7 +916,26 @@ fn transform_field_sets<'a>(
                         .map(|rv| rv.as_array().unwrap().clone()),
                     ref_reset_overrides,
                     mir_enums.clone(),
-                )?);
+                    device.global_config.crc_algorithm,
+                )?;
+
+                // Every register gets a constructor per chip variant, even when its reset value is the same for all of them
+                field_set.chip_variant_reset_values = device
+                    .global_config
+                    .chip_variants
+                    .iter()
+                    .map(|chip_variant| {
+                        let reset_value = r
+                            .chip_variant_reset_values
+                            .iter()
+                            .find(|(name, _)| name == chip_variant)
+                            .map(|(_, reset_value)| reset_value.as_array().unwrap().clone())
+                            .unwrap_or_else(|| field_set.reset_value.clone());
+                        (chip_variant.clone(), reset_value)
+                    })
+                    .collect();
+
+                field_sets.push(field_set);
             }
             mir::Object::Command(c) => {
                 field_sets.push(transform_field_set(
@@ -358,6 +949,7 @@ fn transform_field_sets<'a>(
                     None,
                     Vec::new(),
                     mir_enums.clone(),
+                    device.global_config.crc_algorithm,
                 )?);
                 field_sets.push(transform_field_set(
                     &c.out_fields,
@@ -370,8 +962,31 @@ fn transform_field_sets<'a>(
                     None,
                     Vec::new(),
                     mir_enums.clone(),
+                    device.global_config.crc_algorithm,
                 )?);
             }
+            mir::Object::Buffer(b) => {
+                if let (Some(length_field), Some(size_bits)) =
+                    (&b.length_field, b.length_prefix_size_bits())
+                {
+                    field_sets.push(transform_field_set(
+                        core::slice::from_ref(length_field),
+                        format_ident!("{}Length", b.name),
+                        &b.cfg_attr,
+                        &b.description,
+                        device
+                            .global_config
+                            .register_byte_order()
+                            .map_or(mir::ByteOrder::LE, |bo| bo.inherited_for(size_bits)),
+                        device.global_config.default_bit_order,
+                        size_bits,
+                        None,
+                        Vec::new(),
+                        mir_enums.clone(),
+                        device.global_config.crc_algorithm,
+                    )?);
+                }
+            }
             _ => {}
         }
 
@@ -381,6 +996,138 @@ fn transform_field_sets<'a>(
     Ok(field_sets)
 }
 
+fn collect_length_prefixes(device: &mir::Device) -> anyhow::Result<Vec<lir::LengthPrefix>> {
+    let mut length_prefixes = Vec::new();
+
+    recurse_objects(&device.objects, &mut |object| {
+        if let mir::Object::Buffer(b) = object
+            && let Some(length_field) = &b.length_field
+        {
+            length_prefixes.push(lir::LengthPrefix {
+                cfg_attr: cfg_attr_string_to_tokens(&b.cfg_attr)?,
+                field_set_name: format_ident!("{}Length", b.name),
+                field_name: format_ident!("{}", length_field.name),
+            });
+        }
+
+        Ok(())
+    })?;
+
+    Ok(length_prefixes)
+}
+
+fn collect_variable_size_registers(
+    device: &mir::Device,
+) -> anyhow::Result<Vec<lir::VariableSizeRegister>> {
+    let mut variable_size_registers = Vec::new();
+
+    recurse_objects(&device.objects, &mut |object| {
+        if let mir::Object::Register(r) = object
+            && let Some(length_field) = &r.length_field
+        {
+            let field = r
+                .fields
+                .iter()
+                .find(|field| field.name == *length_field)
+                .expect("The length field is checked in a mir pass");
+
+            variable_size_registers.push(lir::VariableSizeRegister {
+                cfg_attr: cfg_attr_string_to_tokens(&r.cfg_attr)?,
+                field_set_name: format_ident!("{}", r.name),
+                length_field_name: format_ident!("{}", length_field),
+                prefix_size_bits: Literal::u32_unsuffixed(field.field_address.end.div_ceil(8) * 8),
+            });
+        }
+
+        Ok(())
+    })?;
+
+    Ok(variable_size_registers)
+}
+
+fn collect_command_echoes(device: &mir::Device) -> anyhow::Result<Vec<lir::CommandEcho>> {
+    let mut command_echoes = Vec::new();
+
+    recurse_objects(&device.objects, &mut |object| {
+        if let mir::Object::Command(c) = object
+            && !c.in_fields.is_empty()
+            && c.size_bits_in == c.size_bits_out
+            && same_field_layout(&c.in_fields, &c.out_fields)
+        {
+            command_echoes.push(lir::CommandEcho {
+                cfg_attr: cfg_attr_string_to_tokens(&c.cfg_attr)?,
+                field_set_name_in: format_ident!("{}FieldsIn", c.name),
+                field_set_name_out: format_ident!("{}FieldsOut", c.name),
+                size_bytes: Literal::usize_unsuffixed(c.size_bits_in.div_ceil(8) as usize),
+            });
+        }
+
+        Ok(())
+    })?;
+
+    Ok(command_echoes)
+}
+
+/// Collect the absolute address of every register, including every repeat of the register and its blocks
+fn collect_decodable_registers(
+    objects: &[mir::Object],
+    address_offset: i64,
+    registers: &mut Vec<lir::DecodableRegister>,
+) -> anyhow::Result<()> {
+    let repeat_addresses = |address: i64, repeat: &Option<mir::Repeat>| match repeat {
+        Some(mir::Repeat { count, stride }) => (0..*count as i64)
+            .map(|i| address + i * stride)
+            .collect::<Vec<_>>(),
+        None => vec![address],
+    };
+
+    for object in objects {
+        match object {
+            mir::Object::Block(block) => {
+                for address in
+                    repeat_addresses(address_offset + block.address_offset, &block.repeat)
+                {
+                    collect_decodable_registers(&block.objects, address, registers)?;
+                }
+            }
+            mir::Object::Register(register) if register.size_bits > 0 => {
+                let cfg_attr = cfg_attr_string_to_tokens(&register.cfg_attr)?;
+
+                for address in repeat_addresses(address_offset + register.address, &register.repeat)
+                {
+                    registers.push(lir::DecodableRegister {
+                        cfg_attr: cfg_attr.clone(),
+                        field_set_name: format_ident!("{}", register.name),
+                        address,
+                        size_bytes: Literal::usize_unsuffixed(
+                            register.size_bits.div_ceil(8) as usize
+                        ),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Two sets of fields have the same layout when every field has a field with the same name, type and bits in the other set.
+/// The docs and access of the fields don't matter.
+fn same_field_layout(left: &[mir::Field], right: &[mir::Field]) -> bool {
+    left.len() == right.len()
+        && left.iter().all(|l| {
+            right.iter().any(|r| {
+                l.cfg_attr == r.cfg_attr
+                    && l.name == r.name
+                    && l.bit_order == r.bit_order
+                    && l.base_type == r.base_type
+                    && l.field_conversion == r.field_conversion
+                    && l.field_address == r.field_address
+            })
+        })
+}
+
 #[allow(clippy::too_many_arguments)] // Though it is correct... it's too many args
 fn transform_field_set<'a>(
     field_set: &[mir::Field],
@@ -393,6 +1140,7 @@ fn transform_field_set<'a>(
     reset_value: Option<Vec<u8>>,
     ref_reset_overrides: Vec<(String, Vec<u8>)>,
     enum_list: impl Iterator<Item = &'a mir::Enum> + Clone,
+    crc_algorithm: mir::CrcAlgorithm,
 ) -> anyhow::Result<lir::FieldSet> {
     let cfg_attr = cfg_attr_string_to_tokens(cfg_attr)?;
 
@@ -403,6 +1151,10 @@ fn transform_field_set<'a>(
                 cfg_attr,
                 description,
                 name,
+                renamed_from,
+                bit_order: field_bit_order,
+                unit,
+                valid_when,
                 access,
                 base_type,
                 field_conversion,
@@ -437,6 +1189,55 @@ fn transform_field_set<'a>(
                     ),
                     lir::FieldConversionMethod::None,
                 ),
+                (
+                    mir::BaseType::Uint | mir::BaseType::Int,
+                    _,
+                    Some(mir::FieldConversion::Crc { over }),
+                ) => (
+                    format_ident!("u8"),
+                    lir::FieldConversionMethod::Crc {
+                        algorithm: match crc_algorithm {
+                            mir::CrcAlgorithm::Crc8Smbus => quote! { ::device_driver::Crc8::SMBUS },
+                            mir::CrcAlgorithm::Crc8Nrsc5 => quote! { ::device_driver::Crc8::NRSC5 },
+                            mir::CrcAlgorithm::Crc8Autosar => {
+                                quote! { ::device_driver::Crc8::AUTOSAR }
+                            }
+                        },
+                        over: over.clone(),
+                    },
+                ),
+                (
+                    mir::BaseType::Uint | mir::BaseType::Int,
+                    val,
+                    Some(mir::FieldConversion::Functions {
+                        read_type,
+                        read_fn,
+                        write_type,
+                        write_fn,
+                    }),
+                ) => (
+                    format_ident!(
+                        "{}{}",
+                        match base_type {
+                            mir::BaseType::Bool => unreachable!(),
+                            mir::BaseType::Uint => 'u',
+                            mir::BaseType::Int => 'i',
+                        },
+                        val.max(8).next_power_of_two()
+                    ),
+                    {
+                        let [read_type, read_fn, write_type, write_fn] =
+                            [read_type, read_fn, write_type, write_fn]
+                                .map(|path| syn::parse_str::<syn::Path>(path).unwrap());
+
+                        lir::FieldConversionMethod::Functions {
+                            read_type: quote! { #read_type },
+                            read_fn: quote! { #read_fn },
+                            write_type: quote! { #write_type },
+                            write_fn: quote! { #write_fn },
+                        }
+                    },
+                ),
                 (mir::BaseType::Uint | mir::BaseType::Int, val, Some(fc)) => (
                     format_ident!(
                         "{}{}",
@@ -474,6 +1275,18 @@ fn transform_field_set<'a>(
                 cfg_attr,
                 doc_attr: quote! { #[doc = #description] },
                 name: format_ident!("{name}"),
+                renamed_from: renamed_from
+                    .iter()
+                    .map(|old_name| format_ident!("{old_name}"))
+                    .collect(),
+                unit: unit.clone(),
+                valid_when: valid_when.as_ref().map(|valid_when| lir::ValidWhen {
+                    description: valid_when.to_string(),
+                    field_name: format_ident!("{}", valid_when.field),
+                    negated: valid_when.negated,
+                    raw_value: valid_when.raw_value,
+                }),
+                bit_order: field_bit_order.unwrap_or(bit_order),
                 address,
                 base_type,
                 conversion_method,
@@ -487,20 +1300,23 @@ fn transform_field_set<'a>(
         doc_attr: quote! { #[doc = #description] },
         name: field_set_name,
         byte_order,
-        bit_order,
         size_bits,
         reset_value: reset_value.unwrap_or_else(|| vec![0; size_bits.div_ceil(8) as usize]),
         ref_reset_overrides,
+        chip_variant_reset_values: Vec::new(),
         fields,
     })
 }
 
 fn collect_enums(device: &mir::Device) -> anyhow::Result<Vec<(mir::Enum, mir::BaseType, usize)>> {
-    let mut enums = Vec::new();
+    let mut enums: Vec<(mir::Enum, mir::BaseType, usize)> = Vec::new();
 
     recurse_objects(&device.objects, &mut |object| {
         for field in object.field_sets().flatten() {
-            if let Some(mir::FieldConversion::Enum { enum_value, .. }) = &field.field_conversion {
+            if let Some(mir::FieldConversion::Enum { enum_value, .. }) = &field.field_conversion
+                // Identical enums may be defined on multiple fields, but they're only generated once
+                && !enums.iter().any(|(e, _, _)| e.id() == enum_value.id())
+            {
                 enums.push((
                     enum_value.clone(),
                     field.base_type,
@@ -515,20 +1331,99 @@ fn collect_enums(device: &mir::Device) -> anyhow::Result<Vec<(mir::Enum, mir::Ba
     Ok(enums)
 }
 
+fn collect_newtypes(
+    device: &mir::Device,
+) -> anyhow::Result<Vec<(mir::Newtype, mir::BaseType, usize)>> {
+    let mut newtypes: Vec<(mir::Newtype, mir::BaseType, usize)> = Vec::new();
+
+    recurse_objects(&device.objects, &mut |object| {
+        for field in object.field_sets().flatten() {
+            if let Some(mir::FieldConversion::Newtype { newtype }) = &field.field_conversion
+                // Identical newtypes may be defined on multiple fields, but they're only generated once
+                && !newtypes.iter().any(|(n, _, _)| n.id() == newtype.id())
+            {
+                newtypes.push((
+                    newtype.clone(),
+                    field.base_type,
+                    field.field_address.clone().count(),
+                ))
+            }
+        }
+
+        Ok(())
+    })?;
+
+    Ok(newtypes)
+}
+
+fn transform_newtype(
+    newtype: &mir::Newtype,
+    base_type: mir::BaseType,
+    size_bits: usize,
+) -> anyhow::Result<lir::Newtype> {
+    let mir::Newtype {
+        cfg_attr,
+        description,
+        name,
+        inner,
+    } = newtype;
+
+    let base_type = match base_type {
+        mir::BaseType::Bool => unreachable!("Checked in a MIR pass. Bools have no conversion"),
+        mir::BaseType::Uint => format_ident!("u{}", size_bits.max(8).next_power_of_two()),
+        mir::BaseType::Int => format_ident!("i{}", size_bits.max(8).next_power_of_two()),
+    };
+
+    Ok(lir::Newtype {
+        cfg_attr: cfg_attr_string_to_tokens(cfg_attr)?,
+        doc_attr: quote! { #[doc = #description] },
+        name: format_ident!("{name}"),
+        inner: (*inner).into(),
+        base_type,
+    })
+}
+
 fn transform_enum(
     e: &mir::Enum,
     base_type: mir::BaseType,
     size_bits: usize,
+    enum_derives: &[Ident],
 ) -> anyhow::Result<lir::Enum> {
     let mir::Enum {
         cfg_attr,
         description,
         name,
         variants,
+        passthrough_attributes,
         generation_style: _,
     } = e;
 
     let cfg_attr = cfg_attr_string_to_tokens(cfg_attr)?;
+    let passthrough_attrs = passthrough_attributes
+        .iter()
+        .map(|attr| {
+            let meta = syn::parse_str::<syn::Meta>(attr)?;
+
+            // The enum already has the configured derives, so deriving them again would be a duplicate
+            if meta.path().is_ident("derive") {
+                let derives = meta.require_list()?.parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+                )?;
+                if let Some(derive) = derives.iter().find(|derive| {
+                    derive
+                        .get_ident()
+                        .is_some_and(|derive| enum_derives.contains(derive))
+                }) {
+                    anyhow::bail!(
+                        "Enum \"{name}\" derives \"{}\" with an attribute, but it's already derived for all enums",
+                        derive.to_token_stream()
+                    );
+                }
+            }
+
+            Ok(quote! { #[#meta] })
+        })
+        .collect::<Result<TokenStream, anyhow::Error>>()?;
 
     let base_type = match (base_type, size_bits) {
         (mir::BaseType::Bool, _) => format_ident!("u8"),
@@ -552,7 +1447,8 @@ fn transform_enum(
             let number = match value {
                 mir::EnumValue::Unspecified
                 | mir::EnumValue::Default
-                | mir::EnumValue::CatchAll => {
+                | mir::EnumValue::CatchAll
+                | mir::EnumValue::Fallback => {
                     let val = next_variant_number.unwrap_or_default();
                     next_variant_number = Some(val + 1);
                     val
@@ -561,6 +1457,10 @@ fn transform_enum(
                     next_variant_number = Some(*num + 1);
                     *num
                 }
+                mir::EnumValue::Range(range) => {
+                    next_variant_number = Some(*range.end() + 1);
+                    *range.start()
+                }
             };
 
             Ok(lir::EnumVariant {
@@ -568,8 +1468,12 @@ fn transform_enum(
                 doc_attr: quote! { #[doc = #description] },
                 name: format_ident!("{name}"),
                 number: Literal::i128_unsuffixed(number),
-                default: matches!(value, mir::EnumValue::Default),
-                catch_all: matches!(value, mir::EnumValue::CatchAll),
+                default: value.is_default(),
+                catch_all: value.is_catch_all(),
+                range_end: match value {
+                    mir::EnumValue::Range(range) => Some(Literal::i128_unsuffixed(*range.end())),
+                    _ => None,
+                },
             })
         })
         .collect::<Result<_, anyhow::Error>>()?;
@@ -580,6 +1484,7 @@ fn transform_enum(
         name: format_ident!("{name}"),
         base_type,
         variants,
+        passthrough_attrs,
     })
 }
 
@@ -656,6 +1561,337 @@ fn find_best_internal_address(device: &mir::Device) -> proc_macro2::Ident {
     }
 }
 
+/// Create the snapshot of the device, which contains all readable registers of the root block
+fn create_snapshot(blocks: &[lir::Block], driver_name: &str) -> lir::Snapshot {
+    let root_block = blocks
+        .iter()
+        .find(|b| b.root)
+        .expect("There's always a root block");
+
+    lir::Snapshot {
+        name: format_ident!("{driver_name}Snapshot"),
+        registers: root_block
+            .methods
+            .iter()
+            .filter_map(|method| match &method.method_type {
+                lir::BlockMethodType::Register {
+                    field_set_name,
+                    access: mir::Access::RW | mir::Access::RO,
+                    ..
+                } => Some(lir::SnapshotRegister {
+                    cfg_attr: method.cfg_attr.clone(),
+                    name: method.name.clone(),
+                    field_set_name: field_set_name.clone(),
+                    repeat_count: match &method.kind {
+                        lir::BlockMethodKind::Normal => None,
+                        lir::BlockMethodKind::Repeated { count, .. } => {
+                            Some(count.to_string().parse().unwrap())
+                        }
+                    },
+                }),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/// Collect the registers of the root block that can be written by the generated `configure` function.
+///
+/// Registers that are repeated, span multiple addresses or are shadowed are left out.
+/// Registers are marked as following the previous register when they can be written in the same transaction.
+/// That's not done after registers with a cfg, since the previous register may then not exist.
+fn create_configure(
+    blocks: &[lir::Block],
+    field_sets: &[lir::FieldSet],
+    driver_name: &str,
+    address_unit: mir::AddressUnit,
+) -> lir::Configure {
+    let root_block = blocks
+        .iter()
+        .find(|b| b.root)
+        .expect("There's always a root block");
+
+    let registers = root_block
+        .methods
+        .iter()
+        .filter_map(|method| match &method.method_type {
+            lir::BlockMethodType::Register {
+                field_set_name,
+                access: mir::Access::RW | mir::Access::WO,
+                reset_value_function,
+                span_offsets,
+                shadowed: false,
+                ..
+            } if span_offsets.is_empty() && matches!(method.kind, lir::BlockMethodKind::Normal) => {
+                let size_bits = field_sets
+                    .iter()
+                    .find(|fs| fs.name == *field_set_name)
+                    .expect("All field sets are collected")
+                    .size_bits;
+
+                Some((
+                    method,
+                    field_set_name,
+                    reset_value_function,
+                    method.address.to_string().parse::<i64>().unwrap(),
+                    size_bits,
+                ))
+            }
+            _ => None,
+        })
+        .sorted_by_key(|(_, _, _, address, _)| *address)
+        .collect::<Vec<_>>();
+
+    let unit_bits = address_unit.size_bytes() * 8;
+    let buffer_size = registers
+        .iter()
+        .map(|(_, _, _, _, size_bits)| size_bits.div_ceil(8))
+        .sum::<u32>();
+
+    lir::Configure {
+        name: format_ident!("{driver_name}Configure"),
+        registers: registers
+            .iter()
+            .enumerate()
+            .map(
+                |(i, (method, field_set_name, reset_value_function, address, _))| {
+                    let follows_previous = i.checked_sub(1).is_some_and(|previous| {
+                        let (previous_method, _, _, previous_address, previous_size_bits) =
+                            &registers[previous];
+
+                        previous_method.cfg_attr.is_empty()
+                            && previous_size_bits % unit_bits == 0
+                            && previous_address + (previous_size_bits / unit_bits) as i64
+                                == *address
+                    });
+
+                    lir::ConfigureRegister {
+                        cfg_attr: method.cfg_attr.clone(),
+                        name: method.name.clone(),
+                        field_set_name: (*field_set_name).clone(),
+                        reset_value_function: (*reset_value_function).clone(),
+                        address: method.address.clone(),
+                        follows_previous,
+                    }
+                },
+            )
+            .collect(),
+        buffer_size: Literal::u32_unsuffixed(buffer_size),
+    }
+}
+
+/// Lay out the registers of the root block at their addresses for the MMIO struct.
+/// The gaps between the registers are filled with padding.
+fn create_mmio(
+    blocks: &[lir::Block],
+    field_sets: &[lir::FieldSet],
+    driver_name: &str,
+    global_config: &mir::GlobalConfig,
+) -> anyhow::Result<Option<lir::Mmio>> {
+    if !global_config.mmio {
+        return Ok(None);
+    }
+
+    ensure!(
+        global_config.address_unit == mir::AddressUnit::Byte,
+        "Memory-mapped registers must be addressed in bytes"
+    );
+
+    let root_block = blocks
+        .iter()
+        .find(|b| b.root)
+        .expect("There's always a root block");
+
+    let registers = root_block
+        .methods
+        .iter()
+        .filter_map(|method| match &method.method_type {
+            lir::BlockMethodType::Register {
+                field_set_name,
+                access,
+                reset_value_function,
+                span_offsets,
+                ..
+            } if span_offsets.is_empty() && matches!(method.kind, lir::BlockMethodKind::Normal) => {
+                let size_bits = field_sets
+                    .iter()
+                    .find(|fs| fs.name == *field_set_name)
+                    .expect("All field sets are collected")
+                    .size_bits;
+
+                Some((
+                    method,
+                    lir::MmioRegister {
+                        cfg_attr: method.cfg_attr.clone(),
+                        doc_attr: method.doc_attr.clone(),
+                        field_set_name: field_set_name.clone(),
+                        access: *access,
+                        reset_value_function: reset_value_function.clone(),
+                    },
+                    method.address.to_string().parse::<i64>().unwrap(),
+                    size_bits.div_ceil(8) as i64,
+                ))
+            }
+            _ => None,
+        })
+        .sorted_by_key(|(_, _, address, _)| *address);
+
+    let mut fields = Vec::new();
+    let mut offset = 0;
+    let mut previous_name = None;
+
+    for (method, register, address, size_bytes) in registers {
+        ensure!(
+            address >= 0,
+            "Register `{}` has a negative address, so it can't be memory-mapped",
+            method.name
+        );
+
+        if let Some(previous_name) = previous_name
+            && address < offset
+        {
+            anyhow::bail!(
+                "Register `{}` overlaps with register `{previous_name}`, so they can't both be memory-mapped",
+                method.name
+            );
+        }
+
+        if address > offset {
+            fields.push(lir::MmioField {
+                name: format_ident!("_reserved_{offset}"),
+                offset: Literal::i64_unsuffixed(offset),
+                size_bytes: Literal::i64_unsuffixed(address - offset),
+                register: None,
+            });
+        }
+
+        fields.push(lir::MmioField {
+            name: method.name.clone(),
+            offset: Literal::i64_unsuffixed(address),
+            size_bytes: Literal::i64_unsuffixed(size_bytes),
+            register: Some(register),
+        });
+
+        offset = address + size_bytes;
+        previous_name = Some(&method.name);
+    }
+
+    Ok(Some(lir::Mmio {
+        name: format_ident!("{driver_name}Mmio"),
+        fields,
+    }))
+}
+
+/// Find all address ranges that are taken up by registers, including the registers in (repeated) blocks.
+///
+/// A register takes up one address for every address unit (a byte or a 16-bit word) of its size.
+/// The returned ranges are sorted, merged and clamped to the register address type.
+fn find_defined_register_addresses(
+    blocks: &[lir::Block],
+    field_sets: &[lir::FieldSet],
+    register_address_type: mir::Integer,
+    address_unit: mir::AddressUnit,
+) -> Vec<RangeInclusive<i64>> {
+    let root_block = blocks
+        .iter()
+        .find(|b| b.root)
+        .expect("There's always a root block");
+
+    let mut ranges = Vec::new();
+    collect_register_address_ranges(
+        blocks,
+        field_sets,
+        root_block,
+        0,
+        address_unit.size_bytes(),
+        &mut ranges,
+    );
+    ranges.sort_by_key(|range| *range.start());
+
+    let mut merged_ranges: Vec<RangeInclusive<i64>> = Vec::new();
+    for range in ranges {
+        let range = (*range.start()).max(register_address_type.min_value())
+            ..=(*range.end()).min(register_address_type.max_value());
+
+        if range.is_empty() {
+            continue;
+        }
+
+        match merged_ranges.last_mut() {
+            Some(last) if *range.start() <= last.end().saturating_add(1) => {
+                *last = *last.start()..=(*last.end()).max(*range.end());
+            }
+            _ => merged_ranges.push(range),
+        }
+    }
+
+    merged_ranges
+}
+
+fn collect_register_address_ranges(
+    blocks: &[lir::Block],
+    field_sets: &[lir::FieldSet],
+    block: &lir::Block,
+    address_offset: i64,
+    unit_bytes: u32,
+    ranges: &mut Vec<RangeInclusive<i64>>,
+) {
+    for method in &block.methods {
+        let address = address_offset + method.address.to_string().parse::<i64>().unwrap();
+
+        let (repeat_count, repeat_stride): (i64, i64) = match &method.kind {
+            lir::BlockMethodKind::Normal => (1, 0),
+            lir::BlockMethodKind::Repeated { count, stride } => (
+                count.to_string().parse().unwrap(),
+                stride.to_string().parse().unwrap(),
+            ),
+        };
+
+        for i in 0..repeat_count {
+            let address = address + i * repeat_stride;
+
+            match &method.method_type {
+                lir::BlockMethodType::Block { name } => {
+                    let sub_block = blocks
+                        .iter()
+                        .find(|b| b.name == *name)
+                        .expect("All blocks are collected");
+
+                    collect_register_address_ranges(
+                        blocks, field_sets, sub_block, address, unit_bytes, ranges,
+                    );
+                }
+                lir::BlockMethodType::Register {
+                    field_set_name,
+                    span_offsets,
+                    ..
+                } => {
+                    let size_bits = field_sets
+                        .iter()
+                        .find(|fs| fs.name == *field_set_name)
+                        .expect("All field sets are collected")
+                        .size_bits;
+
+                    if span_offsets.is_empty() {
+                        let size_units = size_bits.div_ceil(8 * unit_bytes).max(1) as i64;
+                        ranges.push(address..=address + size_units - 1);
+                    } else {
+                        let part_size_units = (size_bits / 8 / span_offsets.len() as u32)
+                            .div_ceil(unit_bytes)
+                            .max(1) as i64;
+
+                        for offset in span_offsets {
+                            let address = address + offset.to_string().parse::<i64>().unwrap();
+                            ranges.push(address..=address + part_size_units - 1);
+                        }
+                    }
+                }
+                lir::BlockMethodType::Command { .. } | lir::BlockMethodType::Buffer { .. } => {}
+            }
+        }
+    }
+}
+
 fn find_refs<'d>(
     device: &'d mir::Device,
     source_object: &mir::Object,
@@ -663,10 +1899,10 @@ fn find_refs<'d>(
     let mut found_refs = Vec::new();
 
     recurse_objects(&device.objects, &mut |object| {
-        if let mir::Object::Ref(ref_object) = object {
-            if ref_object.object_override.name() == source_object.name() {
-                found_refs.push(ref_object);
-            }
+        if let mir::Object::Ref(ref_object) = object
+            && ref_object.object_override.name() == source_object.name()
+        {
+            found_refs.push(ref_object);
         }
 
         Ok(())
@@ -674,3 +1910,558 @@ fn find_refs<'d>(
 
     Ok(found_refs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_value_in_register_docs() {
+        let mut device = mir::Device {
+            global_config: mir::GlobalConfig {
+                register_address_type: Some(mir::Integer::U8),
+                ..Default::default()
+            },
+            objects: vec![
+                mir::Object::Register(mir::Register {
+                    description: "Foo docs".into(),
+                    name: "Foo".into(),
+                    byte_order: Some(mir::ByteOrder::LE),
+                    size_bits: 16,
+                    reset_value: Some(mir::ResetValue::Integer(0x12AB)),
+                    ..Default::default()
+                }),
+                mir::Object::Register(mir::Register {
+                    name: "Bar".into(),
+                    address: 2,
+                    byte_order: Some(mir::ByteOrder::BE),
+                    size_bits: 24,
+                    reset_value: Some(mir::ResetValue::Array(vec![0x01, 0x02, 0x03])),
+                    ..Default::default()
+                }),
+                mir::Object::Register(mir::Register {
+                    description: "Baz docs".into(),
+                    name: "Baz".into(),
+                    address: 5,
+                    byte_order: Some(mir::ByteOrder::LE),
+                    size_bits: 8,
+                    ..Default::default()
+                }),
+            ],
+        };
+        mir::passes::run_passes(&mut device).unwrap();
+
+        let device = transform(device, "Device").unwrap();
+        let root_block = device.blocks.iter().find(|b| b.root).unwrap();
+        let docs = root_block
+            .methods
+            .iter()
+            .map(|m| m.doc_attr.to_string())
+            .collect::<Vec<_>>();
+
+        pretty_assertions::assert_eq!(
+            docs,
+            [
+                quote! { #[doc = "Foo docs\n\nReset value: `0x12AB`"] }.to_string(),
+                quote! { #[doc = "\n\nReset value: `0x010203`"] }.to_string(),
+                quote! { #[doc = "Baz docs"] }.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn feature_cfg_on_register_accessor() {
+        let mut device = mir::Device {
+            global_config: mir::GlobalConfig {
+                register_address_type: Some(mir::Integer::U8),
+                default_byte_order: Some(mir::ByteOrder::LE),
+                ..Default::default()
+            },
+            objects: vec![mir::Object::Register(mir::Register {
+                cfg_attr: mir::Cfg::new(Some("feature = \"adc\"")),
+                name: "AdcResult".into(),
+                size_bits: 8,
+                ..Default::default()
+            })],
+        };
+        mir::passes::run_passes(&mut device).unwrap();
+
+        let output = crate::lir::token_transform::transform(transform(device, "Device").unwrap());
+        let file = syn::parse2::<syn::File>(output).unwrap();
+        let expected_cfg = quote! { #[cfg(feature = "adc")] }.to_string();
+
+        let accessor = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Impl(item_impl) => Some(item_impl),
+                _ => None,
+            })
+            .flat_map(|item_impl| &item_impl.items)
+            .find_map(|item| match item {
+                syn::ImplItem::Fn(method) if method.sig.ident == "adc_result" => Some(method),
+                _ => None,
+            })
+            .expect("The register accessor is generated");
+        assert!(
+            accessor
+                .attrs
+                .iter()
+                .any(|attr| quote! { #attr }.to_string() == expected_cfg)
+        );
+    }
+
+    #[test]
+    fn configure_registers_follow_each_other() {
+        let register = |name: &str, address, size_bits, cfg| {
+            mir::Object::Register(mir::Register {
+                cfg_attr: mir::Cfg::new(cfg),
+                name: name.into(),
+                address,
+                size_bits,
+                ..Default::default()
+            })
+        };
+
+        let mut device = mir::Device {
+            global_config: mir::GlobalConfig {
+                register_address_type: Some(mir::Integer::U8),
+                default_byte_order: Some(mir::ByteOrder::LE),
+                ..Default::default()
+            },
+            objects: vec![
+                register("C", 3, 8, Some("feature = \"c\"")),
+                register("B", 2, 8, None),
+                register("A", 0, 16, None),
+                register("D", 4, 8, None),
+                register("E", 6, 12, None),
+                register("F", 8, 8, None),
+            ],
+        };
+        mir::passes::run_passes(&mut device).unwrap();
+
+        let configure = transform(device, "Device").unwrap().configure;
+        assert_eq!(
+            configure
+                .registers
+                .iter()
+                .map(|r| (r.name.to_string(), r.follows_previous))
+                .collect::<Vec<_>>(),
+            [
+                ("a".into(), false),
+                ("b".into(), true),
+                ("c".into(), true),
+                ("d".into(), false),
+                ("e".into(), false),
+                ("f".into(), false),
+            ]
+        );
+        assert_eq!(configure.buffer_size.to_string(), "8");
+    }
+
+    #[test]
+    fn dyn_access_leaves_out_repeated_and_wide() {
+        let mut device = mir::Device {
+            global_config: mir::GlobalConfig {
+                register_address_type: Some(mir::Integer::U8),
+                default_byte_order: Some(mir::ByteOrder::LE),
+                dyn_access: true,
+                ..Default::default()
+            },
+            objects: vec![
+                mir::Object::Register(mir::Register {
+                    name: "Foo".into(),
+                    access: mir::Access::RO,
+                    size_bits: 128,
+                    allow_bit_overlap: true,
+                    fields: vec![
+                        mir::Field {
+                            name: "small".into(),
+                            base_type: mir::BaseType::Int,
+                            field_address: 0..4,
+                            ..Default::default()
+                        },
+                        mir::Field {
+                            name: "wide".into(),
+                            base_type: mir::BaseType::Uint,
+                            field_address: 64..128,
+                            ..Default::default()
+                        },
+                        mir::Field {
+                            name: "huge".into(),
+                            base_type: mir::BaseType::Uint,
+                            field_address: 4..69,
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                }),
+                mir::Object::Register(mir::Register {
+                    name: "Bar".into(),
+                    address: 0x10,
+                    size_bits: 8,
+                    repeat: Some(mir::Repeat {
+                        count: 2,
+                        stride: 1,
+                    }),
+                    ..Default::default()
+                }),
+            ],
+        };
+        mir::passes::run_passes(&mut device).unwrap();
+
+        let registers = transform(device, "Device").unwrap().dyn_access.unwrap();
+        assert_eq!(registers.len(), 1);
+        assert_eq!(registers[0].name.to_string(), "foo");
+        assert!(registers[0].readable);
+        assert!(!registers[0].modifiable);
+        assert_eq!(
+            registers[0]
+                .fields
+                .iter()
+                .map(|f| (f.name.to_string(), f.signed, f.size_bits))
+                .collect::<Vec<_>>(),
+            [("small".into(), true, 4), ("wide".into(), false, 64)]
+        );
+    }
+
+    #[test]
+    fn mmio_registers_laid_out_at_addresses() {
+        let register = |name: &str, address, size_bits| {
+            mir::Object::Register(mir::Register {
+                name: name.into(),
+                address,
+                size_bits,
+                allow_address_overlap: true,
+                ..Default::default()
+            })
+        };
+
+        let device = |objects| mir::Device {
+            global_config: mir::GlobalConfig {
+                register_address_type: Some(mir::Integer::U8),
+                default_byte_order: Some(mir::ByteOrder::LE),
+                mmio: true,
+                ..Default::default()
+            },
+            objects,
+        };
+
+        let mut mmio_device = device(vec![
+            register("B", 4, 16),
+            register("A", 0, 12),
+            register("C", 6, 8),
+        ]);
+        mir::passes::run_passes(&mut mmio_device).unwrap();
+
+        let mmio = transform(mmio_device, "Device").unwrap().mmio.unwrap();
+        assert_eq!(mmio.name, "DeviceMmio");
+        assert_eq!(
+            mmio.fields
+                .iter()
+                .map(|f| (
+                    f.name.to_string(),
+                    f.offset.to_string(),
+                    f.size_bytes.to_string(),
+                    f.register.is_some()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("a".into(), "0".into(), "2".into(), true),
+                ("_reserved_2".into(), "2".into(), "2".into(), false),
+                ("b".into(), "4".into(), "2".into(), true),
+                ("c".into(), "6".into(), "1".into(), true),
+            ]
+        );
+
+        let mut overlapping_device = device(vec![register("A", 0, 16), register("B", 1, 8)]);
+        mir::passes::run_passes(&mut overlapping_device).unwrap();
+
+        assert_eq!(
+            transform(overlapping_device, "Device")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Register `b` overlaps with register `a`, so they can't both be memory-mapped"
+        );
+    }
+
+    #[test]
+    fn shared_inline_enum_generated_once() {
+        let mut device = crate::dsl_hir::mir_transform::transform(
+            syn::parse_str::<crate::dsl_hir::Device>(
+                "
+                config {
+                    type RegisterAddressType = u8;
+                }
+                register Foo {
+                    const ADDRESS = 0;
+                    const SIZE_BITS = 8;
+
+                    mode: uint as enum Mode {
+                        Off,
+                        On,
+                    } = 0..1,
+                },
+                register Bar {
+                    const ADDRESS = 1;
+                    const SIZE_BITS = 8;
+
+                    mode: uint as enum Mode {
+                        Off,
+                        On,
+                    } = 4..5,
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        mir::passes::run_passes(&mut device).unwrap();
+
+        let device = transform(device, "Device").unwrap();
+        assert_eq!(
+            device
+                .enums
+                .iter()
+                .map(|e| e.name.to_string())
+                .collect::<Vec<_>>(),
+            ["Mode"]
+        );
+    }
+
+    #[test]
+    fn command_echo_only_for_same_layout() {
+        let mut device = crate::dsl_hir::mir_transform::transform(
+            syn::parse_str::<crate::dsl_hir::Device>(
+                "
+                config {
+                    type CommandAddressType = u8;
+                    type DefaultByteOrder = LE;
+                }
+                command Echo {
+                    const ADDRESS = 0;
+                    const SIZE_BITS_IN = 16;
+                    const SIZE_BITS_OUT = 16;
+
+                    in {
+                        /// The value to echo
+                        value: uint = 0..12,
+                        flag: bool = 15,
+                    }
+                    out {
+                        flag: bool = 15,
+                        /// The echoed value
+                        value: uint = 0..12,
+                    }
+                },
+                command Shifted {
+                    const ADDRESS = 1;
+                    const SIZE_BITS_IN = 16;
+                    const SIZE_BITS_OUT = 16;
+
+                    in {
+                        value: uint = 0..12,
+                    }
+                    out {
+                        value: uint = 4..16,
+                    }
+                },
+                command Retyped {
+                    const ADDRESS = 2;
+                    const SIZE_BITS_IN = 8;
+                    const SIZE_BITS_OUT = 8;
+
+                    in {
+                        value: uint = 0..8,
+                    }
+                    out {
+                        value: int = 0..8,
+                    }
+                }
+                ",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        mir::passes::run_passes(&mut device).unwrap();
+
+        let device = transform(device, "Device").unwrap();
+        assert_eq!(
+            device
+                .command_echoes
+                .iter()
+                .map(|echo| (
+                    echo.field_set_name_in.to_string(),
+                    echo.field_set_name_out.to_string(),
+                    echo.size_bytes.to_string()
+                ))
+                .collect::<Vec<_>>(),
+            [("EchoFieldsIn".into(), "EchoFieldsOut".into(), "2".into())]
+        );
+    }
+
+    #[test]
+    fn id_register_checked() {
+        let id_register_error = |register: mir::Register, field: &str, expected: u128| {
+            let mut device = mir::Device {
+                global_config: mir::GlobalConfig {
+                    register_address_type: Some(mir::Integer::U8),
+                    id_register: Some(mir::IdRegister {
+                        register: "WhoAmI".into(),
+                        field: field.into(),
+                        expected,
+                    }),
+                    ..Default::default()
+                },
+                objects: vec![mir::Object::Register(register)],
+            };
+            mir::passes::run_passes(&mut device).unwrap();
+
+            transform(device, "Device").err().map(|e| e.to_string())
+        };
+        let who_am_i = mir::Register {
+            name: "WhoAmI".into(),
+            size_bits: 8,
+            byte_order: Some(mir::ByteOrder::LE),
+            fields: vec![mir::Field {
+                name: "chip_id".into(),
+                field_address: 0..6,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(id_register_error(who_am_i.clone(), "chip_id", 0x3F), None);
+        assert_eq!(
+            id_register_error(who_am_i.clone(), "chip", 0x3F).as_deref(),
+            Some("Id register \"WhoAmI\" has no field \"chip\"")
+        );
+        assert_eq!(
+            id_register_error(who_am_i.clone(), "chip_id", 0x40).as_deref(),
+            Some("The expected id 0x40 doesn't fit in field \"chip_id\" of id register \"WhoAmI\"")
+        );
+        assert_eq!(
+            id_register_error(
+                mir::Register {
+                    access: mir::Access::WO,
+                    ..who_am_i.clone()
+                },
+                "chip_id",
+                0x3F
+            )
+            .as_deref(),
+            Some("Id register \"WhoAmI\" is not readable")
+        );
+        assert_eq!(
+            id_register_error(
+                mir::Register {
+                    name: "Other".into(),
+                    ..who_am_i
+                },
+                "chip_id",
+                0x3F
+            )
+            .as_deref(),
+            Some("Id register \"WhoAmI\" must be defined in the root of the device")
+        );
+    }
+
+    #[test]
+    fn read_hook_rejects_shadowed_registers() {
+        let read_hook_error = |shadowed: bool| {
+            let mut device = mir::Device {
+                global_config: mir::GlobalConfig {
+                    register_address_type: Some(mir::Integer::U8),
+                    post_read: Some("crate::on_read".into()),
+                    ..Default::default()
+                },
+                objects: vec![mir::Object::Register(mir::Register {
+                    name: "Control".into(),
+                    size_bits: 8,
+                    byte_order: Some(mir::ByteOrder::LE),
+                    shadowed,
+                    ..Default::default()
+                })],
+            };
+            mir::passes::run_passes(&mut device).unwrap();
+
+            transform(device, "Device").err().map(|e| e.to_string())
+        };
+
+        assert_eq!(read_hook_error(false), None);
+        assert_eq!(
+            read_hook_error(true).as_deref(),
+            Some(
+                "Register \"Control\" is shadowed, so reading it never accesses the device. This can't be combined with the post read hook"
+            )
+        );
+    }
+
+    #[test]
+    fn enum_derives_validated() {
+        let derives = |derives: &[&str]| {
+            transform_enum_derives(Some(
+                &derives.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+            ))
+        };
+
+        assert_eq!(
+            transform_enum_derives(None).unwrap(),
+            ["Debug", "Copy", "Clone", "Eq", "PartialEq", "Hash"]
+        );
+        assert_eq!(derives(&["Clone", "Debug"]).unwrap(), ["Clone", "Debug"]);
+
+        assert_eq!(
+            derives(&["Default"]).unwrap_err().to_string(),
+            "The enum derive \"Default\" is not supported. Options are: Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord"
+        );
+        assert_eq!(
+            derives(&["Copy", "Debug"]).unwrap_err().to_string(),
+            "The enum derive \"Copy\" requires \"Clone\" to be derived as well"
+        );
+        assert_eq!(
+            derives(&["Clone"]).unwrap_err().to_string(),
+            "The enum derives must include \"Debug\", because the Debug impl of the field sets prints the enum fields"
+        );
+    }
+
+    #[test]
+    fn enum_passthrough_derive_not_duplicated() {
+        let enum_value = |passthrough_attribute: &str| mir::Enum {
+            passthrough_attributes: vec![passthrough_attribute.into()],
+            ..mir::Enum::new(
+                Default::default(),
+                "Mode".into(),
+                vec![mir::EnumVariant {
+                    name: "Off".into(),
+                    value: mir::EnumValue::Specified(0),
+                    ..Default::default()
+                }],
+            )
+        };
+        let enum_derives = transform_enum_derives(None).unwrap();
+
+        assert!(
+            transform_enum(
+                &enum_value("derive(PartialOrd, Ord)"),
+                mir::BaseType::Uint,
+                1,
+                &enum_derives
+            )
+            .is_ok()
+        );
+        assert_eq!(
+            transform_enum(
+                &enum_value("derive(PartialOrd, Hash)"),
+                mir::BaseType::Uint,
+                1,
+                &enum_derives
+            )
+            .err()
+            .unwrap()
+            .to_string(),
+            "Enum \"Mode\" derives \"Hash\" with an attribute, but it's already derived for all enums"
+        );
+    }
+}
@@ -1,9 +1,13 @@
 //! The MIR takes its data from HIR and makes sure all required data is there
 //! and all optional data is filled in with defaults.
 
-use std::{fmt::Display, ops::Range};
+use std::{
+    fmt::Display,
+    ops::{Range, RangeInclusive},
+};
 
 use convert_case::Boundary;
+use itertools::Itertools;
 use quote::TokenStreamExt;
 
 pub mod lir_transform;
@@ -21,12 +25,57 @@ pub struct GlobalConfig {
     pub default_field_access: Access,
     pub default_buffer_access: Access,
     pub default_byte_order: Option<ByteOrder>,
+    /// The default byte order of registers. Falls back to `default_byte_order`
+    pub default_register_byte_order: Option<ByteOrder>,
+    /// The default byte order of commands. Falls back to `default_byte_order`
+    pub default_command_byte_order: Option<ByteOrder>,
     pub default_bit_order: BitOrder,
     pub register_address_type: Option<Integer>,
     pub command_address_type: Option<Integer>,
     pub buffer_address_type: Option<Integer>,
     pub name_word_boundaries: Vec<Boundary>,
     pub defmt_feature: Option<String>,
+    /// The feature that gates the generated `ufmt::uDebug` impls
+    pub ufmt_feature: Option<String>,
+    pub auto_address: bool,
+    /// Registers and the values that are written to them (in order) by the generated `init` function
+    pub reset_sequence: Vec<(String, ResetValue)>,
+    /// The visibility of the generated types and accessor methods, e.g. `pub(crate)`. Defaults to `pub`
+    pub visibility: Option<String>,
+    /// The maximum amount of fields a field set may have
+    pub max_fields: Option<usize>,
+    /// Generate getters and setters for the fields of the registers directly on the blocks
+    pub flat_accessors: bool,
+    /// The algorithm used by the crc fields
+    pub crc_algorithm: CrcAlgorithm,
+    /// The register field that is checked against an expected value by the generated `check_id` function
+    pub id_register: Option<IdRegister>,
+    /// The unit in which the registers are addressed
+    pub address_unit: AddressUnit,
+    /// Generate a `#[repr(C)]` struct that can be placed over the memory-mapped registers
+    pub mmio: bool,
+    /// The time in microseconds the reset is held by the generated `reset_with_delay` function
+    pub reset_hold_us: Option<u32>,
+    /// Put the generated field sets in a submodule per object kind
+    pub group_by_kind: bool,
+    /// Generate the bitwise operator impls on the field sets
+    pub generate_bit_ops: bool,
+    /// The feature that gates everything that is generated
+    pub feature_gate: Option<String>,
+    /// The variants of the chip that can have different reset values. The first one is the default
+    pub chip_variants: Vec<String>,
+    /// Generate `read_field` and `write_field` functions on the device that access the fields of the root registers by name
+    pub dyn_access: bool,
+    /// The commands are dispatched over the same interface and in the same address space as the registers
+    pub shared_command_interface: bool,
+    /// The function that is called with the address after every successful read of a register
+    pub post_read: Option<String>,
+    /// The derives on the generated enums. When not set, the default derives are used
+    pub enum_derives: Option<Vec<String>>,
+    /// Fail register reads that read all `0x00` or all `0xFF` while the reset value isn't
+    pub detect_bus_stuck: bool,
+    /// Short names for the types of direct field conversions, with the full path they stand for
+    pub type_aliases: Vec<(String, String)>,
 }
 
 impl Default for GlobalConfig {
@@ -36,16 +85,51 @@ impl Default for GlobalConfig {
             default_field_access: Default::default(),
             default_buffer_access: Default::default(),
             default_byte_order: Default::default(),
+            default_register_byte_order: Default::default(),
+            default_command_byte_order: Default::default(),
             default_bit_order: Default::default(),
             register_address_type: Default::default(),
             command_address_type: Default::default(),
             buffer_address_type: Default::default(),
             name_word_boundaries: convert_case::Boundary::defaults(),
             defmt_feature: Default::default(),
+            ufmt_feature: Default::default(),
+            auto_address: Default::default(),
+            reset_sequence: Default::default(),
+            visibility: Default::default(),
+            max_fields: Default::default(),
+            flat_accessors: Default::default(),
+            crc_algorithm: Default::default(),
+            id_register: Default::default(),
+            address_unit: Default::default(),
+            mmio: Default::default(),
+            reset_hold_us: Default::default(),
+            group_by_kind: Default::default(),
+            generate_bit_ops: true,
+            feature_gate: Default::default(),
+            chip_variants: Default::default(),
+            dyn_access: Default::default(),
+            shared_command_interface: Default::default(),
+            post_read: Default::default(),
+            enum_derives: Default::default(),
+            detect_bus_stuck: Default::default(),
+            type_aliases: Default::default(),
         }
     }
 }
 
+impl GlobalConfig {
+    /// The byte order registers get when they don't specify one
+    pub fn register_byte_order(&self) -> Option<ByteOrder> {
+        self.default_register_byte_order.or(self.default_byte_order)
+    }
+
+    /// The byte order commands get when they don't specify one
+    pub fn command_byte_order(&self) -> Option<ByteOrder> {
+        self.default_command_byte_order.or(self.default_byte_order)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Integer {
     U8,
@@ -109,6 +193,8 @@ pub enum Access {
     RW,
     RO,
     WO,
+    /// Read-to-clear. Can be read, but reading has the side effect of clearing the value
+    RC,
 }
 
 impl quote::ToTokens for Access {
@@ -121,6 +207,30 @@ impl quote::ToTokens for Access {
 pub enum ByteOrder {
     LE,
     BE,
+    /// Little endian with the two bytes of every pair swapped
+    SwapPairs,
+}
+
+impl ByteOrder {
+    /// Reorder the little endian bytes into this byte order.
+    /// Doing it again reorders the bytes back to little endian.
+    pub fn reorder_from_le(self, bytes: &mut [u8]) {
+        match self {
+            ByteOrder::LE => {}
+            ByteOrder::BE => bytes.reverse(),
+            ByteOrder::SwapPairs => bytes.chunks_exact_mut(2).for_each(|pair| pair.swap(0, 1)),
+        }
+    }
+
+    /// The byte order that an object of the given size inherits when this is the default.
+    /// [ByteOrder::SwapPairs] needs whole byte pairs, so an object of a single byte uses [ByteOrder::LE] instead.
+    /// For that size the byte order doesn't matter anyway.
+    pub fn inherited_for(self, size_bits: u32) -> Self {
+        match self {
+            ByteOrder::SwapPairs if size_bits <= 8 => ByteOrder::LE,
+            byte_order => byte_order,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -130,6 +240,41 @@ pub enum BitOrder {
     MSB0,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrcAlgorithm {
+    #[default]
+    Crc8Smbus,
+    Crc8Nrsc5,
+    Crc8Autosar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressUnit {
+    /// Every address is a byte
+    #[default]
+    Byte,
+    /// Every address is a 16-bit word
+    Word16,
+}
+
+impl AddressUnit {
+    /// The amount of bytes that are behind a single address
+    pub fn size_bytes(&self) -> u32 {
+        match self {
+            AddressUnit::Byte => 1,
+            AddressUnit::Word16 => 2,
+        }
+    }
+}
+
+/// A field of a register that holds a fixed id of the device, like a `WHO_AM_I` register
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdRegister {
+    pub register: String,
+    pub field: String,
+    pub expected: u128,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Object {
     Block(Block),
@@ -194,7 +339,11 @@ impl Object {
             Object::Command(val) => {
                 vec![val.in_fields.as_mut_slice(), val.out_fields.as_mut_slice()].into_iter()
             }
-            Object::Block(_) | Object::Buffer(_) | Object::Ref(_) => Vec::new().into_iter(),
+            Object::Buffer(val) => match val.length_field.as_deref_mut() {
+                Some(length_field) => vec![core::slice::from_mut(length_field)].into_iter(),
+                None => Vec::new().into_iter(),
+            },
+            Object::Block(_) | Object::Ref(_) => Vec::new().into_iter(),
         }
     }
 
@@ -205,7 +354,11 @@ impl Object {
             Object::Command(val) => {
                 vec![val.in_fields.as_slice(), val.out_fields.as_slice()].into_iter()
             }
-            Object::Block(_) | Object::Buffer(_) | Object::Ref(_) => Vec::new().into_iter(),
+            Object::Buffer(val) => match val.length_field.as_deref() {
+                Some(length_field) => vec![core::slice::from_ref(length_field)].into_iter(),
+                None => Vec::new().into_iter(),
+            },
+            Object::Block(_) | Object::Ref(_) => Vec::new().into_iter(),
         }
     }
 
@@ -307,11 +460,82 @@ pub struct Register {
     pub bit_order: BitOrder,
     pub allow_bit_overlap: bool,
     pub allow_address_overlap: bool,
+    /// All bits that are not covered by a field are reserved
+    pub reserved_rest: bool,
+    /// Keep a copy of the last written value on the device struct. Reads return this copy instead of reading the device
+    pub shadowed: bool,
     pub address: i64,
+    /// When not empty, the register is virtual and made up of equally sized parts.
+    /// Every part is located at `address + offset`.
+    pub span_offsets: Vec<i64>,
     pub size_bits: u32,
     pub reset_value: Option<ResetValue>,
+    /// The reset values per chip variant.
+    /// Filled in by a mir pass when the reset value is given as a table of chip variants.
+    pub chip_variant_reset_values: Vec<(String, ResetValue)>,
     pub repeat: Option<Repeat>,
     pub fields: Vec<Field>,
+    /// The address the datasheet gives for the register. A test is generated that checks it against the computed address
+    pub expect_address: Option<i64>,
+    /// The size in bits the datasheet gives for the register. A test is generated that checks it against the size
+    pub expect_size_bits: Option<u32>,
+    /// The field that holds the amount of bytes that follow the bytes of the field.
+    /// When set, the size of the register is read at runtime and `size_bits` is the maximum size.
+    pub length_field: Option<String>,
+}
+
+impl Register {
+    /// The address right after this register (including all its repeats).
+    /// This is where the next register is placed when using auto addressing.
+    pub fn next_auto_address(&self, address_unit: AddressUnit) -> i64 {
+        let unit_bytes = address_unit.size_bytes();
+
+        let last_address = match self.repeat {
+            Some(Repeat { count, stride }) => {
+                self.address + count.saturating_sub(1) as i64 * stride
+            }
+            None => self.address,
+        };
+
+        match self.span_offsets.iter().max() {
+            Some(last_offset) => {
+                let part_size_bytes = self.size_bits / 8 / self.span_offsets.len() as u32;
+                self.address.max(last_address)
+                    + last_offset
+                    + part_size_bytes.div_ceil(unit_bytes).max(1) as i64
+            }
+            None => self.address.max(last_address) + self.size_bits.div_ceil(8 * unit_bytes) as i64,
+        }
+    }
+
+    /// The bit ranges that are reserved. When `reserved_rest` is set,
+    /// these are all the bits of the register that are not covered by a field.
+    pub fn reserved_bits(&self) -> Vec<Range<u32>> {
+        if !self.reserved_rest {
+            return Vec::new();
+        }
+
+        let mut reserved = Vec::new();
+        let mut start = 0;
+
+        for field_address in self
+            .fields
+            .iter()
+            .map(|field| field.field_address.clone())
+            .sorted_by_key(|field_address| field_address.start)
+        {
+            if field_address.start > start {
+                reserved.push(start..field_address.start);
+            }
+            start = start.max(field_address.end);
+        }
+
+        if start < self.size_bits {
+            reserved.push(start..self.size_bits);
+        }
+
+        reserved
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -319,12 +543,42 @@ pub struct Field {
     pub cfg_attr: Cfg,
     pub description: String,
     pub name: String,
+    /// Old names of the field for which deprecated aliases are generated
+    pub renamed_from: Vec<String>,
+    /// The bit order of the field. When not set, the bit order of the field set is used
+    pub bit_order: Option<BitOrder>,
+    /// The unit of the value of the field, used for documentation
+    pub unit: Option<String>,
+    /// The condition on another field of the field set that must hold for this field to be valid
+    pub valid_when: Option<ValidWhen>,
     pub access: Access,
     pub base_type: BaseType,
     pub field_conversion: Option<FieldConversion>,
     pub field_address: Range<u32>,
 }
 
+/// A comparison of another field with a value, like `mode == Output`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidWhen {
+    pub field: String,
+    pub negated: bool,
+    pub value: FieldResetValue,
+    /// The raw value the field is compared to.
+    /// Filled in by a mir pass.
+    pub raw_value: i128,
+}
+
+impl Display for ValidWhen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operator = if self.negated { "!=" } else { "==" };
+        match &self.value {
+            FieldResetValue::Integer(value) => write!(f, "{} {operator} {value}", self.field),
+            FieldResetValue::Bool(value) => write!(f, "{} {operator} {value}", self.field),
+            FieldResetValue::Variant(value) => write!(f, "{} {operator} {value}", self.field),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BaseType {
     Bool,
@@ -335,8 +589,29 @@ pub enum BaseType {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FieldConversion {
-    Direct { type_name: String, use_try: bool },
-    Enum { enum_value: Enum, use_try: bool },
+    Direct {
+        type_name: String,
+        use_try: bool,
+    },
+    Enum {
+        enum_value: Enum,
+        use_try: bool,
+    },
+    /// The field is a crc over the bits in the range (must be whole bytes)
+    Crc {
+        over: Range<u32>,
+    },
+    /// The field is wrapped in a generated tuple struct
+    Newtype {
+        newtype: Newtype,
+    },
+    /// The field is read with one function and written with another, possibly with different types
+    Functions {
+        read_type: String,
+        read_fn: String,
+        write_type: String,
+        write_fn: String,
+    },
 }
 
 impl FieldConversion {
@@ -344,6 +619,9 @@ impl FieldConversion {
         match self {
             FieldConversion::Direct { use_try, .. } => *use_try,
             FieldConversion::Enum { use_try, .. } => *use_try,
+            FieldConversion::Crc { .. } => false,
+            FieldConversion::Newtype { .. } => false,
+            FieldConversion::Functions { .. } => false,
         }
     }
 
@@ -351,16 +629,31 @@ impl FieldConversion {
         match self {
             FieldConversion::Direct { type_name, .. } => type_name,
             FieldConversion::Enum { enum_value, .. } => &enum_value.name,
+            FieldConversion::Crc { .. } => "u8",
+            FieldConversion::Newtype { newtype } => &newtype.name,
+            FieldConversion::Functions { read_type, .. } => read_type,
         }
     }
 }
 
+/// A tuple struct that wraps the value of a field, so it can't be mixed up with other values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Newtype {
+    pub cfg_attr: Cfg,
+    pub description: String,
+    pub name: String,
+    /// The type of the value in the tuple struct
+    pub inner: Integer,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Enum {
     pub cfg_attr: Cfg,
     pub description: String,
     pub name: String,
     pub variants: Vec<EnumVariant>,
+    /// Attributes (without the `#[]`) that are put on the generated enum as is
+    pub passthrough_attributes: Vec<String>,
     generation_style: Option<EnumGenerationStyle>,
 }
 
@@ -371,6 +664,7 @@ impl Enum {
             description,
             name,
             variants,
+            passthrough_attributes: Vec::new(),
             generation_style: None,
         }
     }
@@ -387,6 +681,7 @@ impl Enum {
             description,
             name,
             variants,
+            passthrough_attributes: Vec::new(),
             generation_style: Some(generation_style),
         }
     }
@@ -421,25 +716,31 @@ pub enum EnumValue {
     #[default]
     Unspecified,
     Specified(i128),
+    /// A range of raw values that all map to the variant. The start is used when writing it
+    Range(RangeInclusive<i128>),
     Default,
     CatchAll,
+    /// Both the default and the catch all. Unknown raw values are kept in the variant and written back unchanged
+    Fallback,
 }
 
 impl EnumValue {
-    /// Returns `true` if the enum value is [`Default`].
+    /// Returns `true` if the enum value is [`Default`] or [`Fallback`].
     ///
     /// [`Default`]: EnumValue::Default
+    /// [`Fallback`]: EnumValue::Fallback
     #[must_use]
     pub fn is_default(&self) -> bool {
-        matches!(self, Self::Default)
+        matches!(self, Self::Default | Self::Fallback)
     }
 
-    /// Returns `true` if the enum value is [`CatchAll`].
+    /// Returns `true` if the enum value is [`CatchAll`] or [`Fallback`].
     ///
     /// [`CatchAll`]: EnumValue::CatchAll
+    /// [`Fallback`]: EnumValue::Fallback
     #[must_use]
     pub fn is_catch_all(&self) -> bool {
-        matches!(self, Self::CatchAll)
+        matches!(self, Self::CatchAll | Self::Fallback)
     }
 }
 
@@ -449,6 +750,9 @@ pub struct Command {
     pub description: String,
     pub name: String,
     pub address: i64,
+    /// The bytes of the opcode when the address is given as a byte array.
+    /// The address is then made up of these bytes in big endian order.
+    pub opcode: Vec<u8>,
     pub byte_order: Option<ByteOrder>,
     pub bit_order: BitOrder,
     pub allow_bit_overlap: bool,
@@ -458,6 +762,27 @@ pub struct Command {
     pub repeat: Option<Repeat>,
     pub in_fields: Vec<Field>,
     pub out_fields: Vec<Field>,
+    /// The bool field of a register that is polled after dispatching until it's set
+    pub poll_until: Option<PollUntil>,
+}
+
+/// A bool field of a register in the same block, like a `done` bit in a status register
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PollUntil {
+    pub register: String,
+    pub field: String,
+}
+
+impl Command {
+    /// The address of a multi-byte opcode, which is made up of the bytes in big endian order.
+    /// Returns `None` when the opcode is empty or longer than 4 bytes.
+    pub fn opcode_address(opcode: &[u8]) -> Option<i64> {
+        (1..=4).contains(&opcode.len()).then(|| {
+            opcode
+                .iter()
+                .fold(0, |address, byte| address << 8 | *byte as i64)
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -467,6 +792,22 @@ pub struct Buffer {
     pub name: String,
     pub access: Access,
     pub address: i64,
+    /// The name of the register whose field set is the layout of the frames in the buffer
+    pub frame: Option<String>,
+    /// The maximum amount of bytes that can be written to the buffer at once
+    pub max_size: Option<u32>,
+    /// The field of the prefix that is read before every payload. It holds the length of the payload in bytes.
+    /// The prefix is as many bytes as needed to hold the field.
+    pub length_field: Option<Box<Field>>,
+}
+
+impl Buffer {
+    /// The size of the length prefix in bits. It's rounded up to whole bytes
+    pub fn length_prefix_size_bits(&self) -> Option<u32> {
+        self.length_field
+            .as_ref()
+            .map(|field| field.field_address.end.div_ceil(8) * 8)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -553,6 +894,17 @@ pub struct CommandOverride {
 pub enum ResetValue {
     Integer(u128),
     Array(Vec<u8>),
+    /// The values of the fields by name. The fields that aren't named are zero
+    Fields(Vec<(String, FieldResetValue)>),
+}
+
+/// The reset value of a single field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldResetValue {
+    Integer(i128),
+    Bool(bool),
+    /// The name of a variant of the enum of the field
+    Variant(String),
 }
 
 impl ResetValue {
@@ -639,6 +991,7 @@ impl_unique!(Buffer);
 impl_unique!(RefObject);
 impl_unique!(Block);
 impl_unique!(Enum);
+impl_unique!(Newtype);
 impl_unique!(EnumVariant);
 
 impl Unique for Object {
@@ -121,4 +121,26 @@ mod tests {
             "The command addresses go as high as 128000, but the selected address type `u16` only goes up to 65535. Choose an address type that can fit the full address range"
         );
     }
+
+    #[test]
+    fn span_not_too_high() {
+        let mut start_mir = Device {
+            global_config: GlobalConfig {
+                register_address_type: Some(Integer::U8),
+                ..Default::default()
+            },
+            objects: vec![Object::Register(Register {
+                name: "MyReg".into(),
+                address: 0xFF,
+                span_offsets: vec![0, 1],
+                size_bits: 16,
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "The register addresses go as high as 256, but the selected address type `u8` only goes up to 255. Choose an address type that can fit the full address range"
+        );
+    }
 }
@@ -0,0 +1,166 @@
+use crate::mir::{Device, Integer, Object, ObjectOverride, RefObject};
+
+use super::{find_min_max_addresses, recurse_objects};
+
+/// Infers the register address type when it's not specified and a register is defined.
+/// The smallest integer type that fits all register addresses is picked.
+/// Unsigned types are preferred and signed types are only picked when an address is negative.
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    if device.global_config.register_address_type.is_some() {
+        return Ok(());
+    }
+
+    let mut register_defined = false;
+    recurse_objects(&device.objects, &mut |object| {
+        register_defined |= matches!(object, Object::Register(_));
+        Ok(())
+    })?;
+
+    if !register_defined {
+        return Ok(());
+    }
+
+    let (min_address, max_address) = find_min_max_addresses(&device.objects, |o| {
+        matches!(
+            o,
+            Object::Block(_)
+                | Object::Register(_)
+                | Object::Ref(RefObject {
+                    object_override: ObjectOverride::Register(_),
+                    ..
+                })
+        )
+    });
+
+    let candidates: &[Integer] = match min_address < 0 {
+        false => &[Integer::U8, Integer::U16, Integer::U32, Integer::I64],
+        true => &[Integer::I8, Integer::I16, Integer::I32, Integer::I64],
+    };
+
+    // I64 can hold any address, so there's always a fitting type
+    device.global_config.register_address_type = candidates
+        .iter()
+        .copied()
+        .find(|integer| min_address >= integer.min_value() && max_address <= integer.max_value());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{Block, GlobalConfig, Register};
+
+    use super::*;
+
+    fn device_with_register_addresses(addresses: &[i64]) -> Device {
+        Device {
+            global_config: Default::default(),
+            objects: addresses
+                .iter()
+                .enumerate()
+                .map(|(i, address)| {
+                    Object::Register(Register {
+                        name: format!("Reg{i}"),
+                        address: *address,
+                        ..Default::default()
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn smallest_fitting_type_inferred() {
+        let mut device = device_with_register_addresses(&[0, 0x1FF]);
+        run_pass(&mut device).unwrap();
+        assert_eq!(
+            device.global_config.register_address_type,
+            Some(Integer::U16)
+        );
+
+        let mut device = device_with_register_addresses(&[0, 0xFF]);
+        run_pass(&mut device).unwrap();
+        assert_eq!(
+            device.global_config.register_address_type,
+            Some(Integer::U8)
+        );
+
+        let mut device = device_with_register_addresses(&[0x1_0000_0000]);
+        run_pass(&mut device).unwrap();
+        assert_eq!(
+            device.global_config.register_address_type,
+            Some(Integer::I64)
+        );
+
+        let mut device = device_with_register_addresses(&[-1, 0x80]);
+        run_pass(&mut device).unwrap();
+        assert_eq!(
+            device.global_config.register_address_type,
+            Some(Integer::I16)
+        );
+    }
+
+    #[test]
+    fn block_offsets_taken_into_account() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Block(Block {
+                name: "Foo".into(),
+                address_offset: 0x100,
+                objects: vec![Object::Register(Register {
+                    name: "Bar".into(),
+                    address: 0x10,
+                    ..Default::default()
+                })],
+                ..Default::default()
+            })],
+        };
+        run_pass(&mut device).unwrap();
+        assert_eq!(
+            device.global_config.register_address_type,
+            Some(Integer::U16)
+        );
+    }
+
+    #[test]
+    fn span_offsets_taken_into_account() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Foo".into(),
+                address: 0xFF,
+                span_offsets: vec![0, 1],
+                size_bits: 16,
+                ..Default::default()
+            })],
+        };
+        run_pass(&mut device).unwrap();
+        assert_eq!(
+            device.global_config.register_address_type,
+            Some(Integer::U16)
+        );
+    }
+
+    #[test]
+    fn specified_type_kept() {
+        let mut device = Device {
+            global_config: GlobalConfig {
+                register_address_type: Some(Integer::U32),
+                ..Default::default()
+            },
+            ..device_with_register_addresses(&[0])
+        };
+        run_pass(&mut device).unwrap();
+        assert_eq!(
+            device.global_config.register_address_type,
+            Some(Integer::U32)
+        );
+    }
+
+    #[test]
+    fn nothing_inferred_without_registers() {
+        let mut device = device_with_register_addresses(&[]);
+        run_pass(&mut device).unwrap();
+        assert_eq!(device.global_config.register_address_type, None);
+    }
+}
@@ -5,15 +5,10 @@ use crate::mir::{Device, Object};
 use super::recurse_objects;
 
 /// Checks if the various address types are specified. If not an error is given out.
+/// The register address type is inferred in an earlier pass, so it's always there when a register is defined.
 pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
     recurse_objects(&device.objects, &mut |object| {
         match object {
-            Object::Register(_) => {
-                ensure!(
-                    device.global_config.register_address_type.is_some(),
-                    "No register address type is specified in the global config, but it's required since a register is defined."
-                );
-            }
             Object::Command(_) => {
                 ensure!(
                     device.global_config.command_address_type.is_some(),
@@ -0,0 +1,143 @@
+use anyhow::ensure;
+
+use super::recurse_objects;
+use crate::mir::{AddressUnit, ByteOrder, Device, Object};
+
+/// Check the registers against the address unit.
+/// With 16-bit words, every register must be made up of whole words and all registers must use the same byte order,
+/// since that decides how the bytes are combined into words.
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    if device.global_config.address_unit == AddressUnit::Byte {
+        return Ok(());
+    }
+
+    let mut first_byte_order: Option<(&str, ByteOrder)> = None;
+
+    recurse_objects(&device.objects, &mut |object| {
+        let Object::Register(register) = object else {
+            return Ok(());
+        };
+
+        ensure!(
+            register.size_bits % 16 == 0,
+            "Register \"{}\" has a size of {} bits, which is not a whole amount of 16-bit words",
+            register.name,
+            register.size_bits
+        );
+        ensure!(
+            register.span_offsets.is_empty(),
+            "Register \"{}\" spans multiple addresses. This is not supported with 16-bit word addressing",
+            register.name
+        );
+
+        let byte_order = register
+            .byte_order
+            .expect("Byte order is specified for all registers in an earlier pass");
+
+        match first_byte_order {
+            None => first_byte_order = Some((&register.name, byte_order)),
+            Some((first_name, first_byte_order)) => ensure!(
+                byte_order == first_byte_order,
+                "Registers \"{first_name}\" and \"{}\" have a different byte order. With 16-bit word addressing all registers must have the same byte order",
+                register.name
+            ),
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{GlobalConfig, Register};
+
+    use super::*;
+
+    fn word_device(registers: Vec<Register>) -> Device {
+        Device {
+            global_config: GlobalConfig {
+                address_unit: AddressUnit::Word16,
+                ..Default::default()
+            },
+            objects: registers.into_iter().map(Object::Register).collect(),
+        }
+    }
+
+    #[test]
+    fn word_registers_accepted() {
+        run_pass(&mut word_device(vec![
+            Register {
+                name: "Foo".into(),
+                size_bits: 16,
+                byte_order: Some(ByteOrder::BE),
+                ..Default::default()
+            },
+            Register {
+                name: "Bar".into(),
+                size_bits: 48,
+                byte_order: Some(ByteOrder::BE),
+                ..Default::default()
+            },
+        ]))
+        .unwrap();
+
+        // Byte addressed devices are not checked
+        run_pass(&mut Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Foo".into(),
+                size_bits: 8,
+                ..Default::default()
+            })],
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn invalid_word_registers_rejected() {
+        assert_eq!(
+            run_pass(&mut word_device(vec![Register {
+                name: "Foo".into(),
+                size_bits: 24,
+                byte_order: Some(ByteOrder::LE),
+                ..Default::default()
+            }]))
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" has a size of 24 bits, which is not a whole amount of 16-bit words"
+        );
+
+        assert_eq!(
+            run_pass(&mut word_device(vec![Register {
+                name: "Foo".into(),
+                size_bits: 32,
+                span_offsets: vec![0, 1],
+                byte_order: Some(ByteOrder::LE),
+                ..Default::default()
+            }]))
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" spans multiple addresses. This is not supported with 16-bit word addressing"
+        );
+
+        assert_eq!(
+            run_pass(&mut word_device(vec![
+                Register {
+                    name: "Foo".into(),
+                    size_bits: 16,
+                    byte_order: Some(ByteOrder::LE),
+                    ..Default::default()
+                },
+                Register {
+                    name: "Bar".into(),
+                    size_bits: 16,
+                    byte_order: Some(ByteOrder::BE),
+                    ..Default::default()
+                },
+            ]))
+            .unwrap_err()
+            .to_string(),
+            "Registers \"Foo\" and \"Bar\" have a different byte order. With 16-bit word addressing all registers must have the same byte order"
+        );
+    }
+}
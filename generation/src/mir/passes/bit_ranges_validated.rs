@@ -2,17 +2,22 @@ use std::ops::Range;
 
 use anyhow::ensure;
 
-use crate::mir::{Device, Field, Object};
+use crate::mir::{BaseType, BitOrder, Device, Field, Object};
 
 use super::recurse_objects_mut;
 
-/// Validate that the bit ranges of fields fall within the max size and don't have overlap if they're not allowed
+/// Validate that registers have a size and that the bit ranges of fields fall within the max size and don't have overlap if they're not allowed
 pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
     recurse_objects_mut(&mut device.objects, &mut |object| match object {
         Object::Register(r) => {
+            ensure!(
+                r.size_bits > 0,
+                "Register \"{}\" has a size of 0 bits. A register needs a size to be generated, so set it to the size of the register",
+                r.name
+            );
             validate_len(&r.fields, r.size_bits, &r.name)?;
             if !r.allow_bit_overlap {
-                validate_overlap(&r.fields, &r.name)?;
+                validate_overlap(&r.fields, r.bit_order, &r.name)?;
             }
 
             Ok(())
@@ -20,17 +25,37 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
         Object::Command(c) => {
             validate_len(&c.in_fields, c.size_bits_in, &format!("{} (in)", c.name))?;
             if !c.allow_bit_overlap {
-                validate_overlap(&c.in_fields, &format!("{} (in)", c.name))?;
+                validate_overlap(&c.in_fields, c.bit_order, &format!("{} (in)", c.name))?;
             }
 
             validate_len(&c.out_fields, c.size_bits_out, &format!("{} (out)", c.name))?;
             if !c.allow_bit_overlap {
-                validate_overlap(&c.out_fields, &format!("{} (out)", c.name))?;
+                validate_overlap(&c.out_fields, c.bit_order, &format!("{} (out)", c.name))?;
+            }
+
+            Ok(())
+        }
+        Object::Buffer(b) => {
+            if let (Some(length_field), Some(size_bits)) =
+                (&b.length_field, b.length_prefix_size_bits())
+            {
+                ensure!(
+                    length_field.base_type == BaseType::Uint
+                        && length_field.field_conversion.is_none(),
+                    "Buffer \"{}\" has length field \"{}\" that is not a uint without conversion",
+                    b.name,
+                    length_field.name
+                );
+                validate_len(
+                    core::slice::from_ref(length_field),
+                    size_bits,
+                    &format!("{} (length)", b.name),
+                )?;
             }
 
             Ok(())
         }
-        Object::Block(_) | Object::Buffer(_) | Object::Ref(_) => Ok(()),
+        Object::Block(_) | Object::Ref(_) => Ok(()),
     })
 }
 
@@ -52,11 +77,25 @@ fn validate_len(field_set: &[Field], size_bits: u32, object_name: &str) -> anyho
     Ok(())
 }
 
-fn validate_overlap(field_set: &[Field], object_name: &str) -> anyhow::Result<()> {
+fn validate_overlap(
+    field_set: &[Field],
+    bit_order: BitOrder,
+    object_name: &str,
+) -> anyhow::Result<()> {
     for (i, field) in field_set.iter().enumerate() {
         for second_field in &field_set[(i + 1).min(field_set.len())..] {
+            let overlap = if field.bit_order.unwrap_or(bit_order)
+                == second_field.bit_order.unwrap_or(bit_order)
+            {
+                ranges_overlap(&field.field_address, &second_field.field_address)
+            } else {
+                // The bit indices mean something different, so compare the lsb0 indices
+                let second_bits = lsb0_bits(second_field, bit_order).collect::<Vec<_>>();
+                lsb0_bits(field, bit_order).any(|bit| second_bits.contains(&bit))
+            };
+
             ensure!(
-                !ranges_overlap(&field.field_address, &second_field.field_address),
+                !overlap,
                 "Object \"{object_name}\" has two overlapping fields: \"{}\" and \"{}\". If this is intended, set the `AllowBitOverlap` option to true",
                 field.name,
                 second_field.name
@@ -71,9 +110,19 @@ fn ranges_overlap(l: &Range<u32>, r: &Range<u32>) -> bool {
     l.start < r.end && r.start < l.end
 }
 
+/// Get the lsb0 indices of all bits of the field
+fn lsb0_bits(field: &Field, bit_order: BitOrder) -> impl Iterator<Item = u32> {
+    let bit_order = field.bit_order.unwrap_or(bit_order);
+    field.field_address.clone().map(move |bit| match bit_order {
+        BitOrder::LSB0 => bit,
+        // In msb0 the bits are reversed within their byte
+        BitOrder::MSB0 => bit / 8 * 8 + 7 - bit % 8,
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::mir::{Command, Register};
+    use crate::mir::{Buffer, Command, FieldConversion, Register};
 
     use super::*;
 
@@ -412,4 +461,132 @@ mod tests {
             "Object \"MyReg (out)\" has two overlapping fields: \"my_field\" and \"my_field2\". If this is intended, set the `AllowBitOverlap` option to true"
         );
     }
+
+    #[test]
+    fn overlap_mixed_bit_order() {
+        let mut start_mir = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "MyReg".into(),
+                size_bits: 8,
+                fields: vec![
+                    Field {
+                        name: "my_field".into(),
+                        field_address: 0..4,
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "my_field2".into(),
+                        bit_order: Some(BitOrder::MSB0),
+                        field_address: 0..4,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            })],
+        };
+
+        run_pass(&mut start_mir).unwrap();
+
+        let mut start_mir = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "MyReg".into(),
+                size_bits: 8,
+                fields: vec![
+                    Field {
+                        name: "my_field".into(),
+                        field_address: 0..5,
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "my_field2".into(),
+                        bit_order: Some(BitOrder::MSB0),
+                        field_address: 0..4,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "Object \"MyReg\" has two overlapping fields: \"my_field\" and \"my_field2\". If this is intended, set the `AllowBitOverlap` option to true"
+        );
+    }
+
+    #[test]
+    fn zero_size_register_rejected() {
+        let mut start_mir = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "MyReg".into(),
+                size_bits: 0,
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "Register \"MyReg\" has a size of 0 bits. A register needs a size to be generated, so set it to the size of the register"
+        );
+
+        // Commands can have empty field sets
+        let mut start_mir = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Command(Command {
+                name: "MyCommand".into(),
+                ..Default::default()
+            })],
+        };
+
+        run_pass(&mut start_mir).unwrap();
+    }
+
+    #[test]
+    fn length_field_checked() {
+        let buffer = |field: Field| Device {
+            global_config: Default::default(),
+            objects: vec![Object::Buffer(Buffer {
+                name: "MyBuffer".into(),
+                length_field: Some(Box::new(field)),
+                ..Default::default()
+            })],
+        };
+
+        run_pass(&mut buffer(Field {
+            name: "length".into(),
+            field_address: 0..12,
+            ..Default::default()
+        }))
+        .unwrap();
+
+        assert_eq!(
+            run_pass(&mut buffer(Field {
+                name: "length".into(),
+                base_type: BaseType::Int,
+                field_address: 0..8,
+                ..Default::default()
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Buffer \"MyBuffer\" has length field \"length\" that is not a uint without conversion"
+        );
+
+        assert_eq!(
+            run_pass(&mut buffer(Field {
+                name: "length".into(),
+                field_address: 0..8,
+                field_conversion: Some(FieldConversion::Direct {
+                    type_name: "Length".into(),
+                    use_try: false,
+                }),
+                ..Default::default()
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Buffer \"MyBuffer\" has length field \"length\" that is not a uint without conversion"
+        );
+    }
 }
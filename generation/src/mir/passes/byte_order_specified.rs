@@ -1,28 +1,25 @@
-use anyhow::bail;
+use anyhow::{bail, ensure};
 
-use crate::mir::{Device, Object};
+use crate::mir::{ByteOrder, Device, Object};
 
 use super::recurse_objects_mut;
 
-/// Checks if the byte order is set for all registers and commands that need it and fills it out for the ones that aren't specified
+/// Checks if the byte order is set for all registers and commands that need it and fills it out for the ones that aren't specified.
+/// Also checks that everything using the [ByteOrder::SwapPairs] byte order consists of whole byte pairs.
 pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
-    if let Some(default_byte_order) = device.global_config.default_byte_order {
-        recurse_objects_mut(&mut device.objects, &mut |object| match object {
-            Object::Register(r) if r.byte_order.is_none() => {
-                r.byte_order = Some(default_byte_order);
-                Ok(())
-            }
-            Object::Command(c) if c.byte_order.is_none() => {
-                c.byte_order = Some(default_byte_order);
-                Ok(())
-            }
-            _ => Ok(()),
-        })?;
-
-        return Ok(());
-    }
+    let register_byte_order = device.global_config.register_byte_order();
+    let command_byte_order = device.global_config.command_byte_order();
 
     recurse_objects_mut(&mut device.objects, &mut |object| match object {
+        Object::Register(r) if r.byte_order.is_none() && register_byte_order.is_some() => {
+            r.byte_order = register_byte_order.map(|bo| bo.inherited_for(r.size_bits));
+            Ok(())
+        }
+        Object::Command(c) if c.byte_order.is_none() && command_byte_order.is_some() => {
+            let size_bits = c.size_bits_in.max(c.size_bits_out);
+            c.byte_order = command_byte_order.map(|bo| bo.inherited_for(size_bits));
+            Ok(())
+        }
         Object::Register(r) if r.size_bits > 8 && r.byte_order.is_none() => {
             bail!(
                 "No byte order is specified for register \"{}\" while it's big enough that byte order is important. Specify it on the register or in the global config",
@@ -47,13 +44,61 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
             c.byte_order = Some(crate::mir::ByteOrder::LE);
             Ok(())
         }
+        Object::Buffer(b)
+            if b.length_prefix_size_bits()
+                .is_some_and(|size_bits| size_bits > 8)
+                && register_byte_order.is_none() =>
+        {
+            bail!(
+                "No byte order is specified for the length prefix of buffer \"{}\" while it's big enough that byte order is important. Specify it in the global config",
+                b.name
+            );
+        }
         _ => Ok(()),
+    })?;
+
+    recurse_objects_mut(&mut device.objects, &mut |object| {
+        match object {
+            Object::Register(r) if r.byte_order == Some(ByteOrder::SwapPairs) => {
+                ensure!(
+                    r.size_bits % 16 == 0,
+                    "Register \"{}\" uses the `SwapPairs` byte order, but its size of {} bits is not a multiple of 16",
+                    r.name,
+                    r.size_bits
+                );
+            }
+            Object::Command(c) if c.byte_order == Some(ByteOrder::SwapPairs) => {
+                ensure!(
+                    c.size_bits_in % 16 == 0 && c.size_bits_out % 16 == 0,
+                    "Command \"{}\" uses the `SwapPairs` byte order, but its sizes of {} (in) and {} (out) bits are not both a multiple of 16",
+                    c.name,
+                    c.size_bits_in,
+                    c.size_bits_out
+                );
+            }
+            Object::Buffer(b) => {
+                if let Some(size_bits) = b.length_prefix_size_bits()
+                    && register_byte_order.map(|bo| bo.inherited_for(size_bits))
+                        == Some(ByteOrder::SwapPairs)
+                {
+                    ensure!(
+                        size_bits % 16 == 0,
+                        "The length prefix of buffer \"{}\" uses the `SwapPairs` byte order, but its size of {} bits is not a multiple of 16",
+                        b.name,
+                        size_bits
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::mir::{ByteOrder, Command, GlobalConfig, Register};
+    use crate::mir::{BaseType, Buffer, ByteOrder, Command, Field, GlobalConfig, Register};
 
     use super::*;
 
@@ -170,4 +215,198 @@ mod tests {
 
         run_pass(&mut input).unwrap();
     }
+
+    #[test]
+    fn separate_register_and_command_defaults() {
+        let mut input = Device {
+            global_config: GlobalConfig {
+                default_byte_order: Some(ByteOrder::BE),
+                default_command_byte_order: Some(ByteOrder::LE),
+                ..Default::default()
+            },
+            objects: vec![
+                Object::Register(Register {
+                    name: "MyRegister".into(),
+                    size_bits: 16,
+                    ..Default::default()
+                }),
+                Object::Command(Command {
+                    name: "MyCommand".into(),
+                    size_bits_in: 16,
+                    ..Default::default()
+                }),
+            ],
+        };
+
+        run_pass(&mut input).unwrap();
+
+        let [Object::Register(register), Object::Command(command)] = input.objects.as_slice()
+        else {
+            unreachable!()
+        };
+        assert_eq!(register.byte_order, Some(ByteOrder::BE));
+        assert_eq!(command.byte_order, Some(ByteOrder::LE));
+
+        let mut input = Device {
+            global_config: GlobalConfig {
+                default_register_byte_order: Some(ByteOrder::BE),
+                ..Default::default()
+            },
+            objects: vec![Object::Command(Command {
+                name: "MyCommand".into(),
+                size_bits_in: 9,
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut input).unwrap_err().to_string(),
+            "No byte order is specified for command \"MyCommand\" while it's big enough that byte order is important. Specify it on the command or in the global config"
+        );
+    }
+
+    #[test]
+    fn length_prefix_needs_byte_order() {
+        let buffer = |end| {
+            Object::Buffer(Buffer {
+                name: "MyBuffer".into(),
+                length_field: Some(Box::new(Field {
+                    name: "length".into(),
+                    base_type: BaseType::Uint,
+                    field_address: 0..end,
+                    ..Default::default()
+                })),
+                ..Default::default()
+            })
+        };
+
+        let mut input = Device {
+            global_config: Default::default(),
+            objects: vec![buffer(8)],
+        };
+
+        run_pass(&mut input).unwrap();
+
+        let mut input = Device {
+            global_config: Default::default(),
+            objects: vec![buffer(16)],
+        };
+
+        assert_eq!(
+            run_pass(&mut input).unwrap_err().to_string(),
+            "No byte order is specified for the length prefix of buffer \"MyBuffer\" while it's big enough that byte order is important. Specify it in the global config"
+        );
+    }
+
+    #[test]
+    fn swap_pairs_needs_whole_pairs() {
+        let register = |size_bits| {
+            Object::Register(Register {
+                name: "MyRegister".into(),
+                size_bits,
+                byte_order: Some(ByteOrder::SwapPairs),
+                ..Default::default()
+            })
+        };
+
+        let mut input = Device {
+            global_config: Default::default(),
+            objects: vec![register(32)],
+        };
+
+        run_pass(&mut input).unwrap();
+
+        let mut input = Device {
+            global_config: Default::default(),
+            objects: vec![register(24)],
+        };
+
+        assert_eq!(
+            run_pass(&mut input).unwrap_err().to_string(),
+            "Register \"MyRegister\" uses the `SwapPairs` byte order, but its size of 24 bits is not a multiple of 16"
+        );
+
+        let mut input = Device {
+            global_config: GlobalConfig {
+                default_command_byte_order: Some(ByteOrder::SwapPairs),
+                ..Default::default()
+            },
+            objects: vec![Object::Command(Command {
+                name: "MyCommand".into(),
+                size_bits_in: 16,
+                size_bits_out: 8,
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut input).unwrap_err().to_string(),
+            "Command \"MyCommand\" uses the `SwapPairs` byte order, but its sizes of 16 (in) and 8 (out) bits are not both a multiple of 16"
+        );
+
+        // Inherited from the default, a single byte doesn't need pairs and uses LE
+        let buffer = |end| {
+            Object::Buffer(Buffer {
+                name: "MyBuffer".into(),
+                length_field: Some(Box::new(Field {
+                    name: "length".into(),
+                    base_type: BaseType::Uint,
+                    field_address: 0..end,
+                    ..Default::default()
+                })),
+                ..Default::default()
+            })
+        };
+        let swap_pairs_default = GlobalConfig {
+            default_byte_order: Some(ByteOrder::SwapPairs),
+            ..Default::default()
+        };
+
+        let mut input = Device {
+            global_config: swap_pairs_default.clone(),
+            objects: vec![
+                Object::Register(Register {
+                    name: "Small".into(),
+                    size_bits: 8,
+                    ..Default::default()
+                }),
+                Object::Command(Command {
+                    name: "SmallCommand".into(),
+                    size_bits_in: 8,
+                    ..Default::default()
+                }),
+                buffer(8),
+            ],
+        };
+
+        run_pass(&mut input).unwrap();
+
+        let [Object::Register(small), Object::Command(small_command), _] = input.objects.as_slice()
+        else {
+            unreachable!()
+        };
+        assert_eq!(small.byte_order, Some(ByteOrder::LE));
+        assert_eq!(small_command.byte_order, Some(ByteOrder::LE));
+
+        // Set explicitly, a single byte is still an error
+        let mut input = Device {
+            global_config: Default::default(),
+            objects: vec![register(8)],
+        };
+
+        assert_eq!(
+            run_pass(&mut input).unwrap_err().to_string(),
+            "Register \"MyRegister\" uses the `SwapPairs` byte order, but its size of 8 bits is not a multiple of 16"
+        );
+
+        let mut input = Device {
+            global_config: swap_pairs_default,
+            objects: vec![buffer(24)],
+        };
+
+        assert_eq!(
+            run_pass(&mut input).unwrap_err().to_string(),
+            "The length prefix of buffer \"MyBuffer\" uses the `SwapPairs` byte order, but its size of 24 bits is not a multiple of 16"
+        );
+    }
 }
@@ -0,0 +1,227 @@
+use anyhow::{bail, ensure};
+
+use crate::mir::{Access, BaseType, Device, Object};
+
+/// Check the polls of commands.
+/// The polled register must live in the same block as the command, be readable and not repeated.
+/// The polled field must be a bool field. The command itself can't be repeated or have out fields,
+/// because the generated function returns the polled register.
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    validate_object_list(&device.objects)
+}
+
+fn validate_object_list(objects: &[Object]) -> anyhow::Result<()> {
+    for object in objects {
+        if let Some(block_objects) = object.get_block_object_list() {
+            validate_object_list(block_objects)?;
+        }
+
+        let Object::Command(command) = object else {
+            continue;
+        };
+        let Some(poll_until) = &command.poll_until else {
+            continue;
+        };
+
+        ensure!(
+            command.repeat.is_none(),
+            "Command \"{}\" polls until a field is set and is repeated. This is not supported",
+            command.name
+        );
+        ensure!(
+            command.out_fields.is_empty(),
+            "Command \"{}\" polls until a field is set and has out fields. This is not supported",
+            command.name
+        );
+
+        let Some(register) = objects.iter().find_map(|object| match object {
+            Object::Register(register) if register.name == poll_until.register => Some(register),
+            _ => None,
+        }) else {
+            bail!(
+                "Command \"{}\" polls register \"{}\", which is not a register in the same block",
+                command.name,
+                poll_until.register
+            );
+        };
+
+        ensure!(
+            register.access != Access::WO,
+            "Command \"{}\" polls register \"{}\", which is not readable",
+            command.name,
+            register.name
+        );
+        ensure!(
+            register.repeat.is_none(),
+            "Command \"{}\" polls register \"{}\", which is repeated. This is not supported",
+            command.name,
+            register.name
+        );
+
+        match register
+            .fields
+            .iter()
+            .find(|field| field.name == poll_until.field)
+        {
+            Some(field) if field.base_type == BaseType::Bool => {}
+            Some(_) => bail!(
+                "Command \"{}\" polls field \"{}\" of register \"{}\", which is not a bool field",
+                command.name,
+                poll_until.field,
+                register.name
+            ),
+            None => bail!(
+                "Command \"{}\" polls field \"{}\", which does not exist in register \"{}\"",
+                command.name,
+                poll_until.field,
+                register.name
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{Block, Command, Field, PollUntil, Register, Repeat};
+
+    use super::*;
+
+    fn status_register(access: Access, base_type: BaseType) -> Object {
+        Object::Register(Register {
+            name: "Status".into(),
+            access,
+            fields: vec![Field {
+                name: "done".into(),
+                base_type,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+    }
+
+    fn polling_command() -> Command {
+        Command {
+            name: "Start".into(),
+            poll_until: Some(PollUntil {
+                register: "Status".into(),
+                field: "done".into(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn valid_poll_accepted() {
+        run_pass(&mut Device {
+            global_config: Default::default(),
+            objects: vec![Object::Block(Block {
+                name: "Bar".into(),
+                objects: vec![
+                    status_register(Access::RO, BaseType::Bool),
+                    Object::Command(polling_command()),
+                ],
+                ..Default::default()
+            })],
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn invalid_polls_rejected() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                status_register(Access::RO, BaseType::Bool),
+                Object::Block(Block {
+                    name: "Bar".into(),
+                    objects: vec![Object::Command(polling_command())],
+                    ..Default::default()
+                }),
+            ],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Command \"Start\" polls register \"Status\", which is not a register in the same block"
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                status_register(Access::WO, BaseType::Bool),
+                Object::Command(polling_command()),
+            ],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Command \"Start\" polls register \"Status\", which is not readable"
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                status_register(Access::RW, BaseType::Uint),
+                Object::Command(polling_command()),
+            ],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Command \"Start\" polls field \"done\" of register \"Status\", which is not a bool field"
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                status_register(Access::RW, BaseType::Bool),
+                Object::Command(Command {
+                    poll_until: Some(PollUntil {
+                        register: "Status".into(),
+                        field: "busy".into(),
+                    }),
+                    ..polling_command()
+                }),
+            ],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Command \"Start\" polls field \"busy\", which does not exist in register \"Status\""
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                status_register(Access::RW, BaseType::Bool),
+                Object::Command(Command {
+                    repeat: Some(Repeat {
+                        count: 2,
+                        stride: 1,
+                    }),
+                    ..polling_command()
+                }),
+            ],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Command \"Start\" polls until a field is set and is repeated. This is not supported"
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                status_register(Access::RW, BaseType::Bool),
+                Object::Command(Command {
+                    out_fields: vec![Field {
+                        name: "result".into(),
+                        ..Default::default()
+                    }],
+                    ..polling_command()
+                }),
+            ],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Command \"Start\" polls until a field is set and has out fields. This is not supported"
+        );
+    }
+}
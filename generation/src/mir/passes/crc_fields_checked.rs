@@ -0,0 +1,117 @@
+use anyhow::ensure;
+
+use super::recurse_objects;
+use crate::mir::{BaseType, Device, Field, FieldConversion, Object};
+
+/// Check all crc fields. They must be an 8 bit uint and the crc must be over whole bytes
+/// within the field set that don't include the crc field itself
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    recurse_objects(&device.objects, &mut |object| match object {
+        Object::Register(r) => check_field_set(&r.fields, r.size_bits, &r.name),
+        Object::Command(c) => {
+            check_field_set(&c.in_fields, c.size_bits_in, &format!("{} (in)", c.name))?;
+            check_field_set(&c.out_fields, c.size_bits_out, &format!("{} (out)", c.name))
+        }
+        Object::Block(_) | Object::Buffer(_) | Object::Ref(_) => Ok(()),
+    })
+}
+
+fn check_field_set(field_set: &[Field], size_bits: u32, object_name: &str) -> anyhow::Result<()> {
+    for field in field_set {
+        let Some(FieldConversion::Crc { over }) = &field.field_conversion else {
+            continue;
+        };
+
+        ensure!(
+            field.base_type == BaseType::Uint && field.field_address.clone().count() == 8,
+            "Object \"{object_name}\" has crc field \"{}\" which is not an 8 bit `uint`. Only 8 bit crcs are supported",
+            field.name
+        );
+        ensure!(
+            !over.is_empty() && over.start.is_multiple_of(8) && over.end.is_multiple_of(8),
+            "Object \"{object_name}\" has crc field \"{}\" which is not calculated over whole bytes",
+            field.name
+        );
+        ensure!(
+            over.end <= size_bits,
+            "Object \"{object_name}\" has crc field \"{}\" which is calculated over bits that exceed the given max size bits",
+            field.name
+        );
+        ensure!(
+            field.field_address.end <= over.start || over.end <= field.field_address.start,
+            "Object \"{object_name}\" has crc field \"{}\" which is calculated over its own bits",
+            field.name
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::Register;
+
+    use super::*;
+
+    fn device_with_crc_field(
+        base_type: BaseType,
+        field_address: std::ops::Range<u32>,
+        over: std::ops::Range<u32>,
+    ) -> Device {
+        Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "MyReg".into(),
+                size_bits: 32,
+                fields: vec![Field {
+                    name: "crc".into(),
+                    base_type,
+                    field_address,
+                    field_conversion: Some(FieldConversion::Crc { over }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })],
+        }
+    }
+
+    #[test]
+    fn valid_crc_field_accepted() {
+        run_pass(&mut device_with_crc_field(BaseType::Uint, 24..32, 0..24)).unwrap();
+        run_pass(&mut device_with_crc_field(BaseType::Uint, 0..8, 8..32)).unwrap();
+    }
+
+    #[test]
+    fn invalid_crc_fields_rejected() {
+        assert_eq!(
+            run_pass(&mut device_with_crc_field(BaseType::Int, 24..32, 0..24))
+                .unwrap_err()
+                .to_string(),
+            "Object \"MyReg\" has crc field \"crc\" which is not an 8 bit `uint`. Only 8 bit crcs are supported"
+        );
+        assert_eq!(
+            run_pass(&mut device_with_crc_field(BaseType::Uint, 16..32, 0..16))
+                .unwrap_err()
+                .to_string(),
+            "Object \"MyReg\" has crc field \"crc\" which is not an 8 bit `uint`. Only 8 bit crcs are supported"
+        );
+        assert_eq!(
+            run_pass(&mut device_with_crc_field(BaseType::Uint, 24..32, 0..20))
+                .unwrap_err()
+                .to_string(),
+            "Object \"MyReg\" has crc field \"crc\" which is not calculated over whole bytes"
+        );
+        assert_eq!(
+            run_pass(&mut device_with_crc_field(BaseType::Uint, 0..8, 8..40))
+                .unwrap_err()
+                .to_string(),
+            "Object \"MyReg\" has crc field \"crc\" which is calculated over bits that exceed the given max size bits"
+        );
+        assert_eq!(
+            run_pass(&mut device_with_crc_field(BaseType::Uint, 24..32, 0..32))
+                .unwrap_err()
+                .to_string(),
+            "Object \"MyReg\" has crc field \"crc\" which is calculated over its own bits"
+        );
+    }
+}
@@ -1,7 +1,11 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
 use anyhow::{bail, ensure};
 use itertools::Itertools;
 
-use crate::mir::{Device, EnumGenerationStyle, EnumValue, FieldConversion, Unique};
+use crate::mir::{
+    BaseType, Device, EnumGenerationStyle, EnumValue, FieldConversion, Unique, UniqueId,
+};
 
 use super::recurse_objects_mut;
 
@@ -33,32 +37,64 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                     &ec.name,
                 );
 
-                // Record all variant values
-                let mut seen_values = Vec::new();
+                // Record all variant values as inclusive intervals.
+                // Ranges can be as big as the field, so they must not be expanded into their values.
+                let mut seen_values: Vec<(RangeInclusive<i128>, UniqueId)> = Vec::new();
+                let next_value = |seen_values: &[(RangeInclusive<i128>, UniqueId)]| {
+                    seen_values
+                        .last()
+                        .map(|(val, _)| *val.end() + 1)
+                        .unwrap_or(0)
+                };
                 for variant in ec.variants.iter_mut() {
                     match &mut variant.value {
                         val @ EnumValue::Unspecified => {
-                            let assigned_value =
-                                seen_values.last().map(|(val, _)| *val + 1).unwrap_or(0);
+                            let assigned_value = next_value(&seen_values);
                             *val = EnumValue::Specified(assigned_value);
-                            seen_values.push((assigned_value, variant.id()));
+                            seen_values.push((assigned_value..=assigned_value, variant.id()));
                         }
                         EnumValue::Specified(num) => {
-                            seen_values.push((*num, variant.id()));
+                            seen_values.push((*num..=*num, variant.id()));
+                        }
+                        EnumValue::Range(range) => {
+                            ensure!(
+                                !range.is_empty(),
+                                "The range of variant \"{}\" is empty for enum \"{}\" in object \"{}\" on field \"{}\": {range:?}",
+                                variant.name,
+                                &ec.name,
+                                object_name,
+                                &field.name
+                            );
+                            seen_values.push((range.clone(), variant.id()));
                         }
-                        EnumValue::Default | EnumValue::CatchAll => {
-                            let assigned_value =
-                                seen_values.last().map(|(val, _)| *val + 1).unwrap_or(0);
-                            seen_values.push((assigned_value, variant.id()));
+                        EnumValue::Default | EnumValue::CatchAll | EnumValue::Fallback => {
+                            let assigned_value = next_value(&seen_values);
+                            seen_values.push((assigned_value..=assigned_value, variant.id()));
                         }
                     }
                 }
 
-                let duplicates = seen_values
+                // Sorted by start, an interval of a variant overlaps an earlier one of the same variant
+                // when it starts before the highest end of that variant so far
+                let sorted_values = seen_values
                     .iter()
-                    .duplicates()
-                    .map(|(num, name)| format!("{name}: {num}"))
+                    .sorted_by_key(|(val, _)| *val.start())
                     .collect::<Vec<_>>();
+                let mut duplicates = Vec::new();
+                let mut highest_ends = HashMap::new();
+                for (val, name) in sorted_values.iter() {
+                    if let Some(&highest_end) = highest_ends.get(name)
+                        && *val.start() <= highest_end
+                    {
+                        let overlap_end = highest_end.min(*val.end());
+                        duplicates.push(match *val.start() == overlap_end {
+                            true => format!("{name}: {}", val.start()),
+                            false => format!("{name}: {}..={overlap_end}", val.start()),
+                        });
+                    }
+                    let highest_end = highest_ends.entry(name).or_insert(*val.end());
+                    *highest_end = (*highest_end).max(*val.end());
+                }
 
                 ensure!(
                     duplicates.is_empty(),
@@ -72,9 +108,14 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                 let has_fallback = ec
                     .variants
                     .iter()
-                    .any(|v| matches!(v.value, EnumValue::Default | EnumValue::CatchAll));
-                let has_bits_covered = (0..=highest_value)
-                    .all(|val| seen_values.iter().any(|(seen_val, _)| val == *seen_val));
+                    .any(|v| v.value.is_default() || v.value.is_catch_all());
+                let mut first_uncovered = 0;
+                for (val, _) in sorted_values.iter() {
+                    if *val.start() <= first_uncovered {
+                        first_uncovered = first_uncovered.max(val.end().saturating_add(1));
+                    }
+                }
+                let has_bits_covered = first_uncovered > highest_value;
 
                 ec.generation_style = Some(if has_fallback || has_bits_covered {
                     EnumGenerationStyle::Infallible {
@@ -85,16 +126,37 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                 });
 
                 // Check if the enum has variants that fall outside of the available bits
-                if let Some(too_big_variant) =
-                    seen_values.iter().find(|(val, _)| *val > highest_value)
+                if let Some((too_big_value, too_big_name)) = seen_values
+                    .iter()
+                    .find(|(val, _)| *val.end() > highest_value)
                 {
                     bail!(
                         "The value of variant \"{}\" is too high for enum \"{}\" in object \"{}\" on field \"{}\": {} (max = {highest_value})",
-                        too_big_variant.1,
+                        too_big_name,
+                        &ec.name,
+                        object_name,
+                        &field.name,
+                        (*too_big_value.start()).max(highest_value + 1)
+                    )
+                }
+
+                // Check if the enum has variants that are negative while the field can't hold them
+                let lowest_value = match field.base_type {
+                    BaseType::Int if field_bits > 0 => i128::MIN >> (128 - field_bits),
+                    BaseType::Int => 0,
+                    BaseType::Uint | BaseType::Bool => 0,
+                };
+                if let Some((too_small_value, too_small_name)) = seen_values
+                    .iter()
+                    .find(|(val, _)| *val.start() < lowest_value)
+                {
+                    bail!(
+                        "The value of variant \"{}\" is too low for enum \"{}\" in object \"{}\" on field \"{}\": {} (min = {lowest_value})",
+                        too_small_name,
                         &ec.name,
                         object_name,
                         &field.name,
-                        too_big_variant.0
+                        too_small_value.start()
                     )
                 }
 
@@ -402,6 +464,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn enum_values_specified_out_of_range() {
+        let run = |base_type, value| {
+            let mut start_mir = Device {
+                global_config: Default::default(),
+                objects: vec![Object::Command(Command {
+                    name: "MyCommand".into(),
+                    out_fields: vec![Field {
+                        name: "MyField".into(),
+                        base_type,
+                        field_conversion: Some(FieldConversion::Enum {
+                            enum_value: Enum::new(
+                                Default::default(),
+                                "MyEnum".into(),
+                                vec![EnumVariant {
+                                    name: "Foo".into(),
+                                    value: EnumValue::Specified(value),
+                                    ..Default::default()
+                                }],
+                            ),
+                            use_try: true,
+                        }),
+                        field_address: 0..8,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })],
+            };
+
+            run_pass(&mut start_mir).map_err(|e| e.to_string())
+        };
+
+        assert_eq!(
+            run(BaseType::Uint, 256).unwrap_err(),
+            "The value of variant \"Foo\" is too high for enum \"MyEnum\" in object \"MyCommand\" on field \"MyField\": 256 (max = 255)"
+        );
+        assert_eq!(
+            run(BaseType::Uint, -1).unwrap_err(),
+            "The value of variant \"Foo\" is too low for enum \"MyEnum\" in object \"MyCommand\" on field \"MyField\": -1 (min = 0)"
+        );
+        assert_eq!(
+            run(BaseType::Int, -129).unwrap_err(),
+            "The value of variant \"Foo\" is too low for enum \"MyEnum\" in object \"MyCommand\" on field \"MyField\": -129 (min = -128)"
+        );
+        assert!(run(BaseType::Int, -128).is_ok());
+        assert!(run(BaseType::Uint, 255).is_ok());
+    }
+
     #[test]
     fn enum_values_no_duplicates() {
         let mut start_mir = Device {
@@ -441,4 +551,142 @@ mod tests {
             "Duplicated assigned value(s) for enum \"MyEnum\" in object \"MyCommand\" on field \"MyField\": [\"var0: 0\"]"
         );
     }
+
+    #[test]
+    fn enum_value_ranges() {
+        let make_mir = |variants| Device {
+            global_config: Default::default(),
+            objects: vec![Object::Command(Command {
+                name: "MyCommand".into(),
+                out_fields: vec![Field {
+                    name: "MyField".into(),
+                    field_conversion: Some(FieldConversion::Enum {
+                        enum_value: Enum::new(Default::default(), "MyEnum".into(), variants),
+                        use_try: false,
+                    }),
+                    field_address: 0..3,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })],
+        };
+
+        // The range covers all remaining values, so the enum is infallible
+        let mut start_mir = make_mir(vec![
+            EnumVariant {
+                name: "Off".into(),
+                value: EnumValue::Unspecified,
+                ..Default::default()
+            },
+            EnumVariant {
+                name: "Reserved".into(),
+                value: EnumValue::Range(1..=7),
+                ..Default::default()
+            },
+        ]);
+        run_pass(&mut start_mir).unwrap();
+        let Object::Command(command) = &start_mir.objects[0] else {
+            unreachable!()
+        };
+        let Some(FieldConversion::Enum { enum_value, .. }) =
+            &command.out_fields[0].field_conversion
+        else {
+            unreachable!()
+        };
+        assert_eq!(
+            enum_value.generation_style,
+            Some(EnumGenerationStyle::Infallible { bit_size: 3 })
+        );
+
+        // Ranges must hold at least one value
+        let mut start_mir = make_mir(vec![EnumVariant {
+            name: "Reserved".into(),
+            #[allow(clippy::reversed_empty_ranges)]
+            value: EnumValue::Range(4..=3),
+            ..Default::default()
+        }]);
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "The range of variant \"Reserved\" is empty for enum \"MyEnum\" in object \"MyCommand\" on field \"MyField\": 4..=3"
+        );
+
+        // Ranges must fit in the field
+        let mut start_mir = make_mir(vec![EnumVariant {
+            name: "Reserved".into(),
+            value: EnumValue::Range(4..=8),
+            ..Default::default()
+        }]);
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "The value of variant \"Reserved\" is too high for enum \"MyEnum\" in object \"MyCommand\" on field \"MyField\": 8 (max = 7)"
+        );
+
+        // A variant may not overlap itself
+        let mut start_mir = make_mir(vec![
+            EnumVariant {
+                name: "Reserved".into(),
+                value: EnumValue::Range(0..=3),
+                ..Default::default()
+            },
+            EnumVariant {
+                name: "Reserved".into(),
+                value: EnumValue::Range(2..=7),
+                ..Default::default()
+            },
+        ]);
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "Duplicated assigned value(s) for enum \"MyEnum\" in object \"MyCommand\" on field \"MyField\": [\"Reserved: 2..=3\"]"
+        );
+    }
+
+    #[test]
+    fn enum_value_range_full_width() {
+        // The range is checked as a whole, so this doesn't go over all of its values
+        let mut start_mir = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Command(Command {
+                name: "MyCommand".into(),
+                out_fields: vec![Field {
+                    name: "MyField".into(),
+                    field_conversion: Some(FieldConversion::Enum {
+                        enum_value: Enum::new(
+                            Default::default(),
+                            "MyEnum".into(),
+                            vec![
+                                EnumVariant {
+                                    name: "Off".into(),
+                                    value: EnumValue::Unspecified,
+                                    ..Default::default()
+                                },
+                                EnumVariant {
+                                    name: "Rest".into(),
+                                    value: EnumValue::Range(1..=0xFFFF_FFFF),
+                                    ..Default::default()
+                                },
+                            ],
+                        ),
+                        use_try: false,
+                    }),
+                    field_address: 0..32,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })],
+        };
+
+        run_pass(&mut start_mir).unwrap();
+        let Object::Command(command) = &start_mir.objects[0] else {
+            unreachable!()
+        };
+        let Some(FieldConversion::Enum { enum_value, .. }) =
+            &command.out_fields[0].field_conversion
+        else {
+            unreachable!()
+        };
+        assert_eq!(
+            enum_value.generation_style,
+            Some(EnumGenerationStyle::Infallible { bit_size: 32 })
+        );
+    }
 }
@@ -0,0 +1,203 @@
+use super::recurse_objects_mut;
+use crate::mir::{Access, BaseType, Device, Field};
+use anyhow::bail;
+
+/// The names of the functions and members that are generated on every field set.
+/// A field with one of these names would generate a getter that collides with them.
+const RESERVED_NAMES: &[&str] = &[
+    "bits",
+    "new",
+    "new_zero",
+    "from_bits",
+    "as_mut_bytes",
+    "log_bytes",
+    "pack_into",
+    "to_wire_bytes",
+    "to_u128",
+    "is_reset",
+    "merge",
+    "readable_field_names",
+    "writable_field_names",
+];
+
+/// Check that no field (or old name of a field) collides with the functions generated on a field set
+/// or with the functions generated for the other fields
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    recurse_objects_mut(&mut device.objects, &mut |object| {
+        let object_name = object.name().to_string();
+
+        for field_set in object.field_sets_mut() {
+            for field in field_set.iter() {
+                for name in std::iter::once(&field.name).chain(&field.renamed_from) {
+                    if RESERVED_NAMES.contains(&name.as_str()) {
+                        bail!(
+                            "Object \"{object_name}\" has field \"{name}\" whose name collides with a generated function of the field set. Rename it, for example to \"{name}_value\""
+                        );
+                    }
+
+                    if let Some(other) = field_set
+                        .iter()
+                        .find(|other| derived_names(other).contains(name))
+                    {
+                        bail!(
+                            "Object \"{object_name}\" has field \"{name}\" whose name collides with a generated function of field \"{}\". Rename one of the fields",
+                            other.name
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// The names of the functions that are generated for the field next to its getter
+fn derived_names(field: &Field) -> Vec<String> {
+    let name = &field.name;
+    let writable = matches!(field.access, Access::RW | Access::WO);
+
+    let mut names = vec![format!("{name}_byte_range"), format!("{name}_bits")];
+    if writable {
+        names.push(format!("set_{name}"));
+        names.push(format!("with_{name}"));
+    }
+    if field.field_conversion.is_some() {
+        names.push(format!("{name}_raw"));
+        if writable {
+            names.push(format!("set_{name}_raw"));
+        }
+    }
+    if field.base_type == BaseType::Bool && field.access == Access::RW {
+        names.push(format!("toggle_{name}"));
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{Command, Field, Object, Register};
+
+    use super::*;
+
+    #[test]
+    fn reserved_field_names_rejected() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                fields: vec![
+                    Field {
+                        name: "value".into(),
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "new".into(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            })],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Object \"Reg\" has field \"new\" whose name collides with a generated function of the field set. Rename it, for example to \"new_value\""
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Command(Command {
+                name: "Cmd".into(),
+                out_fields: vec![Field {
+                    name: "raw".into(),
+                    renamed_from: vec!["bits".into()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Object \"Cmd\" has field \"bits\" whose name collides with a generated function of the field set. Rename it, for example to \"bits_value\""
+        );
+    }
+
+    #[test]
+    fn merge_field_name_rejected() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                fields: vec![Field {
+                    name: "merge".into(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })],
+        };
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Object \"Reg\" has field \"merge\" whose name collides with a generated function of the field set. Rename it, for example to \"merge_value\""
+        );
+    }
+
+    #[test]
+    fn derived_field_names_rejected() {
+        let device = |fields| Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                fields,
+                ..Default::default()
+            })],
+        };
+        let field = |name: &str, access| Field {
+            name: name.into(),
+            access,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            run_pass(&mut device(vec![
+                field("value", Access::RW),
+                field("value_bits", Access::RW)
+            ]))
+            .unwrap_err()
+            .to_string(),
+            "Object \"Reg\" has field \"value_bits\" whose name collides with a generated function of field \"value\". Rename one of the fields"
+        );
+        assert_eq!(
+            run_pass(&mut device(vec![
+                field("set_mode", Access::RO),
+                field("mode", Access::WO)
+            ]))
+            .unwrap_err()
+            .to_string(),
+            "Object \"Reg\" has field \"set_mode\" whose name collides with a generated function of field \"mode\". Rename one of the fields"
+        );
+
+        // Only a field that can be written gets a setter
+        run_pass(&mut device(vec![
+            field("set_mode", Access::RO),
+            field("mode", Access::RO),
+        ]))
+        .unwrap();
+    }
+
+    #[test]
+    fn normal_field_names_accepted() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                fields: vec![Field {
+                    name: "new_value".into(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })],
+        };
+        run_pass(&mut device).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+use super::recurse_objects;
+use crate::mir::Device;
+use anyhow::ensure;
+
+/// Check that no field set has more fields than the configured maximum (if any)
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    let Some(max_fields) = device.global_config.max_fields else {
+        return Ok(());
+    };
+
+    recurse_objects(&device.objects, &mut |object| {
+        for fields in object.field_sets() {
+            ensure!(
+                fields.len() <= max_fields,
+                "Object \"{}\" has a field set with {} fields, which is more than the configured maximum of {max_fields}",
+                object.name(),
+                fields.len()
+            );
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{Field, GlobalConfig, Object, Register};
+
+    use super::*;
+
+    fn device_with_fields(max_fields: Option<usize>, field_count: usize) -> Device {
+        Device {
+            global_config: GlobalConfig {
+                max_fields,
+                ..Default::default()
+            },
+            objects: vec![Object::Register(Register {
+                name: "Foo".into(),
+                fields: (0..field_count)
+                    .map(|i| Field {
+                        name: format!("field{i}"),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })],
+        }
+    }
+
+    #[test]
+    fn field_count_within_limit() {
+        run_pass(&mut device_with_fields(None, 100)).unwrap();
+        run_pass(&mut device_with_fields(Some(3), 3)).unwrap();
+    }
+
+    #[test]
+    fn field_count_over_limit() {
+        assert_eq!(
+            run_pass(&mut device_with_fields(Some(3), 4))
+                .unwrap_err()
+                .to_string(),
+            "Object \"Foo\" has a field set with 4 fields, which is more than the configured maximum of 3"
+        );
+    }
+}
@@ -1,27 +1,49 @@
-use super::{Device, Object, Repeat};
+use super::{Device, Object, ObjectOverride, RefObject, Repeat};
 
 mod address_types_big_enough;
+mod address_types_inferred;
 mod address_types_specified;
+mod address_unit_checked;
 mod bit_ranges_validated;
 mod bool_fields_checked;
 mod byte_order_specified;
+mod command_polls_validated;
+mod crc_fields_checked;
 mod enum_values_checked;
+mod field_names_checked;
+mod max_fields_checked;
 mod names_normalized;
 mod names_unique;
+mod newtypes_checked;
 mod propagate_cfg;
 mod refs_validated;
+mod register_spans_validated;
 mod reset_values_converted;
+mod shadowed_registers_validated;
+mod valid_when_checked;
+mod variable_size_registers_validated;
 
 pub fn run_passes(device: &mut Device) -> anyhow::Result<()> {
     propagate_cfg::run_pass(device)?;
     names_normalized::run_pass(device)?;
     names_unique::run_pass(device)?;
+    field_names_checked::run_pass(device)?;
     enum_values_checked::run_pass(device)?;
+    valid_when_checked::run_pass(device)?;
     byte_order_specified::run_pass(device)?;
-    reset_values_converted::run_pass(device)?;
     bool_fields_checked::run_pass(device)?;
+    reset_values_converted::run_pass(device)?;
+    max_fields_checked::run_pass(device)?;
     bit_ranges_validated::run_pass(device)?;
+    crc_fields_checked::run_pass(device)?;
+    newtypes_checked::run_pass(device)?;
     refs_validated::run_pass(device)?;
+    register_spans_validated::run_pass(device)?;
+    shadowed_registers_validated::run_pass(device)?;
+    command_polls_validated::run_pass(device)?;
+    variable_size_registers_validated::run_pass(device)?;
+    address_unit_checked::run_pass(device)?;
+    address_types_inferred::run_pass(device)?;
     address_types_specified::run_pass(device)?;
     address_types_big_enough::run_pass(device)?;
 
@@ -126,6 +148,24 @@ pub(crate) fn find_min_max_addresses(
             max_address_found = max_address_found
                 .max(count_0_address)
                 .max(count_max_address);
+
+            // The parts of a register that spans multiple addresses are at an offset from its address.
+            // Refs use the span of the register they refer to.
+            let span_offsets = match object {
+                Object::Register(register) => register.span_offsets.as_slice(),
+                Object::Ref(RefObject {
+                    object_override: ObjectOverride::Register(register_override),
+                    ..
+                }) => match search_object(&register_override.name, objects) {
+                    Some(Object::Register(register)) => register.span_offsets.as_slice(),
+                    _ => &[],
+                },
+                _ => &[],
+            };
+            for offset in span_offsets {
+                min_address_found = min_address_found.min(count_0_address + offset);
+                max_address_found = max_address_found.max(count_0_address + offset);
+            }
         }
 
         if let Object::Block(b) = object {
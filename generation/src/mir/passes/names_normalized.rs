@@ -1,13 +1,18 @@
 use convert_case::Case;
 
-use crate::mir::{Device, Enum, FieldConversion};
+use crate::mir::{
+    self, Buffer, Command, Device, Enum, FieldConversion, FieldResetValue, Newtype, Register,
+    ResetValue,
+};
 
 use super::recurse_objects_mut;
 
 /// Changes all names of all objects, enums and enum variants to either Pascal case or snake case
 ///
-/// - PascalCase: Object names, enum names, enum variant names
-/// - snake_case: Field names
+/// - PascalCase: Object names, enum names, enum variant names, register names in the reset sequence and id register, buffer frame names,
+///   enum variant names in reset value tables and valid_when conditions, the register names of command polls
+/// - snake_case: Field names, the field name of the id register, the length field names of registers, field names in reset value tables,
+///   the field names of command polls and valid_when conditions, chip variants (so they can be told apart from the field names in reset value tables)
 pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
     let boundaries = device.global_config.name_word_boundaries.clone();
 
@@ -23,6 +28,15 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
 
         for field in object.field_sets_mut().flatten() {
             field.name = snake_converter.convert(&field.name);
+            for old_name in field.renamed_from.iter_mut() {
+                *old_name = snake_converter.convert(&*old_name);
+            }
+            if let Some(valid_when) = field.valid_when.as_mut() {
+                valid_when.field = snake_converter.convert(&valid_when.field);
+                if let FieldResetValue::Variant(variant) = &mut valid_when.value {
+                    *variant = pascal_converter.convert(&*variant);
+                }
+            }
             if let Some(FieldConversion::Enum {
                 enum_value: Enum { name, variants, .. },
                 ..
@@ -34,15 +48,93 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                     v.name = pascal_converter.convert(&v.name)
                 }
             }
+            if let Some(FieldConversion::Newtype {
+                newtype: Newtype { name, .. },
+            }) = field.field_conversion.as_mut()
+            {
+                *name = pascal_converter.convert(&*name);
+            }
+        }
+
+        if let mir::Object::Buffer(Buffer {
+            frame: Some(frame), ..
+        }) = object
+        {
+            *frame = pascal_converter.convert(&*frame);
+        }
+
+        if let mir::Object::Register(Register {
+            length_field: Some(length_field),
+            ..
+        }) = object
+        {
+            *length_field = snake_converter.convert(&*length_field);
+        }
+
+        if let mir::Object::Command(Command {
+            poll_until: Some(poll_until),
+            ..
+        }) = object
+        {
+            poll_until.register = pascal_converter.convert(&poll_until.register);
+            poll_until.field = snake_converter.convert(&poll_until.field);
+        }
+
+        if let mir::Object::Register(Register {
+            reset_value: Some(reset_value),
+            ..
+        }) = object
+        {
+            normalize_reset_value(reset_value, &pascal_converter, &snake_converter);
         }
 
         if let Some(ref_object) = object.as_ref_object_mut() {
             *ref_object.object_override.name_mut() =
                 pascal_converter.convert(ref_object.object_override.name_mut());
+
+            if let Some(reset_value) = ref_object
+                .object_override
+                .as_register_mut()
+                .and_then(|register_override| register_override.reset_value.as_mut())
+            {
+                normalize_reset_value(reset_value, &pascal_converter, &snake_converter);
+            }
         }
 
         Ok(())
-    })
+    })?;
+
+    for (register_name, reset_value) in device.global_config.reset_sequence.iter_mut() {
+        *register_name = pascal_converter.convert(&*register_name);
+        normalize_reset_value(reset_value, &pascal_converter, &snake_converter);
+    }
+
+    if let Some(id_register) = device.global_config.id_register.as_mut() {
+        id_register.register = pascal_converter.convert(&id_register.register);
+        id_register.field = snake_converter.convert(&id_register.field);
+    }
+
+    for variant in device.global_config.chip_variants.iter_mut() {
+        *variant = snake_converter.convert(&*variant);
+    }
+
+    Ok(())
+}
+
+fn normalize_reset_value(
+    reset_value: &mut ResetValue,
+    pascal_converter: &convert_case::Converter,
+    snake_converter: &convert_case::Converter,
+) {
+    if let ResetValue::Fields(fields) = reset_value {
+        for (field_name, value) in fields.iter_mut() {
+            *field_name = snake_converter.convert(&*field_name);
+
+            if let FieldResetValue::Variant(variant) = value {
+                *variant = pascal_converter.convert(&*variant);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -86,6 +178,10 @@ mod tests {
                             ..Default::default()
                         },
                     ],
+                    reset_value: Some(ResetValue::Fields(vec![(
+                        "my-fielD2".into(),
+                        FieldResetValue::Variant("eNum-Variant".into()),
+                    )])),
                     ..Default::default()
                 }),
                 Object::Buffer(Buffer {
@@ -121,6 +217,10 @@ mod tests {
                             ..Default::default()
                         },
                     ],
+                    reset_value: Some(ResetValue::Fields(vec![(
+                        "my_field2".into(),
+                        FieldResetValue::Variant("EnumVariant".into()),
+                    )])),
                     ..Default::default()
                 }),
                 Object::Buffer(Buffer {
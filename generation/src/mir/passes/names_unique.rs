@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::mir::{Device, Enum, FieldConversion, Unique};
 
@@ -6,9 +6,13 @@ use super::recurse_objects_mut;
 
 /// Checks if all names are unique to prevent later name collisions.
 /// If there is a collision an error is returned.
+///
+/// Inline enums and newtypes with the same name may be defined on multiple fields when they're identical.
+/// They are generated only once and shared by the fields.
 pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
     let mut seen_object_ids = HashSet::new();
-    let mut generated_type_ids = HashSet::new();
+    let mut generated_enums = HashMap::new();
+    let mut generated_newtypes = HashMap::new();
 
     recurse_objects_mut(&mut device.objects, &mut |object| {
         anyhow::ensure!(
@@ -27,6 +31,23 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                     field.name
                 );
 
+                if let Some(FieldConversion::Newtype { newtype }) = field.field_conversion.as_ref()
+                {
+                    let definition = (newtype.clone(), field.base_type, field.field_address.len());
+                    match generated_newtypes.get(&newtype.id()) {
+                        None => {
+                            generated_newtypes.insert(newtype.id(), definition);
+                        }
+                        Some(first_definition) => anyhow::ensure!(
+                            *first_definition == definition,
+                            "Duplicate generated newtype name \"{}\" found in object \"{}\" on field \"{}\" with a different definition. Newtypes with the same name are only shared when they're identical",
+                            newtype.name,
+                            object.name(),
+                            field.name,
+                        ),
+                    }
+                }
+
                 if let Some(FieldConversion::Enum {
                     enum_value: enum_value @ Enum { name, variants, .. },
                     ..
@@ -34,13 +55,27 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                 {
                     let mut seen_variant_names = HashSet::new();
 
-                    anyhow::ensure!(
-                        generated_type_ids.insert(enum_value.id()),
-                        "Duplicate generated enum name \"{}\" found in object \"{}\" on field \"{}\"",
-                        name,
-                        object.name(),
-                        field.name,
+                    // The base type of the generated enum depends on the field, so that must match too
+                    let definition = (
+                        enum_value.clone(),
+                        field.base_type,
+                        field.field_address.len(),
                     );
+                    match generated_enums.get(&enum_value.id()) {
+                        None => {
+                            generated_enums.insert(enum_value.id(), definition);
+                        }
+                        Some(first_definition) => {
+                            anyhow::ensure!(
+                                *first_definition == definition,
+                                "Duplicate generated enum name \"{}\" found in object \"{}\" on field \"{}\" with a different definition. Enums with the same name are only shared when they're identical",
+                                name,
+                                object.name(),
+                                field.name,
+                            );
+                            continue;
+                        }
+                    }
 
                     for v in variants.iter() {
                         anyhow::ensure!(
@@ -124,7 +159,7 @@ mod tests {
 
     #[test]
     #[should_panic(
-        expected = "Duplicate generated enum name \"Enum\" found in object \"Reg\" on field \"field2\""
+        expected = "Duplicate generated enum name \"Enum\" found in object \"Reg\" on field \"field2\" with a different definition. Enums with the same name are only shared when they're identical"
     )]
     fn duplicate_generated_enums() {
         let global_config = GlobalConfig {
@@ -154,7 +189,10 @@ mod tests {
                         field_conversion: Some(FieldConversion::Enum {
                             enum_value: Enum {
                                 name: "Enum".into(),
-                                variants: Default::default(),
+                                variants: vec![EnumVariant {
+                                    name: "Variant".into(),
+                                    ..Default::default()
+                                }],
                                 ..Default::default()
                             },
                             use_try: false,
@@ -169,6 +207,50 @@ mod tests {
         run_pass(&mut start_mir).unwrap();
     }
 
+    #[test]
+    fn identical_generated_enums_shared() {
+        let shared_enum = Enum {
+            name: "Enum".into(),
+            variants: vec![EnumVariant {
+                name: "Variant".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut start_mir = Device {
+            global_config: Default::default(),
+            objects: vec![
+                Object::Register(Register {
+                    name: "Reg".into(),
+                    fields: vec![Field {
+                        name: "field".into(),
+                        field_conversion: Some(FieldConversion::Enum {
+                            enum_value: shared_enum.clone(),
+                            use_try: false,
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                Object::Register(Register {
+                    name: "Reg2".into(),
+                    fields: vec![Field {
+                        name: "field".into(),
+                        field_conversion: Some(FieldConversion::Enum {
+                            enum_value: shared_enum,
+                            use_try: false,
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+            ],
+        };
+
+        run_pass(&mut start_mir).unwrap();
+    }
+
     #[test]
     #[should_panic(
         expected = "Duplicate field \"Variant\" found in generated enum \"Enum\" in object \"Reg\" on field \"field\""
@@ -0,0 +1,135 @@
+use anyhow::ensure;
+
+use super::recurse_objects;
+use crate::mir::{BaseType, Device, FieldConversion};
+
+/// Checks that the inner type of every newtype can hold all values of its field
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    recurse_objects(&device.objects, &mut |object| {
+        for field in object.field_sets().flatten() {
+            let Some(FieldConversion::Newtype { newtype }) = &field.field_conversion else {
+                continue;
+            };
+
+            let field_bits = field.field_address.len() as u32;
+            // The number of bits of the inner type that hold the magnitude of the value
+            let inner_bits = newtype.inner.max_value().count_ones();
+            let inner_signed = newtype.inner.min_value() < 0;
+
+            let fits = match field.base_type {
+                BaseType::Bool => continue,
+                BaseType::Uint => field_bits <= inner_bits,
+                BaseType::Int => inner_signed && field_bits <= inner_bits + 1,
+            };
+
+            ensure!(
+                fits,
+                "Newtype \"{}\" in object \"{}\" on field \"{}\" wraps a `{}`, which can't hold all values of the {field_bits} bit field",
+                newtype.name,
+                object.name(),
+                field.name,
+                newtype.inner,
+            );
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{Cfg, Field, Integer, Newtype, Object, Register};
+
+    use super::*;
+
+    fn device_with_newtype_field(
+        base_type: BaseType,
+        field_address: std::ops::Range<u32>,
+        inner: Integer,
+    ) -> Device {
+        Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                size_bits: 32,
+                fields: vec![Field {
+                    name: "value".into(),
+                    base_type,
+                    field_conversion: Some(FieldConversion::Newtype {
+                        newtype: Newtype {
+                            cfg_attr: Cfg::default(),
+                            description: String::new(),
+                            name: "Value".into(),
+                            inner,
+                        },
+                    }),
+                    field_address,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })],
+        }
+    }
+
+    #[test]
+    fn inner_type_big_enough() {
+        run_pass(&mut device_with_newtype_field(
+            BaseType::Uint,
+            0..8,
+            Integer::U8,
+        ))
+        .unwrap();
+        run_pass(&mut device_with_newtype_field(
+            BaseType::Uint,
+            0..7,
+            Integer::I8,
+        ))
+        .unwrap();
+        run_pass(&mut device_with_newtype_field(
+            BaseType::Int,
+            0..8,
+            Integer::I8,
+        ))
+        .unwrap();
+        run_pass(&mut device_with_newtype_field(
+            BaseType::Int,
+            0..12,
+            Integer::I32,
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn inner_type_too_small() {
+        assert_eq!(
+            run_pass(&mut device_with_newtype_field(
+                BaseType::Uint,
+                0..9,
+                Integer::U8
+            ))
+            .unwrap_err()
+            .to_string(),
+            "Newtype \"Value\" in object \"Reg\" on field \"value\" wraps a `u8`, which can't hold all values of the 9 bit field"
+        );
+        assert_eq!(
+            run_pass(&mut device_with_newtype_field(
+                BaseType::Uint,
+                0..8,
+                Integer::I8
+            ))
+            .unwrap_err()
+            .to_string(),
+            "Newtype \"Value\" in object \"Reg\" on field \"value\" wraps a `i8`, which can't hold all values of the 8 bit field"
+        );
+        assert_eq!(
+            run_pass(&mut device_with_newtype_field(
+                BaseType::Int,
+                0..4,
+                Integer::U8
+            ))
+            .unwrap_err()
+            .to_string(),
+            "Newtype \"Value\" in object \"Reg\" on field \"value\" wraps a `u8`, which can't hold all values of the 4 bit field"
+        );
+    }
+}
@@ -27,6 +27,9 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                 enum_value.cfg_attr = field.cfg_attr.combine(&new_cfg_attr);
                 // Just like we don't have to update the field cfg, we also don't have to update the enum variant cfgs
             }
+            if let Some(FieldConversion::Newtype { newtype }) = field.field_conversion.as_mut() {
+                newtype.cfg_attr = field.cfg_attr.combine(&new_cfg_attr);
+            }
         }
 
         if object.as_block_mut().is_some() {
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use anyhow::ensure;
 
@@ -8,9 +8,10 @@ use super::recurse_objects;
 
 /// Checks if all refs are valid
 pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
-    let mut reffed_blocks = HashMap::new();
-    let mut reffed_registers = HashMap::new();
-    let mut reffed_commands = HashMap::new();
+    // Kept in declaration order so the first invalid ref is always the one reported
+    let mut reffed_blocks = Vec::new();
+    let mut reffed_registers = Vec::new();
+    let mut reffed_commands = Vec::new();
 
     let mut real_blocks = HashSet::new();
     let mut real_registers = HashSet::new();
@@ -19,16 +20,12 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
     recurse_objects(&device.objects, &mut |object| {
         match object {
             Object::Ref(r) => {
-                match r.object_override {
-                    ObjectOverride::Block(_) => {
-                        reffed_blocks.insert(r.object_override.name().to_string(), r.name.clone())
-                    }
-                    ObjectOverride::Register(_) => reffed_registers
-                        .insert(r.object_override.name().to_string(), r.name.clone()),
-                    ObjectOverride::Command(_) => {
-                        reffed_commands.insert(r.object_override.name().to_string(), r.name.clone())
-                    }
+                let reffed = match r.object_override {
+                    ObjectOverride::Block(_) => &mut reffed_blocks,
+                    ObjectOverride::Register(_) => &mut reffed_registers,
+                    ObjectOverride::Command(_) => &mut reffed_commands,
                 };
+                reffed.push((r.object_override.name().to_string(), r.name.clone()));
             }
             Object::Block(v) => {
                 real_blocks.insert(v.name.clone());
@@ -163,4 +160,62 @@ mod tests {
             "Command ref \"MyRef\" refers to unknown command \"MyComm2\""
         );
     }
+
+    #[test]
+    fn ref_to_other_object_kind() {
+        let mut start_mir = Device {
+            global_config: Default::default(),
+            objects: vec![
+                Object::Register(Register {
+                    name: "MyReg".into(),
+                    ..Default::default()
+                }),
+                Object::Ref(RefObject {
+                    cfg_attr: Default::default(),
+                    description: Default::default(),
+                    name: "MyRef".into(),
+                    object_override: ObjectOverride::Command(CommandOverride {
+                        name: "MyReg".into(),
+                        ..Default::default()
+                    }),
+                }),
+            ],
+        };
+
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "Command ref \"MyRef\" refers to unknown command \"MyReg\""
+        );
+    }
+
+    #[test]
+    fn first_bad_ref_reported() {
+        let bad_ref = |name: &str, target: &str| {
+            Object::Ref(RefObject {
+                cfg_attr: Default::default(),
+                description: Default::default(),
+                name: name.into(),
+                object_override: ObjectOverride::Register(RegisterOverride {
+                    name: target.into(),
+                    ..Default::default()
+                }),
+            })
+        };
+
+        for _ in 0..10 {
+            let mut start_mir = Device {
+                global_config: Default::default(),
+                objects: vec![
+                    bad_ref("MyRef", "MyReg"),
+                    bad_ref("MyRef2", "MyReg2"),
+                    bad_ref("MyRef3", "MyReg3"),
+                ],
+            };
+
+            assert_eq!(
+                run_pass(&mut start_mir).unwrap_err().to_string(),
+                "Register ref \"MyRef\" refers to unknown register \"MyReg\""
+            );
+        }
+    }
 }
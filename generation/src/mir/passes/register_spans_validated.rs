@@ -0,0 +1,161 @@
+use anyhow::{bail, ensure};
+use itertools::Itertools;
+
+use super::{recurse_objects, search_object};
+use crate::mir::{Device, Object, ObjectOverride, Register};
+
+/// Check the registers that span multiple addresses.
+/// The register must be divisible in whole-byte parts, the addresses must be unique and the register can't be repeated.
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    recurse_objects(&device.objects, &mut |object| match object {
+        Object::Register(register) if !register.span_offsets.is_empty() => {
+            validate_register_span(register)
+        }
+        Object::Ref(ref_object) => {
+            if let ObjectOverride::Register(register_override) = &ref_object.object_override
+                && register_override.repeat.is_some()
+                && let Some(Object::Register(register)) =
+                    search_object(&register_override.name, &device.objects)
+                && !register.span_offsets.is_empty()
+            {
+                bail!(
+                    "Ref \"{}\" repeats register \"{}\" which spans multiple addresses. This is not supported",
+                    ref_object.name,
+                    register.name
+                );
+            }
+
+            Ok(())
+        }
+        _ => Ok(()),
+    })
+}
+
+fn validate_register_span(register: &Register) -> anyhow::Result<()> {
+    let parts = register.span_offsets.len() as u32;
+
+    ensure!(
+        register.repeat.is_none(),
+        "Register \"{}\" spans multiple addresses and is repeated. This is not supported",
+        register.name
+    );
+
+    ensure!(
+        register.size_bits.is_multiple_of(parts * 8),
+        "Register \"{}\" spans {parts} addresses, but its size ({} bits) can't be divided into {parts} parts of whole bytes",
+        register.name,
+        register.size_bits
+    );
+
+    if let Some(duplicate) = register.span_offsets.iter().duplicates().next() {
+        bail!(
+            "Register \"{}\" has address {} in its address span more than once",
+            register.name,
+            register.address + duplicate
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{RefObject, RegisterOverride, Repeat};
+
+    use super::*;
+
+    fn run_with_register(register: Register) -> anyhow::Result<()> {
+        run_pass(&mut Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(register)],
+        })
+    }
+
+    #[test]
+    fn valid_span_accepted() {
+        run_with_register(Register {
+            name: "Foo".into(),
+            address: 0x10,
+            span_offsets: vec![0, 1, 2],
+            size_bits: 24,
+            ..Default::default()
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn invalid_spans_rejected() {
+        assert_eq!(
+            run_with_register(Register {
+                name: "Foo".into(),
+                span_offsets: vec![0, 1],
+                size_bits: 24,
+                ..Default::default()
+            })
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" spans 2 addresses, but its size (24 bits) can't be divided into 2 parts of whole bytes"
+        );
+
+        assert_eq!(
+            run_with_register(Register {
+                name: "Foo".into(),
+                address: 0x10,
+                span_offsets: vec![0, 1, 0],
+                size_bits: 24,
+                ..Default::default()
+            })
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" has address 16 in its address span more than once"
+        );
+
+        assert_eq!(
+            run_with_register(Register {
+                name: "Foo".into(),
+                span_offsets: vec![0, 1],
+                size_bits: 16,
+                repeat: Some(Repeat {
+                    count: 2,
+                    stride: 2
+                }),
+                ..Default::default()
+            })
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" spans multiple addresses and is repeated. This is not supported"
+        );
+    }
+
+    #[test]
+    fn repeating_ref_rejected() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                Object::Register(Register {
+                    name: "Foo".into(),
+                    span_offsets: vec![0, 1],
+                    size_bits: 16,
+                    ..Default::default()
+                }),
+                Object::Ref(RefObject {
+                    name: "Bar".into(),
+                    object_override: ObjectOverride::Register(RegisterOverride {
+                        name: "Foo".into(),
+                        repeat: Some(Repeat {
+                            count: 2,
+                            stride: 2,
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            ],
+        };
+
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Ref \"Bar\" repeats register \"Foo\" which spans multiple addresses. This is not supported"
+        );
+    }
+}
@@ -1,18 +1,19 @@
 use std::collections::HashMap;
 
-use anyhow::ensure;
+use anyhow::{Context, bail, ensure};
 use bitvec::{
     order::{Lsb0, Msb0},
     view::BitView,
 };
 
 use crate::mir::{
-    BitOrder, ByteOrder, Device, Object, ObjectOverride, RefObject, Register, ResetValue, Unique,
+    BaseType, BitOrder, ByteOrder, Device, Enum, EnumValue, Field, FieldConversion,
+    FieldResetValue, Object, ObjectOverride, RefObject, Register, ResetValue, Unique,
 };
 
 use super::{recurse_objects, recurse_objects_mut, search_object};
 
-/// Checks if the reset values of registers (and ref registers) are valid.
+/// Checks if the reset values of registers (and ref registers) and the values of the reset sequence are valid.
 /// Also converts integer values to the array representation using the correct bit and byte order.
 ///
 /// For the array representation, the rule is that the input must have the same spec as the bit and byte order.
@@ -22,15 +23,49 @@ use super::{recurse_objects, recurse_objects_mut, search_object};
 /// having been run.
 pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
     let mut new_reset_values = HashMap::new();
+    let mut new_chip_variant_reset_values = HashMap::new();
+    let chip_variants = &device.global_config.chip_variants;
 
     recurse_objects(&device.objects, &mut |object| match object {
         Object::Register(reg) => {
             let target_byte_order = get_target_byte_order(reg, device);
 
             match reg.reset_value.as_ref() {
+                Some(reset_value)
+                    if let Some(table) = as_chip_variant_table(
+                        reset_value,
+                        chip_variants,
+                        &reg.fields,
+                        "register",
+                        &reg.name,
+                    )? =>
+                {
+                    let variant_reset_values = chip_variants
+                        .iter()
+                        .map(|chip_variant| {
+                            let value = convert_chip_variant_value(table, chip_variant, &reg.name)?;
+                            let value = convert_reset_value(
+                                value,
+                                &reg.fields,
+                                reg.bit_order,
+                                reg.size_bits,
+                                "register",
+                                &reg.name,
+                                target_byte_order,
+                            )?;
+                            Ok((chip_variant.clone(), value))
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+
+                    // The first chip variant is the default
+                    new_reset_values.insert(reg.id(), variant_reset_values[0].1.clone());
+                    new_chip_variant_reset_values.insert(reg.id(), variant_reset_values);
+                    Ok(())
+                }
                 Some(reset_value) => {
                     let new_reset_value = convert_reset_value(
                         reset_value.clone(),
+                        &reg.fields,
                         reg.bit_order,
                         reg.size_bits,
                         "register",
@@ -60,10 +95,23 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
                     .as_register()
                     .expect("Refs have been validated already for types");
 
+                ensure!(
+                    as_chip_variant_table(
+                        reset_value,
+                        chip_variants,
+                        &base_reg.fields,
+                        "ref register",
+                        name
+                    )?
+                    .is_none(),
+                    "The reset value of ref register \"{name}\" is given per chip variant. This is only supported on registers"
+                );
+
                 let target_byte_order = get_target_byte_order(base_reg, device);
 
                 let new_reset_value = convert_reset_value(
                     reset_value.clone(),
+                    &base_reg.fields,
                     base_reg.bit_order,
                     base_reg.size_bits,
                     "ref register",
@@ -83,6 +131,10 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
             if let Some(new_reset_value) = new_reset_values.remove(&register.id()) {
                 register.reset_value = Some(new_reset_value);
             }
+            if let Some(variant_reset_values) = new_chip_variant_reset_values.remove(&register.id())
+            {
+                register.chip_variant_reset_values = variant_reset_values;
+            }
 
             Ok(())
         }
@@ -106,18 +158,92 @@ pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
 
     assert!(new_reset_values.is_empty());
 
+    let mut reset_sequence = std::mem::take(&mut device.global_config.reset_sequence);
+    for (register_name, value) in reset_sequence.iter_mut() {
+        let Some(Object::Register(register)) = search_object(register_name, &device.objects) else {
+            bail!("The reset sequence refers to \"{register_name}\", which is not a register");
+        };
+
+        *value = convert_reset_value(
+            value.clone(),
+            &register.fields,
+            register.bit_order,
+            register.size_bits,
+            "reset sequence register",
+            register_name,
+            get_target_byte_order(register, device),
+        )?;
+    }
+    device.global_config.reset_sequence = reset_sequence;
+
     Ok(())
 }
 
+/// A reset value table is a table of chip variants when all its keys are chip variants
+///
+/// A key that is both a chip variant and a field makes it unclear what the table means, so that is an error.
+fn as_chip_variant_table<'r>(
+    reset_value: &'r ResetValue,
+    chip_variants: &[String],
+    fields: &[Field],
+    object_type_name: &str,
+    object_name: &str,
+) -> anyhow::Result<Option<&'r [(String, FieldResetValue)]>> {
+    let ResetValue::Fields(values) = reset_value else {
+        return Ok(None);
+    };
+
+    if let Some((ambiguous_name, _)) = values.iter().find(|(name, _)| {
+        chip_variants.contains(name) && fields.iter().any(|field| &field.name == name)
+    }) {
+        bail!(
+            "The reset value of {object_type_name} \"{object_name}\" uses \"{ambiguous_name}\", which is both a chip variant and a field. \
+            It's unclear whether this is a table per chip variant or a table of field values, so rename the field or the chip variant"
+        );
+    }
+
+    match !values.is_empty() && values.iter().all(|(name, _)| chip_variants.contains(name)) {
+        true => Ok(Some(values)),
+        false => Ok(None),
+    }
+}
+
+/// Get the reset value of the chip variant out of the table
+fn convert_chip_variant_value(
+    table: &[(String, FieldResetValue)],
+    chip_variant: &str,
+    register_name: &str,
+) -> anyhow::Result<ResetValue> {
+    let mut values = table.iter().filter(|(name, _)| name == chip_variant);
+
+    let Some((_, value)) = values.next() else {
+        bail!(
+            "The reset value of register \"{register_name}\" is given per chip variant, but chip variant \"{chip_variant}\" is missing"
+        );
+    };
+    ensure!(
+        values.next().is_none(),
+        "The reset value of register \"{register_name}\" has chip variant \"{chip_variant}\" specified more than once"
+    );
+
+    match value {
+        FieldResetValue::Integer(value) => Ok(ResetValue::Integer(*value as u128)),
+        _ => bail!(
+            "The reset value of register \"{register_name}\" for chip variant \"{chip_variant}\" must be an integer"
+        ),
+    }
+}
+
 fn get_target_byte_order(reg: &Register, device: &Device) -> ByteOrder {
     reg.byte_order
-        .or(device.global_config.default_byte_order)
+        .or(device.global_config.register_byte_order())
         .or((reg.size_bits <= 8).then_some(ByteOrder::LE))
         .expect("Register should have a valid byte order or not need one")
 }
 
 fn convert_reset_value(
     reset_value: ResetValue,
+    fields: &[Field],
     bit_order: BitOrder,
     size_bits: u32,
     object_type_name: &str,
@@ -152,10 +278,8 @@ fn convert_reset_value(
                 final_array.iter_mut().for_each(|b| *b = b.reverse_bits());
             }
 
-            // Convert to big endian if required. Bitvec's output is always little endian
-            if target_byte_order == ByteOrder::BE {
-                final_array.reverse();
-            }
+            // Convert to the target byte order if required. Bitvec's output is always little endian
+            target_byte_order.reorder_from_le(&mut final_array);
 
             Ok(ResetValue::Array(final_array))
         }
@@ -168,9 +292,7 @@ fn convert_reset_value(
             );
 
             // Convert to little endian to do the check since that's what bitvec needs
-            if target_byte_order == ByteOrder::BE {
-                array.reverse();
-            }
+            target_byte_order.reorder_from_le(&mut array);
 
             match bit_order {
                 BitOrder::LSB0 => {
@@ -193,19 +315,134 @@ fn convert_reset_value(
                 }
             }
 
-            // Convert back to big endian
-            if target_byte_order == ByteOrder::BE {
-                array.reverse();
-            }
+            // Convert back to the target byte order
+            target_byte_order.reorder_from_le(&mut array);
 
             Ok(ResetValue::Array(array))
         }
+        ResetValue::Fields(field_values) => {
+            let int = fold_field_values(
+                field_values,
+                fields,
+                bit_order,
+                object_type_name,
+                object_name,
+            )?;
+
+            convert_reset_value(
+                ResetValue::Integer(int),
+                fields,
+                bit_order,
+                size_bits,
+                object_type_name,
+                object_name,
+                target_byte_order,
+            )
+        }
+    }
+}
+
+/// Put the values of the fields at their bits in an integer, like it would be given as an integer reset value
+fn fold_field_values(
+    field_values: Vec<(String, FieldResetValue)>,
+    fields: &[Field],
+    bit_order: BitOrder,
+    object_type_name: &str,
+    object_name: &str,
+) -> anyhow::Result<u128> {
+    let mut int = 0u128;
+
+    for (index, (field_name, value)) in field_values.iter().enumerate() {
+        ensure!(
+            !field_values[..index]
+                .iter()
+                .any(|(previous_name, _)| previous_name == field_name),
+            "The reset value of {object_type_name} \"{object_name}\" has field \"{field_name}\" specified more than once",
+        );
+
+        let Some(field) = fields.iter().find(|field| field.name == *field_name) else {
+            bail!(
+                "The reset value of {object_type_name} \"{object_name}\" specifies field \"{field_name}\", which is not a field of the register"
+            );
+        };
+
+        // The bits of an integer reset value are the bits of the register in LSB0 order,
+        // so only fields that use LSB0 can be put in as a whole
+        ensure!(
+            field.bit_order.unwrap_or(bit_order) == BitOrder::LSB0,
+            "The reset value of {object_type_name} \"{object_name}\" specifies field \"{field_name}\", which doesn't have the LSB0 bit order. \
+            Field values can only be given for LSB0 fields, so use an integer or array reset value instead",
+        );
+
+        let value = match value {
+            FieldResetValue::Integer(value) => *value,
+            FieldResetValue::Bool(value) => *value as i128,
+            FieldResetValue::Variant(variant_name) => {
+                let Some(FieldConversion::Enum { enum_value, .. }) = &field.field_conversion else {
+                    bail!(
+                        "The reset value of {object_type_name} \"{object_name}\" specifies variant \"{variant_name}\" for field \"{field_name}\", which is not an enum"
+                    );
+                };
+
+                variant_value(enum_value, variant_name).with_context(|| {
+                    format!(
+                        "The reset value of {object_type_name} \"{object_name}\" specifies variant \"{variant_name}\" for field \"{field_name}\", which is not a variant with a single value of enum \"{}\"",
+                        enum_value.name
+                    )
+                })?
+            }
+        };
+
+        let field_bits = field.field_address.len() as u32;
+        let mask = u128::MAX.checked_shr(128 - field_bits).unwrap_or(0);
+        let fits = match field.base_type {
+            BaseType::Int => {
+                let max = (mask >> 1) as i128;
+                (-max - 1..=max).contains(&value)
+            }
+            BaseType::Bool | BaseType::Uint => value >= 0 && value as u128 <= mask,
+        };
+        ensure!(
+            fits,
+            "The reset value of {object_type_name} \"{object_name}\" specifies {value} for field \"{field_name}\", which doesn't fit in its {field_bits} bits",
+        );
+
+        int |= (value as u128 & mask) << field.field_address.start;
+    }
+
+    Ok(int)
+}
+
+/// The value of the variant as it's written. Catch-all variants don't have a single value
+pub(super) fn variant_value(enum_value: &Enum, variant_name: &str) -> Option<i128> {
+    let mut last_value = None;
+
+    for variant in &enum_value.variants {
+        let value = match &variant.value {
+            EnumValue::Specified(value) => *value,
+            EnumValue::Range(range) => *range.start(),
+            EnumValue::Unspecified
+            | EnumValue::Default
+            | EnumValue::CatchAll
+            | EnumValue::Fallback => last_value.map(|value: i128| value + 1).unwrap_or(0),
+        };
+
+        if variant.name == variant_name {
+            return (!variant.value.is_catch_all()).then_some(value);
+        }
+
+        last_value = Some(match &variant.value {
+            EnumValue::Range(range) => *range.end(),
+            _ => value,
+        });
     }
+
+    None
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::mir::{GlobalConfig, Register};
+    use crate::mir::{EnumVariant, GlobalConfig, Register, RegisterOverride};
 
     use super::*;
 
@@ -445,4 +682,334 @@ mod tests {
 
         assert_eq!(start_mir, end_mir);
     }
+
+    #[test]
+    fn reset_sequence_converted() {
+        let mut start_mir = Device {
+            global_config: GlobalConfig {
+                reset_sequence: vec![("Reg".into(), ResetValue::Integer(0x423))],
+                ..Default::default()
+            },
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                size_bits: 11,
+                byte_order: Some(ByteOrder::BE),
+                ..Default::default()
+            })],
+        };
+
+        run_pass(&mut start_mir).unwrap();
+
+        assert_eq!(
+            start_mir.global_config.reset_sequence,
+            vec![("Reg".into(), ResetValue::Array(vec![0x04, 0x23]))]
+        );
+
+        let mut start_mir = Device {
+            global_config: GlobalConfig {
+                reset_sequence: vec![("Reg".into(), ResetValue::Integer(0x800))],
+                ..Default::default()
+            },
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                size_bits: 11,
+                byte_order: Some(ByteOrder::BE),
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "The reset value of reset sequence register \"Reg\" has (a) bit(s) specified above the size of the register. While you can specify them, this is likely a mistake and thus not accepted. Keep the bits `11..` all at zero"
+        );
+
+        let mut start_mir = Device {
+            global_config: GlobalConfig {
+                reset_sequence: vec![("Foo".into(), ResetValue::Integer(0))],
+                ..Default::default()
+            },
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                size_bits: 8,
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "The reset sequence refers to \"Foo\", which is not a register"
+        );
+    }
+
+    fn field_values_register(reset_value: Vec<(&str, FieldResetValue)>) -> Device {
+        Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                size_bits: 16,
+                byte_order: Some(ByteOrder::BE),
+                reset_value: Some(ResetValue::Fields(
+                    reset_value
+                        .into_iter()
+                        .map(|(name, value)| (name.into(), value))
+                        .collect(),
+                )),
+                fields: vec![
+                    Field {
+                        name: "value".into(),
+                        field_address: 0..4,
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "offset".into(),
+                        base_type: BaseType::Int,
+                        field_address: 4..8,
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "enabled".into(),
+                        base_type: BaseType::Bool,
+                        field_address: 8..9,
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "mode".into(),
+                        field_address: 9..11,
+                        field_conversion: Some(FieldConversion::Enum {
+                            enum_value: Enum::new(
+                                String::new(),
+                                "Mode".into(),
+                                vec![
+                                    EnumVariant {
+                                        name: "Input".into(),
+                                        ..Default::default()
+                                    },
+                                    EnumVariant {
+                                        name: "Output".into(),
+                                        ..Default::default()
+                                    },
+                                    EnumVariant {
+                                        name: "Analog".into(),
+                                        value: EnumValue::Specified(3),
+                                        ..Default::default()
+                                    },
+                                ],
+                            ),
+                            use_try: false,
+                        }),
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "flipped".into(),
+                        bit_order: Some(BitOrder::MSB0),
+                        field_address: 12..16,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            })],
+        }
+    }
+
+    #[test]
+    fn field_values_packed() {
+        let mut start_mir = field_values_register(vec![
+            ("value", FieldResetValue::Integer(5)),
+            ("offset", FieldResetValue::Integer(-2)),
+            ("enabled", FieldResetValue::Bool(true)),
+            ("mode", FieldResetValue::Variant("Output".into())),
+        ]);
+
+        run_pass(&mut start_mir).unwrap();
+
+        assert_eq!(
+            start_mir.objects[0].as_register().unwrap().reset_value,
+            Some(ResetValue::Array(vec![0x03, 0xE5]))
+        );
+
+        let mut start_mir =
+            field_values_register(vec![("mode", FieldResetValue::Variant("Analog".into()))]);
+
+        run_pass(&mut start_mir).unwrap();
+
+        assert_eq!(
+            start_mir.objects[0].as_register().unwrap().reset_value,
+            Some(ResetValue::Array(vec![0x06, 0x00]))
+        );
+    }
+
+    #[test]
+    fn invalid_field_values() {
+        let error = |reset_value| {
+            run_pass(&mut field_values_register(reset_value))
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error(vec![("foo", FieldResetValue::Integer(1))]),
+            "The reset value of register \"Reg\" specifies field \"foo\", which is not a field of the register"
+        );
+        assert_eq!(
+            error(vec![
+                ("value", FieldResetValue::Integer(1)),
+                ("value", FieldResetValue::Integer(2))
+            ]),
+            "The reset value of register \"Reg\" has field \"value\" specified more than once"
+        );
+        assert_eq!(
+            error(vec![("value", FieldResetValue::Integer(16))]),
+            "The reset value of register \"Reg\" specifies 16 for field \"value\", which doesn't fit in its 4 bits"
+        );
+        assert_eq!(
+            error(vec![("offset", FieldResetValue::Integer(-9))]),
+            "The reset value of register \"Reg\" specifies -9 for field \"offset\", which doesn't fit in its 4 bits"
+        );
+        assert_eq!(
+            error(vec![("value", FieldResetValue::Variant("Output".into()))]),
+            "The reset value of register \"Reg\" specifies variant \"Output\" for field \"value\", which is not an enum"
+        );
+        assert_eq!(
+            error(vec![("mode", FieldResetValue::Variant("Digital".into()))]),
+            "The reset value of register \"Reg\" specifies variant \"Digital\" for field \"mode\", which is not a variant with a single value of enum \"Mode\""
+        );
+        assert_eq!(
+            error(vec![("flipped", FieldResetValue::Integer(1))]),
+            "The reset value of register \"Reg\" specifies field \"flipped\", which doesn't have the LSB0 bit order. Field values can only be given for LSB0 fields, so use an integer or array reset value instead"
+        );
+    }
+
+    fn chip_variant_register(reset_value: Vec<(&str, FieldResetValue)>) -> Device {
+        Device {
+            global_config: GlobalConfig {
+                chip_variants: vec!["a".into(), "b".into()],
+                ..Default::default()
+            },
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                size_bits: 16,
+                byte_order: Some(ByteOrder::BE),
+                reset_value: Some(ResetValue::Fields(
+                    reset_value
+                        .into_iter()
+                        .map(|(name, value)| (name.into(), value))
+                        .collect(),
+                )),
+                fields: vec![Field {
+                    name: "value".into(),
+                    field_address: 0..16,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })],
+        }
+    }
+
+    #[test]
+    fn chip_variant_values_converted() {
+        let mut start_mir = chip_variant_register(vec![
+            ("b", FieldResetValue::Integer(0x0102)),
+            ("a", FieldResetValue::Integer(0x01)),
+        ]);
+
+        run_pass(&mut start_mir).unwrap();
+
+        let register = start_mir.objects[0].as_register().unwrap();
+        assert_eq!(
+            register.reset_value,
+            Some(ResetValue::Array(vec![0x00, 0x01]))
+        );
+        assert_eq!(
+            register.chip_variant_reset_values,
+            vec![
+                ("a".into(), ResetValue::Array(vec![0x00, 0x01])),
+                ("b".into(), ResetValue::Array(vec![0x01, 0x02])),
+            ]
+        );
+
+        // Field names are not chip variants, so this stays a field table
+        let mut start_mir = chip_variant_register(vec![("value", FieldResetValue::Integer(3))]);
+
+        run_pass(&mut start_mir).unwrap();
+
+        let register = start_mir.objects[0].as_register().unwrap();
+        assert_eq!(
+            register.reset_value,
+            Some(ResetValue::Array(vec![0x00, 0x03]))
+        );
+        assert!(register.chip_variant_reset_values.is_empty());
+    }
+
+    #[test]
+    fn invalid_chip_variant_values() {
+        let error = |reset_value| {
+            run_pass(&mut chip_variant_register(reset_value))
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error(vec![("a", FieldResetValue::Integer(1))]),
+            "The reset value of register \"Reg\" is given per chip variant, but chip variant \"b\" is missing"
+        );
+        assert_eq!(
+            error(vec![
+                ("a", FieldResetValue::Integer(1)),
+                ("a", FieldResetValue::Integer(2)),
+                ("b", FieldResetValue::Integer(2)),
+            ]),
+            "The reset value of register \"Reg\" has chip variant \"a\" specified more than once"
+        );
+        assert_eq!(
+            error(vec![
+                ("a", FieldResetValue::Integer(1)),
+                ("b", FieldResetValue::Bool(true)),
+            ]),
+            "The reset value of register \"Reg\" for chip variant \"b\" must be an integer"
+        );
+        assert_eq!(
+            error(vec![
+                ("a", FieldResetValue::Integer(1)),
+                ("b", FieldResetValue::Integer(0x10000)),
+            ]),
+            "The reset value of register \"Reg\" has (a) bit(s) specified above the size of the register. \
+            While you can specify them, this is likely a mistake and thus not accepted. Keep the bits `16..` all at zero"
+        );
+
+        let mut start_mir = chip_variant_register(vec![
+            ("a", FieldResetValue::Integer(1)),
+            ("b", FieldResetValue::Integer(2)),
+        ]);
+        start_mir.objects.push(Object::Ref(RefObject {
+            name: "Ref".into(),
+            object_override: ObjectOverride::Register(RegisterOverride {
+                name: "Reg".into(),
+                reset_value: Some(ResetValue::Fields(vec![
+                    ("a".into(), FieldResetValue::Integer(1)),
+                    ("b".into(), FieldResetValue::Integer(2)),
+                ])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "The reset value of ref register \"Ref\" is given per chip variant. This is only supported on registers"
+        );
+    }
+
+    #[test]
+    fn ambiguous_chip_variant_table() {
+        let mut start_mir = chip_variant_register(vec![
+            ("a", FieldResetValue::Integer(1)),
+            ("b", FieldResetValue::Integer(2)),
+        ]);
+        start_mir.objects[0].as_register_mut().unwrap().fields[0].name = "a".into();
+
+        assert_eq!(
+            run_pass(&mut start_mir).unwrap_err().to_string(),
+            "The reset value of register \"Reg\" uses \"a\", which is both a chip variant and a field. \
+            It's unclear whether this is a table per chip variant or a table of field values, so rename the field or the chip variant"
+        );
+    }
 }
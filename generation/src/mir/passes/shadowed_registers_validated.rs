@@ -0,0 +1,190 @@
+use anyhow::{bail, ensure};
+
+use super::{recurse_objects_with_depth, search_object};
+use crate::mir::{Device, Object, ObjectOverride};
+
+/// Check the shadowed registers.
+/// The shadow is stored on the device struct, so shadowed registers must be in the root block
+/// and can't be repeated or span multiple addresses.
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    recurse_objects_with_depth(&device.objects, &mut |object, depth| match object {
+        Object::Register(register) if register.shadowed => {
+            ensure!(
+                depth == 0,
+                "Register \"{}\" is shadowed, but is not in the root block. This is not supported",
+                register.name
+            );
+            ensure!(
+                register.repeat.is_none(),
+                "Register \"{}\" is shadowed and repeated. This is not supported",
+                register.name
+            );
+            ensure!(
+                register.span_offsets.is_empty(),
+                "Register \"{}\" is shadowed and spans multiple addresses. This is not supported",
+                register.name
+            );
+
+            Ok(())
+        }
+        Object::Ref(ref_object) => {
+            if let ObjectOverride::Register(register_override) = &ref_object.object_override
+                && let Some(Object::Register(register)) =
+                    search_object(&register_override.name, &device.objects)
+                && register.shadowed
+            {
+                if depth != 0 {
+                    bail!(
+                        "Ref \"{}\" refers to shadowed register \"{}\", but is not in the root block. This is not supported",
+                        ref_object.name,
+                        register.name
+                    );
+                }
+                if register_override.repeat.is_some() {
+                    bail!(
+                        "Ref \"{}\" repeats shadowed register \"{}\". This is not supported",
+                        ref_object.name,
+                        register.name
+                    );
+                }
+            }
+
+            Ok(())
+        }
+        _ => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{Block, RefObject, Register, RegisterOverride, Repeat};
+
+    use super::*;
+
+    #[test]
+    fn root_shadowed_register_accepted() {
+        run_pass(&mut Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Foo".into(),
+                shadowed: true,
+                ..Default::default()
+            })],
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn invalid_shadowed_registers_rejected() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Block(Block {
+                name: "Bar".into(),
+                objects: vec![Object::Register(Register {
+                    name: "Foo".into(),
+                    shadowed: true,
+                    ..Default::default()
+                })],
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Register \"Foo\" is shadowed, but is not in the root block. This is not supported"
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Foo".into(),
+                shadowed: true,
+                repeat: Some(Repeat {
+                    count: 2,
+                    stride: 1,
+                }),
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Register \"Foo\" is shadowed and repeated. This is not supported"
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Foo".into(),
+                shadowed: true,
+                span_offsets: vec![0, 1],
+                size_bits: 16,
+                ..Default::default()
+            })],
+        };
+
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Register \"Foo\" is shadowed and spans multiple addresses. This is not supported"
+        );
+    }
+
+    #[test]
+    fn invalid_refs_rejected() {
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                Object::Register(Register {
+                    name: "Foo".into(),
+                    shadowed: true,
+                    ..Default::default()
+                }),
+                Object::Ref(RefObject {
+                    name: "Bar".into(),
+                    object_override: ObjectOverride::Register(RegisterOverride {
+                        name: "Foo".into(),
+                        repeat: Some(Repeat {
+                            count: 2,
+                            stride: 1,
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            ],
+        };
+
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Ref \"Bar\" repeats shadowed register \"Foo\". This is not supported"
+        );
+
+        let mut device = Device {
+            global_config: Default::default(),
+            objects: vec![
+                Object::Register(Register {
+                    name: "Foo".into(),
+                    shadowed: true,
+                    ..Default::default()
+                }),
+                Object::Block(Block {
+                    name: "Blk".into(),
+                    objects: vec![Object::Ref(RefObject {
+                        name: "Bar".into(),
+                        object_override: ObjectOverride::Register(RegisterOverride {
+                            name: "Foo".into(),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    })],
+                    ..Default::default()
+                }),
+            ],
+        };
+
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Ref \"Bar\" refers to shadowed register \"Foo\", but is not in the root block. This is not supported"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{bail, ensure};
+
+use super::{recurse_objects_mut, reset_values_converted::variant_value};
+use crate::mir::{BaseType, Device, Field, FieldConversion, FieldResetValue, ValidWhen};
+
+/// Check the valid_when conditions of the fields and find the raw value they compare with.
+/// The condition must be on another field in the same field set and the value must match the type of that field.
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    recurse_objects_mut(&mut device.objects, &mut |object| {
+        let object_name = object.name().to_string();
+
+        for fields in object.field_sets_mut() {
+            for index in 0..fields.len() {
+                let Some(valid_when) = &fields[index].valid_when else {
+                    continue;
+                };
+
+                let raw_value =
+                    get_raw_value(&object_name, &fields[index].name, valid_when, fields)?;
+                fields[index]
+                    .valid_when
+                    .as_mut()
+                    .expect("Checked above")
+                    .raw_value = raw_value;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+fn get_raw_value(
+    object_name: &str,
+    field_name: &str,
+    valid_when: &ValidWhen,
+    fields: &[Field],
+) -> anyhow::Result<i128> {
+    ensure!(
+        valid_when.field != field_name,
+        "Field \"{field_name}\" of object \"{object_name}\" is only valid when `{valid_when}`, but it can't depend on itself"
+    );
+
+    let Some(condition_field) = fields.iter().find(|field| field.name == valid_when.field) else {
+        bail!(
+            "Field \"{field_name}\" of object \"{object_name}\" is only valid when `{valid_when}`, but field \"{}\" does not exist in the same field set",
+            valid_when.field
+        );
+    };
+
+    let raw_value = match (&valid_when.value, condition_field.base_type) {
+        (FieldResetValue::Bool(value), BaseType::Bool) => Some(*value as i128),
+        (FieldResetValue::Integer(value), BaseType::Uint) if *value >= 0 => Some(*value),
+        (FieldResetValue::Integer(value), BaseType::Int) => Some(*value),
+        (FieldResetValue::Variant(variant), _) => match &condition_field.field_conversion {
+            Some(FieldConversion::Enum { enum_value, .. }) => variant_value(enum_value, variant),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    match raw_value {
+        Some(raw_value) => Ok(raw_value),
+        None => bail!(
+            "Field \"{field_name}\" of object \"{object_name}\" is only valid when `{valid_when}`, but the value can't be compared with field \"{}\". Use a bool for bool fields, a variant with a single value for enum fields and an integer otherwise",
+            valid_when.field
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{Enum, EnumValue, EnumVariant, Object, Register};
+
+    use super::*;
+
+    fn device_with_condition(value: FieldResetValue) -> Device {
+        Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Reg".into(),
+                fields: vec![
+                    Field {
+                        name: "enabled".into(),
+                        base_type: BaseType::Bool,
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "mode".into(),
+                        base_type: BaseType::Uint,
+                        field_conversion: Some(FieldConversion::Enum {
+                            enum_value: Enum::new(
+                                "".into(),
+                                "Mode".into(),
+                                vec![
+                                    EnumVariant {
+                                        name: "Input".into(),
+                                        value: EnumValue::Specified(1),
+                                        ..Default::default()
+                                    },
+                                    EnumVariant {
+                                        name: "Output".into(),
+                                        ..Default::default()
+                                    },
+                                ],
+                            ),
+                            use_try: true,
+                        }),
+                        ..Default::default()
+                    },
+                    Field {
+                        name: "level".into(),
+                        base_type: BaseType::Uint,
+                        valid_when: Some(ValidWhen {
+                            field: "mode".into(),
+                            negated: false,
+                            value,
+                            raw_value: 0,
+                        }),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            })],
+        }
+    }
+
+    fn raw_value(device: &Device) -> i128 {
+        device.objects[0].as_register().unwrap().fields[2]
+            .valid_when
+            .as_ref()
+            .unwrap()
+            .raw_value
+    }
+
+    #[test]
+    fn variant_condition_resolved() {
+        let mut device = device_with_condition(FieldResetValue::Variant("Output".into()));
+        run_pass(&mut device).unwrap();
+        assert_eq!(raw_value(&device), 2);
+
+        let mut device = device_with_condition(FieldResetValue::Integer(1));
+        run_pass(&mut device).unwrap();
+        assert_eq!(raw_value(&device), 1);
+    }
+
+    #[test]
+    fn invalid_conditions_rejected() {
+        let mut device = device_with_condition(FieldResetValue::Bool(true));
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Field \"level\" of object \"Reg\" is only valid when `mode == true`, but the value can't be compared with field \"mode\". Use a bool for bool fields, a variant with a single value for enum fields and an integer otherwise"
+        );
+
+        let mut device = device_with_condition(FieldResetValue::Variant("Analog".into()));
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Field \"level\" of object \"Reg\" is only valid when `mode == Analog`, but the value can't be compared with field \"mode\". Use a bool for bool fields, a variant with a single value for enum fields and an integer otherwise"
+        );
+
+        let mut device = device_with_condition(FieldResetValue::Integer(1));
+        device.objects[0].as_register_mut().unwrap().fields[2]
+            .valid_when
+            .as_mut()
+            .unwrap()
+            .field = "speed".into();
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Field \"level\" of object \"Reg\" is only valid when `speed == 1`, but field \"speed\" does not exist in the same field set"
+        );
+
+        let mut device = device_with_condition(FieldResetValue::Integer(1));
+        device.objects[0].as_register_mut().unwrap().fields[2]
+            .valid_when
+            .as_mut()
+            .unwrap()
+            .field = "level".into();
+        assert_eq!(
+            run_pass(&mut device).unwrap_err().to_string(),
+            "Field \"level\" of object \"Reg\" is only valid when `level == 1`, but it can't depend on itself"
+        );
+    }
+}
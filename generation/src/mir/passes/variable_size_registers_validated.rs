@@ -0,0 +1,147 @@
+use anyhow::{Context, ensure};
+
+use super::recurse_objects;
+use crate::mir::{BaseType, BitOrder, ByteOrder, Device, Object};
+
+/// Check the registers with a length field.
+/// Only the bytes up to the length field are read first, so the field must be a plain uint and
+/// its bits must be in the first bytes of the register, whatever the size of the register turns out to be.
+pub fn run_pass(device: &mut Device) -> anyhow::Result<()> {
+    recurse_objects(&device.objects, &mut |object| match object {
+        Object::Register(register) => {
+            let Some(length_field) = register.length_field.as_ref() else {
+                return Ok(());
+            };
+
+            let field = register
+                .fields
+                .iter()
+                .find(|field| field.name == *length_field)
+                .with_context(|| {
+                    format!(
+                        "Register \"{}\" has length field \"{length_field}\", which is not a field of the register",
+                        register.name
+                    )
+                })?;
+
+            ensure!(
+                field.base_type == BaseType::Uint && field.field_conversion.is_none(),
+                "Register \"{}\" has length field \"{length_field}\" that is not a uint without conversion",
+                register.name
+            );
+            ensure!(
+                field.bit_order.unwrap_or(register.bit_order) == BitOrder::LSB0
+                    && (register.size_bits <= 8 || register.byte_order == Some(ByteOrder::LE)),
+                "Register \"{}\" has a length field, but is not LE with LSB0 bit order. Only then the length field is always in the first bytes",
+                register.name
+            );
+            ensure!(
+                register.span_offsets.is_empty() && !register.shadowed,
+                "Register \"{}\" has a length field and is shadowed or spans multiple addresses. This is not supported",
+                register.name
+            );
+
+            Ok(())
+        }
+        _ => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mir::{Field, FieldConversion, Register};
+
+    use super::*;
+
+    fn device(register: Register) -> Device {
+        Device {
+            global_config: Default::default(),
+            objects: vec![Object::Register(Register {
+                name: "Foo".into(),
+                length_field: Some("len".into()),
+                ..register
+            })],
+        }
+    }
+
+    fn len_field(field: Field) -> Field {
+        Field {
+            name: "len".into(),
+            field_address: 0..8,
+            ..field
+        }
+    }
+
+    #[test]
+    fn valid_length_field_accepted() {
+        run_pass(&mut device(Register {
+            fields: vec![len_field(Default::default())],
+            size_bits: 32,
+            byte_order: Some(ByteOrder::LE),
+            ..Default::default()
+        }))
+        .unwrap();
+    }
+
+    #[test]
+    fn invalid_length_fields_rejected() {
+        assert_eq!(
+            run_pass(&mut device(Register::default()))
+                .unwrap_err()
+                .to_string(),
+            "Register \"Foo\" has length field \"len\", which is not a field of the register"
+        );
+
+        assert_eq!(
+            run_pass(&mut device(Register {
+                fields: vec![len_field(Field {
+                    field_conversion: Some(FieldConversion::Direct {
+                        type_name: "Len".into(),
+                        use_try: false,
+                    }),
+                    ..Default::default()
+                })],
+                ..Default::default()
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" has length field \"len\" that is not a uint without conversion"
+        );
+
+        assert_eq!(
+            run_pass(&mut device(Register {
+                fields: vec![len_field(Default::default())],
+                size_bits: 32,
+                byte_order: Some(ByteOrder::BE),
+                ..Default::default()
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" has a length field, but is not LE with LSB0 bit order. Only then the length field is always in the first bytes"
+        );
+
+        assert_eq!(
+            run_pass(&mut device(Register {
+                fields: vec![len_field(Field {
+                    bit_order: Some(BitOrder::MSB0),
+                    ..Default::default()
+                })],
+                ..Default::default()
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" has a length field, but is not LE with LSB0 bit order. Only then the length field is always in the first bytes"
+        );
+
+        assert_eq!(
+            run_pass(&mut device(Register {
+                fields: vec![len_field(Default::default())],
+                shadowed: true,
+                ..Default::default()
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Register \"Foo\" has a length field and is shadowed or spans multiple addresses. This is not supported"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::{fs, path::Path, process::Command};
+
+/// Put the generated code in a crate of its own and run its tests
+fn run_generated_tests(crate_name: &str, generated_code: &str) -> std::process::Output {
+    let crate_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(crate_name);
+    let device_driver_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../device-driver");
+
+    fs::create_dir_all(crate_dir.join("src")).unwrap();
+    fs::write(
+        crate_dir.join("Cargo.toml"),
+        format!(
+            "[package]\n\
+            name = \"{crate_name}\"\n\
+            edition = \"2024\"\n\
+            \n\
+            [dependencies]\n\
+            device-driver = {{ path = {:?}, default-features = false }}\n\
+            \n\
+            [workspace]\n",
+            device_driver_dir.display().to_string()
+        ),
+    )
+    .unwrap();
+    fs::write(crate_dir.join("src/lib.rs"), generated_code).unwrap();
+
+    Command::new(env!("CARGO"))
+        .args(["test", "--offline", "--lib"])
+        .current_dir(&crate_dir)
+        // Don't wait on the lock of the target dir of the test that is running this
+        .env("CARGO_TARGET_DIR", crate_dir.join("target"))
+        .output()
+        .unwrap()
+}
+
+/// A wrong datasheet expectation generates a test that compares the computed value to the wrong expected value,
+/// so the generated test fails
+#[test]
+fn wrong_expectation_generates_failing_test() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u8;
+        }
+        block Bank {
+            const ADDRESS_OFFSET = 0x10;
+
+            register Foo {
+                const ADDRESS = 2;
+                const SIZE_BITS = 8;
+                const EXPECT_ADDRESS = 0x02;
+                const EXPECT_SIZE = 16;
+
+                value: uint = 0..8,
+            },
+        },
+    ";
+
+    let output =
+        device_driver_generation::transform_dsl(syn::parse_str(dsl_text).unwrap(), "Device")
+            .to_string();
+
+    assert!(output.contains(
+        "fn foo_address_matches_datasheet () { let computed = 18i64 ; let expected = 2i64 ; assert_eq ! (computed , expected"
+    ));
+    assert!(output.contains(
+        "fn foo_size_matches_datasheet () { let computed = < field_sets :: Foo as :: device_driver :: FieldSet > :: SIZE_BITS ; let expected = 16 ; assert_eq ! (computed , expected"
+    ));
+
+    let test_run = run_generated_tests("wrong_datasheet_expectation", &output);
+    let test_output = String::from_utf8_lossy(&test_run.stdout);
+    let test_errors = String::from_utf8_lossy(&test_run.stderr);
+
+    assert!(!test_run.status.success(), "{test_output}\n{test_errors}");
+    assert!(
+        test_output.contains("test datasheet_tests::foo_address_matches_datasheet ... FAILED"),
+        "{test_output}\n{test_errors}"
+    );
+    assert!(
+        test_output.contains("test datasheet_tests::foo_size_matches_datasheet ... FAILED"),
+        "{test_output}\n{test_errors}"
+    );
+    assert!(test_output.contains("The address of register `Foo` doesn't match the datasheet"));
+    assert!(test_output.contains("The size of register `Foo` doesn't match the datasheet"));
+}
+
+/// Without expectations no test module is generated
+#[test]
+fn no_expectations_no_test_module() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Foo {
+            const ADDRESS = 2;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    ";
+
+    let output =
+        device_driver_generation::transform_dsl(syn::parse_str(dsl_text).unwrap(), "Device")
+            .to_string();
+
+    assert!(!output.contains("datasheet_tests"));
+}
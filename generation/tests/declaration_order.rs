@@ -0,0 +1,66 @@
+/// The generated code must follow the order of the source so the output is stable
+#[test]
+fn output_in_declaration_order() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u8;
+            type DefaultByteOrder = LE;
+        }
+        register Zeta {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+        },
+        register Alpha {
+            const ADDRESS = 1;
+            const SIZE_BITS = 8;
+        },
+        register Mid {
+            const ADDRESS = 2;
+            const SIZE_BITS = 8;
+        },
+    ";
+    let yaml_text = "
+        config:
+            register_address_type: u8
+            default_byte_order: LE
+        Zeta:
+            type: register
+            address: 0
+            size_bits: 8
+        Alpha:
+            type: register
+            address: 1
+            size_bits: 8
+        Mid:
+            type: register
+            address: 2
+            size_bits: 8
+    ";
+
+    let dsl_output =
+        device_driver_generation::transform_dsl(syn::parse_str(dsl_text).unwrap(), "Device")
+            .to_string();
+    let yaml_output = device_driver_generation::transform_yaml(yaml_text, "Device").to_string();
+
+    for output in [&dsl_output, &yaml_output] {
+        let positions = [
+            "fn zeta",
+            "fn alpha",
+            "fn mid",
+            "struct Zeta",
+            "struct Alpha",
+            "struct Mid",
+        ]
+        .map(|item| output.find(item).unwrap());
+
+        assert!(positions[0] < positions[1] && positions[1] < positions[2]);
+        assert!(positions[3] < positions[4] && positions[4] < positions[5]);
+    }
+
+    assert_eq!(
+        dsl_output,
+        device_driver_generation::transform_dsl(syn::parse_str(dsl_text).unwrap(), "Device")
+            .to_string()
+    );
+    assert_eq!(dsl_output, yaml_output);
+}
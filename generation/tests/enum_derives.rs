@@ -0,0 +1,28 @@
+/// The Debug impl of the field sets prints the enum fields, so leaving out `Debug` is reported with a clear error
+#[test]
+fn enum_derives_without_debug_is_compile_error() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u8;
+            type EnumDerives = [Copy, Clone];
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            mode: uint as enum Mode {
+                Off,
+                On,
+            } = 0..1,
+        },
+    ";
+
+    let output =
+        device_driver_generation::transform_dsl(syn::parse_str(dsl_text).unwrap(), "Device")
+            .to_string();
+
+    assert_eq!(
+        output,
+        ":: core :: compile_error ! { \"The enum derives must include \\\"Debug\\\", because the Debug impl of the field sets prints the enum fields\" }"
+    );
+}
@@ -0,0 +1,36 @@
+/// With `FeatureGate`, every generated item is put behind the feature
+#[test]
+fn everything_behind_feature_gate() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u8;
+            type FeatureGate = \"my-driver\";
+        }
+        #[cfg(unix)]
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            mode: uint as enum Mode {
+                A,
+                B,
+            } = 0..1,
+        },
+    ";
+
+    let output =
+        device_driver_generation::transform_dsl(syn::parse_str(dsl_text).unwrap(), "Device")
+            .to_string();
+
+    assert!(
+        output.contains("# [cfg (feature = \"my-driver\")] # [derive (Debug)] pub struct Device")
+    );
+    assert!(output.contains("# [cfg (feature = \"my-driver\")] pub mod field_sets"));
+    assert!(output.contains(
+        "# [cfg (all (feature = \"my-driver\" , unix))] # [derive (Copy , Clone , Eq , PartialEq)] pub struct Foo"
+    ));
+    assert!(
+        output.contains("# [cfg (all (feature = \"my-driver\" , unix))] pub fn foo (& mut self ,)")
+    );
+    assert!(output.contains("# [cfg (all (feature = \"my-driver\" , unix))] # [repr (u8)]"));
+}
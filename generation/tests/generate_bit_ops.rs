@@ -0,0 +1,33 @@
+/// The bitwise operators are generated by default and can be turned off with `GenerateBitOps`
+#[test]
+fn bit_ops_only_when_enabled() {
+    let generate = |extra_config: &str| {
+        let dsl_text = format!(
+            "
+            config {{
+                type RegisterAddressType = u8;
+                type DefaultByteOrder = LE;
+                {extra_config}
+            }}
+            register Foo {{
+                const ADDRESS = 0;
+                const SIZE_BITS = 8;
+
+                value: uint = 0..8,
+            }},
+            "
+        );
+
+        device_driver_generation::transform_dsl(syn::parse_str(&dsl_text).unwrap(), "Device")
+            .to_string()
+    };
+
+    for output in [generate(""), generate("type GenerateBitOps = true;")] {
+        assert!(output.contains("impl core :: ops :: BitAnd for Foo"));
+        assert!(output.contains("impl core :: ops :: Not for Foo"));
+    }
+
+    let output = generate("type GenerateBitOps = false;");
+    assert!(!output.contains("impl core :: ops :: BitAnd"));
+    assert!(!output.contains("impl core :: ops :: Not"));
+}
@@ -0,0 +1,44 @@
+/// With `GroupByKind` the field sets are put in a submodule per object kind
+#[test]
+fn field_sets_grouped_by_kind() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u8;
+            type CommandAddressType = u8;
+            type DefaultByteOrder = LE;
+            type GroupByKind = true;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+        command Bar {
+            const ADDRESS = 1;
+            const SIZE_BITS_IN = 8;
+
+            in {
+                value: uint = 0..8,
+            }
+        },
+    ";
+
+    let output = prettyplease::unparse(
+        &syn::parse2(device_driver_generation::transform_dsl(
+            syn::parse_str(dsl_text).unwrap(),
+            "Device",
+        ))
+        .unwrap(),
+    );
+
+    let registers_module = output.find("pub mod registers {").unwrap();
+    let registers_use = output.find("pub use registers::*;").unwrap();
+    let commands_module = output.find("pub mod commands {").unwrap();
+    let commands_use = output.find("pub use commands::*;").unwrap();
+    let register = output.find("pub struct Foo {").unwrap();
+    let command = output.find("pub struct BarFieldsIn {").unwrap();
+
+    assert!(registers_module < register && register < registers_use);
+    assert!(commands_module < command && command < commands_use);
+}
@@ -0,0 +1,79 @@
+/// An included file adds its registers and configs to the device
+#[test]
+fn included_register_generated() {
+    let dsl_text = "
+        include!(\"tests/included-registers.dsl\");
+        register Bar {
+            const ADDRESS = 1;
+            const SIZE_BITS = 8;
+
+            value: uint = 0..8,
+        },
+    ";
+
+    let output =
+        device_driver_generation::transform_dsl(syn::parse_str(dsl_text).unwrap(), "Device")
+            .to_string();
+
+    assert!(output.contains("pub struct Foo"));
+    assert!(output.contains("pub fn foo (& mut self ,)"));
+    assert!(output.contains("pub struct Bar"));
+    assert!(output.contains("pub fn bar (& mut self ,)"));
+}
+
+/// Specifying the same config as the included file is fine, as long as it's the same value
+#[test]
+fn same_global_config_accepted() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u8;
+        }
+        include!(\"tests/included-registers.dsl\");
+    ";
+
+    assert!(
+        device_driver_generation::_private_transform_dsl_mir(syn::parse_str(dsl_text).unwrap())
+            .is_ok()
+    );
+}
+
+#[test]
+fn conflicting_global_config_rejected() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u16;
+        }
+        include!(\"tests/included-registers.dsl\");
+    ";
+
+    assert_eq!(
+        device_driver_generation::_private_transform_dsl_mir(syn::parse_str(dsl_text).unwrap())
+            .unwrap_err()
+            .to_string(),
+        "The included file \"tests/included-registers.dsl\" has a conflicting global config: `RegisterAddressType(Ident(u8))`"
+    );
+}
+
+#[test]
+fn missing_file_rejected() {
+    let dsl_text = "include!(\"tests/does-not-exist.dsl\");";
+
+    assert!(
+        device_driver_generation::_private_transform_dsl_mir(syn::parse_str(dsl_text).unwrap())
+            .unwrap_err()
+            .to_string()
+            .starts_with("Could not read the included file")
+    );
+}
+
+#[test]
+fn include_cycle_rejected() {
+    let dsl_text = "include!(\"tests/include-cycle.dsl\");";
+
+    assert!(
+        device_driver_generation::_private_transform_dsl_mir(syn::parse_str(dsl_text).unwrap())
+            .unwrap_err()
+            .to_string()
+            .contains("includes itself")
+    );
+}
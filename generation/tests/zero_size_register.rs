@@ -0,0 +1,22 @@
+/// A register with a size of zero would generate no field set, so it's reported with a clear error instead
+#[test]
+fn zero_size_register_is_compile_error() {
+    let dsl_text = "
+        config {
+            type RegisterAddressType = u8;
+        }
+        register Foo {
+            const ADDRESS = 0;
+            const SIZE_BITS = 0;
+        },
+    ";
+
+    let output =
+        device_driver_generation::transform_dsl(syn::parse_str(dsl_text).unwrap(), "Device")
+            .to_string();
+
+    assert_eq!(
+        output,
+        ":: core :: compile_error ! { \"Register \\\"Foo\\\" has a size of 0 bits. A register needs a size to be generated, so set it to the size of the register\" }"
+    );
+}